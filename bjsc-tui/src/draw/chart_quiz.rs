@@ -0,0 +1,103 @@
+use super::strategy::code_color;
+use bjsc::chart_quiz::{ChartQuiz, ChartQuizResult};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+// Missed cells listed under a graded chart before the rest are summarised.
+const MISSED_SHOWN: usize = 10;
+
+/// A chart being filled in, with the cursor on the cell being typed. Once graded, right
+/// cells show in green and wrong ones show the right code in red.
+pub fn chart_quiz_lines<'a>(
+    quiz: &'a ChartQuiz,
+    result: Option<&ChartQuizResult>,
+) -> Vec<Line<'a>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(quiz.title(), bold.fg(Color::Cyan)),
+            Span::styled(
+                match result {
+                    Some(result) => format!("  {}", result.summary()),
+                    None => format!("  {}/{} filled in", quiz.filled(), quiz.rows() * 10),
+                },
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Line::default(),
+    ];
+
+    let mut header = vec![Span::raw(format!("{:<6}", ""))];
+    for h in quiz.col_labels() {
+        header.push(Span::styled(format!("{:>4}", h), bold.fg(Color::Yellow)));
+    }
+    lines.push(Line::from(header));
+
+    for row in 0..quiz.rows() {
+        let mut spans = vec![Span::styled(
+            format!("{:<6}", quiz.row_label(row)),
+            Style::default().fg(Color::Gray),
+        )];
+        for col in 0..10 {
+            let entered = quiz.entry(row, col).map(|a| a.code());
+            let (text, style) = match result.map(|r| r.cell(row, col)) {
+                Some(cell) if cell.is_correct() => {
+                    (cell.expected.code(), Style::default().fg(Color::Green))
+                }
+                Some(cell) => (cell.expected.code(), bold.fg(Color::Red)),
+                None if quiz.cursor() == (row, col) => {
+                    let text = if quiz.pending().is_empty() {
+                        entered.unwrap_or("_")
+                    } else {
+                        quiz.pending()
+                    };
+                    (text, Style::default().add_modifier(Modifier::REVERSED))
+                }
+                None => match entered {
+                    Some(code) => (code, Style::default().fg(code_color(code))),
+                    None => ("·", Style::default().fg(Color::DarkGray)),
+                },
+            };
+            spans.push(Span::raw(" ".repeat(4 - text.chars().count())));
+            spans.push(Span::styled(text, style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(result) = result {
+        let missed: Vec<_> = result.missed().collect();
+        lines.push(Line::default());
+        if missed.is_empty() {
+            lines.push(Line::styled("Every cell right!", bold.fg(Color::Green)));
+        } else {
+            lines.push(Line::styled(
+                "Missed (added to your deck to drill):",
+                bold.fg(Color::Red),
+            ));
+            for cell in missed.iter().take(MISSED_SHOWN) {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {:<14}", cell.index),
+                        Style::default().fg(Color::Red),
+                    ),
+                    Span::raw(format!(
+                        "{} (you: {})",
+                        cell.expected.code(),
+                        cell.entered.map_or("blank", |a| a.code())
+                    )),
+                ]));
+            }
+            if missed.len() > MISSED_SHOWN {
+                lines.push(Line::raw(format!(
+                    "  ...and {} more",
+                    missed.len() - MISSED_SHOWN
+                )));
+            }
+        }
+        lines.push(Line::styled(
+            "Enter: Try again",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines
+}
@@ -0,0 +1,137 @@
+use crate::App;
+use bjsc::progress::ProgressStats;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+pub fn draw_class(f: &mut ratatui::Frame, area: Rect, app: &App) {
+    let teaching = app.teaches_class();
+    let assignments_height = if teaching {
+        Constraint::Length(app.assignments.len().clamp(1, 6) as u16 + 2)
+    } else {
+        Constraint::Min(5)
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // title
+            Constraint::Length(2), // class
+            assignments_height,    // assignments
+            Constraint::Min(0),    // members, for the instructor
+            Constraint::Length(1), // hint
+        ])
+        .split(area);
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let title = Span::styled("Class", bold.fg(Color::Cyan));
+    f.render_widget(Paragraph::new(Line::from(title)), chunks[0]);
+
+    let class_line = match app.classes.get(app.class) {
+        Some(class) => Line::from(vec![
+            Span::styled("Class: ", bold),
+            Span::styled(class.name.as_str(), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                if teaching {
+                    format!("  you teach it (join code {})", class.join_code)
+                } else {
+                    "  you're a member".to_string()
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("  {}/{}", app.class + 1, app.classes.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        None if app.auth.is_none() => Line::styled(
+            "Sign in to teach a class or join one.",
+            Style::default().fg(Color::DarkGray),
+        ),
+        None => Line::styled(
+            "Not in a class yet: (N)ew class to teach one, or (J)oin one with its code",
+            Style::default().fg(Color::Yellow),
+        ),
+    };
+    f.render_widget(Paragraph::new(class_line), chunks[1]);
+
+    let lines: Vec<Line> = if let Some(error) = &app.class_error {
+        vec![Line::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )]
+    } else if app.assignments.is_empty() {
+        vec![Line::styled(
+            "Nothing assigned yet.",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else {
+        app.assignments
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let style = if i == app.assignment {
+                    bold.fg(Color::Black).bg(Color::Cyan)
+                } else {
+                    Style::default()
+                };
+                Line::styled(format!(" {} ", row.assignment.label()), style)
+            })
+            .collect()
+    };
+    let list =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Assignments"));
+    f.render_widget(list, chunks[2]);
+
+    if teaching {
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![Line::styled(
+            format!(
+                "{:<20}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}  {}",
+                "", "Answers", "All", "Hard", "Soft", "Split", "Dbl", "Most missed"
+            ),
+            dim,
+        )];
+        if app.class_progress.is_empty() {
+            lines.push(Line::styled(
+                "No members yet. Share the join code to let them in.",
+                dim,
+            ));
+        }
+        for progress in &app.class_progress {
+            let stats = &progress.stats;
+            let pct = ProgressStats::category_pct;
+            let worst = stats
+                .trouble_spots
+                .first()
+                .map(|(cell, wrong, seen)| format!("{} ({}/{} wrong)", cell, wrong, seen))
+                .unwrap_or_default();
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<20}", progress.member.display_name), bold),
+                Span::raw(format!(
+                    "{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}  ",
+                    stats.total_answers,
+                    pct(stats.total_correct, stats.total_answers),
+                    pct(stats.hard_correct, stats.hard_total),
+                    pct(stats.soft_correct, stats.soft_total),
+                    pct(stats.split_correct, stats.split_total),
+                    pct(stats.double_correct, stats.double_total),
+                )),
+                Span::styled(worst, Style::default().fg(Color::Red)),
+            ]));
+        }
+        let members =
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Members"));
+        f.render_widget(members, chunks[3]);
+    }
+
+    super::footer_with_hint(
+        f,
+        chunks[4],
+        if teaching {
+            "C: Next class | A: Assign | X (shift): Withdraw | Enter: Try it | N: New | J: Join | Esc: Menu"
+        } else {
+            "C: Next class | Up/Down, Enter: Start | N: New class | J: Join | D (shift): Leave | Esc: Menu"
+        },
+    );
+}
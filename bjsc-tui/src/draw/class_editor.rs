@@ -0,0 +1,66 @@
+use crate::{ClassEditor, ClassInput};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+pub fn draw_class_editor(f: &mut ratatui::Frame, area: Rect, editor: &ClassEditor) {
+    let width = area.width.min(60);
+    let height = 9u16;
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    f.render_widget(ratatui::widgets::Clear, popup);
+
+    let (title, help, enter) = match editor.kind {
+        ClassInput::New => (
+            " New class ",
+            "A name for the class; share its join code with your players",
+            " = Create   ",
+        ),
+        ClassInput::Join => (
+            " Join a class ",
+            "The join code your instructor gave you. They'll see your progress",
+            " = Join   ",
+        ),
+        ClassInput::Assign => (
+            " Assign ",
+            "A study set (\"name: filter\"), \"exam\", or \"exam 10\" for 10 cells a table",
+            " = Assign   ",
+        ),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(title);
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let mut text = vec![
+        Line::styled(help, Style::default().fg(Color::DarkGray)),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("> "),
+            Span::styled(
+                format!("{}_", editor.input),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]),
+    ];
+    if let Some(error) = &editor.error {
+        text.push(Line::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        ));
+    } else {
+        text.push(Line::from(""));
+    }
+    text.push(Line::from(vec![
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(enter),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" = Cancel"),
+    ]));
+    f.render_widget(Paragraph::new(text).wrap(Wrap { trim: true }), inner);
+}
@@ -0,0 +1,87 @@
+use bjsc::exam::{ExamReport, PASS_MARK};
+use bjsc::progress::ProgressStats;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+// Missed questions listed before the rest are summarised.
+const MISSED_SHOWN: usize = 8;
+
+pub fn draw_exam_report(f: &mut ratatui::Frame, area: Rect, report: &ExamReport) {
+    let width = area.width.min(56);
+    let missed = report.missed.len().min(MISSED_SHOWN) as u16;
+    let height = (10 + report.by_table.len() as u16 + missed).min(area.height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    f.render_widget(ratatui::widgets::Clear, popup);
+
+    let (verdict, color) = if report.passed() {
+        ("PASSED", Color::Green)
+    } else {
+        ("FAILED", Color::Red)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(" Exam report ");
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut text = vec![
+        Line::from(vec![
+            Span::styled(verdict, bold.fg(color)),
+            Span::raw(format!(
+                "  {}  (pass mark {:.0}%)",
+                report.summary(),
+                PASS_MARK * 100.0
+            )),
+        ]),
+        Line::styled(
+            format!(
+                "{} rules | {}",
+                bjsc::preset_for_rules(&report.rules).map_or("Custom", |p| p.name),
+                report.strategy_label()
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Line::from(""),
+    ];
+    for score in &report.by_table {
+        text.push(Line::from(vec![
+            Span::styled(format!("{:<8}", score.table.to_string()), bold),
+            Span::raw(ProgressStats::category_pct(score.correct, score.total)),
+        ]));
+    }
+    if !report.missed.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::styled("Missed:", bold));
+        for answer in report.missed.iter().take(MISSED_SHOWN) {
+            text.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<14}", answer.index),
+                    Style::default().fg(Color::Red),
+                ),
+                Span::raw(format!(
+                    "{} (you: {})",
+                    answer.correct_action, answer.answer
+                )),
+            ]));
+        }
+        if report.missed.len() > MISSED_SHOWN {
+            text.push(Line::raw(format!(
+                "...and {} more",
+                report.missed.len() - MISSED_SHOWN
+            )));
+        }
+    }
+    text.push(Line::from(""));
+    text.push(Line::styled(
+        "Saved. Press any key to continue.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    f.render_widget(Paragraph::new(text).wrap(Wrap { trim: true }), inner);
+}
@@ -64,7 +64,7 @@ pub fn draw_histogram(f: &mut ratatui::Frame, area: Rect, app: &App) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw(format!("  (Mode: {})", app.game_state.study_mode())),
+        Span::raw(format!("  (Mode: {})", app.game_state.mode_label())),
     ]);
     f.render_widget(Paragraph::new(title), chunks[0]);
 
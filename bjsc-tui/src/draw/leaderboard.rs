@@ -0,0 +1,107 @@
+use crate::App;
+use bjsc::leaderboard::Board;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+pub fn draw_leaderboard(f: &mut ratatui::Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // title
+            Constraint::Length(2), // group
+            Constraint::Length(2), // board tabs
+            Constraint::Min(5),    // standings
+            Constraint::Length(1), // hint
+        ])
+        .split(area);
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let title = Span::styled("Leaderboard", bold.fg(Color::Cyan));
+    f.render_widget(Paragraph::new(Line::from(title)), chunks[0]);
+
+    let group_line = match app.groups.get(app.group) {
+        Some(group) => Line::from(vec![
+            Span::styled("Group: ", bold),
+            Span::styled(group.name.as_str(), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("  (join code {})", group.join_code),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("  {}/{}", app.group + 1, app.groups.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        None => Line::styled(
+            "Not in a group yet: (N)ew group, or (J)oin one with its code",
+            Style::default().fg(Color::Yellow),
+        ),
+    };
+    f.render_widget(Paragraph::new(group_line), chunks[1]);
+
+    let mut tabs = Vec::new();
+    for board in Board::ALL {
+        let style = if board == app.board {
+            bold.fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        tabs.push(Span::styled(format!(" {} ", board.label()), style));
+        tabs.push(Span::raw(" "));
+    }
+    if app.board == Board::Blitz {
+        tabs.push(Span::styled(
+            format!("{}s, your rules and strategy", app.blitz_secs),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(tabs)), chunks[2]);
+
+    let me = app.auth.as_ref().map(|a| a.user_id.as_str());
+    let lines: Vec<Line> = if app.auth.is_none() {
+        vec![Line::styled(
+            "Sign in to compare scores with a group.",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else if let Some(error) = &app.leaderboard_error {
+        vec![Line::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )]
+    } else if app.standings.is_empty() && !app.groups.is_empty() {
+        vec![Line::styled(
+            "No scores on this board yet.",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else {
+        app.standings
+            .iter()
+            .map(|s| {
+                let style = if Some(s.user_id.as_str()) == me {
+                    bold.fg(Color::Green)
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:>3}. ", s.rank), Style::default().fg(Color::Gray)),
+                    Span::styled(format!("{:<20}", s.display_name), style),
+                    Span::styled(s.score.as_str(), style),
+                ])
+            })
+            .collect()
+    };
+    let list = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(app.board.label()),
+    );
+    f.render_widget(list, chunks[3]);
+
+    super::footer_with_hint(
+        f,
+        chunks[4],
+        "Tab: Board | G: Next group | N: New group | J: Join | D (shift): Leave | Esc: Menu",
+    );
+}
@@ -1,9 +1,16 @@
+mod chart_quiz;
+mod class;
+mod class_editor;
 mod coach;
 mod confirm_quit;
+mod exam_report;
+mod group_editor;
 mod histogram;
+mod leaderboard;
 mod play;
 mod progress;
 mod screen_picker;
+mod set_editor;
 mod strategy;
 
 use crate::App;
@@ -23,9 +30,19 @@ pub fn draw(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &App) ->
             Screen::Progress => progress::draw_progress(f, area, app),
             Screen::Coach => coach::draw_coach(f, area, app),
             Screen::Strategy => strategy::draw_strategy(f, area, app),
+            Screen::Leaderboard => leaderboard::draw_leaderboard(f, area, app),
+            Screen::Class => class::draw_class(f, area, app),
         }
         if app.confirm_quit {
             confirm_quit::draw_confirm_quit(f, area);
+        } else if let Some(report) = &app.exam_report {
+            exam_report::draw_exam_report(f, area, report);
+        } else if let Some(editor) = &app.set_editor {
+            set_editor::draw_set_editor(f, area, editor);
+        } else if let Some(editor) = &app.group_editor {
+            group_editor::draw_group_editor(f, area, editor);
+        } else if let Some(editor) = &app.class_editor {
+            class_editor::draw_class_editor(f, area, editor);
         } else if let Some(sel) = app.screen_picker {
             screen_picker::draw_screen_picker(f, area, sel);
         }
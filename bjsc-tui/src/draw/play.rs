@@ -5,19 +5,21 @@ use bjsc::hand::Hand;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
 
 pub fn draw_play(f: &mut ratatui::Frame, area: Rect, app: &App) {
+    let why_height = if app.explanation.is_some() { 6 } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // mode
-            Constraint::Length(6), // stats
-            Constraint::Length(3), // dealer
-            Constraint::Length(3), // player
-            Constraint::Length(2), // status
-            Constraint::Min(4),    // error log
-            Constraint::Length(1), // keymap
+            Constraint::Length(1),          // mode
+            Constraint::Length(6),          // stats
+            Constraint::Length(3),          // dealer
+            Constraint::Length(3),          // player
+            Constraint::Length(2),          // status
+            Constraint::Length(why_height), // why
+            Constraint::Min(4),             // error log
+            Constraint::Length(1),          // keymap
         ])
         .split(area);
 
@@ -26,13 +28,36 @@ pub fn draw_play(f: &mut ratatui::Frame, area: Rect, app: &App) {
         .constraints([Constraint::Min(1), Constraint::Length(30)])
         .split(chunks[0]);
 
-    let mode_line = Line::from(vec![
+    let mode_label = match (app.game_state.exam(), app.game_state.blitz()) {
+        (Some(exam), _) => format!("Exam ({} questions)", exam.len()),
+        (None, Some(blitz)) => {
+            let secs = app.blitz_remaining_ms().unwrap_or(0).div_ceil(1000);
+            let mut label = format!(
+                "Blitz {}:{:02} left | {} right, {} wrong",
+                secs / 60,
+                secs % 60,
+                blitz.correct(),
+                blitz.wrong()
+            );
+            if let Some(best) = &app.blitz_best {
+                label.push_str(&format!(" | best {}", best.correct));
+            }
+            label
+        }
+        (None, None) => app.game_state.mode_label(),
+    };
+    let mut mode_spans = vec![
         Span::styled("Mode: ", Style::default().add_modifier(Modifier::BOLD)),
-        Span::styled(
-            app.game_state.study_mode().to_string(),
-            Style::default().fg(Color::Yellow),
-        ),
-    ]);
+        Span::styled(mode_label, Style::default().fg(Color::Yellow)),
+    ];
+    let variant = app.game_state.rules().variant;
+    if variant != bjsc::GameVariant::Standard {
+        mode_spans.push(Span::styled(
+            format!("  ({})", variant),
+            Style::default().fg(Color::LightMagenta),
+        ));
+    }
+    let mode_line = Line::from(mode_spans);
     f.render_widget(Paragraph::new(mode_line), mode_cols[0]);
 
     if let Some(ref auth) = app.auth {
@@ -45,7 +70,17 @@ pub fn draw_play(f: &mut ratatui::Frame, area: Rect, app: &App) {
 
     if app.drill_waiting {
         // Show waiting message instead of cards
-        let wait_msg = if let Some(secs) = app.game_state.drill_wait_secs() {
+        let wait_msg = if app.game_state.study_mode() == bjsc::StudyMode::Changes {
+            "Nothing changes from your baseline rules (b on the Strategy screen sets them)"
+                .to_string()
+        } else if app.game_state.study_mode() == bjsc::StudyMode::Weak {
+            format!(
+                "No weak spots left: each was right {} times in a row",
+                bjsc::WeakSpots::GRADUATE_AFTER
+            )
+        } else if app.game_state.study_mode() == bjsc::StudyMode::Custom {
+            "No cells match this study set ((N)ew set to make another)".to_string()
+        } else if let Some(secs) = app.game_state.drill_wait_secs() {
             format!(
                 "All cards reviewed! Next card due in {}",
                 bjsc::format_wait_time(secs)
@@ -88,11 +123,18 @@ pub fn draw_play(f: &mut ratatui::Frame, area: Rect, app: &App) {
         StatusMessage::SyncError(msg) => {
             Paragraph::new(msg.as_str()).style(Style::default().fg(Color::Yellow))
         }
+        StatusMessage::Info(msg) => {
+            Paragraph::new(msg.as_str()).style(Style::default().fg(Color::Cyan))
+        }
         StatusMessage::None => Paragraph::new(""),
     };
     f.render_widget(status_widget, super::centered_line(chunks[4], 1));
 
-    let log_width = chunks[5].width.saturating_sub(2) as usize;
+    if let Some(ref explanation) = app.explanation {
+        draw_explanation(f, chunks[5], explanation);
+    }
+
+    let log_width = chunks[6].width.saturating_sub(2) as usize;
     let log_items: Vec<ListItem> = app
         .error_log
         .iter()
@@ -135,7 +177,7 @@ pub fn draw_play(f: &mut ratatui::Frame, area: Rect, app: &App) {
         .collect();
     let log_list =
         List::new(log_items).block(Block::default().borders(Borders::ALL).title("Mistakes"));
-    f.render_widget(log_list, chunks[5]);
+    f.render_widget(log_list, chunks[6]);
 
     let keymap = if app.show_shuffle_prompt {
         Paragraph::new("Shoe empty. Press ENTER or SPACE to shuffle.").style(
@@ -143,16 +185,54 @@ pub fn draw_play(f: &mut ratatui::Frame, area: Rect, app: &App) {
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )
+    } else if app.game_state.exam().is_some() {
+        Paragraph::new(if app.game_state.rules().late_surrender() {
+            "(H)it | (S)tand | (D)ouble | S(P)lit | Su(R)render | (X) Cancel exam"
+        } else {
+            "(H)it | (S)tand | (D)ouble | S(P)lit | (X) Cancel exam"
+        })
+    } else if app.game_state.blitz().is_some() {
+        Paragraph::new(if app.game_state.rules().late_surrender() {
+            "(H)it | (S)tand | (D)ouble | S(P)lit | Su(R)render | (Z) Cancel Blitz"
+        } else {
+            "(H)it | (S)tand | (D)ouble | S(P)lit | (Z) Cancel Blitz"
+        })
     } else if app.drill_waiting {
-        Paragraph::new("Waiting for next card... (M)ode | Esc:Menu")
+        Paragraph::new("Waiting for next card... (M)ode | (N)ew set | E(x)am | Bli(z)tz | Esc:Menu")
             .style(Style::default().fg(Color::DarkGray))
+    } else if app.game_state.rules().late_surrender() {
+        Paragraph::new(
+            "(H)it | (S)tand | (D)ouble | S(P)lit | Su(R)render | (M)ode | (N)ew set | E(x)am | Bli(z)tz | Esc:Menu",
+        )
     } else {
-        Paragraph::new("(H)it | (S)tand | (D)ouble | S(P)lit | (M)ode | Esc:Menu")
+        Paragraph::new(
+            "(H)it | (S)tand | (D)ouble | S(P)lit | (M)ode | (N)ew set | E(x)am | Bli(z)tz | Esc:Menu",
+        )
     };
 
-    super::footer_with_hint(f, chunks[6], "");
+    super::footer_with_hint(f, chunks[7], "");
     // Override the hint with the keymap which is special for play
-    f.render_widget(keymap, chunks[6]);
+    f.render_widget(keymap, chunks[7]);
+}
+
+fn draw_explanation(f: &mut ratatui::Frame, area: Rect, explanation: &bjsc::CellExplanation) {
+    let lines: Vec<Line> = explanation
+        .lines()
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let style = match i {
+                0 => Style::default().fg(Color::White),
+                1 => Style::default().fg(Color::Yellow),
+                _ => Style::default().fg(Color::Gray),
+            };
+            Line::from(Span::styled(line, style))
+        })
+        .collect();
+    let why = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Why"));
+    f.render_widget(why, area);
 }
 
 fn card_color(card: &Card) -> Color {
@@ -193,6 +273,16 @@ fn draw_hand(f: &mut ratatui::Frame, area: Rect, label: &str, hand: &Hand) {
         bot_spans.push(Span::styled("└──────┘", border));
     }
 
+    // The dealer's hole card is drawn face down
+    if hand.hole_card().is_some() {
+        let border = Style::default().fg(Color::DarkGray);
+        top_spans.push(Span::styled("┌──────┐", border));
+        mid_spans.push(Span::styled("│", border));
+        mid_spans.push(Span::styled("░░░░░░", Style::default().fg(Color::Blue)));
+        mid_spans.push(Span::styled("│", border));
+        bot_spans.push(Span::styled("└──────┘", border));
+    }
+
     let lines = vec![
         Line::from(top_spans),
         Line::from(mid_spans),
@@ -80,12 +80,16 @@ pub fn draw_progress(f: &mut ratatui::Frame, area: Rect, app: &App) {
         f.render_widget(Paragraph::new(line), cat_cols[i]);
     }
 
-    // Trouble spots
+    // Trouble spots, and the slowest cells beside them
+    let spot_cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[3]);
     let trouble_block = Block::default()
         .borders(Borders::ALL)
         .title("Trouble Spots");
-    let trouble_inner = trouble_block.inner(chunks[3]);
-    f.render_widget(trouble_block, chunks[3]);
+    let trouble_inner = trouble_block.inner(spot_cols[0]);
+    f.render_widget(trouble_block, spot_cols[0]);
 
     let trouble_items: Vec<ListItem> = p
         .trouble_spots
@@ -111,6 +115,36 @@ pub fn draw_progress(f: &mut ratatui::Frame, area: Rect, app: &App) {
         f.render_widget(List::new(trouble_items), trouble_inner);
     }
 
+    let slow_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Slowest Cells (median)");
+    let slow_inner = slow_block.inner(spot_cols[1]);
+    f.render_widget(slow_block, spot_cols[1]);
+
+    let slow_items: Vec<ListItem> = p
+        .slow_spots
+        .iter()
+        .map(|(idx, ms)| {
+            let color = if *ms > bjsc::SLOW_ANSWER_MS {
+                Color::Yellow
+            } else {
+                Color::Gray
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<14}", idx), Style::default().fg(color)),
+                Span::raw(format!("{:.1}s", *ms as f64 / 1000.0)),
+            ]))
+        })
+        .collect();
+    if slow_items.is_empty() {
+        f.render_widget(
+            Paragraph::new("No timed answers yet.").style(Style::default().fg(Color::DarkGray)),
+            slow_inner,
+        );
+    } else {
+        f.render_widget(List::new(slow_items), slow_inner);
+    }
+
     // Recent sessions
     let session_block = Block::default()
         .borders(Borders::ALL)
@@ -152,5 +186,5 @@ pub fn draw_progress(f: &mut ratatui::Frame, area: Rect, app: &App) {
         f.render_widget(List::new(session_items), session_inner);
     }
 
-    super::footer_with_hint(f, chunks[6], "Esc: Menu");
+    super::footer_with_hint(f, chunks[6], "w: Practise weak spots | Esc: Menu");
 }
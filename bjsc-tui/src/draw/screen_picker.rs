@@ -5,7 +5,7 @@ use ratatui::widgets::{Block, Borders, List, ListItem};
 
 pub fn draw_screen_picker(f: &mut ratatui::Frame, area: Rect, selected: usize) {
     let width = 24u16;
-    let height = 11u16;
+    let height = 13u16;
     let x = area.x + (area.width.saturating_sub(width)) / 2;
     let y = area.y + (area.height.saturating_sub(height)) / 2;
     let popup = Rect::new(x, y, width, height);
@@ -20,12 +20,14 @@ pub fn draw_screen_picker(f: &mut ratatui::Frame, area: Rect, selected: usize) {
     f.render_widget(block, popup);
 
     // (index, before_key, key_char, after_key)
-    let entries: [(usize, &str, &str, &str); 5] = [
+    let entries: [(usize, &str, &str, &str); 7] = [
         (0, "", "P", "lay"),
         (1, "", "S", "tats"),
         (2, "Pro", "g", "ress"),
         (3, "", "C", "oach"),
         (4, "S", "t", "rategy"),
+        (5, "", "L", "eaderboard"),
+        (6, "Cl", "a", "ss"),
     ];
 
     let mut items: Vec<ListItem> = entries
@@ -62,7 +64,7 @@ pub fn draw_screen_picker(f: &mut ratatui::Frame, area: Rect, selected: usize) {
     )));
 
     // Quit
-    let is_sel = selected == 5;
+    let is_sel = selected == 7;
     let bg = if is_sel { Color::Red } else { Color::Reset };
     let fg = if is_sel { Color::Black } else { Color::Red };
     let key_fg = if is_sel {
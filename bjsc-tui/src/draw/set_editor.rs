@@ -0,0 +1,51 @@
+use crate::SetEditor;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+pub fn draw_set_editor(f: &mut ratatui::Frame, area: Rect, editor: &SetEditor) {
+    let width = area.width.min(64);
+    let height = 9u16;
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    f.render_widget(ratatui::widgets::Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" New study set ");
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let hint = Style::default().fg(Color::DarkGray);
+    let mut text = vec![
+        Line::styled("name: tables rows vs upcards plays", hint),
+        Line::styled("e.g. Soft doubles: soft vs 3-6 double", hint),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("> "),
+            Span::styled(
+                format!("{}_", editor.input),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]),
+    ];
+    if let Some(error) = &editor.error {
+        text.push(Line::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        ));
+    } else {
+        text.push(Line::from(""));
+    }
+    text.push(Line::from(vec![
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" = Save and study   "),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" = Cancel"),
+    ]));
+    f.render_widget(Paragraph::new(text).wrap(Wrap { trim: true }), inner);
+}
@@ -4,6 +4,18 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
+/// The colour of a chart code, e.g. "Dh".
+pub(super) fn code_color(code: &str) -> Color {
+    match code {
+        "H" => Color::LightRed,
+        "S" => Color::LightGreen,
+        "Dh" | "Ds" => Color::Yellow,
+        "P" | "Pd" => Color::LightBlue,
+        "Rh" => Color::LightMagenta,
+        _ => Color::DarkGray,
+    }
+}
+
 pub fn draw_strategy(f: &mut ratatui::Frame, area: Rect, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -29,6 +41,13 @@ pub fn draw_strategy(f: &mut ratatui::Frame, area: Rect, app: &App) {
     } else {
         Style::default().fg(Color::DarkGray)
     };
+    let changes_style = if app.strategy_tab == StrategyTab::Changes {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
     let title = Line::from(vec![
         Span::styled(
             "Strategy",
@@ -40,6 +59,18 @@ pub fn draw_strategy(f: &mut ratatui::Frame, area: Rect, app: &App) {
         Span::styled("Descriptive", desc_style),
         Span::raw("  |  "),
         Span::styled("Tables", table_style),
+        Span::raw("  |  "),
+        Span::styled("Changes", changes_style),
+        Span::raw("    "),
+        Span::styled(
+            format!(
+                "{} ({}) · {}",
+                bjsc::preset_for_rules(app.game_state.rules()).map_or("Custom", |p| p.name),
+                app.game_state.rules().summary(),
+                app.game_state.rules().hole_card
+            ),
+            Style::default().fg(Color::Yellow),
+        ),
     ]);
     f.render_widget(Paragraph::new(title), chunks[0]);
 
@@ -48,10 +79,21 @@ pub fn draw_strategy(f: &mut ratatui::Frame, area: Rect, app: &App) {
     let inner = block.inner(chunks[1]);
     f.render_widget(block, chunks[1]);
 
+    if let Some(quiz) = &app.chart_quiz {
+        let lines = super::chart_quiz::chart_quiz_lines(quiz, app.chart_quiz_result.as_ref());
+        f.render_widget(Paragraph::new(lines), inner);
+        super::footer_with_hint(
+            f,
+            chunks[2],
+            "Arrows: Move | H S Dh Ds P Pd Rh -: Fill in | Backspace: Clear | Enter: Grade | Tab: Next chart | Esc: Leave",
+        );
+        return;
+    }
+
     let lines: Vec<Line> = match app.strategy_tab {
         StrategyTab::Descriptive => {
             let mut lines = Vec::new();
-            for (category, phrases) in bjsc::all_phrases() {
+            for (category, phrases) in bjsc::all_phrases(&app.game_state.charts()) {
                 lines.push(Line::from(Span::styled(
                     category,
                     Style::default()
@@ -69,8 +111,36 @@ pub fn draw_strategy(f: &mut ratatui::Frame, area: Rect, app: &App) {
             lines
         }
         StrategyTab::Tables => {
-            let mut lines = Vec::new();
-            for chart in bjsc::all_charts() {
+            let edge = bjsc::house_edge(app.game_state.rules());
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled(
+                        "House edge: ",
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:.2}%", edge.total()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(
+                        format!("  ({})", edge.describe().join(", ")),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]),
+                Line::default(),
+            ];
+            let learning = match &app.chart_file {
+                Some(path) => format!("Custom chart: {}", path),
+                None => format!("Learning: {}", app.game_state.strategy_kind()),
+            };
+            lines.push(Line::from(Span::styled(
+                learning,
+                Style::default().fg(Color::LightMagenta),
+            )));
+            lines.push(Line::default());
+            for chart in bjsc::strategy_charts(&app.game_state.charts()) {
                 lines.push(Line::from(Span::styled(
                     chart.title,
                     Style::default()
@@ -98,24 +168,78 @@ pub fn draw_strategy(f: &mut ratatui::Frame, area: Rect, app: &App) {
                         Style::default().fg(Color::Gray),
                     )];
                     for cell in cells {
-                        let color = match *cell {
-                            "H" => Color::LightRed,
-                            "S" => Color::LightGreen,
-                            "Dh" | "Ds" => Color::Yellow,
-                            "P" | "Pd" => Color::LightBlue,
-                            _ => Color::DarkGray,
-                        };
                         spans.push(Span::styled(
                             format!("{:>4}", cell),
-                            Style::default().fg(color),
+                            Style::default().fg(code_color(cell)),
                         ));
                     }
                     lines.push(Line::from(spans));
                 }
+                for note in &chart.notes {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", note),
+                        Style::default().fg(Color::Gray),
+                    )));
+                }
                 lines.push(Line::default());
             }
             lines
         }
+        StrategyTab::Changes => {
+            let describe = |rules: &bjsc::Rules| {
+                format!(
+                    "{} ({})",
+                    bjsc::preset_for_rules(rules).map_or("Custom", |p| p.name),
+                    rules.summary()
+                )
+            };
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled(
+                        "From: ",
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        describe(app.game_state.baseline()),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled(
+                        "To:   ",
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        describe(app.game_state.rules()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ]),
+                Line::default(),
+            ];
+            let changes = app.game_state.chart_changes();
+            if changes.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No chart cells change.",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            for change in changes {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", change),
+                    Style::default().fg(Color::White),
+                )));
+            }
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled(
+                "Press b to make these rules your baseline. Study mode \"What changes\" drills only these cells.",
+                Style::default().fg(Color::DarkGray),
+            )));
+            lines
+        }
     };
 
     let text = Paragraph::new(lines)
@@ -123,5 +247,9 @@ pub fn draw_strategy(f: &mut ratatui::Frame, area: Rect, app: &App) {
         .wrap(ratatui::widgets::Wrap { trim: false });
     f.render_widget(text, inner);
 
-    super::footer_with_hint(f, chunks[2], "↑/↓: Scroll | Tab: Switch | Esc: Menu");
+    super::footer_with_hint(
+        f,
+        chunks[2],
+        "↑/↓: Scroll | Tab: Switch | l: Strategy | r: Rules preset | v: Game | e: Hole card rule | b: Baseline | f: Fill in a chart | Esc: Menu",
+    );
 }
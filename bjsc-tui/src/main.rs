@@ -4,6 +4,10 @@ mod draw;
 mod split_bar_chart;
 
 use auth::AuthTokens;
+use bjsc::blitz::{BLITZ_LENGTHS_SECS, BLITZ_SECS, BlitzScore};
+use bjsc::chart_quiz::{ChartQuiz, ChartQuizResult, QUIZ_TABLES};
+use bjsc::class::{Assignment, MemberProgress};
+use bjsc::leaderboard::{Board, Standing};
 use bjsc::{Action, GameState, SupabaseConfig, persistence};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use crossterm::execute;
@@ -26,12 +30,15 @@ pub(crate) enum Screen {
     Progress,
     Coach,
     Strategy,
+    Leaderboard,
+    Class,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum StrategyTab {
     Descriptive,
     Tables,
+    Changes,
 }
 
 #[derive(Debug, Clone)]
@@ -39,9 +46,14 @@ pub(crate) enum StatusMessage {
     Correct(String),
     Wrong(String),
     SyncError(String),
+    /// Exam progress and other news that says nothing about the last answer.
+    Info(String),
     None,
 }
 
+/// Questions from each table in a short exam (X).
+const EXAM_SAMPLE_PER_TABLE: usize = 10;
+
 pub(crate) struct App {
     pub(crate) game_state: GameState,
     pub(crate) status: StatusMessage,
@@ -63,14 +75,116 @@ pub(crate) struct App {
     pub(crate) strategy_tab: StrategyTab,
     pub(crate) strategy_scroll: u16,
     pub(crate) drill_waiting: bool,
+    /// Custom chart file in use, if any.
+    pub(crate) chart_file: Option<String>,
+    /// Why the last wrong answer was wrong.
+    pub(crate) explanation: Option<bjsc::CellExplanation>,
+    /// The new study set being typed, as "name: filter", while the editor is open.
+    pub(crate) set_editor: Option<SetEditor>,
+    /// When the exam being taken started.
+    exam_started: Option<std::time::Instant>,
+    /// The report of the exam just finished, shown until a key is pressed.
+    pub(crate) exam_report: Option<bjsc::exam::ExamReport>,
+    /// The chart being filled in from memory on the Strategy screen.
+    pub(crate) chart_quiz: Option<ChartQuiz>,
+    /// The quiz's grading, once it's handed in.
+    pub(crate) chart_quiz_result: Option<ChartQuizResult>,
+    /// When the hand on the table was dealt, or last came back into view, and the deal count
+    /// it was for. Answers are timed from here.
+    shown_at: std::time::Instant,
+    shown_deal: u64,
+    /// Length of the next Blitz (Z cycles through them).
+    pub(crate) blitz_secs: u32,
+    /// When the Blitz being played started.
+    blitz_started: Option<std::time::Instant>,
+    /// The personal best for the Blitz being played, here or in the cloud.
+    pub(crate) blitz_best: Option<BlitzScore>,
+    blitz_best_rx: Option<mpsc::Receiver<BlitzScore>>,
+    /// The groups the player has joined, the one on the Leaderboard screen, and its board.
+    pub(crate) groups: Vec<bjsc::supabase::GroupRow>,
+    pub(crate) group: usize,
+    pub(crate) board: Board,
+    pub(crate) standings: Vec<Standing>,
+    pub(crate) leaderboard_error: Option<String>,
+    /// The group name or join code being typed, while the popup is open.
+    pub(crate) group_editor: Option<GroupEditor>,
+    /// The classes the player teaches or belongs to, the one on the Class screen, its
+    /// assignments (newest first) and the one picked, and for its instructor, each
+    /// member's progress.
+    pub(crate) classes: Vec<bjsc::supabase::ClassRow>,
+    pub(crate) class: usize,
+    pub(crate) assignments: Vec<bjsc::supabase::AssignmentRow>,
+    pub(crate) assignment: usize,
+    pub(crate) class_progress: Vec<MemberProgress>,
+    pub(crate) class_error: Option<String>,
+    /// The class name, join code or assignment being typed, while the popup is open.
+    pub(crate) class_editor: Option<ClassEditor>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct GroupEditor {
+    /// Joining with a code rather than creating a group.
+    pub(crate) joining: bool,
+    pub(crate) input: String,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ClassInput {
+    /// A name for a new class.
+    New,
+    /// A class's join code.
+    Join,
+    /// An assignment for the class on show, e.g. "exam 10".
+    Assign,
+}
+
+#[derive(Debug)]
+pub(crate) struct ClassEditor {
+    pub(crate) kind: ClassInput,
+    pub(crate) input: String,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SetEditor {
+    pub(crate) input: String,
+    pub(crate) error: Option<String>,
 }
 
 impl App {
-    fn new(mut auth: Option<AuthTokens>, rt: tokio::runtime::Runtime) -> Self {
+    /// `chart_arg` is the `--chart` command line option: `Some(None)` turns a saved custom
+    /// chart off, `None` keeps whatever was saved.
+    fn new(
+        mut auth: Option<AuthTokens>,
+        rt: tokio::runtime::Runtime,
+        chart_arg: Option<Option<String>>,
+    ) -> Self {
         let saved = persistence::load_state();
         let mut game_state = GameState::default();
-        game_state.set_deck(saved.deck);
         game_state.set_study_mode(saved.mode);
+        game_state.set_study_sets(saved.study_sets, saved.study_set);
+        game_state.set_rules(bjsc::resolve_rules(saved.preset.as_deref(), saved.rules));
+        game_state.set_strategy(saved.strategy);
+        let mut chart_file = chart_arg.unwrap_or(saved.chart_file);
+        let mut chart_error = None;
+        if let Some(path) = &chart_file {
+            match persistence::load_chart(path) {
+                Ok(charts) => game_state.set_custom_charts(Some(charts)),
+                Err(e) => chart_error = Some(e),
+            }
+        }
+        if chart_error.is_some() {
+            chart_file = None;
+        }
+        // After the rules and charts: old decks are migrated to the keys of the rules and
+        // strategy they were saved with, and the lesson was unlocked under them
+        game_state.set_deck(saved.deck);
+        game_state.set_lesson(saved.lesson);
+        game_state.set_half_credit(saved.half_credit);
+        if let Some(baseline) = saved.baseline {
+            game_state.set_baseline(baseline);
+        }
 
         // If authenticated, refresh token if expired, then load from cloud
         if let Some(ref mut auth) = auth {
@@ -89,15 +203,35 @@ impl App {
                 &auth.access_token,
             ));
             if let Ok(Some(row)) = result {
-                game_state.set_deck(row.deck);
                 game_state.set_study_mode(row.study_mode);
+                game_state.set_study_sets(row.study_sets, row.study_set);
+                game_state.set_rules(bjsc::resolve_rules(row.preset.as_deref(), row.rules));
+                game_state.set_strategy(row.strategy);
+                game_state.set_deck(row.deck);
+                game_state.set_lesson(row.lesson);
+                game_state.set_half_credit(row.half_credit);
+            }
+
+            // Resuming the Weak spots mode needs the answer history to deal from
+            if game_state.study_mode() == bjsc::StudyMode::Weak
+                && let Ok(logs) = rt.block_on(bjsc::api::fetch_answer_logs(
+                    &api::ReqwestClient,
+                    &config,
+                    &auth.access_token,
+                    &auth.user_id,
+                    1000,
+                ))
+            {
+                game_state.set_weak_history(&bjsc::progress::ProgressStats::from_logs(&logs));
             }
         }
 
         let dealt = game_state.deal_a_hand();
 
-        let status = if game_state.study_mode() != bjsc::StudyMode::All {
-            StatusMessage::Correct(format!("Resumed: {}", game_state.study_mode()))
+        let status = if let Some(e) = chart_error {
+            StatusMessage::Wrong(format!("Chart not loaded: {}", e))
+        } else if game_state.study_mode() != bjsc::StudyMode::All {
+            StatusMessage::Correct(format!("Resumed: {}", game_state.mode_label()))
         } else {
             StatusMessage::None
         };
@@ -125,6 +259,32 @@ impl App {
             strategy_tab: StrategyTab::Descriptive,
             strategy_scroll: 0,
             drill_waiting: !dealt,
+            chart_file,
+            explanation: None,
+            set_editor: None,
+            exam_started: None,
+            exam_report: None,
+            chart_quiz: None,
+            chart_quiz_result: None,
+            shown_at: std::time::Instant::now(),
+            shown_deal: 0,
+            blitz_secs: BLITZ_SECS,
+            blitz_started: None,
+            blitz_best: None,
+            blitz_best_rx: None,
+            groups: Vec::new(),
+            group: 0,
+            board: Board::default(),
+            standings: Vec::new(),
+            leaderboard_error: None,
+            group_editor: None,
+            classes: Vec::new(),
+            class: 0,
+            assignments: Vec::new(),
+            assignment: 0,
+            class_progress: Vec::new(),
+            class_error: None,
+            class_editor: None,
         }
     }
 
@@ -135,6 +295,8 @@ impl App {
             Screen::Progress => 2,
             Screen::Coach => 3,
             Screen::Strategy => 4,
+            Screen::Leaderboard => 5,
+            Screen::Class => 6,
         }
     }
 
@@ -145,12 +307,22 @@ impl App {
             2 => Screen::Progress,
             3 => Screen::Coach,
             4 => Screen::Strategy,
+            5 => Screen::Leaderboard,
+            6 => Screen::Class,
             _ => return,
         };
         self.screen = screen;
         if screen == Screen::Progress {
             self.refresh_progress();
         }
+        if screen == Screen::Leaderboard {
+            // Brings the player's own streak up to date first
+            self.refresh_progress();
+            self.refresh_groups();
+        }
+        if screen == Screen::Class {
+            self.refresh_classes();
+        }
         if screen == Screen::Coach && self.should_refresh_coaching() {
             self.refresh_coaching();
         }
@@ -167,15 +339,35 @@ impl App {
             return false;
         }
 
+        if self.set_editor.is_some() {
+            self.handle_set_editor_key(code);
+            return false;
+        }
+
+        if self.group_editor.is_some() {
+            self.handle_group_editor_key(code);
+            return false;
+        }
+
+        if self.class_editor.is_some() {
+            self.handle_class_editor_key(code);
+            return false;
+        }
+
+        if self.exam_report.is_some() {
+            self.exam_report = None;
+            return false;
+        }
+
         // Screen picker is open
         if let Some(ref mut sel) = self.screen_picker {
             match code {
                 KeyCode::Up | KeyCode::Char('k') => *sel = sel.saturating_sub(1),
-                KeyCode::Down | KeyCode::Char('j') => *sel = (*sel + 1).min(5),
+                KeyCode::Down | KeyCode::Char('j') => *sel = (*sel + 1).min(7),
                 KeyCode::Enter => {
                     let idx = *sel;
                     self.screen_picker = None;
-                    if idx == 5 {
+                    if idx == 7 {
                         self.confirm_quit = true;
                     } else {
                         self.go_to_screen(idx);
@@ -201,6 +393,14 @@ impl App {
                     self.screen_picker = None;
                     self.go_to_screen(4);
                 }
+                KeyCode::Char('l') => {
+                    self.screen_picker = None;
+                    self.go_to_screen(5);
+                }
+                KeyCode::Char('a') => {
+                    self.screen_picker = None;
+                    self.go_to_screen(6);
+                }
                 KeyCode::Char('q') => {
                     self.screen_picker = None;
                     self.confirm_quit = true;
@@ -211,6 +411,11 @@ impl App {
             return false;
         }
 
+        if self.screen == Screen::Strategy && self.chart_quiz.is_some() {
+            self.handle_chart_quiz_key(code);
+            return false;
+        }
+
         if code == KeyCode::Esc {
             self.screen_picker = Some(self.screen_index());
             return false;
@@ -224,18 +429,116 @@ impl App {
                 KeyCode::Up | KeyCode::Char('k') => {
                     self.strategy_scroll = self.strategy_scroll.saturating_sub(1)
                 }
-                KeyCode::Tab | KeyCode::Left | KeyCode::Right => {
+                KeyCode::Tab | KeyCode::Right => {
                     self.strategy_tab = match self.strategy_tab {
                         StrategyTab::Descriptive => StrategyTab::Tables,
+                        StrategyTab::Tables => StrategyTab::Changes,
+                        StrategyTab::Changes => StrategyTab::Descriptive,
+                    };
+                    self.strategy_scroll = 0;
+                }
+                KeyCode::BackTab | KeyCode::Left => {
+                    self.strategy_tab = match self.strategy_tab {
+                        StrategyTab::Descriptive => StrategyTab::Changes,
                         StrategyTab::Tables => StrategyTab::Descriptive,
+                        StrategyTab::Changes => StrategyTab::Tables,
                     };
                     self.strategy_scroll = 0;
                 }
+                KeyCode::Char('e') => {
+                    let mut rules = *self.game_state.rules();
+                    rules.hole_card = rules.hole_card.next();
+                    self.set_rules(rules);
+                }
+                KeyCode::Char('v') => {
+                    let mut rules = *self.game_state.rules();
+                    rules.variant = rules.variant.next();
+                    self.set_rules(rules);
+                }
+                KeyCode::Char('l') => {
+                    let strategy = self.game_state.strategy_kind().next();
+                    self.game_state.set_strategy(strategy);
+                    self.status = StatusMessage::None;
+                    self.save();
+                }
+                KeyCode::Char('r') => {
+                    let preset = bjsc::next_preset(self.game_state.rules());
+                    self.set_rules(preset.rules);
+                }
+                KeyCode::Char('b') => {
+                    let rules = *self.game_state.rules();
+                    self.game_state.set_baseline(rules);
+                    // Same rules, but the Changes mode has nothing left to deal
+                    self.set_rules(rules);
+                }
+                KeyCode::Char('f') => self.chart_quiz = Some(ChartQuiz::new(QUIZ_TABLES[0])),
+                _ => {}
+            }
+            return false;
+        }
+
+        if self.screen == Screen::Leaderboard {
+            match code {
+                KeyCode::Tab | KeyCode::Right => {
+                    self.board = self.board.next();
+                    self.refresh_standings();
+                }
+                KeyCode::Char('g') if !self.groups.is_empty() => {
+                    self.group = (self.group + 1) % self.groups.len();
+                    self.refresh_standings();
+                }
+                KeyCode::Char('n') | KeyCode::Char('j') if self.auth.is_some() => {
+                    self.group_editor = Some(GroupEditor {
+                        joining: code == KeyCode::Char('j'),
+                        ..GroupEditor::default()
+                    });
+                }
+                KeyCode::Char('D') => self.leave_group(),
+                _ => {}
+            }
+            return false;
+        }
+
+        if self.screen == Screen::Class {
+            let teaching = self.teaches_class();
+            match code {
+                KeyCode::Char('c') if !self.classes.is_empty() => {
+                    self.class = (self.class + 1) % self.classes.len();
+                    self.refresh_class();
+                }
+                KeyCode::Up => self.assignment = self.assignment.saturating_sub(1),
+                KeyCode::Down => {
+                    self.assignment =
+                        (self.assignment + 1).min(self.assignments.len().saturating_sub(1))
+                }
+                KeyCode::Enter => self.start_assignment(),
+                KeyCode::Char(ch @ ('n' | 'j' | 'a')) if self.auth.is_some() => {
+                    let kind = match ch {
+                        'n' => ClassInput::New,
+                        'j' => ClassInput::Join,
+                        _ if teaching => ClassInput::Assign,
+                        _ => return false,
+                    };
+                    self.class_editor = Some(ClassEditor {
+                        kind,
+                        input: String::new(),
+                        error: None,
+                    });
+                }
+                KeyCode::Char('X') if teaching => self.unassign(),
+                KeyCode::Char('D') if !teaching => self.leave_class(),
                 _ => {}
             }
             return false;
         }
 
+        if self.screen == Screen::Progress {
+            if code == KeyCode::Char('w') {
+                self.practise_weak_spots();
+            }
+            return false;
+        }
+
         if self.screen == Screen::Coach {
             match code {
                 KeyCode::Down | KeyCode::Char('j') => {
@@ -262,18 +565,53 @@ impl App {
             return false;
         }
 
+        // x starts a full exam, X a shorter one; either cancels an exam under way
+        if let KeyCode::Char(ch @ ('x' | 'X')) = code {
+            if self.game_state.exam().is_some() {
+                self.game_state.cancel_exam();
+                self.exam_started = None;
+                self.status = StatusMessage::Wrong("Exam cancelled".to_string());
+            } else {
+                self.start_exam((ch == 'X').then_some(EXAM_SAMPLE_PER_TABLE));
+            }
+            return false;
+        }
+
+        // z starts a Blitz or cancels one under way; Z changes its length
+        if code == KeyCode::Char('z') {
+            if self.game_state.blitz().is_some() {
+                self.game_state.cancel_blitz();
+                self.blitz_started = None;
+                self.status = StatusMessage::Wrong("Blitz cancelled".to_string());
+            } else {
+                self.start_blitz();
+            }
+            return false;
+        }
+        if code == KeyCode::Char('Z') && self.game_state.blitz().is_none() {
+            let at = BLITZ_LENGTHS_SECS
+                .iter()
+                .position(|&s| s == self.blitz_secs);
+            self.blitz_secs =
+                BLITZ_LENGTHS_SECS[at.map_or(0, |i| (i + 1) % BLITZ_LENGTHS_SECS.len())];
+            self.status = StatusMessage::Info(format!("Blitz length: {}s", self.blitz_secs));
+            return false;
+        }
+
         // In drill waiting mode, block play actions (mode change and menu still allowed)
-        if self.drill_waiting && code != KeyCode::Char('m') && code != KeyCode::Esc {
+        if self.drill_waiting && !matches!(code, KeyCode::Char('m' | 'n') | KeyCode::Esc) {
             return false;
         }
 
         if code == KeyCode::Char('m') {
-            let new_mode = self.game_state.study_mode().next();
-            self.game_state.set_study_mode(new_mode);
-            let dealt = self.game_state.deal_a_hand();
-            self.drill_waiting = !dealt && new_mode == bjsc::StudyMode::Drill;
+            self.next_mode();
+            self.redeal_for_mode();
             self.status = StatusMessage::None;
-            self.save();
+            return false;
+        }
+
+        if code == KeyCode::Char('n') {
+            self.set_editor = Some(SetEditor::default());
             return false;
         }
 
@@ -285,8 +623,31 @@ impl App {
             return false;
         };
 
-        if let Some(result) = self.game_state.check_answer(action) {
+        if self.game_state.exam().is_some() {
+            self.answer_exam(action);
+            return false;
+        }
+        if self.game_state.blitz().is_some() {
+            let elapsed = self
+                .blitz_started
+                .map_or(0, |t| t.elapsed().as_millis() as u64);
+            self.status = match self.game_state.answer_blitz(action, elapsed) {
+                Some((true, _)) => StatusMessage::Correct("Right".to_string()),
+                Some((false, right)) => StatusMessage::Wrong(format!(
+                    "Wrong: {} (-{}s)",
+                    right,
+                    bjsc::blitz::WRONG_ANSWER_PENALTY_MS / 1000
+                )),
+                // Time's up: the answer is too late, and the next tick ends the Blitz
+                None => StatusMessage::None,
+            };
+            return false;
+        }
+
+        let latency = u32::try_from(self.shown_at.elapsed().as_millis()).unwrap_or(u32::MAX);
+        if let Some(result) = self.game_state.check_timed_answer(action, Some(latency)) {
             let log_data = result.log_data();
+            self.explanation = result.explanation.clone();
 
             if result.correct {
                 self.status = StatusMessage::Correct(result.status_message());
@@ -299,11 +660,17 @@ impl App {
 
             self.save();
             if let Some((key, was_correct, player_act, correct_act)) = log_data {
-                self.log_answer(&key, was_correct, &player_act, &correct_act);
+                self.log_answer(
+                    &key,
+                    was_correct,
+                    &player_act,
+                    &correct_act,
+                    result.latency_ms,
+                );
             }
 
             if !self.game_state.deal_a_hand() {
-                if self.game_state.study_mode() == bjsc::StudyMode::Drill {
+                if waits_when_empty(self.game_state.study_mode()) {
                     self.drill_waiting = true;
                 } else {
                     self.show_shuffle_prompt = true;
@@ -313,19 +680,626 @@ impl App {
         false
     }
 
+    /// Step to the next study mode, then through the player's study sets, then back to All.
+    fn next_mode(&mut self) {
+        let gs = &mut self.game_state;
+        let names: Vec<String> = gs.study_sets().iter().map(|s| s.name.clone()).collect();
+        let next_set = match gs.study_mode() {
+            bjsc::StudyMode::Curriculum => names.first(),
+            bjsc::StudyMode::Custom => {
+                let current = gs.study_set().map(|s| s.name.clone());
+                let at = names.iter().position(|n| Some(n) == current.as_ref());
+                at.and_then(|i| names.get(i + 1))
+            }
+            _ => None,
+        };
+        match next_set {
+            Some(name) => {
+                gs.select_study_set(name);
+            }
+            None => {
+                let mode = gs.study_mode().next();
+                gs.set_study_mode(mode);
+                if mode == bjsc::StudyMode::Weak {
+                    self.refresh_progress();
+                }
+            }
+        }
+    }
+
+    /// Switch to the Weak spots mode and go and play them.
+    fn practise_weak_spots(&mut self) {
+        self.game_state.set_study_mode(bjsc::StudyMode::Weak);
+        self.refresh_progress();
+        self.redeal_for_mode();
+        self.status = StatusMessage::None;
+        self.screen = Screen::Play;
+    }
+
+    fn start_exam(&mut self, per_table: Option<usize>) {
+        self.game_state.start_exam(per_table);
+        self.exam_started = Some(std::time::Instant::now());
+        self.drill_waiting = false;
+        self.show_shuffle_prompt = false;
+        self.explanation = None;
+        self.status = self.exam_progress();
+    }
+
+    fn exam_progress(&self) -> StatusMessage {
+        match self.game_state.exam() {
+            Some(exam) => StatusMessage::Info(format!(
+                "Exam: question {} of {}",
+                exam.answered() + 1,
+                exam.len()
+            )),
+            None => StatusMessage::None,
+        }
+    }
+
+    /// Answer an exam question. After the last, grade the exam, save the report locally and
+    /// to the cloud, and show it.
+    fn answer_exam(&mut self, action: Action) {
+        if self.game_state.answer_exam(action) != Some(false) {
+            self.status = self.exam_progress();
+            return;
+        }
+        let duration = self
+            .exam_started
+            .take()
+            .map_or(0, |t| t.elapsed().as_secs());
+        let taken_at = chrono::Utc::now().to_rfc3339();
+        let Some(report) = self.game_state.finish_exam(taken_at, duration) else {
+            return;
+        };
+        persistence::save_exam_report(&report);
+        self.status = if report.passed() {
+            StatusMessage::Correct(format!("Exam: {}", report.summary()))
+        } else {
+            StatusMessage::Wrong(format!("Exam: {}", report.summary()))
+        };
+        self.redeal_for_mode();
+
+        if let Some(ref auth) = self.auth {
+            let config = supabase_config();
+            let token = auth.access_token.clone();
+            let row = bjsc::supabase::ExamResultRow {
+                user_id: auth.user_id.clone(),
+                report: report.clone(),
+            };
+            let err_tx = self.sync_error_tx.clone();
+            self.rt.spawn(async move {
+                if let Err(e) =
+                    bjsc::api::insert_exam_result(&api::ReqwestClient, &config, &token, &row).await
+                {
+                    let _ = err_tx.send(format!("Exam sync failed: {}", e));
+                }
+            });
+        }
+        self.exam_report = Some(report);
+    }
+
+    /// Start a Blitz, and look up the personal best to beat, here and in the cloud.
+    fn start_blitz(&mut self) {
+        self.game_state.start_blitz(self.blitz_secs);
+        self.blitz_started = Some(std::time::Instant::now());
+        self.drill_waiting = false;
+        self.show_shuffle_prompt = false;
+        self.explanation = None;
+        self.status = StatusMessage::Info("Blitz: go!".to_string());
+        let Some(challenge) = self.game_state.blitz().map(|b| b.score(String::new())) else {
+            return;
+        };
+        self.blitz_best =
+            bjsc::blitz::best_for(&persistence::load_blitz_bests(), &challenge).cloned();
+
+        if let Some(ref auth) = self.auth {
+            let config = supabase_config();
+            let token = auth.access_token.clone();
+            let user_id = auth.user_id.clone();
+            let (tx, rx) = mpsc::channel();
+            self.blitz_best_rx = Some(rx);
+            self.rt.spawn(async move {
+                if let Ok(Some(best)) = bjsc::api::fetch_blitz_best(
+                    &api::ReqwestClient,
+                    &config,
+                    &token,
+                    &user_id,
+                    &challenge,
+                )
+                .await
+                {
+                    let _ = tx.send(best);
+                }
+            });
+        }
+    }
+
+    /// Time left in the Blitz being played.
+    pub(crate) fn blitz_remaining_ms(&self) -> Option<u64> {
+        let elapsed = self.blitz_started?.elapsed().as_millis();
+        Some(self.game_state.blitz()?.remaining_ms(elapsed as u64))
+    }
+
+    /// Pick up the cloud's personal best, and end the Blitz once time is up: save the score
+    /// locally and to the cloud, and say if it's a new best.
+    fn tick_blitz(&mut self) {
+        if let Some(best) = self
+            .blitz_best_rx
+            .as_ref()
+            .and_then(|rx| rx.try_recv().ok())
+        {
+            self.blitz_best_rx = None;
+            // Played on another machine: keep it here too
+            persistence::save_blitz_score(&best);
+            if self.blitz_best.as_ref().is_none_or(|b| best.beats(b)) {
+                self.blitz_best = Some(best);
+            }
+        }
+        if self.blitz_remaining_ms() != Some(0) {
+            return;
+        }
+        self.blitz_started = None;
+        let taken_at = chrono::Utc::now().to_rfc3339();
+        let Some(score) = self.game_state.finish_blitz(taken_at) else {
+            return;
+        };
+        persistence::save_blitz_score(&score);
+        let new_best = self.blitz_best.as_ref().is_none_or(|b| score.beats(b));
+        self.status = if new_best {
+            StatusMessage::Correct(format!("Blitz: {}. New personal best!", score.summary()))
+        } else {
+            let best = self.blitz_best.as_ref().map_or(0, |b| b.correct);
+            StatusMessage::Info(format!("Blitz: {} (best: {} right)", score.summary(), best))
+        };
+        self.redeal_for_mode();
+
+        if let Some(ref auth) = self.auth {
+            let config = supabase_config();
+            let token = auth.access_token.clone();
+            let row = bjsc::supabase::BlitzScoreRow {
+                user_id: auth.user_id.clone(),
+                score,
+            };
+            let err_tx = self.sync_error_tx.clone();
+            self.rt.spawn(async move {
+                if let Err(e) =
+                    bjsc::api::insert_blitz_score(&api::ReqwestClient, &config, &token, &row).await
+                {
+                    let _ = err_tx.send(format!("Blitz sync failed: {}", e));
+                }
+            });
+        }
+    }
+
+    /// Deal the first hand of a newly picked mode or study set.
+    fn redeal_for_mode(&mut self) {
+        let dealt = self.game_state.deal_a_hand();
+        self.drill_waiting = !dealt && waits_when_empty(self.game_state.study_mode());
+        self.explanation = None;
+        self.save();
+    }
+
+    /// Arrows move around the chart, action codes fill it in, Backspace clears a cell and
+    /// Enter hands it in. Once graded, Enter starts the chart again. Tab moves on to the next
+    /// chart and Esc leaves the quiz.
+    fn handle_chart_quiz_key(&mut self, code: KeyCode) {
+        let Some(quiz) = self.chart_quiz.as_mut() else {
+            return;
+        };
+        let table = quiz.table();
+        match code {
+            KeyCode::Esc => {
+                self.chart_quiz = None;
+                self.chart_quiz_result = None;
+            }
+            KeyCode::Tab => {
+                let at = QUIZ_TABLES.iter().position(|t| *t == table).unwrap_or(0);
+                *quiz = ChartQuiz::new(QUIZ_TABLES[(at + 1) % QUIZ_TABLES.len()]);
+                self.chart_quiz_result = None;
+            }
+            KeyCode::Enter if self.chart_quiz_result.is_some() => {
+                *quiz = ChartQuiz::new(table);
+                self.chart_quiz_result = None;
+            }
+            _ if self.chart_quiz_result.is_some() => {}
+            KeyCode::Up => quiz.move_cursor(-1, 0),
+            KeyCode::Down => quiz.move_cursor(1, 0),
+            KeyCode::Left => quiz.move_cursor(0, -1),
+            KeyCode::Right => quiz.move_cursor(0, 1),
+            KeyCode::Backspace | KeyCode::Delete => quiz.clear(),
+            // Enter finishes a code that could still grow, like "P", or else hands it in
+            KeyCode::Enter if !quiz.finish_code() => {
+                let result = self.game_state.grade_chart_quiz(quiz);
+                self.chart_quiz_result = Some(result);
+                self.save();
+            }
+            KeyCode::Char(ch) => {
+                quiz.type_char(ch);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_set_editor_key(&mut self, code: KeyCode) {
+        let Some(editor) = self.set_editor.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.set_editor = None,
+            KeyCode::Backspace => {
+                editor.input.pop();
+            }
+            KeyCode::Char(ch) => editor.input.push(ch),
+            KeyCode::Enter => match editor.input.parse::<bjsc::StudySet>() {
+                Ok(set) => {
+                    let cells = set.indices(&self.game_state.charts()).len();
+                    self.status = if cells == 0 {
+                        StatusMessage::Wrong(format!("{}: no cells match", set))
+                    } else {
+                        StatusMessage::Correct(format!("{} ({} cells)", set, cells))
+                    };
+                    self.game_state.save_study_set(set);
+                    self.set_editor = None;
+                    self.redeal_for_mode();
+                }
+                Err(e) => editor.error = Some(e.to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    /// Type a group name or join code; Enter creates or joins the group and shows it.
+    fn handle_group_editor_key(&mut self, code: KeyCode) {
+        let Some(editor) = self.group_editor.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.group_editor = None,
+            KeyCode::Backspace => {
+                editor.input.pop();
+            }
+            KeyCode::Char(ch) => editor.input.push(ch),
+            KeyCode::Enter if !editor.input.trim().is_empty() => {
+                let Some(ref auth) = self.auth else {
+                    return;
+                };
+                let config = supabase_config();
+                let input = editor.input.trim();
+                // Groups see the part of the email before the @
+                let name = auth.email.split('@').next().unwrap_or_default();
+                let result = if editor.joining {
+                    self.rt.block_on(bjsc::api::join_group(
+                        &api::ReqwestClient,
+                        &config,
+                        &auth.access_token,
+                        input,
+                        name,
+                    ))
+                } else {
+                    self.rt.block_on(bjsc::api::create_group(
+                        &api::ReqwestClient,
+                        &config,
+                        &auth.access_token,
+                        input,
+                        name,
+                    ))
+                };
+                match result {
+                    Ok(group) => {
+                        self.group_editor = None;
+                        self.status = StatusMessage::Info(format!(
+                            "In {} (join code {})",
+                            group.name, group.join_code
+                        ));
+                        self.refresh_groups();
+                        self.group = self
+                            .groups
+                            .iter()
+                            .position(|g| g.id == group.id)
+                            .unwrap_or(0);
+                        self.refresh_standings();
+                    }
+                    Err(e) => editor.error = Some(e),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Leave the group on show, which takes the player off its leaderboards.
+    fn leave_group(&mut self) {
+        let (Some(auth), Some(group)) = (&self.auth, self.groups.get(self.group)) else {
+            return;
+        };
+        let result = self.rt.block_on(bjsc::api::leave_group(
+            &api::ReqwestClient,
+            &supabase_config(),
+            &auth.access_token,
+            &group.id,
+            &auth.user_id,
+        ));
+        self.status = match result {
+            Ok(()) => StatusMessage::Info(format!("Left {}", group.name)),
+            Err(e) => StatusMessage::SyncError(e),
+        };
+        self.refresh_groups();
+    }
+
+    /// Fetch the groups the player has joined, then the board for the one on show.
+    fn refresh_groups(&mut self) {
+        let Some(ref auth) = self.auth else {
+            return;
+        };
+        match self.rt.block_on(bjsc::api::fetch_groups(
+            &api::ReqwestClient,
+            &supabase_config(),
+            &auth.access_token,
+        )) {
+            Ok(groups) => {
+                self.groups = groups;
+                self.group = self.group.min(self.groups.len().saturating_sub(1));
+                self.refresh_standings();
+            }
+            Err(e) => self.leaderboard_error = Some(e),
+        }
+    }
+
+    /// Fetch the leaderboard on show. The Blitz board is for the rules, strategy and Blitz
+    /// length being played.
+    fn refresh_standings(&mut self) {
+        self.standings.clear();
+        self.leaderboard_error = None;
+        let (Some(auth), Some(group)) = (&self.auth, self.groups.get(self.group)) else {
+            return;
+        };
+        let challenge = self.game_state.blitz_challenge(self.blitz_secs);
+        let today = chrono::Utc::now().date_naive().to_string();
+        match self.rt.block_on(bjsc::api::fetch_leaderboard(
+            &api::ReqwestClient,
+            &supabase_config(),
+            &auth.access_token,
+            &group.id,
+            self.board,
+            &challenge,
+            &today,
+        )) {
+            Ok(standings) => self.standings = standings,
+            Err(e) => self.leaderboard_error = Some(e),
+        }
+    }
+
+    /// Whether the player is the instructor of the class on show.
+    pub(crate) fn teaches_class(&self) -> bool {
+        match (&self.auth, self.classes.get(self.class)) {
+            (Some(auth), Some(class)) => class.instructor_id == auth.user_id,
+            _ => false,
+        }
+    }
+
+    /// Type a class name, join code or assignment; Enter creates or joins the class, or
+    /// sets the class on show the assignment.
+    fn handle_class_editor_key(&mut self, code: KeyCode) {
+        let Some(editor) = self.class_editor.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.class_editor = None,
+            KeyCode::Backspace => {
+                editor.input.pop();
+            }
+            KeyCode::Char(ch) => editor.input.push(ch),
+            KeyCode::Enter if !editor.input.trim().is_empty() => {
+                let Some(ref auth) = self.auth else {
+                    return;
+                };
+                let config = supabase_config();
+                let client = &api::ReqwestClient;
+                let token = &auth.access_token;
+                let input = editor.input.trim();
+                let result = match editor.kind {
+                    ClassInput::New => self
+                        .rt
+                        .block_on(bjsc::api::create_class(client, &config, token, input))
+                        .map(|class| Some(class.id)),
+                    ClassInput::Join => {
+                        // The instructor sees the part of the email before the @
+                        let name = auth.email.split('@').next().unwrap_or_default();
+                        self.rt
+                            .block_on(bjsc::api::join_class(client, &config, token, input, name))
+                            .map(|class| Some(class.id))
+                    }
+                    ClassInput::Assign => {
+                        match (input.parse::<Assignment>(), self.classes.get(self.class)) {
+                            (Ok(assignment), Some(class)) => self
+                                .rt
+                                .block_on(bjsc::api::assign(
+                                    client,
+                                    &config,
+                                    token,
+                                    &class.id,
+                                    &assignment,
+                                ))
+                                .map(|()| None),
+                            (Err(e), _) => Err(e.to_string()),
+                            (_, None) => Err("No class to assign to".to_string()),
+                        }
+                    }
+                };
+                match result {
+                    Ok(class_id) => {
+                        self.class_editor = None;
+                        self.refresh_classes();
+                        if let Some(at) =
+                            class_id.and_then(|id| self.classes.iter().position(|c| c.id == id))
+                        {
+                            self.class = at;
+                            self.refresh_class();
+                        }
+                    }
+                    Err(e) => editor.error = Some(e),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Leave the class on show, which ends its instructor's view of the player's progress.
+    fn leave_class(&mut self) {
+        let (Some(auth), Some(class)) = (&self.auth, self.classes.get(self.class)) else {
+            return;
+        };
+        let result = self.rt.block_on(bjsc::api::leave_class(
+            &api::ReqwestClient,
+            &supabase_config(),
+            &auth.access_token,
+            &class.id,
+            &auth.user_id,
+        ));
+        self.status = match result {
+            Ok(()) => StatusMessage::Info(format!("Left {}", class.name)),
+            Err(e) => StatusMessage::SyncError(e),
+        };
+        self.refresh_classes();
+    }
+
+    /// Withdraw the assignment picked.
+    fn unassign(&mut self) {
+        let Some(ref auth) = self.auth else {
+            return;
+        };
+        let Some(id) = self.assignments.get(self.assignment).and_then(|a| a.id) else {
+            return;
+        };
+        if let Err(e) = self.rt.block_on(bjsc::api::unassign(
+            &api::ReqwestClient,
+            &supabase_config(),
+            &auth.access_token,
+            id,
+        )) {
+            self.class_error = Some(e);
+            return;
+        }
+        self.refresh_class();
+    }
+
+    /// Start the assignment picked: drill its study set, or take its exam.
+    fn start_assignment(&mut self) {
+        let Some(row) = self.assignments.get(self.assignment) else {
+            return;
+        };
+        match row.assignment.clone() {
+            Assignment::StudySet(set) => {
+                self.status = StatusMessage::Info(format!("Studying {}", set));
+                self.game_state.save_study_set(set);
+                self.redeal_for_mode();
+            }
+            Assignment::Exam { per_table } => {
+                // Ends any Blitz under way
+                self.blitz_started = None;
+                self.start_exam(per_table);
+            }
+        }
+        self.screen = Screen::Play;
+    }
+
+    /// Fetch the classes the player teaches or belongs to, then the one on show.
+    fn refresh_classes(&mut self) {
+        let Some(ref auth) = self.auth else {
+            return;
+        };
+        match self.rt.block_on(bjsc::api::fetch_classes(
+            &api::ReqwestClient,
+            &supabase_config(),
+            &auth.access_token,
+        )) {
+            Ok(classes) => {
+                self.classes = classes;
+                self.class = self.class.min(self.classes.len().saturating_sub(1));
+                self.refresh_class();
+            }
+            Err(e) => self.class_error = Some(e),
+        }
+    }
+
+    /// Fetch the assignments of the class on show, and for its instructor, how each member
+    /// is getting on.
+    fn refresh_class(&mut self) {
+        self.assignments.clear();
+        self.class_progress.clear();
+        self.class_error = None;
+        let (Some(auth), Some(class)) = (&self.auth, self.classes.get(self.class)) else {
+            return;
+        };
+        let config = supabase_config();
+        let result = self.rt.block_on(bjsc::api::fetch_assignments(
+            &api::ReqwestClient,
+            &config,
+            &auth.access_token,
+            std::slice::from_ref(&class.id),
+        ));
+        match result {
+            Ok(assignments) => self.assignments = assignments,
+            Err(e) => self.class_error = Some(e),
+        }
+        self.assignment = self
+            .assignment
+            .min(self.assignments.len().saturating_sub(1));
+        if class.instructor_id != auth.user_id {
+            return;
+        }
+        match self.rt.block_on(bjsc::api::fetch_class_progress(
+            &api::ReqwestClient,
+            &config,
+            &auth.access_token,
+            &class.id,
+            1000,
+        )) {
+            Ok(progress) => self.class_progress = progress,
+            Err(e) => self.class_error = Some(e),
+        }
+    }
+
+    /// Switch to new table rules and deal a fresh hand under them.
+    fn set_rules(&mut self, rules: bjsc::Rules) {
+        self.game_state.set_rules(rules);
+        let dealt = self.game_state.deal_a_hand();
+        self.drill_waiting = !dealt && waits_when_empty(self.game_state.study_mode());
+        self.show_shuffle_prompt = !dealt && !self.drill_waiting;
+        self.status = StatusMessage::None;
+        self.explanation = None;
+        self.save();
+    }
+
     fn save(&mut self) {
         // Save locally
         persistence::save_state(&bjsc::SavedState {
             mode: self.game_state.study_mode(),
             deck: self.game_state.deck().clone(),
+            rules: *self.game_state.rules(),
+            preset: bjsc::preset_for_rules(self.game_state.rules()).map(|p| p.key.to_string()),
+            chart_file: self.chart_file.clone(),
+            strategy: self.game_state.strategy_kind(),
+            baseline: Some(*self.game_state.baseline()),
+            study_sets: self.game_state.study_sets().to_vec(),
+            study_set: self.game_state.study_set().map(|s| s.name.clone()),
+            lesson: self.game_state.lesson(),
+            half_credit: self.game_state.half_credit(),
         });
 
         // Sync to cloud in background
         if let Some(ref auth) = self.auth {
             let config = supabase_config();
             let auth_clone = auth.clone();
-            let mode = self.game_state.study_mode();
-            let deck = self.game_state.deck().clone();
+            let row = bjsc::supabase::UserDeckRow::new(
+                &auth.user_id,
+                self.game_state.study_mode(),
+                self.game_state.deck().clone(),
+                *self.game_state.rules(),
+                self.game_state.strategy_kind(),
+            )
+            .with_study_sets(self.game_state.study_sets(), self.game_state.study_set())
+            .with_lesson(self.game_state.lesson())
+            .with_half_credit(self.game_state.half_credit());
 
             let err_tx = self.sync_error_tx.clone();
             self.rt.spawn(async move {
@@ -333,9 +1307,7 @@ impl App {
                     &api::ReqwestClient,
                     &config,
                     &auth_clone.access_token,
-                    &auth_clone.user_id,
-                    mode,
-                    &deck,
+                    &row,
                 )
                 .await;
                 if let Err(e) = result {
@@ -346,9 +1318,7 @@ impl App {
                             &api::ReqwestClient,
                             &config,
                             &new_auth.access_token,
-                            &new_auth.user_id,
-                            mode,
-                            &deck,
+                            &row,
                         )
                         .await
                         {
@@ -396,11 +1366,16 @@ impl App {
 
             let config = supabase_config();
             let auth_clone = auth.clone();
+            let strategy = self.game_state.coaching_prompt();
 
             self.rt.spawn(async move {
-                let result =
-                    bjsc::api::get_coaching(&api::ReqwestClient, &config, &auth_clone.access_token)
-                        .await;
+                let result = bjsc::api::get_coaching(
+                    &api::ReqwestClient,
+                    &config,
+                    &auth_clone.access_token,
+                    &strategy,
+                )
+                .await;
 
                 // If failed, try refreshing token and retry
                 let result = if result.is_err() {
@@ -411,6 +1386,7 @@ impl App {
                             &api::ReqwestClient,
                             &config,
                             &new_auth.access_token,
+                            &strategy,
                         )
                         .await
                     } else {
@@ -453,19 +1429,38 @@ impl App {
                 &api::ReqwestClient,
                 &config,
                 &auth.access_token,
+                &auth.user_id,
                 1000,
             )) {
                 self.progress = bjsc::progress::ProgressStats::from_logs(&logs);
+                self.game_state.set_weak_history(&self.progress);
             }
         }
     }
 
+    /// Restart the answer clock when a new hand is dealt, and while the hand is out of
+    /// view, so only time spent looking at it counts.
+    fn time_the_hand(&mut self) {
+        let in_view = self.screen == Screen::Play
+            && !self.drill_waiting
+            && !self.show_shuffle_prompt
+            && self.screen_picker.is_none()
+            && self.set_editor.is_none()
+            && self.exam_report.is_none()
+            && !self.confirm_quit;
+        if !in_view || self.shown_deal != self.game_state.deals() {
+            self.shown_at = std::time::Instant::now();
+            self.shown_deal = self.game_state.deals();
+        }
+    }
+
     fn log_answer(
         &self,
         table_index_key: &str,
         correct: bool,
         player_action: &str,
         correct_action: &str,
+        latency_ms: Option<u32>,
     ) {
         if let Some(ref auth) = self.auth {
             let config = supabase_config();
@@ -476,6 +1471,7 @@ impl App {
                 correct,
                 player_action: player_action.to_string(),
                 correct_action: correct_action.to_string(),
+                latency_ms,
             };
             let err_tx = self.sync_error_tx.clone();
             self.rt.spawn(async move {
@@ -489,7 +1485,53 @@ impl App {
     }
 }
 
+/// Modes that wait, rather than offer a new shoe, when they have nothing to deal.
+fn waits_when_empty(mode: bjsc::StudyMode) -> bool {
+    matches!(
+        mode,
+        bjsc::StudyMode::Drill
+            | bjsc::StudyMode::Curriculum
+            | bjsc::StudyMode::Changes
+            | bjsc::StudyMode::Custom
+            | bjsc::StudyMode::Weak
+    )
+}
+
+/// Parse `--chart <file>` (grade against a custom chart) and `--no-chart`.
+fn chart_arg() -> Option<Option<String>> {
+    let mut args = std::env::args().skip(1);
+    let mut result = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--chart" => result = Some(args.next()),
+            "--no-chart" => result = Some(None),
+            _ => {}
+        }
+    }
+    result
+}
+
+/// `--verify`: cross-check the phrases, rendered tables and coaching prompt against the
+/// charts for every preset, and report any mismatch.
+fn verify() -> bool {
+    let mut ok = true;
+    for preset in bjsc::PRESETS.iter() {
+        for mismatch in bjsc::verify::verify(&preset.rules) {
+            eprintln!("{}: {}", preset.key, mismatch);
+            ok = false;
+        }
+    }
+    if ok {
+        println!("Charts, phrases and coaching prompt agree.");
+    }
+    ok
+}
+
 fn main() -> io::Result<()> {
+    if std::env::args().any(|arg| arg == "--verify") {
+        std::process::exit(if verify() { 0 } else { 1 });
+    }
+    let chart_arg = chart_arg();
     let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
 
     // Authenticate before entering TUI
@@ -520,11 +1562,12 @@ fn main() -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(auth, rt);
+    let mut app = App::new(auth, rt, chart_arg);
 
     loop {
         app.poll_coaching();
         app.poll_sync_errors();
+        app.tick_blitz();
         // In drill waiting mode, try to deal when a card becomes due
         if app.drill_waiting {
             let ready = app.game_state.drill_wait_secs().is_none();
@@ -532,6 +1575,7 @@ fn main() -> io::Result<()> {
                 app.drill_waiting = false;
             }
         }
+        app.time_the_hand();
         draw::draw(&mut terminal, &app)?;
 
         if event::poll(std::time::Duration::from_millis(100))?
@@ -2,13 +2,34 @@ mod api;
 mod auth;
 
 use auth::AuthState;
+use bjsc::blitz::{BLITZ_LENGTHS_SECS, BLITZ_SECS, BlitzScore};
+use bjsc::chart_quiz::{ChartQuiz, ChartQuizResult, QUIZ_TABLES};
+use bjsc::class::{Assignment, MemberProgress};
+use bjsc::exam::ExamReport;
+use bjsc::leaderboard::{Board, Standing};
+use bjsc::supabase::{AssignmentRow, ClassRow, GroupRow};
 use bjsc::{Action, GameState, Stats, SupabaseConfig};
+use gloo_storage::{LocalStorage, Storage};
 use leptos::prelude::*;
 use spaced_rep::NUM_BOXES;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
 
+/// Questions from each table in a short exam.
+const EXAM_SAMPLE_PER_TABLE: usize = 10;
+
+/// What's being typed on the Class screen.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ClassInput {
+    /// A name for a new class.
+    New,
+    /// A class's join code.
+    Join,
+    /// An assignment for the class on show, e.g. "exam 10".
+    Assign,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Screen {
     Play,
@@ -16,6 +37,8 @@ enum Screen {
     Progress,
     Coach,
     Strategy,
+    Leaderboard,
+    Class,
 }
 
 impl Screen {
@@ -26,10 +49,18 @@ impl Screen {
             Screen::Progress => "Progress",
             Screen::Coach => "Coach",
             Screen::Strategy => "Strategy",
+            Screen::Leaderboard => "Leaderboard",
+            Screen::Class => "Class",
         }
     }
 }
 
+/// Today in UTC as "YYYY-MM-DD", the days practice streaks are counted in.
+fn utc_date_string() -> String {
+    let iso: String = js_sys::Date::new_0().to_iso_string().into();
+    iso[..10].to_string()
+}
+
 fn local_date_string() -> String {
     let d = js_sys::Date::new_0();
     format!(
@@ -46,6 +77,25 @@ thread_local! {
         gs.deal_a_hand();
         gs
     });
+    /// The deal count of the hand on the table and when it was shown, in milliseconds since
+    /// the epoch. Answers are timed from here.
+    static SHOWN: Cell<(u64, f64)> = const { Cell::new((0, 0.0)) };
+}
+
+/// Start the answer clock for a newly dealt hand, or with `restart`, when the hand comes
+/// back into view.
+fn time_the_hand(restart: bool) {
+    let deals = GAME.with_borrow(|gs| gs.deals());
+    SHOWN.with(|shown| {
+        if restart || shown.get().0 != deals {
+            shown.set((deals, js_sys::Date::now()));
+        }
+    });
+}
+
+/// Milliseconds since the hand on the table was shown.
+fn answer_latency() -> u32 {
+    SHOWN.with(|shown| (js_sys::Date::now() - shown.get().1).max(0.0) as u32)
 }
 
 fn supabase_config() -> SupabaseConfig {
@@ -57,17 +107,21 @@ fn main() {
     leptos::mount::mount_to_body(App);
 }
 
-/// Start a 1-second interval that ticks the countdown display while drill-waiting.
-/// When a card becomes due, deals it and clears the interval.
-/// If not in drill-waiting state, clears any existing interval.
-fn schedule_drill_timer(timer_id: RwSignal<Option<i32>>, game_display: RwSignal<DisplayData>) {
-    // Clear existing interval
+/// Stop an interval timer, if it's running.
+fn clear_timer(timer_id: RwSignal<Option<i32>>) {
     if let Some(id) = timer_id.get_untracked() {
         if let Some(w) = web_sys::window() {
             w.clear_interval_with_handle(id);
         }
         timer_id.set(None);
     }
+}
+
+/// Start a 1-second interval that ticks the countdown display while drill-waiting.
+/// When a card becomes due, deals it and clears the interval.
+/// If not in drill-waiting state, clears any existing interval.
+fn schedule_drill_timer(timer_id: RwSignal<Option<i32>>, game_display: RwSignal<DisplayData>) {
+    clear_timer(timer_id);
 
     let wait = GAME.with_borrow(|gs| gs.drill_wait_secs());
     if wait.is_some() {
@@ -78,13 +132,7 @@ fn schedule_drill_timer(timer_id: RwSignal<Option<i32>>, game_display: RwSignal<
                 GAME.with_borrow_mut(|gs| {
                     gs.deal_a_hand();
                 });
-                // Clear the interval
-                if let Some(id) = timer_id.get_untracked() {
-                    if let Some(w) = web_sys::window() {
-                        w.clear_interval_with_handle(id);
-                    }
-                    timer_id.set(None);
-                }
+                clear_timer(timer_id);
             }
             // Refresh display (updates countdown or shows new card)
             game_display.set(read_display());
@@ -118,10 +166,26 @@ struct DisplayData {
     mastered_count: u32,
     due_count: u32,
     mode_key: String,
+    /// The mode's name, or the study set's in the Custom mode.
+    mode_label: String,
+    study_sets: Vec<bjsc::StudySet>,
+    /// Name of the study set in use.
+    study_set: Option<String>,
     drill_wait_secs: Option<u64>,
+    rules: bjsc::Rules,
+    hole_card: bool,
+    custom_charts: Option<bjsc::ChartSet>,
+    strategy: bjsc::StrategyKind,
+    /// The charts answers are graded against.
+    charts: bjsc::ChartSet,
+    /// Questions answered and asked in the exam being taken.
+    exam: Option<(usize, usize)>,
+    /// Right and wrong answers in the Blitz being played.
+    blitz: Option<(u32, u32)>,
 }
 
 fn read_display() -> DisplayData {
+    time_the_hand(false);
     GAME.with_borrow(|gs| {
         let s = gs.stats();
         let ds = gs.deck_summary();
@@ -134,6 +198,9 @@ fn read_display() -> DisplayData {
             split: Stats::numbers_string(s.split_count, s.split_wrong),
             double: Stats::numbers_string(s.double_count, s.double_wrong),
             mode_key: gs.study_mode().key().to_string(),
+            mode_label: gs.mode_label(),
+            study_sets: gs.study_sets().to_vec(),
+            study_set: gs.study_set().map(|s| s.name.clone()),
             box_counts: gs.box_counts(),
             box_due: gs.box_due_counts(),
             unseen: gs.unseen_count(),
@@ -142,10 +209,97 @@ fn read_display() -> DisplayData {
             mastered_count: ds.mastered,
             due_count: ds.due,
             drill_wait_secs: gs.drill_wait_secs(),
+            rules: *gs.rules(),
+            hole_card: gs.dealer_hand().hole_card().is_some(),
+            custom_charts: gs.custom_charts().cloned(),
+            strategy: gs.strategy_kind(),
+            charts: gs.charts(),
+            exam: gs.exam().map(|e| (e.answered(), e.len())),
+            blitz: gs.blitz().map(|b| (b.correct(), b.wrong())),
         }
     })
 }
 
+const EXAMS_KEY: &str = "bjsc_exams";
+
+/// Add an exam report to those kept in local storage.
+fn save_exam_locally(report: &ExamReport) {
+    let mut exams: Vec<ExamReport> = LocalStorage::get(EXAMS_KEY).unwrap_or_default();
+    exams.push(report.clone());
+    let _ = LocalStorage::set(EXAMS_KEY, exams);
+}
+
+/// Save an exam report to Supabase (fire-and-forget).
+fn save_exam_to_cloud(auth: &AuthState, report: &ExamReport) {
+    let config = supabase_config();
+    let token = auth.access_token.clone();
+    let row = bjsc::supabase::ExamResultRow {
+        user_id: auth.user_id.clone(),
+        report: report.clone(),
+    };
+
+    leptos::task::spawn_local(async move {
+        if let Err(e) = bjsc::api::insert_exam_result(&api::GlooClient, &config, &token, &row).await
+        {
+            web_sys::console::warn_1(&format!("Exam sync failed: {}", e).into());
+        }
+    });
+}
+
+const BLITZ_KEY: &str = "bjsc_blitz";
+
+/// Keep a Blitz score in local storage if it's a personal best. Returns whether it was.
+fn save_blitz_locally(score: &BlitzScore) -> bool {
+    let mut bests: Vec<BlitzScore> = LocalStorage::get(BLITZ_KEY).unwrap_or_default();
+    let new_best = bjsc::blitz::record_best(&mut bests, score);
+    if new_best {
+        let _ = LocalStorage::set(BLITZ_KEY, bests);
+    }
+    new_best
+}
+
+/// Save a Blitz score to Supabase (fire-and-forget).
+fn save_blitz_to_cloud(auth: &AuthState, score: &BlitzScore) {
+    let config = supabase_config();
+    let token = auth.access_token.clone();
+    let row = bjsc::supabase::BlitzScoreRow {
+        user_id: auth.user_id.clone(),
+        score: score.clone(),
+    };
+
+    leptos::task::spawn_local(async move {
+        if let Err(e) = bjsc::api::insert_blitz_score(&api::GlooClient, &config, &token, &row).await
+        {
+            web_sys::console::warn_1(&format!("Blitz sync failed: {}", e).into());
+        }
+    });
+}
+
+/// Fetch the cloud's personal best for a Blitz, keeping it locally too, and show it if it
+/// beats the local one.
+fn fetch_blitz_best(auth: &AuthState, challenge: BlitzScore, best: RwSignal<Option<BlitzScore>>) {
+    let config = supabase_config();
+    let token = auth.access_token.clone();
+    let user_id = auth.user_id.clone();
+
+    leptos::task::spawn_local(async move {
+        match bjsc::api::fetch_blitz_best(&api::GlooClient, &config, &token, &user_id, &challenge)
+            .await
+        {
+            Ok(Some(cloud)) => {
+                save_blitz_locally(&cloud);
+                if best.get_untracked().is_none_or(|b| cloud.beats(&b)) {
+                    best.set(Some(cloud));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                web_sys::console::warn_1(&format!("Fetch Blitz best failed: {}", e).into());
+            }
+        }
+    });
+}
+
 /// Log an answer to Supabase (fire-and-forget).
 fn log_answer_to_cloud(
     auth: &AuthState,
@@ -153,6 +307,7 @@ fn log_answer_to_cloud(
     correct: bool,
     player_action: &str,
     correct_action: &str,
+    latency_ms: Option<u32>,
 ) {
     let config = supabase_config();
     let token = auth.access_token.clone();
@@ -162,6 +317,7 @@ fn log_answer_to_cloud(
         correct,
         player_action: player_action.to_string(),
         correct_action: correct_action.to_string(),
+        latency_ms,
     };
 
     leptos::task::spawn_local(async move {
@@ -176,14 +332,21 @@ fn log_answer_to_cloud(
 fn save_to_cloud(auth: &AuthState) {
     let config = supabase_config();
     let token = auth.access_token.clone();
-    let user_id = auth.user_id.clone();
-    let (mode, deck) = GAME.with_borrow(|gs| (gs.study_mode(), gs.deck().clone()));
+    let row = GAME.with_borrow(|gs| {
+        bjsc::supabase::UserDeckRow::new(
+            &auth.user_id,
+            gs.study_mode(),
+            gs.deck().clone(),
+            *gs.rules(),
+            gs.strategy_kind(),
+        )
+        .with_study_sets(gs.study_sets(), gs.study_set())
+        .with_lesson(gs.lesson())
+        .with_half_credit(gs.half_credit())
+    });
 
     leptos::task::spawn_local(async move {
-        if let Err(e) =
-            bjsc::api::upsert_user_deck(&api::GlooClient, &config, &token, &user_id, mode, &deck)
-                .await
-        {
+        if let Err(e) = bjsc::api::upsert_user_deck(&api::GlooClient, &config, &token, &row).await {
             web_sys::console::warn_1(&format!("Cloud save failed: {}", e).into());
         }
     });
@@ -233,6 +396,7 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
     let status_is_error = RwSignal::new(false);
     let status_visible = RwSignal::new(false);
     let errors: RwSignal<Vec<String>> = RwSignal::new(vec![]);
+    let explanation: RwSignal<Option<bjsc::CellExplanation>> = RwSignal::new(None);
     let show_shuffle = RwSignal::new(false);
     let screen = RwSignal::new(Screen::Play);
     let coaching_text = RwSignal::new(String::new());
@@ -242,6 +406,16 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
         RwSignal::new(bjsc::progress::ProgressStats::default());
     let loading = RwSignal::new(true);
     let drill_timer_id: RwSignal<Option<i32>> = RwSignal::new(None);
+    // When the exam being taken started, in milliseconds since the epoch
+    let exam_started: RwSignal<Option<f64>> = RwSignal::new(None);
+    let exam_report: RwSignal<Option<ExamReport>> = RwSignal::new(None);
+    // The next Blitz's length, when the one being played started, the time it has left and
+    // the personal best to beat
+    let blitz_secs = RwSignal::new(BLITZ_SECS);
+    let blitz_started: RwSignal<Option<f64>> = RwSignal::new(None);
+    let blitz_left_ms: RwSignal<Option<u64>> = RwSignal::new(None);
+    let blitz_best: RwSignal<Option<BlitzScore>> = RwSignal::new(None);
+    let blitz_timer_id: RwSignal<Option<i32>> = RwSignal::new(None);
 
     let sync_all = move || {
         game_display.set(read_display());
@@ -276,8 +450,15 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
             match result {
                 Ok(Some(row)) => {
                     GAME.with_borrow_mut(|gs| {
-                        gs.set_deck(row.deck);
                         gs.set_study_mode(row.study_mode);
+                        gs.set_study_sets(row.study_sets, row.study_set);
+                        // Before the deck, which is migrated to the keys of these rules and
+                        // strategy, and the lesson, which was unlocked under them
+                        gs.set_rules(bjsc::resolve_rules(row.preset.as_deref(), row.rules));
+                        gs.set_strategy(row.strategy);
+                        gs.set_deck(row.deck);
+                        gs.set_lesson(row.lesson);
+                        gs.set_half_credit(row.half_credit);
                         gs.deal_a_hand();
                     });
                 }
@@ -311,8 +492,60 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
         if game_display.get_untracked().drill_wait_secs.is_some() {
             return;
         }
+        // Blitz answers count against the clock, not towards the stats
+        if GAME.with_borrow(|gs| gs.blitz().is_some()) {
+            let elapsed = blitz_started
+                .get_untracked()
+                .map_or(0.0, |t| js_sys::Date::now() - t);
+            // None once time's up: the answer is too late, and the next tick ends the Blitz
+            let Some((correct, right)) =
+                GAME.with_borrow_mut(|gs| gs.answer_blitz(action, elapsed as u64))
+            else {
+                return;
+            };
+            status_text.set(if correct {
+                "Right".to_string()
+            } else {
+                format!(
+                    "Wrong: {} (-{}s)",
+                    right,
+                    bjsc::blitz::WRONG_ANSWER_PENALTY_MS / 1000
+                )
+            });
+            status_is_error.set(!correct);
+            status_visible.set(true);
+            sync_all();
+            return;
+        }
+        // Exam answers get no feedback until the exam is graded
+        if let Some(more) = GAME.with_borrow_mut(|gs| gs.answer_exam(action)) {
+            if more {
+                sync_all();
+                return;
+            }
+            let duration_ms = exam_started
+                .get_untracked()
+                .map_or(0.0, |t| js_sys::Date::now() - t);
+            exam_started.set(None);
+            let taken_at: String = js_sys::Date::new_0().to_iso_string().into();
+            let report =
+                GAME.with_borrow_mut(|gs| gs.finish_exam(taken_at, (duration_ms / 1000.0) as u64));
+            if let Some(report) = report {
+                save_exam_locally(&report);
+                if let Some(auth) = auth_state.get_untracked() {
+                    save_exam_to_cloud(&auth, &report);
+                }
+                status_text.set(format!("Exam: {}", report.summary()));
+                status_is_error.set(!report.passed());
+                status_visible.set(true);
+                exam_report.set(Some(report));
+            }
+            sync_all();
+            schedule_drill_timer(drill_timer_id, game_display);
+            return;
+        }
         let outcome = GAME.with_borrow_mut(|gs| {
-            let result = gs.check_answer(action);
+            let result = gs.check_timed_answer(action, Some(answer_latency()));
             let shoe_done = if result.is_some() {
                 !gs.deal_a_hand()
             } else {
@@ -320,11 +553,22 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
             };
             (result, shoe_done)
         });
-        let is_drill = GAME.with_borrow(|gs| gs.study_mode() == bjsc::StudyMode::Drill);
+        // A new shoe doesn't help modes that deal from a list of cells
+        let is_drill = GAME.with_borrow(|gs| {
+            matches!(
+                gs.study_mode(),
+                bjsc::StudyMode::Drill
+                    | bjsc::StudyMode::Curriculum
+                    | bjsc::StudyMode::Changes
+                    | bjsc::StudyMode::Custom
+                    | bjsc::StudyMode::Weak
+            )
+        });
         if let (Some(result), shoe_done) = outcome {
             let log_data = result.log_data();
             status_text.set(result.status_message());
             status_is_error.set(!result.correct);
+            explanation.set(result.explanation.clone());
             if !result.correct
                 && let Some(entry) = result.log_entry
             {
@@ -339,7 +583,14 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
             if let Some(auth) = auth_state.get_untracked() {
                 save_to_cloud(&auth);
                 if let Some((key, was_correct, player_act, correct_act)) = log_data {
-                    log_answer_to_cloud(&auth, &key, was_correct, &player_act, &correct_act);
+                    log_answer_to_cloud(
+                        &auth,
+                        &key,
+                        was_correct,
+                        &player_act,
+                        &correct_act,
+                        result.latency_ms,
+                    );
                 }
             }
         }
@@ -347,6 +598,117 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
         schedule_drill_timer(drill_timer_id, game_display);
     };
 
+    // Start a full exam, or a short one of `per_table` cells from each table; cancels an
+    // exam under way instead.
+    let toggle_exam = move |per_table: Option<usize>| {
+        GAME.with_borrow_mut(|gs| {
+            if gs.exam().is_some() {
+                gs.cancel_exam();
+            } else {
+                gs.start_exam(per_table);
+            }
+        });
+        let started = GAME.with_borrow(|gs| gs.exam().is_some());
+        exam_started.set(started.then(js_sys::Date::now));
+        exam_report.set(None);
+        status_visible.set(false);
+        explanation.set(None);
+        show_shuffle.set(false);
+        sync_all();
+        schedule_drill_timer(drill_timer_id, game_display);
+    };
+
+    // End the Blitz: save the score here and in the cloud, and say if it's a new best.
+    let finish_blitz = move || {
+        clear_timer(blitz_timer_id);
+        blitz_started.set(None);
+        blitz_left_ms.set(None);
+        let taken_at: String = js_sys::Date::new_0().to_iso_string().into();
+        let Some(score) = GAME.with_borrow_mut(|gs| gs.finish_blitz(taken_at)) else {
+            return;
+        };
+        save_blitz_locally(&score);
+        if let Some(auth) = auth_state.get_untracked() {
+            save_blitz_to_cloud(&auth, &score);
+        }
+        let best = blitz_best.get_untracked();
+        status_text.set(match best {
+            Some(best) if !score.beats(&best) => {
+                format!("Blitz: {} (best: {} right)", score.summary(), best.correct)
+            }
+            _ => format!("Blitz: {}. New personal best!", score.summary()),
+        });
+        status_is_error.set(false);
+        status_visible.set(true);
+        sync_all();
+        schedule_drill_timer(drill_timer_id, game_display);
+    };
+
+    // Count the Blitz down, and end it once time is up
+    let tick_blitz = move || {
+        let elapsed = blitz_started
+            .get_untracked()
+            .map_or(0.0, |t| js_sys::Date::now() - t);
+        let left = GAME.with_borrow(|gs| gs.blitz().map(|b| b.remaining_ms(elapsed as u64)));
+        blitz_left_ms.set(left);
+        match left {
+            Some(0) => finish_blitz(),
+            // An exam took over
+            None => clear_timer(blitz_timer_id),
+            Some(_) => {}
+        }
+    };
+
+    // Start a Blitz, or cancel the one under way
+    let toggle_blitz = move || {
+        if GAME.with_borrow(|gs| gs.blitz().is_some()) {
+            GAME.with_borrow_mut(|gs| gs.cancel_blitz());
+            clear_timer(blitz_timer_id);
+            blitz_started.set(None);
+            blitz_left_ms.set(None);
+            status_text.set("Blitz cancelled".to_string());
+            status_is_error.set(true);
+            status_visible.set(true);
+        } else {
+            GAME.with_borrow_mut(|gs| gs.start_blitz(blitz_secs.get_untracked()));
+            blitz_started.set(Some(js_sys::Date::now()));
+            exam_started.set(None);
+            exam_report.set(None);
+            status_visible.set(false);
+            let challenge = GAME.with_borrow(|gs| gs.blitz().map(|b| b.score(String::new())));
+            if let Some(challenge) = challenge {
+                let bests: Vec<BlitzScore> = LocalStorage::get(BLITZ_KEY).unwrap_or_default();
+                blitz_best.set(bjsc::blitz::best_for(&bests, &challenge).cloned());
+                if let Some(auth) = auth_state.get_untracked() {
+                    fetch_blitz_best(&auth, challenge, blitz_best);
+                }
+            }
+            tick_blitz();
+            let cb = Closure::<dyn FnMut()>::new(tick_blitz);
+            if let Some(w) = web_sys::window()
+                && let Ok(id) = w.set_interval_with_callback_and_timeout_and_arguments_0(
+                    cb.as_ref().unchecked_ref(),
+                    250,
+                )
+            {
+                blitz_timer_id.set(Some(id));
+            }
+            cb.forget();
+        }
+        explanation.set(None);
+        show_shuffle.set(false);
+        sync_all();
+        schedule_drill_timer(drill_timer_id, game_display);
+    };
+
+    // Step through the Blitz lengths
+    let cycle_blitz_secs = move || {
+        let at = BLITZ_LENGTHS_SECS
+            .iter()
+            .position(|&s| s == blitz_secs.get_untracked());
+        blitz_secs.set(BLITZ_LENGTHS_SECS[at.map_or(0, |i| (i + 1) % BLITZ_LENGTHS_SECS.len())]);
+    };
+
     let do_shuffle = move || {
         GAME.with_borrow_mut(|gs| {
             gs.shuffle();
@@ -356,42 +718,479 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
         sync_all();
     };
 
+    // Fetch the answer history for the progress screen and the Weak spots mode. Weak spots
+    // that arrive after the mode was picked are dealt straight away.
+    let load_progress = move || {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let config = supabase_config();
+        let token = auth.access_token.clone();
+        let user_id = auth.user_id.clone();
+        leptos::task::spawn_local(async move {
+            if let Ok(logs) =
+                bjsc::api::fetch_answer_logs(&api::GlooClient, &config, &token, &user_id, 1000)
+                    .await
+            {
+                let stats = bjsc::progress::ProgressStats::from_logs(&logs);
+                GAME.with_borrow_mut(|gs| {
+                    let was_empty = gs.weak_spots().indices().is_empty();
+                    gs.set_weak_history(&stats);
+                    if gs.study_mode() == bjsc::StudyMode::Weak && was_empty {
+                        gs.deal_a_hand();
+                    }
+                });
+                progress_stats.set(stats);
+                sync_all();
+            }
+        });
+    };
+
     let set_mode = move |mode: bjsc::StudyMode| {
         GAME.with_borrow_mut(|gs| {
             gs.set_study_mode(mode);
+            if mode == bjsc::StudyMode::Weak {
+                gs.set_weak_history(&progress_stats.get_untracked());
+            }
+            gs.deal_a_hand();
+        });
+        if mode == bjsc::StudyMode::Weak {
+            load_progress();
+        }
+        status_visible.set(false);
+        show_shuffle.set(false);
+        sync_all();
+        schedule_drill_timer(drill_timer_id, game_display);
+
+        // Save mode change to cloud immediately
+        if let Some(auth) = auth_state.get_untracked() {
+            save_to_cloud(&auth);
+        }
+    };
+
+    // Study sets: pick one, save a new one typed as "name: filter", or delete one.
+    let study_set_error: RwSignal<Option<String>> = RwSignal::new(None);
+    let after_set_change = move || {
+        status_visible.set(false);
+        show_shuffle.set(false);
+        sync_all();
+        schedule_drill_timer(drill_timer_id, game_display);
+        if let Some(auth) = auth_state.get_untracked() {
+            save_to_cloud(&auth);
+        }
+    };
+    let select_set = move |name: String| {
+        GAME.with_borrow_mut(|gs| {
+            if gs.select_study_set(&name) {
+                gs.deal_a_hand();
+            }
+        });
+        after_set_change();
+    };
+    let save_set = move |text: String| -> bool {
+        match text.parse::<bjsc::StudySet>() {
+            Ok(set) => {
+                GAME.with_borrow_mut(|gs| {
+                    gs.save_study_set(set);
+                    gs.deal_a_hand();
+                });
+                study_set_error.set(None);
+                after_set_change();
+                true
+            }
+            Err(e) => {
+                study_set_error.set(Some(e.to_string()));
+                false
+            }
+        }
+    };
+    let remove_set = move |name: String| {
+        GAME.with_borrow_mut(|gs| {
+            gs.remove_study_set(&name);
+            gs.deal_a_hand();
+        });
+        after_set_change();
+    };
+
+    let set_rules = move |rules: bjsc::Rules| {
+        GAME.with_borrow_mut(|gs| {
+            gs.set_rules(rules);
+            gs.deal_a_hand();
+        });
+        status_visible.set(false);
+        show_shuffle.set(false);
+        sync_all();
+        schedule_drill_timer(drill_timer_id, game_display);
+
+        if let Some(auth) = auth_state.get_untracked() {
+            save_to_cloud(&auth);
+        }
+    };
+
+    let set_strategy = move |strategy: bjsc::StrategyKind| {
+        GAME.with_borrow_mut(|gs| gs.set_strategy(strategy));
+        sync_all();
+
+        if let Some(auth) = auth_state.get_untracked() {
+            save_to_cloud(&auth);
+        }
+    };
+
+    let set_custom_charts = move |charts: Option<bjsc::ChartSet>| {
+        GAME.with_borrow_mut(|gs| {
+            gs.set_custom_charts(charts);
             gs.deal_a_hand();
         });
-        status_visible.set(false);
-        show_shuffle.set(false);
-        sync_all();
-        schedule_drill_timer(drill_timer_id, game_display);
+        status_visible.set(false);
+        show_shuffle.set(false);
+        sync_all();
+    };
+
+    // Grade a chart filled in on the Strategy screen. Its wrong cells go into the deck.
+    let grade_quiz = move |quiz: &ChartQuiz| {
+        let result = GAME.with_borrow_mut(|gs| gs.grade_chart_quiz(quiz));
+        sync_all();
+        if let Some(auth) = auth_state.get_untracked() {
+            save_to_cloud(&auth);
+        }
+        result
+    };
+
+    let sign_out = move || {
+        auth::clear_storage();
+        auth_state.set(None);
+    };
+
+    // Leaderboards: the groups joined, the one shown, which board and its standings
+    let groups: RwSignal<Vec<GroupRow>> = RwSignal::new(Vec::new());
+    let group_idx = RwSignal::new(0usize);
+    let board = RwSignal::new(Board::default());
+    let standings: RwSignal<Vec<Standing>> = RwSignal::new(Vec::new());
+    let leaderboard_error: RwSignal<Option<String>> = RwSignal::new(None);
+
+    // Fetch the shown group's standings on the chosen board. The Blitz board is for the
+    // rules, strategy and length being played.
+    let load_standings = move || {
+        standings.set(Vec::new());
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let Some(group) = groups
+            .get_untracked()
+            .get(group_idx.get_untracked())
+            .cloned()
+        else {
+            return;
+        };
+        let challenge = GAME.with_borrow(|gs| gs.blitz_challenge(blitz_secs.get_untracked()));
+        let board = board.get_untracked();
+        let today = utc_date_string();
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            match bjsc::api::fetch_leaderboard(
+                &api::GlooClient,
+                &config,
+                &auth.access_token,
+                &group.id,
+                board,
+                &challenge,
+                &today,
+            )
+            .await
+            {
+                Ok(rows) => {
+                    leaderboard_error.set(None);
+                    standings.set(rows);
+                }
+                Err(e) => leaderboard_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Fetch the groups joined, then show `select` if given, or stay on the group shown.
+    let load_groups = move |select: Option<String>| {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            match bjsc::api::fetch_groups(&api::GlooClient, &config, &auth.access_token).await {
+                Ok(rows) => {
+                    let at = select
+                        .and_then(|id| rows.iter().position(|g| g.id == id))
+                        .unwrap_or(group_idx.get_untracked())
+                        .min(rows.len().saturating_sub(1));
+                    group_idx.set(at);
+                    groups.set(rows);
+                    leaderboard_error.set(None);
+                    load_standings();
+                }
+                Err(e) => leaderboard_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Create a group named `input`, or join the one whose join code it is. Members see the
+    // local part of the email address.
+    let create_or_join = move |joining: bool, input: String| {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let input = input.trim().to_string();
+        if input.is_empty() {
+            return;
+        }
+        let display_name = auth.email.split('@').next().unwrap_or_default().to_string();
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            let token = &auth.access_token;
+            let result = if joining {
+                bjsc::api::join_group(&api::GlooClient, &config, token, &input, &display_name).await
+            } else {
+                bjsc::api::create_group(&api::GlooClient, &config, token, &input, &display_name)
+                    .await
+            };
+            match result {
+                Ok(group) => load_groups(Some(group.id)),
+                Err(e) => leaderboard_error.set(Some(e)),
+            }
+        });
+    };
+
+    let leave_group = move || {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let Some(group) = groups
+            .get_untracked()
+            .get(group_idx.get_untracked())
+            .cloned()
+        else {
+            return;
+        };
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            match bjsc::api::leave_group(
+                &api::GlooClient,
+                &config,
+                &auth.access_token,
+                &group.id,
+                &auth.user_id,
+            )
+            .await
+            {
+                Ok(()) => {
+                    group_idx.set(0);
+                    load_groups(None);
+                }
+                Err(e) => leaderboard_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Classes: those taught or joined, the one shown, its assignments and, for its
+    // instructor, each member's progress
+    let classes: RwSignal<Vec<ClassRow>> = RwSignal::new(Vec::new());
+    let class_idx = RwSignal::new(0usize);
+    let assignments: RwSignal<Vec<AssignmentRow>> = RwSignal::new(Vec::new());
+    let class_progress: RwSignal<Vec<MemberProgress>> = RwSignal::new(Vec::new());
+    let class_error: RwSignal<Option<String>> = RwSignal::new(None);
+
+    // Fetch the shown class's assignments, and for its instructor, how each member is
+    // getting on.
+    let load_class = move || {
+        assignments.set(Vec::new());
+        class_progress.set(Vec::new());
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let Some(class) = classes
+            .get_untracked()
+            .get(class_idx.get_untracked())
+            .cloned()
+        else {
+            return;
+        };
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            let token = &auth.access_token;
+            let ids = [class.id.clone()];
+            match bjsc::api::fetch_assignments(&api::GlooClient, &config, token, &ids).await {
+                Ok(rows) => {
+                    class_error.set(None);
+                    assignments.set(rows);
+                }
+                Err(e) => class_error.set(Some(e)),
+            }
+            if class.instructor_id != auth.user_id {
+                return;
+            }
+            match bjsc::api::fetch_class_progress(&api::GlooClient, &config, token, &class.id, 1000)
+                .await
+            {
+                Ok(rows) => class_progress.set(rows),
+                Err(e) => class_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Fetch the classes taught or joined, then show `select` if given, or stay on the class
+    // shown.
+    let load_classes = move |select: Option<String>| {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            match bjsc::api::fetch_classes(&api::GlooClient, &config, &auth.access_token).await {
+                Ok(rows) => {
+                    let at = select
+                        .and_then(|id| rows.iter().position(|c| c.id == id))
+                        .unwrap_or(class_idx.get_untracked())
+                        .min(rows.len().saturating_sub(1));
+                    class_idx.set(at);
+                    classes.set(rows);
+                    class_error.set(None);
+                    load_class();
+                }
+                Err(e) => class_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Create a class named `input`, join the one whose join code it is, or set the class
+    // shown the assignment it describes. The instructor sees the local part of the email
+    // address.
+    let class_input = move |kind: ClassInput, input: String| {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let input = input.trim().to_string();
+        if input.is_empty() {
+            return;
+        }
+        let class_id = classes
+            .get_untracked()
+            .get(class_idx.get_untracked())
+            .map(|c| c.id.clone());
+        let assignment = match (kind, input.parse::<Assignment>()) {
+            (ClassInput::Assign, Ok(assignment)) => Some(assignment),
+            (ClassInput::Assign, Err(e)) => {
+                class_error.set(Some(e.to_string()));
+                return;
+            }
+            _ => None,
+        };
+        let display_name = auth.email.split('@').next().unwrap_or_default().to_string();
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            let client = &api::GlooClient;
+            let token = &auth.access_token;
+            let result = match (kind, assignment, class_id) {
+                (ClassInput::New, _, _) => bjsc::api::create_class(client, &config, token, &input)
+                    .await
+                    .map(|c| Some(c.id)),
+                (ClassInput::Join, _, _) => {
+                    bjsc::api::join_class(client, &config, token, &input, &display_name)
+                        .await
+                        .map(|c| Some(c.id))
+                }
+                (ClassInput::Assign, Some(assignment), Some(id)) => {
+                    bjsc::api::assign(client, &config, token, &id, &assignment)
+                        .await
+                        .map(|()| Some(id))
+                }
+                (ClassInput::Assign, _, _) => Err("No class to assign to".to_string()),
+            };
+            match result {
+                Ok(select) => load_classes(select),
+                Err(e) => class_error.set(Some(e)),
+            }
+        });
+    };
+
+    let unassign = move |id: i64| {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            match bjsc::api::unassign(&api::GlooClient, &config, &auth.access_token, id).await {
+                Ok(()) => load_class(),
+                Err(e) => class_error.set(Some(e)),
+            }
+        });
+    };
 
-        // Save mode change to cloud immediately
-        if let Some(auth) = auth_state.get_untracked() {
-            save_to_cloud(&auth);
-        }
+    let leave_class = move || {
+        let Some(auth) = auth_state.get_untracked() else {
+            return;
+        };
+        let Some(class) = classes
+            .get_untracked()
+            .get(class_idx.get_untracked())
+            .cloned()
+        else {
+            return;
+        };
+        let config = supabase_config();
+        leptos::task::spawn_local(async move {
+            match bjsc::api::leave_class(
+                &api::GlooClient,
+                &config,
+                &auth.access_token,
+                &class.id,
+                &auth.user_id,
+            )
+            .await
+            {
+                Ok(()) => {
+                    class_idx.set(0);
+                    load_classes(None);
+                }
+                Err(e) => class_error.set(Some(e)),
+            }
+        });
     };
 
-    let sign_out = move || {
-        auth::clear_storage();
-        auth_state.set(None);
+    // Start an assignment: drill its study set, or take its exam in place of any exam or
+    // Blitz under way.
+    let start_assignment = move |assignment: Assignment| {
+        match assignment {
+            Assignment::StudySet(set) => {
+                GAME.with_borrow_mut(|gs| {
+                    gs.save_study_set(set);
+                    gs.deal_a_hand();
+                });
+                after_set_change();
+            }
+            Assignment::Exam { per_table } => {
+                if GAME.with_borrow(|gs| gs.blitz().is_some()) {
+                    toggle_blitz();
+                }
+                if GAME.with_borrow(|gs| gs.exam().is_some()) {
+                    toggle_exam(None);
+                }
+                toggle_exam(per_table);
+            }
+        }
+        time_the_hand(true);
+        screen.set(Screen::Play);
     };
 
     let menu_open = RwSignal::new(false);
 
     let go_to_screen = move |next: Screen| {
-        if next == Screen::Progress
-            && let Some(auth) = auth_state.get_untracked()
-        {
-            let config = supabase_config();
-            let token = auth.access_token.clone();
-            leptos::task::spawn_local(async move {
-                if let Ok(logs) =
-                    bjsc::api::fetch_answer_logs(&api::GlooClient, &config, &token, 1000).await
-                {
-                    progress_stats.set(bjsc::progress::ProgressStats::from_logs(&logs));
-                }
-            });
+        if next == Screen::Progress {
+            load_progress();
+        }
+        if next == Screen::Leaderboard {
+            // Publishes the practice streak before the board is read
+            load_progress();
+            load_groups(None);
+        }
+        if next == Screen::Class {
+            load_classes(None);
         }
         if next == Screen::Coach {
             let current_count = GAME.with_borrow(|gs| gs.stats().question_count);
@@ -410,17 +1209,25 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
                 coaching_text.set("Loading coaching advice...".to_string());
                 if let Some(auth) = auth_state.get_untracked() {
                     let config = supabase_config();
+                    let strategy = GAME.with_borrow(|gs| gs.coaching_prompt());
                     leptos::task::spawn_local(async move {
                         let mut token = auth.access_token.clone();
                         let result =
-                            bjsc::api::get_coaching(&api::GlooClient, &config, &token).await;
+                            bjsc::api::get_coaching(&api::GlooClient, &config, &token, &strategy)
+                                .await;
                         let result = if result.is_err() {
                             if let Some(new_auth) =
                                 bjsc::api::refresh_session(&api::GlooClient, &config, &auth).await
                             {
                                 token = new_auth.access_token.clone();
                                 auth_state.set(Some(new_auth));
-                                bjsc::api::get_coaching(&api::GlooClient, &config, &token).await
+                                bjsc::api::get_coaching(
+                                    &api::GlooClient,
+                                    &config,
+                                    &token,
+                                    &strategy,
+                                )
+                                .await
                             } else {
                                 result
                             }
@@ -435,6 +1242,9 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
                 }
             }
         }
+        if next == Screen::Play {
+            time_the_hand(true);
+        }
         screen.set(next);
         menu_open.set(false);
     };
@@ -480,6 +1290,14 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
                         go_to_screen(Screen::Strategy);
                         return;
                     }
+                    "l" => {
+                        go_to_screen(Screen::Leaderboard);
+                        return;
+                    }
+                    "a" => {
+                        go_to_screen(Screen::Class);
+                        return;
+                    }
                     _ => {}
                 }
                 return;
@@ -495,6 +1313,20 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
                 }
                 return;
             }
+            if key == "x" || key == "X" {
+                toggle_exam((key == "X").then_some(EXAM_SAMPLE_PER_TABLE));
+                return;
+            }
+            if key == "z" {
+                toggle_blitz();
+                return;
+            }
+            if key == "Z" {
+                if game_display.get_untracked().blitz.is_none() {
+                    cycle_blitz_secs();
+                }
+                return;
+            }
             if let Some(ch) = key.chars().next()
                 && let Some(action) = Action::from_key(ch)
             {
@@ -543,14 +1375,11 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
                                     bjsc::StudyMode::from_key(&key).map(|m| m.icon()).unwrap_or("\u{1F0CF}")
                                 }}
                                 <span class="hidden sm:inline ml-2 text-gray-200">
-                                    {move || {
-                                        let key = game_display.get().mode_key;
-                                        bjsc::StudyMode::from_key(&key).map(|m| m.to_string()).unwrap_or_default()
-                                    }}
+                                    {move || game_display.get().mode_label}
                                 </span>
                             </button>
                             <div
-                                class="absolute top-full left-0 mt-1 bg-slate-800 border border-gray-600 rounded shadow-lg z-30 w-48"
+                                class="absolute top-full left-0 mt-1 bg-slate-800 border border-gray-600 rounded shadow-lg z-30 w-72"
                                 class:hidden=move || !mode_dropdown_open.get()
                             >
                                 {bjsc::StudyMode::ALL.iter().map(|m| {
@@ -571,10 +1400,110 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
                                         </button>
                                     }
                                 }).collect::<Vec<_>>()}
+                                <div class="border-t border-gray-600 mt-1 pt-1">
+                                    {move || {
+                                        let d = game_display.get();
+                                        d.study_sets.iter().map(|set| {
+                                            let name = set.name.clone();
+                                            let current = d.mode_key == bjsc::StudyMode::Custom.key()
+                                                && d.study_set.as_ref() == Some(&name);
+                                            let (pick, remove) = (name.clone(), name.clone());
+                                            view! {
+                                                <div class="flex items-center hover:bg-slate-700">
+                                                    <button
+                                                        class="flex-1 text-left px-3 py-1.5 text-sm cursor-pointer"
+                                                        class:text-amber-300=current
+                                                        class:text-gray-300=!current
+                                                        title=set.filter()
+                                                        on:click=move |_| {
+                                                            select_set(pick.clone());
+                                                            mode_dropdown_open.set(false);
+                                                        }
+                                                    >
+                                                        {format!("{} {}", bjsc::StudyMode::Custom.icon(), name)}
+                                                    </button>
+                                                    <button
+                                                        aria-label="Delete study set"
+                                                        class="px-2 text-xs text-gray-500 cursor-pointer hover:text-red-400"
+                                                        on:click=move |_| remove_set(remove.clone())
+                                                    >
+                                                        "\u{2715}"
+                                                    </button>
+                                                </div>
+                                            }
+                                        }).collect::<Vec<_>>()
+                                    }}
+                                    {
+                                        let new_set = RwSignal::new(String::new());
+                                        view! {
+                                            <form
+                                                class="px-2 py-1.5"
+                                                on:submit=move |ev| {
+                                                    ev.prevent_default();
+                                                    if save_set(new_set.get_untracked()) {
+                                                        new_set.set(String::new());
+                                                        mode_dropdown_open.set(false);
+                                                    }
+                                                }
+                                            >
+                                                <input
+                                                    aria-label="New study set"
+                                                    class="w-full text-xs px-2 py-1 rounded bg-slate-900 border border-gray-600 text-gray-200"
+                                                    placeholder="Soft doubles: soft vs 3-6 double"
+                                                    prop:value=move || new_set.get()
+                                                    on:input=move |ev| new_set.set(event_target_value(&ev))
+                                                />
+                                                <p
+                                                    class="text-xs text-red-400 mt-1"
+                                                    class:hidden=move || study_set_error.get().is_none()
+                                                >
+                                                    {move || study_set_error.get().unwrap_or_default()}
+                                                </p>
+                                            </form>
+                                        }
+                                    }
+                                </div>
                             </div>
                         </div>
                     }
                 }
+                // Exam controls (play screen only)
+                <div class="flex gap-2" class:hidden=move || screen.get() != Screen::Play>
+                    <button
+                        class="text-sm px-2 py-1 border border-gray-600 rounded bg-slate-800 cursor-pointer hover:border-cyan-400 text-gray-300"
+                        class:hidden=move || game_display.get().exam.is_some()
+                        title="Every cell once (x)"
+                        on:click=move |_| toggle_exam(None)
+                    >"Exam"</button>
+                    <button
+                        class="text-sm px-2 py-1 border border-gray-600 rounded bg-slate-800 cursor-pointer hover:border-cyan-400 text-gray-300"
+                        class:hidden=move || game_display.get().exam.is_some()
+                        title=format!("{} cells from each table (X)", EXAM_SAMPLE_PER_TABLE)
+                        on:click=move |_| toggle_exam(Some(EXAM_SAMPLE_PER_TABLE))
+                    >"Short exam"</button>
+                    <button
+                        class="text-sm px-2 py-1 border border-red-800 rounded bg-slate-800 cursor-pointer hover:bg-red-950 text-red-400"
+                        class:hidden=move || game_display.get().exam.is_none()
+                        on:click=move |_| toggle_exam(None)
+                    >"Cancel exam"</button>
+                    <button
+                        class="text-sm px-2 py-1 border border-gray-600 rounded bg-slate-800 cursor-pointer hover:border-cyan-400 text-gray-300"
+                        class:hidden=move || game_display.get().blitz.is_some()
+                        title="As many right as you can before time's up (z)"
+                        on:click=move |_| toggle_blitz()
+                    >"Blitz"</button>
+                    <button
+                        class="text-sm px-2 py-1 border border-gray-600 rounded bg-slate-800 cursor-pointer hover:border-cyan-400 text-gray-400"
+                        class:hidden=move || game_display.get().blitz.is_some()
+                        title="Blitz length (Z)"
+                        on:click=move |_| cycle_blitz_secs()
+                    >{move || format!("{}s", blitz_secs.get())}</button>
+                    <button
+                        class="text-sm px-2 py-1 border border-red-800 rounded bg-slate-800 cursor-pointer hover:bg-red-950 text-red-400"
+                        class:hidden=move || game_display.get().blitz.is_none()
+                        on:click=move |_| toggle_blitz()
+                    >"Cancel Blitz"</button>
+                </div>
                 // Username + hamburger menu (right side)
                 <span class="ml-auto text-xs text-gray-500">{move || auth_state.get().map(|a| a.email).unwrap_or_default()}</span>
                 <button
@@ -640,6 +1569,20 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
                         class:text-gray-300=move || screen.get() != Screen::Strategy
                         on:click=move |_| go_to_screen(Screen::Strategy)
                     >"S"<span class="text-amber-400 font-bold">"t"</span>"rategy"</button>
+                    <button
+                        class="text-left px-3 py-2 rounded text-sm hover:bg-slate-800"
+                        class:text-cyan-400=move || screen.get() == Screen::Leaderboard
+                        class:font-bold=move || screen.get() == Screen::Leaderboard
+                        class:text-gray-300=move || screen.get() != Screen::Leaderboard
+                        on:click=move |_| go_to_screen(Screen::Leaderboard)
+                    ><span class="text-amber-400 font-bold">"L"</span>"eaderboard"</button>
+                    <button
+                        class="text-left px-3 py-2 rounded text-sm hover:bg-slate-800"
+                        class:text-cyan-400=move || screen.get() == Screen::Class
+                        class:font-bold=move || screen.get() == Screen::Class
+                        class:text-gray-300=move || screen.get() != Screen::Class
+                        on:click=move |_| go_to_screen(Screen::Class)
+                    >"Cl"<span class="text-amber-400 font-bold">"a"</span>"ss"</button>
                 </nav>
                 <div class="border-t border-gray-700 mx-2" />
                 <div class="px-2 py-2">
@@ -651,19 +1594,62 @@ fn GameView(auth_state: RwSignal<Option<AuthState>>) -> impl IntoView {
             </div>
 
             <HistogramScreen screen=screen game_data=game_display />
-            <ProgressScreen screen=screen progress_stats=progress_stats />
+            <ProgressScreen
+                screen=screen
+                progress_stats=progress_stats
+                practise=move || {
+                    set_mode(bjsc::StudyMode::Weak);
+                    go_to_screen(Screen::Play);
+                }
+            />
             <CoachScreen screen=screen coaching_text=coaching_text />
-            <StrategyScreen screen=screen />
+            <StrategyScreen
+                screen=screen
+                game_data=game_display
+                set_rules=set_rules
+                set_strategy=set_strategy
+                set_custom_charts=set_custom_charts
+                grade_quiz=grade_quiz
+            />
+            <LeaderboardScreen
+                screen=screen
+                auth_state=auth_state
+                groups=groups
+                group_idx=group_idx
+                board=board
+                standings=standings
+                leaderboard_error=leaderboard_error
+                blitz_secs=blitz_secs
+                load_standings=load_standings
+                create_or_join=create_or_join
+                leave_group=leave_group
+            />
+            <ClassScreen
+                screen=screen
+                auth_state=auth_state
+                classes=classes
+                class_idx=class_idx
+                assignments=assignments
+                class_progress=class_progress
+                class_error=class_error
+                load_class=load_class
+                class_input=class_input
+                unassign=unassign
+                leave_class=leave_class
+                start_assignment=start_assignment
+            />
             <PlayScreen
                 screen=screen game_data=game_display
                 status_text=status_text status_is_error=status_is_error status_visible=status_visible
-                show_shuffle=show_shuffle errors=errors
+                show_shuffle=show_shuffle errors=errors explanation=explanation
+                exam_report=exam_report
+                blitz_left_ms=blitz_left_ms blitz_best=blitz_best
                 do_action=do_action do_shuffle=do_shuffle
             />
 
             // Keyboard hint
             <div class="flex justify-between text-xs py-4">
-                <span class="text-gray-400">"Keyboard: h / s / d / p"</span>
+                <span class="text-gray-400">"Keyboard: h / s / d / p, x exam, z blitz"</span>
                 <span class="text-gray-600">{env!("BUILD_TIME")}</span>
             </div>
         </div>
@@ -729,10 +1715,17 @@ fn HistogramScreen(screen: RwSignal<Screen>, game_data: RwSignal<DisplayData>) -
 fn ProgressScreen(
     screen: RwSignal<Screen>,
     progress_stats: RwSignal<bjsc::progress::ProgressStats>,
+    practise: impl Fn() + Copy + 'static,
 ) -> impl IntoView {
     view! {
         <div class:hidden=move || screen.get() != Screen::Progress>
-            <h2 class="font-bold text-cyan-400 text-lg mb-4">"Progress Dashboard"</h2>
+            <div class="flex justify-between items-center mb-4">
+                <h2 class="font-bold text-cyan-400 text-lg">"Progress Dashboard"</h2>
+                <button
+                    class="px-3 py-1 rounded text-sm border border-red-800 text-red-400 hover:bg-red-950"
+                    on:click=move |_| practise()
+                >{format!("{} Practise weak spots", bjsc::StudyMode::Weak.icon())}</button>
+            </div>
 
             // Overall accuracy
             <div class="border border-gray-700 rounded-md px-4 py-3 mb-4">
@@ -775,6 +1768,31 @@ fn ProgressScreen(
                 }}
             </div>
 
+            // Slowest cells
+            <div class="border border-gray-700 rounded-md px-4 py-3 mb-4">
+                <div class="font-bold text-cyan-400 text-sm uppercase tracking-wider mb-2">"Slowest Cells (median)"</div>
+                {move || {
+                    let stats = progress_stats.get();
+                    if stats.slow_spots.is_empty() {
+                        view! { <div class="text-gray-500 text-sm">"No timed answers yet."</div> }.into_any()
+                    } else {
+                        view! {
+                            <div>
+                                {stats.slow_spots.iter().map(|(idx, ms)| {
+                                    let slow = *ms > bjsc::SLOW_ANSWER_MS;
+                                    view! {
+                                        <div class="flex justify-between py-0.5 text-sm border-b border-gray-800">
+                                            <span class:text-yellow-300=slow class:text-gray-300=!slow>{idx.clone()}</span>
+                                            <span>{format!("{:.1}s", *ms as f64 / 1000.0)}</span>
+                                        </div>
+                                    }
+                                }).collect::<Vec<_>>()}
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+
             // Recent sessions
             <div class="border border-gray-700 rounded-md px-4 py-3 mb-4">
                 <div class="font-bold text-cyan-400 text-sm uppercase tracking-wider mb-2">"Recent Sessions"</div>
@@ -826,6 +1844,357 @@ fn CoachScreen(screen: RwSignal<Screen>, coaching_text: RwSignal<String>) -> imp
     }
 }
 
+#[component]
+fn LeaderboardScreen(
+    screen: RwSignal<Screen>,
+    auth_state: RwSignal<Option<AuthState>>,
+    groups: RwSignal<Vec<GroupRow>>,
+    group_idx: RwSignal<usize>,
+    board: RwSignal<Board>,
+    standings: RwSignal<Vec<Standing>>,
+    leaderboard_error: RwSignal<Option<String>>,
+    blitz_secs: RwSignal<u32>,
+    load_standings: impl Fn() + Copy + 'static,
+    create_or_join: impl Fn(bool, String) + Copy + 'static,
+    leave_group: impl Fn() + Copy + 'static,
+) -> impl IntoView {
+    let new_group = RwSignal::new(String::new());
+    let join_code = RwSignal::new(String::new());
+    let current = move || groups.get().get(group_idx.get()).cloned();
+
+    view! {
+        <div class:hidden=move || screen.get() != Screen::Leaderboard>
+            <h2 class="font-bold text-cyan-400 text-lg mb-4">"Leaderboard"</h2>
+
+            <div class="text-gray-500 text-sm" class:hidden=move || auth_state.get().is_some()>
+                "Sign in to compare scores with a group."
+            </div>
+
+            <div class:hidden=move || auth_state.get().is_none()>
+                // Group picker
+                <div class="flex flex-wrap items-center gap-3 mb-4">
+                    <select
+                        aria-label="Group"
+                        class="text-sm px-2 py-1 rounded bg-slate-800 border border-gray-600 text-amber-300"
+                        class:hidden=move || groups.get().is_empty()
+                        on:change=move |ev| {
+                            if let Ok(i) = event_target_value(&ev).parse() {
+                                group_idx.set(i);
+                                load_standings();
+                            }
+                        }
+                    >
+                        {move || groups.get().iter().enumerate().map(|(i, g)| view! {
+                            <option value=i.to_string() selected=move || group_idx.get() == i>
+                                {g.name.clone()}
+                            </option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                    <span class="text-xs text-gray-500" class:hidden=move || current().is_none()>
+                        {move || current().map(|g| format!("Join code {}", g.join_code)).unwrap_or_default()}
+                    </span>
+                    <button
+                        class="text-sm px-2 py-1 border border-red-800 rounded bg-slate-800 cursor-pointer hover:bg-red-950 text-red-400"
+                        class:hidden=move || current().is_none()
+                        on:click=move |_| leave_group()
+                    >"Leave"</button>
+                    <span class="text-sm text-yellow-300" class:hidden=move || !groups.get().is_empty()>
+                        "Not in a group yet: start one, or join one with its code."
+                    </span>
+                </div>
+
+                // Board tabs
+                <div class="flex items-center gap-2 mb-3" class:hidden=move || groups.get().is_empty()>
+                    {Board::ALL.iter().map(|&b| view! {
+                        <button
+                            class="text-sm px-3 py-1 rounded cursor-pointer border"
+                            class:border-cyan-400=move || board.get() == b
+                            class:text-cyan-400=move || board.get() == b
+                            class:border-gray-600=move || board.get() != b
+                            class:text-gray-400=move || board.get() != b
+                            on:click=move |_| {
+                                board.set(b);
+                                load_standings();
+                            }
+                        >{b.label()}</button>
+                    }).collect::<Vec<_>>()}
+                    <span class="text-xs text-gray-500" class:hidden=move || board.get() != Board::Blitz>
+                        {move || format!("{}s, your rules and strategy", blitz_secs.get())}
+                    </span>
+                </div>
+
+                // Standings
+                <div class="border border-gray-700 rounded-md px-4 py-3 mb-4" class:hidden=move || groups.get().is_empty()>
+                    {move || {
+                        let me = auth_state.get().map(|a| a.user_id).unwrap_or_default();
+                        let rows = standings.get();
+                        if let Some(error) = leaderboard_error.get() {
+                            view! { <div class="text-red-400 text-sm">{error}</div> }.into_any()
+                        } else if rows.is_empty() {
+                            view! { <div class="text-gray-500 text-sm">"No scores on this board yet."</div> }.into_any()
+                        } else {
+                            view! {
+                                <div>
+                                    {rows.into_iter().map(|s| {
+                                        let mine = s.user_id == me;
+                                        view! {
+                                            <div
+                                                class="flex justify-between py-0.5 text-sm border-b border-gray-800"
+                                                class:text-green-400=mine
+                                                class:font-bold=mine
+                                            >
+                                                <span>
+                                                    <span class="text-gray-500">{format!("{:>3}. ", s.rank)}</span>
+                                                    {s.display_name}
+                                                </span>
+                                                <span>{s.score}</span>
+                                            </div>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                            }.into_any()
+                        }
+                    }}
+                </div>
+
+                // Start or join a group
+                <div class="flex flex-wrap gap-4">
+                    <form
+                        class="flex gap-2"
+                        on:submit=move |ev| {
+                            ev.prevent_default();
+                            create_or_join(false, new_group.get_untracked());
+                            new_group.set(String::new());
+                        }
+                    >
+                        <input
+                            aria-label="New group"
+                            class="text-sm px-2 py-1 rounded bg-slate-900 border border-gray-600 text-gray-200"
+                            placeholder="Group name"
+                            prop:value=move || new_group.get()
+                            on:input=move |ev| new_group.set(event_target_value(&ev))
+                        />
+                        <button
+                            type="submit"
+                            class="text-sm px-2 py-1 border border-gray-600 rounded bg-slate-800 cursor-pointer hover:border-cyan-400 text-gray-300"
+                        >"New group"</button>
+                    </form>
+                    <form
+                        class="flex gap-2"
+                        on:submit=move |ev| {
+                            ev.prevent_default();
+                            create_or_join(true, join_code.get_untracked());
+                            join_code.set(String::new());
+                        }
+                    >
+                        <input
+                            aria-label="Join code"
+                            class="text-sm px-2 py-1 rounded bg-slate-900 border border-gray-600 text-gray-200"
+                            placeholder="Join code"
+                            prop:value=move || join_code.get()
+                            on:input=move |ev| join_code.set(event_target_value(&ev))
+                        />
+                        <button
+                            type="submit"
+                            class="text-sm px-2 py-1 border border-gray-600 rounded bg-slate-800 cursor-pointer hover:border-cyan-400 text-gray-300"
+                        >"Join"</button>
+                    </form>
+                </div>
+                <p class="text-xs text-red-400 mt-2" class:hidden=move || leaderboard_error.get().is_none() || !groups.get().is_empty()>
+                    {move || leaderboard_error.get().unwrap_or_default()}
+                </p>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ClassScreen(
+    screen: RwSignal<Screen>,
+    auth_state: RwSignal<Option<AuthState>>,
+    classes: RwSignal<Vec<ClassRow>>,
+    class_idx: RwSignal<usize>,
+    assignments: RwSignal<Vec<AssignmentRow>>,
+    class_progress: RwSignal<Vec<MemberProgress>>,
+    class_error: RwSignal<Option<String>>,
+    load_class: impl Fn() + Copy + 'static,
+    class_input: impl Fn(ClassInput, String) + Copy + 'static,
+    unassign: impl Fn(i64) + Copy + Send + 'static,
+    leave_class: impl Fn() + Copy + 'static,
+    start_assignment: impl Fn(Assignment) + Copy + Send + 'static,
+) -> impl IntoView {
+    let current = move || classes.get().get(class_idx.get()).cloned();
+    let teaching = move || {
+        let me = auth_state.get().map(|a| a.user_id);
+        current().is_some_and(|c| Some(c.instructor_id) == me)
+    };
+    // A text box and button that sends what's typed as `kind`
+    let input_form = move |kind: ClassInput, label: &'static str, placeholder: &'static str| {
+        let text = RwSignal::new(String::new());
+        view! {
+            <form
+                class="flex gap-2"
+                on:submit=move |ev| {
+                    ev.prevent_default();
+                    class_input(kind, text.get_untracked());
+                    text.set(String::new());
+                }
+            >
+                <input
+                    aria-label=label
+                    class="text-sm px-2 py-1 rounded bg-slate-900 border border-gray-600 text-gray-200"
+                    placeholder=placeholder
+                    prop:value=move || text.get()
+                    on:input=move |ev| text.set(event_target_value(&ev))
+                />
+                <button
+                    type="submit"
+                    class="text-sm px-2 py-1 border border-gray-600 rounded bg-slate-800 cursor-pointer hover:border-cyan-400 text-gray-300"
+                >{label}</button>
+            </form>
+        }
+    };
+
+    view! {
+        <div class:hidden=move || screen.get() != Screen::Class>
+            <h2 class="font-bold text-cyan-400 text-lg mb-4">"Class"</h2>
+
+            <div class="text-gray-500 text-sm" class:hidden=move || auth_state.get().is_some()>
+                "Sign in to teach a class or join one."
+            </div>
+
+            <div class:hidden=move || auth_state.get().is_none()>
+                // Class picker
+                <div class="flex flex-wrap items-center gap-3 mb-4">
+                    <select
+                        aria-label="Class"
+                        class="text-sm px-2 py-1 rounded bg-slate-800 border border-gray-600 text-amber-300"
+                        class:hidden=move || classes.get().is_empty()
+                        on:change=move |ev| {
+                            if let Ok(i) = event_target_value(&ev).parse() {
+                                class_idx.set(i);
+                                load_class();
+                            }
+                        }
+                    >
+                        {move || classes.get().iter().enumerate().map(|(i, c)| view! {
+                            <option value=i.to_string() selected=move || class_idx.get() == i>
+                                {c.name.clone()}
+                            </option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                    <span class="text-xs text-gray-500" class:hidden=move || current().is_none()>
+                        {move || match current() {
+                            Some(c) if teaching() => format!("You teach it. Join code {}", c.join_code),
+                            Some(_) => "You're a member".to_string(),
+                            None => String::new(),
+                        }}
+                    </span>
+                    <button
+                        class="text-sm px-2 py-1 border border-red-800 rounded bg-slate-800 cursor-pointer hover:bg-red-950 text-red-400"
+                        class:hidden=move || current().is_none() || teaching()
+                        on:click=move |_| leave_class()
+                    >"Leave"</button>
+                    <span class="text-sm text-yellow-300" class:hidden=move || !classes.get().is_empty()>
+                        "Not in a class yet: start one to teach, or join one with its code."
+                    </span>
+                </div>
+
+                <p class="text-sm text-red-400 mb-3" class:hidden=move || class_error.get().is_none()>
+                    {move || class_error.get().unwrap_or_default()}
+                </p>
+
+                // Assignments
+                <div class="border border-gray-700 rounded-md px-4 py-3 mb-4" class:hidden=move || current().is_none()>
+                    <div class="font-bold text-cyan-400 text-sm uppercase tracking-wider mb-2">"Assignments"</div>
+                    {move || {
+                        let rows = assignments.get();
+                        if rows.is_empty() {
+                            view! { <div class="text-gray-500 text-sm">"Nothing assigned yet."</div> }.into_any()
+                        } else {
+                            let teaching = teaching();
+                            view! {
+                                <div>
+                                    {rows.into_iter().map(|row| {
+                                        let assignment = row.assignment.clone();
+                                        view! {
+                                            <div class="flex items-center gap-2 py-0.5 text-sm border-b border-gray-800">
+                                                <span class="flex-1">{row.assignment.label()}</span>
+                                                <button
+                                                    class="px-2 py-0.5 border border-gray-600 rounded cursor-pointer hover:border-cyan-400 text-gray-300"
+                                                    on:click=move |_| start_assignment(assignment.clone())
+                                                >{if teaching { "Try it" } else { "Start" }}</button>
+                                                <button
+                                                    aria-label="Withdraw assignment"
+                                                    class="px-2 text-xs text-gray-500 cursor-pointer hover:text-red-400"
+                                                    class:hidden=!teaching
+                                                    on:click=move |_| {
+                                                        if let Some(id) = row.id {
+                                                            unassign(id);
+                                                        }
+                                                    }
+                                                >"\u{2715}"</button>
+                                            </div>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                            }.into_any()
+                        }
+                    }}
+                    <div class="mt-2" class:hidden=move || !teaching()>
+                        {input_form(ClassInput::Assign, "Assign", "exam 10, or Soft doubles: soft vs 3-6 double")}
+                    </div>
+                </div>
+
+                // Members' progress, for the instructor
+                <div class="border border-gray-700 rounded-md px-4 py-3 mb-4" class:hidden=move || !teaching()>
+                    <div class="font-bold text-cyan-400 text-sm uppercase tracking-wider mb-2">"Members"</div>
+                    {move || {
+                        let rows = class_progress.get();
+                        if rows.is_empty() {
+                            view! { <div class="text-gray-500 text-sm">"No members yet. Share the join code to let them in."</div> }.into_any()
+                        } else {
+                            let pct = bjsc::progress::ProgressStats::category_pct;
+                            view! {
+                                <table class="w-full text-sm">
+                                    <tr class="text-gray-500 text-left">
+                                        <th>"Member"</th><th>"Answers"</th><th>"All"</th><th>"Hard"</th>
+                                        <th>"Soft"</th><th>"Split"</th><th>"Dbl"</th><th>"Most missed"</th>
+                                    </tr>
+                                    {rows.into_iter().map(|p| {
+                                        let s = p.stats;
+                                        let worst = s.trouble_spots.first()
+                                            .map(|(cell, wrong, seen)| format!("{} ({}/{} wrong)", cell, wrong, seen))
+                                            .unwrap_or_default();
+                                        view! {
+                                            <tr class="border-b border-gray-800">
+                                                <td class="font-bold">{p.member.display_name}</td>
+                                                <td>{s.total_answers}</td>
+                                                <td>{pct(s.total_correct, s.total_answers)}</td>
+                                                <td>{pct(s.hard_correct, s.hard_total)}</td>
+                                                <td>{pct(s.soft_correct, s.soft_total)}</td>
+                                                <td>{pct(s.split_correct, s.split_total)}</td>
+                                                <td>{pct(s.double_correct, s.double_total)}</td>
+                                                <td class="text-red-400">{worst}</td>
+                                            </tr>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </table>
+                            }.into_any()
+                        }
+                    }}
+                </div>
+
+                // Start or join a class
+                <div class="flex flex-wrap gap-4">
+                    {input_form(ClassInput::New, "New class", "Class name")}
+                    {input_form(ClassInput::Join, "Join", "Join code")}
+                </div>
+            </div>
+        </div>
+    }
+}
+
 #[component]
 fn PlayScreen(
     screen: RwSignal<Screen>,
@@ -835,6 +2204,10 @@ fn PlayScreen(
     status_visible: RwSignal<bool>,
     show_shuffle: RwSignal<bool>,
     errors: RwSignal<Vec<String>>,
+    explanation: RwSignal<Option<bjsc::CellExplanation>>,
+    exam_report: RwSignal<Option<ExamReport>>,
+    blitz_left_ms: RwSignal<Option<u64>>,
+    blitz_best: RwSignal<Option<BlitzScore>>,
     do_action: impl Fn(Action) + Copy + 'static,
     do_shuffle: impl Fn() + Copy + 'static,
 ) -> impl IntoView {
@@ -878,11 +2251,48 @@ fn PlayScreen(
                 </div>
             </div>
 
+            // Exam progress
+            <div
+                class="text-center px-4 py-2 rounded font-bold mb-6 bg-cyan-950 text-cyan-300"
+                class:hidden=move || game_data.get().exam.is_none()
+            >
+                {move || {
+                    game_data.get().exam.map(|(answered, total)| {
+                        format!("Exam: question {} of {}", answered + 1, total)
+                    }).unwrap_or_default()
+                }}
+            </div>
+
+            // Blitz clock
+            <div
+                class="text-center px-4 py-2 rounded font-bold mb-6 bg-cyan-950 text-cyan-300"
+                class:hidden=move || game_data.get().blitz.is_none()
+            >
+                {move || {
+                    game_data.get().blitz.map(|(correct, wrong)| {
+                        let secs = blitz_left_ms.get().unwrap_or(0).div_ceil(1000);
+                        let best = blitz_best
+                            .get()
+                            .map(|b| format!(" | best {}", b.correct))
+                            .unwrap_or_default();
+                        format!(
+                            "Blitz {}:{:02} left | {} right, {} wrong{}",
+                            secs / 60,
+                            secs % 60,
+                            correct,
+                            wrong,
+                            best
+                        )
+                    }).unwrap_or_default()
+                }}
+            </div>
+
             // Hands (hidden when drill waiting)
             <div class="mb-6" class:hidden=move || game_data.get().drill_wait_secs.is_some()>
                 <div class="text-xl py-1">
                     <span class="font-bold text-cyan-400">"Dealer: "</span>
                     <span class="text-2xl tracking-wide">{move || game_data.get().dealer.clone()}</span>
+                    <span class="text-2xl text-blue-400" class:hidden=move || !game_data.get().hole_card>" \u{1F0A0}"</span>
                 </div>
                 <div class="text-xl py-1">
                     <span class="font-bold text-cyan-400">"Player: "</span>
@@ -902,6 +2312,73 @@ fn PlayScreen(
                 {move || status_text.get()}
             </div>
 
+            // Why the last wrong answer was wrong
+            <div
+                class="border border-red-900 rounded-md px-4 py-3 mb-6 text-sm"
+                class:hidden=move || !status_visible.get() || explanation.get().is_none()
+            >
+                <div class="font-bold text-cyan-400 text-sm uppercase tracking-wider mb-2">"Why"</div>
+                {move || {
+                    explanation
+                        .get()
+                        .map(|ex| ex.lines())
+                        .unwrap_or_default()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, line)| {
+                            let class = match i {
+                                0 => "py-0.5 text-gray-200",
+                                1 => "py-0.5 text-yellow-300",
+                                _ => "py-0.5 text-gray-400",
+                            };
+                            view! { <div class=class>{line}</div> }
+                        })
+                        .collect::<Vec<_>>()
+                }}
+            </div>
+
+            // The report of the exam just finished
+            {move || exam_report.get().map(|report| {
+                let border = if report.passed() { "border-green-700" } else { "border-red-800" };
+                view! {
+                    <div class=format!("border {} rounded-md px-4 py-3 mb-6 text-sm", border)>
+                        <div class="flex justify-between items-center mb-2">
+                            <span class="font-bold text-cyan-400 text-sm uppercase tracking-wider">"Exam report"</span>
+                            <button
+                                aria-label="Close exam report"
+                                class="text-xs text-gray-500 cursor-pointer hover:text-cyan-400"
+                                on:click=move |_| exam_report.set(None)
+                            >"\u{2715}"</button>
+                        </div>
+                        <div class="mb-1">
+                            <span class="font-bold">{report.summary()}</span>
+                            <span class="text-gray-500">{format!("  (pass mark {:.0}%)", bjsc::exam::PASS_MARK * 100.0)}</span>
+                        </div>
+                        <div class="text-gray-500 mb-2">
+                            {format!(
+                                "{} rules | {}",
+                                bjsc::preset_for_rules(&report.rules).map_or("Custom", |p| p.name),
+                                report.strategy_label()
+                            )}
+                        </div>
+                        <div class="flex gap-6 mb-2">
+                            {report.by_table.iter().map(|score| view! {
+                                <span>
+                                    <span class="font-bold text-gray-400">{format!("{}: ", score.table)}</span>
+                                    {bjsc::progress::ProgressStats::category_pct(score.correct, score.total)}
+                                </span>
+                            }).collect::<Vec<_>>()}
+                        </div>
+                        {report.missed.iter().map(|answer| view! {
+                            <div class="flex justify-between py-0.5 border-b border-gray-800">
+                                <span class="text-red-400">{answer.index.to_string()}</span>
+                                <span>{format!("{} (you: {})", answer.correct_action, answer.answer)}</span>
+                            </div>
+                        }).collect::<Vec<_>>()}
+                    </div>
+                }
+            })}
+
             // Action buttons (hidden when drill waiting)
             <div
                 class="grid grid-cols-2 sm:grid-cols-4 gap-3 justify-center mb-6"
@@ -938,6 +2415,12 @@ fn PlayScreen(
                     class:hidden=move || show_shuffle.get()
                     on:click=move |_| do_action(Action::Split)
                 >"S(p)lit"</button>
+                <button
+                    aria-label="Surrender (R key)"
+                    class="px-5 py-2.5 border border-gray-600 rounded-md bg-slate-800 text-gray-200 text-base font-mono cursor-pointer transition-colors hover:bg-slate-700 hover:border-cyan-400 active:bg-slate-600"
+                    class:hidden=move || show_shuffle.get() || !game_data.get().rules.late_surrender()
+                    on:click=move |_| do_action(Action::Surrender)
+                >"Su(r)render"</button>
             </div>
 
             // Error log
@@ -963,8 +2446,59 @@ fn PlayScreen(
 }
 
 #[component]
-fn StrategyScreen(screen: RwSignal<Screen>) -> impl IntoView {
-    let tab = RwSignal::new(0u8); // 0 = Descriptive, 1 = Tables
+fn StrategyScreen(
+    screen: RwSignal<Screen>,
+    game_data: RwSignal<DisplayData>,
+    set_rules: impl Fn(bjsc::Rules) + Copy + 'static,
+    set_strategy: impl Fn(bjsc::StrategyKind) + Copy + 'static,
+    set_custom_charts: impl Fn(Option<bjsc::ChartSet>) + Copy + 'static,
+    grade_quiz: impl Fn(&ChartQuiz) -> ChartQuizResult + Copy + 'static,
+) -> impl IntoView {
+    let tab = RwSignal::new(0u8); // 0 = Descriptive, 1 = Tables, 2 = Fill in
+    let chart_text = RwSignal::new(String::new());
+    let chart_error = RwSignal::new(String::new());
+    let quiz = RwSignal::new(ChartQuiz::new(QUIZ_TABLES[0]));
+    let quiz_result: RwSignal<Option<ChartQuizResult>> = RwSignal::new(None);
+
+    // Arrows move around the chart, action codes fill it in, Backspace clears a cell and
+    // Enter grades it. Once graded, Enter starts the chart again.
+    let quiz_key = move |ev: web_sys::KeyboardEvent| {
+        let key = ev.key();
+        if quiz_result.get_untracked().is_some() {
+            if key == "Enter" {
+                quiz.update(|q| *q = ChartQuiz::new(q.table()));
+                quiz_result.set(None);
+            }
+            return;
+        }
+        let mut chars = key.chars();
+        let typed = match (chars.next(), chars.next()) {
+            (Some(ch), None) => Some(ch),
+            _ => None,
+        };
+        let mut handled = true;
+        let mut hand_in = false;
+        quiz.update(|q| match key.as_str() {
+            "ArrowUp" => q.move_cursor(-1, 0),
+            "ArrowDown" => q.move_cursor(1, 0),
+            "ArrowLeft" => q.move_cursor(0, -1),
+            "ArrowRight" => q.move_cursor(0, 1),
+            "Backspace" | "Delete" => q.clear(),
+            "Enter" => hand_in = !q.finish_code(),
+            _ => match typed {
+                Some(ch) => {
+                    q.type_char(ch);
+                }
+                None => handled = false,
+            },
+        });
+        if handled {
+            ev.prevent_default();
+        }
+        if hand_in {
+            quiz_result.set(Some(grade_quiz(&quiz.get_untracked())));
+        }
+    };
 
     view! {
         <div class:hidden=move || screen.get() != Screen::Strategy>
@@ -984,11 +2518,45 @@ fn StrategyScreen(screen: RwSignal<Screen>) -> impl IntoView {
                     class:text-gray-500=move || tab.get() != 1
                     on:click=move |_| tab.set(1)
                 >"Tables"</button>
+                <button
+                    class="text-sm font-bold cursor-pointer"
+                    class:text-cyan-400=move || tab.get() == 2
+                    class:underline=move || tab.get() == 2
+                    class:text-gray-500=move || tab.get() != 2
+                    on:click=move |_| tab.set(2)
+                >"Fill in"</button>
+                <button
+                    class="ml-auto text-xs px-2 py-0.5 border border-gray-600 rounded text-amber-300 cursor-pointer hover:border-amber-300"
+                    title=move || game_data.get().rules.summary()
+                    on:click=move |_| set_rules(bjsc::next_preset(&game_data.get_untracked().rules).rules)
+                >{move || {
+                    bjsc::preset_for_rules(&game_data.get().rules).map_or("Custom", |p| p.name)
+                }}</button>
+                <button
+                    class="text-xs px-2 py-0.5 border border-gray-600 rounded text-amber-300 cursor-pointer hover:border-amber-300"
+                    on:click=move |_| {
+                        let mut rules = game_data.get_untracked().rules;
+                        rules.variant = rules.variant.next();
+                        set_rules(rules);
+                    }
+                >{move || game_data.get().rules.variant.to_string()}</button>
+                <button
+                    class="text-xs px-2 py-0.5 border border-gray-600 rounded text-amber-300 cursor-pointer hover:border-amber-300"
+                    on:click=move |_| {
+                        let mut rules = game_data.get_untracked().rules;
+                        rules.hole_card = rules.hole_card.next();
+                        set_rules(rules);
+                    }
+                >{move || game_data.get().rules.hole_card.to_string()}</button>
+                <button
+                    class="text-xs px-2 py-0.5 border border-gray-600 rounded text-fuchsia-300 cursor-pointer hover:border-fuchsia-300"
+                    on:click=move |_| set_strategy(game_data.get_untracked().strategy.next())
+                >{move || game_data.get().strategy.to_string()}</button>
             </div>
 
             // Descriptive tab
             <div class:hidden=move || tab.get() != 0>
-                {bjsc::all_phrases().into_iter().map(|(category, phrases)| {
+                {move || bjsc::all_phrases(&game_data.get().charts).into_iter().map(|(category, phrases)| {
                     view! {
                         <div class="mb-4">
                             <h3 class="font-bold text-cyan-400 mb-1">{category}</h3>
@@ -1004,7 +2572,54 @@ fn StrategyScreen(screen: RwSignal<Screen>) -> impl IntoView {
 
             // Tables tab
             <div class:hidden=move || tab.get() != 1>
-                {bjsc::all_charts().into_iter().map(|chart| {
+                {move || {
+                    let edge = bjsc::house_edge(&game_data.get().rules);
+                    view! {
+                        <div class="mb-4 text-sm">
+                            <span class="font-bold text-cyan-400">"House edge: "</span>
+                            <span class="text-yellow-300">{format!("{:.2}%", edge.total())}</span>
+                            <span class="text-gray-400 ml-2">{format!("({})", edge.describe().join(", "))}</span>
+                        </div>
+                    }
+                }}
+                <details class="mb-4 text-sm">
+                    <summary class="cursor-pointer text-gray-400">
+                        {move || if game_data.get().custom_charts.is_some() {
+                            "Custom chart (loaded)"
+                        } else {
+                            "Custom chart"
+                        }}
+                    </summary>
+                    <p class="text-xs text-gray-500 my-1">
+                        "Paste a chart as TOML or CSV using the codes below. Every row must be given."
+                    </p>
+                    <textarea
+                        class="w-full h-32 bg-gray-800 text-gray-200 font-mono text-xs p-2 rounded"
+                        prop:value=move || chart_text.get()
+                        on:input=move |ev| chart_text.set(event_target_value(&ev))
+                    ></textarea>
+                    <div class="flex gap-2 mt-1">
+                        <button
+                            class="text-xs px-2 py-0.5 border border-gray-600 rounded cursor-pointer hover:border-cyan-400"
+                            on:click=move |_| {
+                                match bjsc::ChartSet::from_text(&chart_text.get_untracked()) {
+                                    Ok(charts) => {
+                                        chart_error.set(String::new());
+                                        set_custom_charts(Some(charts));
+                                    }
+                                    Err(e) => chart_error.set(e.to_string()),
+                                }
+                            }
+                        >"Load"</button>
+                        <button
+                            class="text-xs px-2 py-0.5 border border-gray-600 rounded cursor-pointer hover:border-cyan-400"
+                            class:hidden=move || game_data.get().custom_charts.is_none()
+                            on:click=move |_| set_custom_charts(None)
+                        >"Use standard charts"</button>
+                    </div>
+                    <p class="text-xs text-red-400 mt-1">{move || chart_error.get()}</p>
+                </details>
+                {move || bjsc::strategy_charts(&game_data.get().charts).into_iter().map(|chart| {
                     view! {
                         <div class="mb-6">
                             <h3 class="font-bold text-cyan-400 mb-2">{chart.title}</h3>
@@ -1024,13 +2639,7 @@ fn StrategyScreen(screen: RwSignal<Screen>) -> impl IntoView {
                                                 <tr>
                                                     <td class="px-2 py-0.5 text-gray-400 font-bold">{*label}</td>
                                                     {cells.iter().map(|cell| {
-                                                        let color = match *cell {
-                                                            "H" => "text-red-400",
-                                                            "S" => "text-green-400",
-                                                            "Dh" | "Ds" => "text-yellow-300",
-                                                            "P" | "Pd" => "text-blue-400",
-                                                            _ => "text-gray-600",
-                                                        };
+                                                        let color = code_class(cell);
                                                         view! { <td class=format!("px-2 py-0.5 text-center {}", color)>{*cell}</td> }
                                                     }).collect::<Vec<_>>()}
                                                 </tr>
@@ -1039,6 +2648,11 @@ fn StrategyScreen(screen: RwSignal<Screen>) -> impl IntoView {
                                     </tbody>
                                 </table>
                             </div>
+                            <ul class="mt-2 space-y-0.5">
+                                {chart.notes.into_iter().map(|note| {
+                                    view! { <li class="text-xs text-gray-400 ml-2">{note}</li> }
+                                }).collect::<Vec<_>>()}
+                            </ul>
                         </div>
                     }
                 }).collect::<Vec<_>>()}
@@ -1049,9 +2663,133 @@ fn StrategyScreen(screen: RwSignal<Screen>) -> impl IntoView {
                     <span class="text-yellow-300">"Dh"</span>" = Double (hit), "
                     <span class="text-yellow-300">"Ds"</span>" = Double (stand), "
                     <span class="text-blue-400">"P"</span>" = Split, "
-                    <span class="text-blue-400">"Pd"</span>" = Split (DAS)"
+                    <span class="text-blue-400">"Pd"</span>" = Split (DAS), "
+                    <span class="text-fuchsia-400">"Rh"</span>" = Surrender (hit)"
+                </div>
+            </div>
+
+            // Fill in tab: a blank chart to complete from memory
+            <div class:hidden=move || tab.get() != 2>
+                <div class="flex gap-2 mb-3">
+                    {QUIZ_TABLES.iter().map(|table| {
+                        let table = *table;
+                        let title = ChartQuiz::new(table).title();
+                        view! {
+                            <button
+                                class="text-xs px-2 py-0.5 border rounded cursor-pointer hover:border-cyan-400"
+                                class:border-cyan-400=move || quiz.get().table() == table
+                                class:text-cyan-400=move || quiz.get().table() == table
+                                class:border-gray-600=move || quiz.get().table() != table
+                                on:click=move |_| {
+                                    quiz.set(ChartQuiz::new(table));
+                                    quiz_result.set(None);
+                                }
+                            >{title}</button>
+                        }
+                    }).collect::<Vec<_>>()}
+                </div>
+                <p class="text-xs text-gray-500 mb-2">
+                    "Click a cell or use the arrow keys, and type H, S, Dh, Ds, P, Pd, Rh or -. Enter grades the chart; cells you get wrong are added to your deck."
+                </p>
+                <div
+                    tabindex="0"
+                    aria-label="Chart to fill in"
+                    class="overflow-x-auto mb-4 outline-none focus:ring-1 focus:ring-cyan-700 rounded"
+                    on:keydown=quiz_key
+                >
+                    {move || {
+                        let q = quiz.get();
+                        let result = quiz_result.get();
+                        view! {
+                            <div class="text-sm mb-2">
+                                <span class="font-bold text-cyan-400">{q.title()}</span>
+                                <span class="text-yellow-300 ml-2">{match &result {
+                                    Some(result) => result.summary(),
+                                    None => format!("{}/{} filled in", q.filled(), q.rows() * 10),
+                                }}</span>
+                            </div>
+                            <table class="text-xs font-mono border-collapse">
+                                <thead>
+                                    <tr>
+                                        <th class="px-2 py-1"></th>
+                                        {q.col_labels().iter().map(|h| {
+                                            view! { <th class="px-2 py-1 text-yellow-400 font-bold">{*h}</th> }
+                                        }).collect::<Vec<_>>()}
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {(0..q.rows()).map(|row| view! {
+                                        <tr>
+                                            <td class="px-2 py-0.5 text-gray-400 font-bold">{q.row_label(row)}</td>
+                                            {(0..10).map(|col| {
+                                                let entered = q.entry(row, col).map(|a| a.code());
+                                                let (text, class) = match result.as_ref().map(|r| r.cell(row, col)) {
+                                                    Some(cell) if cell.is_correct() => (cell.expected.code().to_string(), "text-green-400"),
+                                                    Some(cell) => (cell.expected.code().to_string(), "text-red-400 font-bold bg-red-950"),
+                                                    None if q.cursor() == (row, col) => {
+                                                        let text = if q.pending().is_empty() {
+                                                            entered.unwrap_or("_")
+                                                        } else {
+                                                            q.pending()
+                                                        };
+                                                        (text.to_string(), "bg-cyan-900 text-white")
+                                                    }
+                                                    None => match entered {
+                                                        Some(code) => (code.to_string(), code_class(code)),
+                                                        None => ("\u{00B7}".to_string(), "text-gray-600"),
+                                                    },
+                                                };
+                                                view! {
+                                                    <td
+                                                        class=format!("px-2 py-0.5 text-center cursor-pointer {}", class)
+                                                        on:click=move |_| quiz.update(|q| q.set_cursor(row, col))
+                                                    >{text}</td>
+                                                }
+                                            }).collect::<Vec<_>>()}
+                                        </tr>
+                                    }).collect::<Vec<_>>()}
+                                </tbody>
+                            </table>
+                        }
+                    }}
                 </div>
+                {move || quiz_result.get().map(|result| {
+                    let missed: Vec<_> = result.missed().copied().collect();
+                    view! {
+                        <div class="text-sm">
+                            <div class="font-bold text-red-400 mb-1" class:hidden=missed.is_empty()>
+                                "Missed (added to your deck to drill):"
+                            </div>
+                            <div class="font-bold text-green-400 mb-1" class:hidden=!missed.is_empty()>
+                                "Every cell right!"
+                            </div>
+                            {missed.into_iter().map(|cell| view! {
+                                <div class="flex justify-between py-0.5 border-b border-gray-800">
+                                    <span class="text-red-400">{cell.index.to_string()}</span>
+                                    <span>{format!(
+                                        "{} (you: {})",
+                                        cell.expected.code(),
+                                        cell.entered.map_or("blank", |a| a.code())
+                                    )}</span>
+                                </div>
+                            }).collect::<Vec<_>>()}
+                            <p class="text-xs text-gray-500 mt-2">"Press Enter on the chart to try again."</p>
+                        </div>
+                    }
+                })}
             </div>
         </div>
     }
 }
+
+/// The text colour of a chart code, e.g. "Dh".
+fn code_class(code: &str) -> &'static str {
+    match code {
+        "H" => "text-red-400",
+        "S" => "text-green-400",
+        "Dh" | "Ds" => "text-yellow-300",
+        "P" | "Pd" => "text-blue-400",
+        "Rh" => "text-fuchsia-400",
+        _ => "text-gray-600",
+    }
+}
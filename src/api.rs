@@ -1,10 +1,23 @@
-use crate::StudyMode;
+use crate::blitz::BlitzScore;
+use crate::class::{Assignment, MemberProgress};
+use crate::exam::ExamReport;
+use crate::leaderboard::{Board, Standing, blitz_standings, exam_standings, streak_standings};
+use crate::progress::ProgressStats;
 use crate::supabase::{
-    AnswerLogEntry, AnswerLogRow, SupabaseConfig, UserDeckRow, coaching_request,
-    fetch_answer_logs_request, fetch_deck_request, insert_answer_log_request, upsert_deck_request,
+    AnswerLogEntry, AnswerLogRow, AssignmentRow, BlitzScoreRow, ClassMemberRow, ClassRow,
+    ExamResultRow, GroupMemberRow, GroupRow, MemberAnswerRow, RequestDetails, StreakRow,
+    SupabaseConfig, UserDeckRow, coaching_request, create_class_request, create_group_request,
+    delete_assignment_request, fetch_answer_logs_request, fetch_assignments_request,
+    fetch_blitz_best_request, fetch_class_answers_request, fetch_class_members_request,
+    fetch_classes_request, fetch_deck_request, fetch_exam_results_request,
+    fetch_group_blitz_scores_request, fetch_group_exam_results_request,
+    fetch_group_members_request, fetch_group_streaks_request, fetch_groups_request,
+    insert_answer_log_request, insert_assignment_request, insert_blitz_score_request,
+    insert_exam_result_request, join_class_request, join_group_request, leave_class_request,
+    leave_group_request, upsert_deck_request,
 };
-use spaced_rep::Deck;
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// A minimal HTTP response abstraction shared across frontends.
 pub struct HttpResponse {
@@ -50,11 +63,9 @@ pub async fn upsert_user_deck(
     client: &(impl HttpClient + ?Sized),
     config: &SupabaseConfig,
     token: &str,
-    user_id: &str,
-    mode: StudyMode,
-    deck: &Deck,
+    row: &UserDeckRow,
 ) -> Result<(), String> {
-    let req = upsert_deck_request(config, token, user_id, mode, deck)?;
+    let req = upsert_deck_request(config, token, row)?;
     let resp = client
         .request(&req.method, &req.url, &req.headers, req.body.as_deref())
         .await?;
@@ -91,9 +102,10 @@ pub async fn fetch_answer_logs(
     client: &(impl HttpClient + ?Sized),
     config: &SupabaseConfig,
     token: &str,
+    user_id: &str,
     limit: u32,
 ) -> Result<Vec<AnswerLogEntry>, String> {
-    let req = fetch_answer_logs_request(config, token, limit);
+    let req = fetch_answer_logs_request(config, token, user_id, limit);
     let resp = client
         .request(&req.method, &req.url, &req.headers, req.body.as_deref())
         .await?;
@@ -108,13 +120,308 @@ pub async fn fetch_answer_logs(
     serde_json::from_str(&resp.body).map_err(|e| e.to_string())
 }
 
+/// Save an exam result to Supabase.
+pub async fn insert_exam_result(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    row: &ExamResultRow,
+) -> Result<(), String> {
+    let req = insert_exam_result_request(config, token, row)?;
+    send(client, &req, "Exam result").await?;
+    Ok(())
+}
+
+/// Fetch the user's recent exam results from Supabase, newest first.
+pub async fn fetch_exam_results(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    user_id: &str,
+    limit: u32,
+) -> Result<Vec<ExamReport>, String> {
+    let req = fetch_exam_results_request(config, token, user_id, limit);
+    let body = send(client, &req, "Fetch exams").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Save a Blitz score to Supabase.
+pub async fn insert_blitz_score(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    row: &BlitzScoreRow,
+) -> Result<(), String> {
+    let req = insert_blitz_score_request(config, token, row)?;
+    send(client, &req, "Blitz score").await?;
+    Ok(())
+}
+
+/// Fetch the user's best Blitz score for the same challenge as `challenge`, if they've
+/// played it.
+pub async fn fetch_blitz_best(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    user_id: &str,
+    challenge: &BlitzScore,
+) -> Result<Option<BlitzScore>, String> {
+    let req = fetch_blitz_best_request(config, token, user_id, challenge);
+    let body = send(client, &req, "Fetch Blitz best").await?;
+    let rows: Vec<BlitzScore> = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    Ok(rows.into_iter().next())
+}
+
+/// Send a request and return the body of a successful response. `what` names the request
+/// in the error.
+async fn send(
+    client: &(impl HttpClient + ?Sized),
+    req: &RequestDetails,
+    what: &str,
+) -> Result<String, String> {
+    let resp = client
+        .request(&req.method, &req.url, &req.headers, req.body.as_deref())
+        .await?;
+
+    if !(200..300).contains(&resp.status) {
+        return Err(format!("{} failed ({}): {}", what, resp.status, resp.body));
+    }
+    Ok(resp.body)
+}
+
+/// Create a group and join it as `display_name`.
+pub async fn create_group(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    name: &str,
+    display_name: &str,
+) -> Result<GroupRow, String> {
+    let req = create_group_request(config, token, name, display_name);
+    let body = send(client, &req, "Create group").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Join a group with its join code as `display_name`.
+pub async fn join_group(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    join_code: &str,
+    display_name: &str,
+) -> Result<GroupRow, String> {
+    let req = join_group_request(config, token, join_code, display_name);
+    let body = send(client, &req, "Join group").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Leave a group, which takes the user off its leaderboards.
+pub async fn leave_group(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    group_id: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    let req = leave_group_request(config, token, group_id, user_id);
+    send(client, &req, "Leave group").await?;
+    Ok(())
+}
+
+/// Fetch the groups the user belongs to.
+pub async fn fetch_groups(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+) -> Result<Vec<GroupRow>, String> {
+    let req = fetch_groups_request(config, token);
+    let body = send(client, &req, "Fetch groups").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Fetch a group's leaderboard. The Blitz board ranks scores for the same rule set,
+/// strategy and length as `challenge`, and the exam board exams for the same rule set and
+/// strategy; the streak board counts streaks still going on
+/// `today` ("YYYY-MM-DD").
+pub async fn fetch_leaderboard(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    group_id: &str,
+    board: Board,
+    challenge: &BlitzScore,
+    today: &str,
+) -> Result<Vec<Standing>, String> {
+    let req = fetch_group_members_request(config, token, group_id);
+    let body = send(client, &req, "Fetch members").await?;
+    let members: Vec<GroupMemberRow> = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    if members.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids: Vec<String> = members.iter().map(|m| m.user_id.clone()).collect();
+
+    match board {
+        Board::Blitz => {
+            let req = fetch_group_blitz_scores_request(config, token, &ids, challenge);
+            let body = send(client, &req, "Fetch Blitz scores").await?;
+            let scores: Vec<BlitzScoreRow> =
+                serde_json::from_str(&body).map_err(|e| e.to_string())?;
+            Ok(blitz_standings(&members, &scores))
+        }
+        Board::Streak => {
+            let req = fetch_group_streaks_request(config, token, &ids);
+            let body = send(client, &req, "Fetch streaks").await?;
+            let streaks: Vec<StreakRow> = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+            Ok(streak_standings(&members, &streaks, today))
+        }
+        Board::Exam => {
+            let req = fetch_group_exam_results_request(config, token, &ids);
+            let body = send(client, &req, "Fetch exams").await?;
+            let results: Vec<ExamResultRow> =
+                serde_json::from_str(&body).map_err(|e| e.to_string())?;
+            Ok(exam_standings(&members, &results, challenge))
+        }
+    }
+}
+
+/// Create a class with the user as its instructor.
+pub async fn create_class(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    name: &str,
+) -> Result<ClassRow, String> {
+    let req = create_class_request(config, token, name);
+    let body = send(client, &req, "Create class").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Join a class with its join code as `display_name`.
+pub async fn join_class(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    join_code: &str,
+    display_name: &str,
+) -> Result<ClassRow, String> {
+    let req = join_class_request(config, token, join_code, display_name);
+    let body = send(client, &req, "Join class").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Take `user_id` out of a class: the user leaving, or their instructor removing them.
+pub async fn leave_class(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    class_id: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    let req = leave_class_request(config, token, class_id, user_id);
+    send(client, &req, "Leave class").await?;
+    Ok(())
+}
+
+/// Fetch the classes the user teaches or belongs to.
+pub async fn fetch_classes(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+) -> Result<Vec<ClassRow>, String> {
+    let req = fetch_classes_request(config, token);
+    let body = send(client, &req, "Fetch classes").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Set a class an assignment.
+pub async fn assign(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    class_id: &str,
+    assignment: &Assignment,
+) -> Result<(), String> {
+    let row = AssignmentRow {
+        id: None,
+        class_id: class_id.to_string(),
+        assignment: assignment.clone(),
+    };
+    let req = insert_assignment_request(config, token, &row)?;
+    send(client, &req, "Assign").await?;
+    Ok(())
+}
+
+/// Withdraw an assignment.
+pub async fn unassign(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    id: i64,
+) -> Result<(), String> {
+    let req = delete_assignment_request(config, token, id);
+    send(client, &req, "Unassign").await?;
+    Ok(())
+}
+
+/// Fetch the assignments of `class_ids`, newest first.
+pub async fn fetch_assignments(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    class_ids: &[String],
+) -> Result<Vec<AssignmentRow>, String> {
+    if class_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let req = fetch_assignments_request(config, token, class_ids);
+    let body = send(client, &req, "Fetch assignments").await?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Fetch the progress of each member of a class the user teaches, from their last `limit`
+/// answers. The whole class's answers come in one request.
+pub async fn fetch_class_progress(
+    client: &(impl HttpClient + ?Sized),
+    config: &SupabaseConfig,
+    token: &str,
+    class_id: &str,
+    limit: u32,
+) -> Result<Vec<MemberProgress>, String> {
+    let req = fetch_class_members_request(config, token, class_id);
+    let body = send(client, &req, "Fetch class members").await?;
+    let members: Vec<ClassMemberRow> = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    if members.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids: Vec<String> = members.iter().map(|m| m.user_id.clone()).collect();
+
+    let req = fetch_class_answers_request(config, token, &ids, limit);
+    let body = send(client, &req, "Fetch class answers").await?;
+    let rows: Vec<MemberAnswerRow> = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    let mut logs: HashMap<String, Vec<AnswerLogEntry>> = HashMap::new();
+    for row in rows {
+        logs.entry(row.user_id).or_default().push(row.entry);
+    }
+    Ok(members
+        .into_iter()
+        .map(|member| {
+            let member_logs = logs.remove(&member.user_id).unwrap_or_default();
+            MemberProgress {
+                member,
+                stats: ProgressStats::from_logs(&member_logs),
+            }
+        })
+        .collect())
+}
+
 /// Get coaching advice from the Claude-powered edge function.
 pub async fn get_coaching(
     client: &(impl HttpClient + ?Sized),
     config: &SupabaseConfig,
     token: &str,
+    strategy: &str,
 ) -> Result<String, String> {
-    let req = coaching_request(config, token);
+    let req = coaching_request(config, token, strategy);
     let resp = client
         .request(&req.method, &req.url, &req.headers, req.body.as_deref())
         .await?;
@@ -150,3 +457,838 @@ pub async fn refresh_session(
     let json: serde_json::Value = serde_json::from_str(&resp.body).ok()?;
     crate::supabase::parse_refresh_response(&json, &session.refresh_token)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rules;
+    use crate::blitz::Blitz;
+    use crate::strat::StrategyKind;
+    use serde_json::{Value, json};
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    /// An in-memory stand-in for PostgREST, signed in as `user`: tables of JSON rows, with
+    /// `eq.` and `in.()` filters, `order`, `limit`, inserts, upserts, deletes, the group
+    /// and class functions and the practice_streaks view. Rows can only be written as their
+    /// own user, groups are only visible to their members, and classes to their members and
+    /// instructor, who alone sees the members' answers and sets assignments. Scores,
+    /// exam results and streaks are visible to their owner and the groups they've joined, as
+    /// the migrations' policies have it. Inserts are stamped with `today`.
+    struct StubPostgrest {
+        user: String,
+        tables: Rc<RefCell<HashMap<String, Vec<Value>>>>,
+        today: Rc<RefCell<String>>,
+    }
+
+    impl StubPostgrest {
+        fn new(user: &str) -> Self {
+            StubPostgrest {
+                user: user.to_string(),
+                tables: Rc::default(),
+                today: Rc::new(RefCell::new("2025-08-01".to_string())),
+            }
+        }
+
+        /// Another user on the same database.
+        fn as_user(&self, user: &str) -> Self {
+            StubPostgrest {
+                user: user.to_string(),
+                tables: Rc::clone(&self.tables),
+                today: Rc::clone(&self.today),
+            }
+        }
+
+        /// Move the database's clock on to `day` ("YYYY-MM-DD").
+        fn set_today(&self, day: &str) {
+            *self.today.borrow_mut() = day.to_string();
+        }
+
+        fn respond(&self, method: &str, url: &str, body: Option<&str>) -> HttpResponse {
+            let reply = |status, body: Value| HttpResponse {
+                status,
+                body: body.to_string(),
+            };
+            let path = url.split("/rest/v1/").nth(1).unwrap_or_default();
+            let (table, query) = path.split_once('?').unwrap_or((path, ""));
+            let params: Vec<(&str, &str)> =
+                query.split('&').filter_map(|p| p.split_once('=')).collect();
+            let param = |name| params.iter().find(|(k, _)| *k == name).map(|(_, v)| *v);
+            let filters: Vec<(&str, &str)> = params
+                .iter()
+                .filter(|(k, _)| !["select", "order", "limit", "on_conflict"].contains(k))
+                .copied()
+                .collect();
+            let body: Value = body
+                .and_then(|b| serde_json::from_str(b).ok())
+                .unwrap_or_default();
+            let mut tables = self.tables.borrow_mut();
+            let user = self.user.as_str();
+            let classes = tables.get("classes").cloned().unwrap_or_default();
+            let class_members = tables.get("class_members").cloned().unwrap_or_default();
+            let instructs = |class: &Value| {
+                classes
+                    .iter()
+                    .any(|c| c["id"] == *class && c["instructor_id"] == user)
+            };
+            let in_class = |class: &Value| {
+                class_members
+                    .iter()
+                    .any(|m| m["class_id"] == *class && m["user_id"] == user)
+            };
+            // Whether the user teaches the row's author, and did when the row was stored
+            let teaches = |row: &Value| {
+                class_members.iter().any(|m| {
+                    m["user_id"] == row["user_id"]
+                        && instructs(&m["class_id"])
+                        && m["joined_at"].as_str() <= row["created_at"].as_str()
+                })
+            };
+            let group_members = tables.get("group_members").cloned().unwrap_or_default();
+            let shares_group_with = |other: &Value| {
+                group_members.iter().any(|mine| {
+                    mine["user_id"] == user
+                        && group_members.iter().any(|theirs| {
+                            theirs["group_id"] == mine["group_id"] && theirs["user_id"] == *other
+                        })
+                })
+            };
+
+            match (method, table) {
+                ("POST", "rpc/create_group") => {
+                    let id = format!("g{}", tables.get("groups").map_or(0, |g| g.len()) + 1);
+                    let group = json!({
+                        "id": id,
+                        "name": body["group_name"],
+                        "join_code": format!("code-{}", id),
+                    });
+                    tables
+                        .entry("groups".into())
+                        .or_default()
+                        .push(group.clone());
+                    let member = json!({
+                        "group_id": id,
+                        "user_id": self.user,
+                        "display_name": body["display_name"],
+                    });
+                    tables
+                        .entry("group_members".into())
+                        .or_default()
+                        .push(member);
+                    reply(200, group)
+                }
+                ("POST", "rpc/join_group") => {
+                    let groups = tables.get("groups").cloned().unwrap_or_default();
+                    let Some(group) = groups.into_iter().find(|g| g["join_code"] == body["code"])
+                    else {
+                        return reply(400, json!({ "message": "No group with that code" }));
+                    };
+                    let members = tables.entry("group_members".into()).or_default();
+                    members.retain(|m| {
+                        m["group_id"] != group["id"] || m["user_id"] != self.user.as_str()
+                    });
+                    members.push(json!({
+                        "group_id": group["id"],
+                        "user_id": self.user,
+                        "display_name": body["display_name"],
+                    }));
+                    reply(200, group)
+                }
+                ("POST", "rpc/create_class") => {
+                    let id = format!("c{}", classes.len() + 1);
+                    let class = json!({
+                        "id": id,
+                        "name": body["class_name"],
+                        "join_code": format!("class-{}", id),
+                        "instructor_id": user,
+                    });
+                    tables
+                        .entry("classes".into())
+                        .or_default()
+                        .push(class.clone());
+                    reply(200, class)
+                }
+                ("POST", "rpc/join_class") => {
+                    let Some(class) = classes.iter().find(|c| c["join_code"] == body["code"])
+                    else {
+                        return reply(400, json!({ "message": "No class with that code" }));
+                    };
+                    let members = tables.entry("class_members".into()).or_default();
+                    let same = |m: &Value| m["class_id"] == class["id"] && m["user_id"] == user;
+                    // Renaming keeps the day the member joined; the stub's clock moves by days
+                    let joined_at = members.iter().find(|m| same(m)).map_or_else(
+                        || format!("{}T00:00:00Z", self.today.borrow()).into(),
+                        |m| m["joined_at"].clone(),
+                    );
+                    members.retain(|m| !same(m));
+                    members.push(json!({
+                        "class_id": class["id"],
+                        "user_id": user,
+                        "display_name": body["display_name"],
+                        "joined_at": joined_at,
+                    }));
+                    reply(200, class.clone())
+                }
+                ("POST", _) => {
+                    let allowed = match table {
+                        "class_assignments" => instructs(&body["class_id"]),
+                        _ => body["user_id"] == user,
+                    };
+                    if !allowed {
+                        return reply(
+                            403,
+                            json!({ "message": "violates row-level security policy" }),
+                        );
+                    }
+                    let count = |column: &str| body[column].as_i64().unwrap_or(-1);
+                    let checked = match table {
+                        "blitz_scores" => {
+                            let secs = count("duration_secs");
+                            [30, 60, 120].contains(&secs)
+                                && (0..=secs * 3).contains(&count("correct"))
+                                && (0..=secs / 5).contains(&count("wrong"))
+                        }
+                        "exam_results" => {
+                            let missed = body["missed"].as_array().map_or(0, |m| m.len() as i64);
+                            count("total") > 0
+                                && (0..=count("total")).contains(&count("correct"))
+                                && missed == count("total") - count("correct")
+                        }
+                        _ => true,
+                    };
+                    if !checked {
+                        return reply(400, json!({ "message": "violates check constraint" }));
+                    }
+                    let rows = tables.entry(table.into()).or_default();
+                    if let Some(column) = param("on_conflict") {
+                        rows.retain(|r| r[column] != body[column]);
+                    }
+                    // The columns the database fills in
+                    let mut body = body;
+                    if body.get("id").is_none() {
+                        body["id"] = (rows.len() + 1).into();
+                    }
+                    body["created_at"] =
+                        format!("{}T10:{:02}:00Z", self.today.borrow(), rows.len() % 60).into();
+                    rows.push(body);
+                    reply(201, Value::Null)
+                }
+                ("DELETE", _) => {
+                    let allowed = |r: &Value| match table {
+                        "class_members" => r["user_id"] == user || instructs(&r["class_id"]),
+                        "class_assignments" => instructs(&r["class_id"]),
+                        _ => r["user_id"] == user,
+                    };
+                    let rows = tables.entry(table.into()).or_default();
+                    rows.retain(|r| !(matches(r, &filters) && allowed(r)));
+                    reply(204, Value::Null)
+                }
+                ("GET", "practice_streaks") => {
+                    let logs = tables.get("answer_log").cloned().unwrap_or_default();
+                    let mut users: Vec<&Value> = Vec::new();
+                    for log in &logs {
+                        if !users.contains(&&log["user_id"]) {
+                            users.push(&log["user_id"]);
+                        }
+                    }
+                    let rows = users
+                        .into_iter()
+                        .filter(|u| **u == user || shares_group_with(u))
+                        .filter_map(|u| {
+                            let entries: Vec<AnswerLogEntry> = logs
+                                .iter()
+                                .filter(|r| r["user_id"] == *u)
+                                .filter_map(|r| serde_json::from_value(r.clone()).ok())
+                                .collect();
+                            let (last_day, days) =
+                                ProgressStats::from_logs(&entries).practice_streak?;
+                            Some(json!({ "user_id": u, "last_day": last_day, "days": days }))
+                        })
+                        .filter(|r| matches(r, &filters))
+                        .collect();
+                    reply(200, Value::Array(rows))
+                }
+                ("GET", "recent_answers") => {
+                    let mut logs: Vec<Value> = tables
+                        .get("answer_log")
+                        .into_iter()
+                        .flatten()
+                        .filter(|r| r["user_id"] == user || teaches(r))
+                        .cloned()
+                        .collect();
+                    logs.sort_by(|a, b| {
+                        b["created_at"]
+                            .to_string()
+                            .cmp(&a["created_at"].to_string())
+                    });
+                    let mut counts: HashMap<String, u32> = HashMap::new();
+                    let rows = logs
+                        .into_iter()
+                        .map(|mut r| {
+                            let count = counts.entry(r["user_id"].to_string()).or_default();
+                            *count += 1;
+                            r["recent"] = (*count).into();
+                            r
+                        })
+                        .filter(|r| matches(r, &filters))
+                        .collect();
+                    reply(200, Value::Array(rows))
+                }
+                ("GET", _) => {
+                    let is_member = |group: &Value| {
+                        group_members
+                            .iter()
+                            .any(|m| m["group_id"] == *group && m["user_id"] == user)
+                    };
+                    let mut rows: Vec<Value> = tables
+                        .get(table)
+                        .into_iter()
+                        .flatten()
+                        .filter(|r| matches(r, &filters))
+                        .filter(|r| match table {
+                            "groups" => is_member(&r["id"]),
+                            "group_members" => is_member(&r["group_id"]),
+                            "classes" => instructs(&r["id"]) || in_class(&r["id"]),
+                            "class_members" => r["user_id"] == user || instructs(&r["class_id"]),
+                            "class_assignments" => {
+                                instructs(&r["class_id"]) || in_class(&r["class_id"])
+                            }
+                            "answer_log" => r["user_id"] == user || teaches(r),
+                            "blitz_scores" => {
+                                r["user_id"] == user || shares_group_with(&r["user_id"])
+                            }
+                            "exam_results" => {
+                                r["user_id"] == user
+                                    || shares_group_with(&r["user_id"])
+                                    || teaches(r)
+                            }
+                            _ => true,
+                        })
+                        .cloned()
+                        .collect();
+                    for order in param("order").into_iter().flat_map(|o| o.split(',')).rev() {
+                        let (column, dir) = order.split_once('.').unwrap_or((order, "asc"));
+                        rows.sort_by(|a, b| {
+                            let ord = a[column].to_string().cmp(&b[column].to_string());
+                            if dir == "desc" { ord.reverse() } else { ord }
+                        });
+                    }
+                    if let Some(limit) = param("limit").and_then(|l| l.parse().ok()) {
+                        rows.truncate(limit);
+                    }
+                    reply(200, Value::Array(rows))
+                }
+                _ => reply(405, Value::Null),
+            }
+        }
+    }
+
+    /// Whether a row passes PostgREST filters such as `user_id=eq.u1` or `id=in.(a,b)`.
+    fn matches(row: &Value, filters: &[(&str, &str)]) -> bool {
+        filters.iter().all(|(column, filter)| {
+            let value = match &row[*column] {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            match filter.split_once('.') {
+                Some(("eq", want)) => value == want,
+                Some(("lte", max)) => matches!(
+                    (value.parse::<i64>(), max.parse::<i64>()),
+                    (Ok(value), Ok(max)) if value <= max
+                ),
+                Some(("in", list)) => list
+                    .trim_matches(|c| c == '(' || c == ')')
+                    .split(',')
+                    .any(|want| value == want),
+                _ => false,
+            }
+        })
+    }
+
+    impl HttpClient for StubPostgrest {
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            _headers: &[(Cow<'static, str>, Cow<'static, str>)],
+            body: Option<&str>,
+        ) -> impl std::future::Future<Output = Result<HttpResponse, String>> {
+            let response = self.respond(method, url, body);
+            async move { Ok(response) }
+        }
+    }
+
+    /// Run a future that never waits, as the stand-in's don't.
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        loop {
+            if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    fn config() -> SupabaseConfig {
+        SupabaseConfig {
+            base_url: "http://localhost:3000".to_string(),
+            anon_key: "anon".to_string(),
+        }
+    }
+
+    fn score(correct: u32, wrong: u32) -> BlitzScore {
+        let mut blitz = Blitz::new(60, &Rules::default(), StrategyKind::Basic, false);
+        (0..correct).for_each(|_| blitz.record(true));
+        (0..wrong).for_each(|_| blitz.record(false));
+        blitz.score("2025-07-20T10:00:00Z".to_string())
+    }
+
+    fn post_score(db: &StubPostgrest, correct: u32, wrong: u32) {
+        let row = BlitzScoreRow {
+            user_id: db.user.clone(),
+            score: score(correct, wrong),
+        };
+        block_on(insert_blitz_score(db, &config(), "t", &row)).unwrap();
+    }
+
+    #[test]
+    fn groups_rank_their_members() {
+        let ann = StubPostgrest::new("u-ann");
+        let bob = ann.as_user("u-bob");
+        let dan = ann.as_user("u-dan");
+        let config = config();
+        let group = block_on(create_group(&ann, &config, "t", "Team", "Ann")).unwrap();
+        assert_eq!(
+            group,
+            block_on(join_group(&bob, &config, "t", &group.join_code, "Bob")).unwrap()
+        );
+        assert!(block_on(join_group(&dan, &config, "t", "nope", "Dan")).is_err());
+        assert_eq!(
+            vec![group.clone()],
+            block_on(fetch_groups(&bob, &config, "t")).unwrap()
+        );
+        assert!(
+            block_on(fetch_groups(&dan, &config, "t"))
+                .unwrap()
+                .is_empty()
+        );
+
+        post_score(&ann, 20, 2);
+        post_score(&ann, 22, 3);
+        post_score(&bob, 21, 0);
+        // Not in the group
+        post_score(&dan, 40, 0);
+        let board = |db, board| {
+            block_on(fetch_leaderboard(
+                db,
+                &config,
+                "t",
+                &group.id,
+                board,
+                &score(0, 0),
+                "2025-07-20",
+            ))
+            .unwrap()
+            .into_iter()
+            .map(|s| format!("{} {}: {}", s.rank, s.display_name, s.score))
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec!["1 Ann: 22 right, 3 wrong", "2 Bob: 21 right, 0 wrong"],
+            board(&bob, Board::Blitz)
+        );
+        assert!(board(&dan, Board::Blitz).is_empty());
+
+        // A score no game could produce is refused
+        let forged = BlitzScoreRow {
+            user_id: "u-dan".to_string(),
+            score: score(9999, 0),
+        };
+        assert!(block_on(insert_blitz_score(&dan, &config, "t", &forged)).is_err());
+
+        // Streaks come from the days the answers were logged on
+        let log = |db: &StubPostgrest, day: &str| {
+            db.set_today(day);
+            let row = AnswerLogRow {
+                user_id: db.user.clone(),
+                table_index: "hard:16,10".to_string(),
+                correct: true,
+                player_action: "Hit".to_string(),
+                correct_action: "Hit".to_string(),
+                latency_ms: None,
+            };
+            block_on(insert_answer_log(db, &config, "t", &row)).unwrap();
+        };
+        for day in ["2025-07-10", "2025-07-17", "2025-07-18", "2025-07-19"] {
+            log(&ann, day);
+        }
+        for day in ["2025-07-19", "2025-07-20", "2025-07-20"] {
+            log(&bob, day);
+        }
+        assert_eq!(
+            vec!["1 Ann: 3 days", "2 Bob: 2 days"],
+            board(&ann, Board::Streak)
+        );
+        assert!(board(&ann, Board::Exam).is_empty());
+
+        block_on(leave_group(&bob, &config, "t", &group.id, "u-bob")).unwrap();
+        assert_eq!(vec!["1 Ann: 3 days"], board(&ann, Board::Streak));
+        assert!(
+            block_on(fetch_groups(&bob, &config, "t"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn scores_and_streaks_stay_in_the_group() {
+        let ann = StubPostgrest::new("u-ann");
+        let bob = ann.as_user("u-bob");
+        let dan = ann.as_user("u-dan");
+        let config = config();
+        let group = block_on(create_group(&ann, &config, "t", "Team", "Ann")).unwrap();
+        block_on(join_group(&bob, &config, "t", &group.join_code, "Bob")).unwrap();
+
+        post_score(&ann, 25, 0);
+        post_score(&bob, 20, 1);
+        let exam = ExamResultRow {
+            user_id: "u-ann".to_string(),
+            report: ExamReport {
+                taken_at: "2025-07-20T10:00:00Z".to_string(),
+                duration_secs: 300,
+                rules: Rules::default(),
+                strategy: StrategyKind::Basic,
+                custom_charts: false,
+                correct: 10,
+                total: 10,
+                by_table: Vec::new(),
+                missed: Vec::new(),
+            },
+        };
+        block_on(insert_exam_result(&ann, &config, "t", &exam)).unwrap();
+        let row = AnswerLogRow {
+            user_id: "u-ann".to_string(),
+            table_index: "hard:16,10".to_string(),
+            correct: true,
+            player_action: "Hit".to_string(),
+            correct_action: "Hit".to_string(),
+            latency_ms: None,
+        };
+        block_on(insert_answer_log(&ann, &config, "t", &row)).unwrap();
+
+        // What `db` can read of Ann's scores, exam results and streak
+        let sees_ann = |db: &StubPostgrest| {
+            let ids = vec!["u-ann".to_string()];
+            [
+                fetch_group_blitz_scores_request(&config, "t", &ids, &score(0, 0)),
+                fetch_group_exam_results_request(&config, "t", &ids),
+                fetch_group_streaks_request(&config, "t", &ids),
+            ]
+            .map(|req| block_on(send(db, &req, "Fetch")).unwrap() != "[]")
+        };
+        assert_eq!([true; 3], sees_ann(&ann));
+        assert_eq!([true; 3], sees_ann(&bob));
+        assert_eq!([false; 3], sees_ann(&dan));
+        // A personal best is the player's own, even with the group's scores in view
+        let best = block_on(fetch_blitz_best(&bob, &config, "t", "u-bob", &score(0, 0)));
+        assert_eq!(Some(score(20, 1)), best.unwrap());
+
+        block_on(leave_group(&bob, &config, "t", &group.id, "u-bob")).unwrap();
+        assert_eq!([false; 3], sees_ann(&bob));
+    }
+
+    #[test]
+    fn the_best_blitz_comes_back_from_the_table() {
+        let ann = StubPostgrest::new("u-ann");
+        post_score(&ann, 20, 2);
+        post_score(&ann, 22, 3);
+        post_score(&ann, 22, 1);
+        let best = block_on(fetch_blitz_best(
+            &ann,
+            &config(),
+            "t",
+            "u-ann",
+            &score(0, 0),
+        ))
+        .unwrap();
+        assert_eq!(Some(score(22, 1)), best);
+        let other = ann.as_user("u-bob");
+        let mut challenge = score(0, 0);
+        challenge.duration_secs = 30;
+        assert_eq!(
+            None,
+            block_on(fetch_blitz_best(
+                &other,
+                &config(),
+                "t",
+                "u-bob",
+                &challenge
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn instructors_see_only_answers_since_the_member_joined() {
+        let teacher = StubPostgrest::new("u-teach");
+        let ann = teacher.as_user("u-ann");
+        let config = config();
+        let class = block_on(create_class(&teacher, &config, "t", "August intake")).unwrap();
+        let log = |cell: &str| {
+            let row = AnswerLogRow {
+                user_id: "u-ann".to_string(),
+                table_index: cell.to_string(),
+                correct: true,
+                player_action: "Stand".to_string(),
+                correct_action: "Stand".to_string(),
+                latency_ms: None,
+            };
+            block_on(insert_answer_log(&ann, &config, "t", &row)).unwrap();
+        };
+        let exam = |total| {
+            let row = ExamResultRow {
+                user_id: "u-ann".to_string(),
+                report: ExamReport {
+                    taken_at: "2025-08-01T10:00:00Z".to_string(),
+                    duration_secs: 300,
+                    rules: Rules::default(),
+                    strategy: StrategyKind::Basic,
+                    custom_charts: false,
+                    correct: total,
+                    total,
+                    by_table: Vec::new(),
+                    missed: Vec::new(),
+                },
+            };
+            block_on(insert_exam_result(&ann, &config, "t", &row)).unwrap();
+        };
+        log("hard:12,5");
+        log("hard:13,6");
+        exam(10);
+
+        teacher.set_today("2025-08-02");
+        block_on(join_class(&ann, &config, "t", &class.join_code, "Ann")).unwrap();
+        log("soft:18,9");
+        exam(20);
+        teacher.set_today("2025-08-03");
+        // Changing the name shown doesn't move the day Ann joined
+        block_on(join_class(&ann, &config, "t", &class.join_code, "Annie")).unwrap();
+
+        let progress = block_on(fetch_class_progress(
+            &teacher, &config, "t", &class.id, 1000,
+        ));
+        let stats = progress.unwrap().remove(0).stats;
+        assert_eq!(
+            (1, 0, 1),
+            (stats.total_answers, stats.hard_total, stats.soft_total)
+        );
+        let exams = block_on(fetch_exam_results(&teacher, &config, "t", "u-ann", 10)).unwrap();
+        assert_eq!(vec![20], exams.iter().map(|e| e.total).collect::<Vec<_>>());
+        // Ann's own history is all there
+        let own = block_on(fetch_answer_logs(&ann, &config, "t", "u-ann", 1000)).unwrap();
+        assert_eq!(3, own.len());
+    }
+
+    #[test]
+    fn instructors_see_their_class_and_set_its_work() {
+        let teacher = StubPostgrest::new("u-teach");
+        let ann = teacher.as_user("u-ann");
+        let bob = teacher.as_user("u-bob");
+        let dan = teacher.as_user("u-dan");
+        let config = config();
+        let class = block_on(create_class(&teacher, &config, "t", "August intake")).unwrap();
+        assert_eq!("u-teach", class.instructor_id);
+        for (db, name) in [(&ann, "Ann"), (&bob, "Bob")] {
+            let joined = block_on(join_class(db, &config, "t", &class.join_code, name));
+            assert_eq!(class, joined.unwrap());
+        }
+        assert!(block_on(join_class(&dan, &config, "t", "nope", "Dan")).is_err());
+        assert_eq!(
+            vec![class.clone()],
+            block_on(fetch_classes(&ann, &config, "t")).unwrap()
+        );
+        assert!(
+            block_on(fetch_classes(&dan, &config, "t"))
+                .unwrap()
+                .is_empty()
+        );
+
+        let log = |db: &StubPostgrest, cell: &str, correct| {
+            let row = AnswerLogRow {
+                user_id: db.user.clone(),
+                table_index: cell.to_string(),
+                correct,
+                player_action: if correct { "Stand" } else { "Hit" }.to_string(),
+                correct_action: "Stand".to_string(),
+                latency_ms: None,
+            };
+            block_on(insert_answer_log(db, &config, "t", &row)).unwrap();
+        };
+        log(&ann, "hard:12,5", true);
+        log(&ann, "hard:13,6", false);
+        log(&bob, "soft:18,9", true);
+        log(&dan, "hard:16,10", true);
+
+        let progress = |db| {
+            block_on(fetch_class_progress(db, &config, "t", &class.id, 1000))
+                .unwrap()
+                .into_iter()
+                .map(|p| {
+                    let stats = p.stats;
+                    let name = p.member.display_name;
+                    (
+                        name,
+                        stats.total_answers,
+                        stats.hard_total,
+                        stats.soft_total,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![("Ann".to_string(), 2, 2, 0), ("Bob".to_string(), 1, 0, 1)],
+            progress(&teacher)
+        );
+        // Members see only themselves, and none of the others' answers
+        assert_eq!(vec![("Ann".to_string(), 2, 2, 0)], progress(&ann));
+        // The instructor's own history leaves out the members' answers
+        assert!(
+            block_on(fetch_answer_logs(&teacher, &config, "t", "u-teach", 1000))
+                .unwrap()
+                .is_empty()
+        );
+
+        let exam = Assignment::Exam {
+            per_table: Some(10),
+        };
+        let set: Assignment = "Soft doubles: soft vs 3-6 double".parse().unwrap();
+        block_on(assign(&teacher, &config, "t", &class.id, &exam)).unwrap();
+        block_on(assign(&teacher, &config, "t", &class.id, &set)).unwrap();
+        assert!(block_on(assign(&ann, &config, "t", &class.id, &exam)).is_err());
+        let ids = vec![class.id.clone()];
+        let assignments = block_on(fetch_assignments(&bob, &config, "t", &ids)).unwrap();
+        assert_eq!(
+            vec![set.clone(), exam.clone()],
+            assignments
+                .iter()
+                .map(|a| a.assignment.clone())
+                .collect::<Vec<_>>()
+        );
+        assert!(
+            block_on(fetch_assignments(&dan, &config, "t", &ids))
+                .unwrap()
+                .is_empty()
+        );
+
+        let exam_id = assignments[1].id.unwrap();
+        block_on(unassign(&ann, &config, "t", exam_id)).unwrap();
+        assert_eq!(
+            2,
+            block_on(fetch_assignments(&ann, &config, "t", &ids))
+                .unwrap()
+                .len()
+        );
+        block_on(unassign(&teacher, &config, "t", exam_id)).unwrap();
+        assert_eq!(
+            1,
+            block_on(fetch_assignments(&ann, &config, "t", &ids))
+                .unwrap()
+                .len()
+        );
+
+        // Bob leaves, and the instructor removes Ann
+        block_on(leave_class(&bob, &config, "t", &class.id, "u-bob")).unwrap();
+        block_on(leave_class(&teacher, &config, "t", &class.id, "u-ann")).unwrap();
+        assert!(progress(&teacher).is_empty());
+        assert!(
+            block_on(fetch_classes(&ann, &config, "t"))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            vec![class],
+            block_on(fetch_classes(&teacher, &config, "t")).unwrap()
+        );
+    }
+
+    /// The stand-in, counting the requests made of it.
+    struct Counting<'a> {
+        db: &'a StubPostgrest,
+        requests: Cell<usize>,
+    }
+
+    impl HttpClient for Counting<'_> {
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            _headers: &[(Cow<'static, str>, Cow<'static, str>)],
+            body: Option<&str>,
+        ) -> impl std::future::Future<Output = Result<HttpResponse, String>> {
+            self.requests.set(self.requests.get() + 1);
+            let response = self.db.respond(method, url, body);
+            async move { Ok(response) }
+        }
+    }
+
+    #[test]
+    fn class_progress_fetches_every_members_answers_at_once() {
+        let teacher = StubPostgrest::new("u-teach");
+        let config = config();
+        let class = block_on(create_class(&teacher, &config, "t", "August intake")).unwrap();
+        let members = [("u-ann", "Ann"), ("u-bob", "Bob"), ("u-cat", "Cat")];
+        for (user, name) in members {
+            let db = teacher.as_user(user);
+            block_on(join_class(&db, &config, "t", &class.join_code, name)).unwrap();
+        }
+        let log = |user: &str, cell: &str, correct| {
+            let row = AnswerLogRow {
+                user_id: user.to_string(),
+                table_index: cell.to_string(),
+                correct,
+                player_action: "Stand".to_string(),
+                correct_action: "Stand".to_string(),
+                latency_ms: None,
+            };
+            block_on(insert_answer_log(
+                &teacher.as_user(user),
+                &config,
+                "t",
+                &row,
+            ))
+            .unwrap();
+        };
+        log("u-bob", "soft:18,9", true);
+        for _ in 0..5 {
+            log("u-ann", "hard:12,5", false);
+        }
+        log("u-ann", "hard:12,5", true);
+        log("u-ann", "hard:13,6", true);
+
+        let counting = Counting {
+            db: &teacher,
+            requests: Cell::new(0),
+        };
+        let progress = block_on(fetch_class_progress(&counting, &config, "t", &class.id, 3));
+        let totals: Vec<(String, u32, u32)> = progress
+            .unwrap()
+            .into_iter()
+            .map(|p| {
+                let name = p.member.display_name;
+                (name, p.stats.total_answers, p.stats.hard_correct)
+            })
+            .collect();
+        // Each member's own last three: Ann's many answers don't crowd out Bob's
+        assert_eq!(
+            vec![
+                ("Ann".to_string(), 3, 2),
+                ("Bob".to_string(), 1, 0),
+                ("Cat".to_string(), 0, 0),
+            ],
+            totals
+        );
+        // The members, then everyone's answers
+        assert_eq!(2, counting.requests.get());
+    }
+}
@@ -20,6 +20,15 @@ pub enum BjError {
     #[error("Table index couldn't be parsed, '{0}'.")]
     BadTableIndex(String),
 
+    #[error("Invalid strategy chart: {0}.")]
+    InvalidChart(String),
+
+    #[error("Invalid study set: {0}.")]
+    InvalidStudySet(String),
+
+    #[error("Unknown action: '{0}'.")]
+    UnknownAction(String),
+
     #[error("Unknown table type: '{0}'.")]
     UnknownTableType(String),
 
@@ -0,0 +1,242 @@
+use crate::rules::Rules;
+use crate::strat::StrategyKind;
+use crate::table_index_keys::rules_scope;
+use serde::{Deserialize, Serialize};
+
+/// The challenge lengths on offer, in seconds.
+pub const BLITZ_LENGTHS_SECS: [u32; 3] = [30, 60, 120];
+
+/// The usual challenge length, in seconds.
+pub const BLITZ_SECS: u32 = 60;
+
+/// Time a wrong answer takes off the clock, in milliseconds.
+pub const WRONG_ANSWER_PENALTY_MS: u64 = 5000;
+
+/// Added to the rule set of challenges graded against the player's own charts.
+const CUSTOM_CHARTS_SUFFIX: &str = "/custom";
+
+/// A speed challenge under way: as many right answers as possible before the clock runs
+/// out. The frontend keeps the time and passes in how long the challenge has been running.
+#[derive(Debug, Clone)]
+pub struct Blitz {
+    duration_secs: u32,
+    correct: u32,
+    wrong: u32,
+    /// The rule set and strategy the answers are graded against.
+    rule_set: String,
+    strategy: StrategyKind,
+}
+
+impl Blitz {
+    /// A challenge graded against `strategy`'s charts for `rules`, or against the player's
+    /// own charts if `custom_charts`, which makes it a challenge of its own.
+    pub fn new(
+        duration_secs: u32,
+        rules: &Rules,
+        strategy: StrategyKind,
+        custom_charts: bool,
+    ) -> Self {
+        let mut rule_set = rules_scope(rules);
+        if custom_charts {
+            rule_set.push_str(CUSTOM_CHARTS_SUFFIX);
+        }
+        Blitz {
+            duration_secs,
+            correct: 0,
+            wrong: 0,
+            rule_set,
+            strategy,
+        }
+    }
+
+    pub fn duration_secs(&self) -> u32 {
+        self.duration_secs
+    }
+
+    pub fn correct(&self) -> u32 {
+        self.correct
+    }
+
+    pub fn wrong(&self) -> u32 {
+        self.wrong
+    }
+
+    /// Time left after `elapsed_ms`, less the penalties for wrong answers.
+    pub fn remaining_ms(&self, elapsed_ms: u64) -> u64 {
+        (self.duration_secs as u64 * 1000)
+            .saturating_sub(elapsed_ms)
+            .saturating_sub(self.wrong as u64 * WRONG_ANSWER_PENALTY_MS)
+    }
+
+    pub fn is_over(&self, elapsed_ms: u64) -> bool {
+        self.remaining_ms(elapsed_ms) == 0
+    }
+
+    pub fn record(&mut self, correct: bool) {
+        if correct {
+            self.correct += 1;
+        } else {
+            self.wrong += 1;
+        }
+    }
+
+    /// The final score. `taken_at` is when the challenge ended, as an RFC 3339 timestamp.
+    pub fn score(&self, taken_at: String) -> BlitzScore {
+        BlitzScore {
+            taken_at,
+            rule_set: self.rule_set.clone(),
+            strategy: self.strategy,
+            duration_secs: self.duration_secs,
+            correct: self.correct,
+            wrong: self.wrong,
+        }
+    }
+}
+
+/// A finished challenge, as saved locally and to the `blitz_scores` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlitzScore {
+    /// When the challenge ended, as an RFC 3339 timestamp.
+    pub taken_at: String,
+    /// The rules' key scope, e.g. "v3/bj-h17-das-nols-peek", ending "/custom" when the
+    /// player's own charts graded it.
+    pub rule_set: String,
+    pub strategy: StrategyKind,
+    pub duration_secs: u32,
+    pub correct: u32,
+    pub wrong: u32,
+}
+
+impl BlitzScore {
+    /// e.g. "23 right, 2 wrong in 60s".
+    pub fn summary(&self) -> String {
+        format!(
+            "{} right, {} wrong in {}s",
+            self.correct, self.wrong, self.duration_secs
+        )
+    }
+
+    /// Whether two scores are for the same challenge: rules, strategy and length.
+    pub fn same_challenge(&self, other: &BlitzScore) -> bool {
+        self.rule_set == other.rule_set
+            && self.strategy == other.strategy
+            && self.duration_secs == other.duration_secs
+    }
+
+    /// More right answers win, then fewer wrong ones.
+    pub fn beats(&self, other: &BlitzScore) -> bool {
+        (self.correct, other.wrong) > (other.correct, self.wrong)
+    }
+}
+
+/// The best score in `bests` for the same challenge as `score`.
+pub fn best_for<'a>(bests: &'a [BlitzScore], score: &BlitzScore) -> Option<&'a BlitzScore> {
+    bests.iter().find(|best| best.same_challenge(score))
+}
+
+/// Keep `score` in `bests`, one per challenge, if it beats the best so far. Returns whether
+/// it's a new personal best.
+pub fn record_best(bests: &mut Vec<BlitzScore>, score: &BlitzScore) -> bool {
+    match bests.iter_mut().find(|best| best.same_challenge(score)) {
+        Some(best) if score.beats(best) => *best = score.clone(),
+        Some(_) => return false,
+        None => bests.push(score.clone()),
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_answers_cost_time() {
+        let mut blitz = Blitz::new(BLITZ_SECS, &Rules::default(), StrategyKind::Basic, false);
+        assert_eq!(60_000, blitz.remaining_ms(0));
+        blitz.record(true);
+        blitz.record(false);
+        assert_eq!(45_000, blitz.remaining_ms(10_000));
+        assert!(!blitz.is_over(54_999));
+        assert!(blitz.is_over(55_000));
+        assert!(blitz.is_over(u64::MAX));
+        let score = blitz.score("2025-07-01T08:00:00Z".to_string());
+        assert_eq!("1 right, 1 wrong in 60s", score.summary());
+        assert_eq!("v3/bj-h17-das-nols-peek", score.rule_set);
+    }
+
+    #[test]
+    fn custom_charts_are_a_challenge_of_their_own() {
+        let rules = Rules::default();
+        let builtin =
+            Blitz::new(BLITZ_SECS, &rules, StrategyKind::Basic, false).score(String::new());
+        let custom = Blitz::new(BLITZ_SECS, &rules, StrategyKind::Basic, true).score(String::new());
+        assert_eq!("v3/bj-h17-das-nols-peek/custom", custom.rule_set);
+        assert!(!custom.same_challenge(&builtin));
+        assert_eq!(None, best_for(&[builtin], &custom));
+    }
+
+    #[test]
+    fn bests_are_kept_per_challenge() {
+        let blitz = |secs, strategy, correct, wrong| {
+            let mut blitz = Blitz::new(secs, &Rules::default(), strategy, false);
+            (0..correct).for_each(|_| blitz.record(true));
+            (0..wrong).for_each(|_| blitz.record(false));
+            blitz.score(String::new())
+        };
+        let mut bests = Vec::new();
+        assert!(record_best(
+            &mut bests,
+            &blitz(60, StrategyKind::Basic, 20, 3)
+        ));
+        assert!(!record_best(
+            &mut bests,
+            &blitz(60, StrategyKind::Basic, 19, 0)
+        ));
+        assert!(!record_best(
+            &mut bests,
+            &blitz(60, StrategyKind::Basic, 20, 3)
+        ));
+        assert!(record_best(
+            &mut bests,
+            &blitz(60, StrategyKind::Basic, 20, 1)
+        ));
+        assert!(record_best(
+            &mut bests,
+            &blitz(30, StrategyKind::Basic, 5, 0)
+        ));
+        assert!(record_best(
+            &mut bests,
+            &blitz(60, StrategyKind::Simple, 5, 0)
+        ));
+        assert_eq!(3, bests.len());
+        let best = best_for(&bests, &blitz(60, StrategyKind::Basic, 0, 0)).unwrap();
+        assert_eq!((20, 1), (best.correct, best.wrong));
+
+        let text = toml::to_string(&best).unwrap();
+        assert_eq!(*best, toml::from_str(&text).unwrap());
+    }
+
+    #[test]
+    fn score_rows_and_best_queries_match_the_table() {
+        let mut blitz = Blitz::new(30, &Rules::default(), StrategyKind::Simple, false);
+        blitz.record(true);
+        let score = blitz.score("2025-07-01T08:00:00Z".to_string());
+        let row = crate::supabase::BlitzScoreRow {
+            user_id: "u1".to_string(),
+            score: score.clone(),
+        };
+        let mut json = serde_json::to_value(&row).unwrap();
+        assert_eq!("u1", json["user_id"]);
+        assert_eq!("Simple", json["strategy"]);
+        json["id"] = 7.into();
+        let back: Vec<BlitzScore> = serde_json::from_value(vec![json].into()).unwrap();
+        assert_eq!(vec![score.clone()], back);
+
+        let config = crate::supabase::default_config();
+        let req = crate::supabase::fetch_blitz_best_request(&config, "t", "u1", &score);
+        assert!(req.url.ends_with(
+            "blitz_scores?select=*&user_id=eq.u1&rule_set=eq.v3/bj-h17-das-nols-peek&strategy=eq.Simple\
+             &duration_secs=eq.30&order=correct.desc,wrong.asc&limit=1"
+        ));
+    }
+}
@@ -13,6 +13,18 @@ impl Card {
     pub fn value(&self) -> u8 {
         self.pip.value()
     }
+
+    /// The card as it's typed, e.g. "TH", which parses back to the card.
+    pub fn code(&self) -> String {
+        format!("{}{}", self.pip, self.suit.letter())
+    }
+}
+
+impl From<Card> for u8 {
+    /// The inverse of `Card::try_from(u8)`: suit * 13 + pip, in 0..52.
+    fn from(card: Card) -> u8 {
+        card.suit as u8 * 13 + card.pip as u8
+    }
 }
 
 impl TryFrom<u8> for Card {
@@ -161,6 +173,16 @@ impl Suit {
     pub fn is_red(&self) -> bool {
         matches!(self, Suit::Hearts | Suit::Diamonds)
     }
+
+    /// The letter the suit is typed as.
+    pub fn letter(&self) -> char {
+        match self {
+            Suit::Spades => 'S',
+            Suit::Hearts => 'H',
+            Suit::Diamonds => 'D',
+            Suit::Clubs => 'C',
+        }
+    }
 }
 
 impl TryFrom<u8> for Suit {
@@ -256,4 +278,14 @@ mod test {
         assert_eq!(card(Pip::Five, Suit::Diamonds), 30.try_into().unwrap());
         assert_eq!(card(Pip::King, Suit::Clubs), 51.try_into().unwrap());
     }
+
+    #[test]
+    fn test_code_and_byte_round_trip() {
+        for n in 0..52u8 {
+            let c = Card::try_from(n).unwrap();
+            assert_eq!(n, u8::from(c));
+            assert_eq!(c, c.code().parse().unwrap());
+        }
+        assert_eq!("TD", card(Pip::Ten, Suit::Diamonds).code());
+    }
 }
@@ -0,0 +1,314 @@
+use crate::strat::{
+    COL_LABELS, ChartAction, ChartSet, ColIndex, RowIndex, TableIndex, TableType, new_table_index,
+    row_labels,
+};
+
+/// The tables a quiz can ask for, in the order the charts print them.
+pub const QUIZ_TABLES: [TableType; 3] = [TableType::Hard, TableType::Soft, TableType::Split];
+
+/// A blank hard, soft or pairs chart being filled in from memory. Rows and columns are in
+/// the printed chart's order: the highest total first, and dealer 2-9, T, A.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartQuiz {
+    table: TableType,
+    entries: Vec<[Option<ChartAction>; 10]>,
+    /// Row and column of the cell being filled in.
+    cursor: (usize, usize),
+    /// What's been typed of a code that isn't finished, e.g. "D", or "P" which may become
+    /// "Pd".
+    pending: String,
+}
+
+impl ChartQuiz {
+    pub fn new(table: TableType) -> Self {
+        ChartQuiz {
+            table,
+            entries: vec![[None; 10]; row_labels(table).len()],
+            cursor: (0, 0),
+            pending: String::new(),
+        }
+    }
+
+    pub fn table(&self) -> TableType {
+        self.table
+    }
+
+    /// The chart's title as the Strategy screen prints it.
+    pub fn title(&self) -> &'static str {
+        table_title(self.table)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn row_label(&self, row: usize) -> &'static str {
+        let labels = row_labels(self.table);
+        labels[labels.len() - 1 - row]
+    }
+
+    pub fn col_labels(&self) -> [&'static str; 10] {
+        COL_LABELS
+    }
+
+    pub fn entry(&self, row: usize, col: usize) -> Option<ChartAction> {
+        self.entries[row][col]
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    pub fn pending(&self) -> &str {
+        &self.pending
+    }
+
+    /// The chart cell at a row and column.
+    pub fn index(&self, row: usize, col: usize) -> TableIndex {
+        let chart_row = self.table.rows().rev().nth(row).unwrap_or_default();
+        let row = RowIndex::new(self.table, chart_row).expect("quiz rows are chart rows");
+        // Columns 2-9 and T are the card's value; ColIndex takes 11 for an ace
+        let col = ColIndex::new(col as u8 + 2).expect("quiz columns are chart columns");
+        new_table_index(row, col)
+    }
+
+    /// Move the cursor, stopping at the edges of the chart. Drops a half-typed code.
+    pub fn move_cursor(&mut self, rows: isize, cols: isize) {
+        let (row, col) = self.cursor;
+        self.set_cursor(
+            row.saturating_add_signed(rows),
+            col.saturating_add_signed(cols),
+        );
+    }
+
+    pub fn set_cursor(&mut self, row: usize, col: usize) {
+        self.cursor = (row.min(self.rows() - 1), col.min(9));
+        self.pending.clear();
+    }
+
+    /// Type one character of an action code ("H", "S", "Dh", "Ds", "P", "Pd", "Rh" or "-",
+    /// in any case). A code is entered as soon as it can't be the start of a longer one, and
+    /// the cursor moves on. Returns false if the character can't start or continue a code.
+    pub fn type_char(&mut self, c: char) -> bool {
+        let typed = format!("{}{}", self.pending, c);
+        if is_code_start(&typed) {
+            self.pending = typed;
+        } else if let Some(action) = ChartAction::from_code(&self.pending) {
+            // "P" followed by something other than "d"
+            self.enter(action);
+            return self.type_char(c);
+        } else {
+            self.pending.clear();
+            return false;
+        }
+        if let Some(action) = ChartAction::from_code(&self.pending)
+            && !ChartAction::ALL
+                .iter()
+                .any(|a| a.code().len() > self.pending.len() && is_start_of(&self.pending, a))
+        {
+            self.enter(action);
+        }
+        true
+    }
+
+    /// Enter a code that could still grow, such as "P". Returns false if nothing complete
+    /// was typed.
+    pub fn finish_code(&mut self) -> bool {
+        match ChartAction::from_code(&self.pending) {
+            Some(action) => {
+                self.enter(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fill in the cell under the cursor and move to the next one, reading across the rows.
+    pub fn enter(&mut self, action: ChartAction) {
+        let (row, col) = self.cursor;
+        self.entries[row][col] = Some(action);
+        self.pending.clear();
+        if col < 9 {
+            self.cursor = (row, col + 1);
+        } else if row + 1 < self.rows() {
+            self.cursor = (row + 1, 0);
+        }
+    }
+
+    /// Drop the half-typed code, or blank the cell under the cursor if there isn't one.
+    pub fn clear(&mut self) {
+        if self.pending.is_empty() {
+            let (row, col) = self.cursor;
+            self.entries[row][col] = None;
+        }
+        self.pending.clear();
+    }
+
+    pub fn filled(&self) -> usize {
+        self.entries
+            .iter()
+            .flatten()
+            .filter(|e| e.is_some())
+            .count()
+    }
+
+    /// Grade every cell against a chart set. Blank cells are wrong.
+    pub fn grade(&self, charts: &ChartSet) -> ChartQuizResult {
+        let cells = (0..self.rows())
+            .flat_map(|row| (0..10).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let index = self.index(row, col);
+                QuizCell {
+                    index,
+                    expected: charts.get(&index).unwrap_or(ChartAction::NoAc),
+                    entered: self.entry(row, col),
+                }
+            })
+            .collect();
+        ChartQuizResult {
+            table: self.table,
+            cells,
+        }
+    }
+}
+
+fn table_title(table: TableType) -> &'static str {
+    match table {
+        TableType::Hard => "Hard Totals",
+        TableType::Soft => "Soft Totals",
+        _ => "Pairs (Split)",
+    }
+}
+
+/// Whether some code starts with `typed`.
+fn is_code_start(typed: &str) -> bool {
+    ChartAction::ALL.iter().any(|a| is_start_of(typed, a))
+}
+
+fn is_start_of(typed: &str, action: &ChartAction) -> bool {
+    let code = action.code();
+    code.len() >= typed.len() && code[..typed.len()].eq_ignore_ascii_case(typed)
+}
+
+/// One graded cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuizCell {
+    pub index: TableIndex,
+    pub expected: ChartAction,
+    pub entered: Option<ChartAction>,
+}
+
+impl QuizCell {
+    pub fn is_correct(&self) -> bool {
+        self.entered == Some(self.expected)
+    }
+}
+
+/// A graded chart, cell by cell in the quiz's row and column order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartQuizResult {
+    pub table: TableType,
+    pub cells: Vec<QuizCell>,
+}
+
+impl ChartQuizResult {
+    pub fn cell(&self, row: usize, col: usize) -> &QuizCell {
+        &self.cells[row * 10 + col]
+    }
+
+    pub fn correct(&self) -> usize {
+        self.cells.iter().filter(|c| c.is_correct()).count()
+    }
+
+    pub fn missed(&self) -> impl Iterator<Item = &QuizCell> {
+        self.cells.iter().filter(|c| !c.is_correct())
+    }
+
+    /// e.g. "Hard Totals: 152/160 cells right".
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {}/{} cells right",
+            table_title(self.table),
+            self.correct(),
+            self.cells.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rules;
+
+    #[test]
+    fn cells_follow_the_printed_chart() {
+        let quiz = ChartQuiz::new(TableType::Hard);
+        assert_eq!(16, quiz.rows());
+        assert_eq!("20", quiz.row_label(0));
+        assert_eq!("hard:20,2", quiz.index(0, 0).to_string());
+        assert_eq!("hard:5,1", quiz.index(15, 9).to_string());
+        let pairs = ChartQuiz::new(TableType::Split);
+        assert_eq!(("T,T", "A,A"), (pairs.row_label(0), pairs.row_label(9)));
+        assert_eq!("split:1,10", pairs.index(9, 8).to_string());
+    }
+
+    #[test]
+    fn typing_codes_fills_cells_and_moves_on() {
+        let mut quiz = ChartQuiz::new(TableType::Split);
+        assert!(quiz.type_char('h'));
+        assert!(quiz.type_char('D'));
+        assert_eq!("D", quiz.pending());
+        assert!(quiz.type_char('s'));
+        // "P" waits to see if it's "Pd"
+        assert!(quiz.type_char('p'));
+        assert_eq!(None, quiz.entry(0, 3));
+        assert!(quiz.type_char('d'));
+        assert!(quiz.type_char('P'));
+        assert!(quiz.type_char('-'));
+        assert!(!quiz.type_char('x'));
+        assert_eq!(
+            vec![
+                Some(ChartAction::Hit_),
+                Some(ChartAction::DblS),
+                Some(ChartAction::SDas),
+                Some(ChartAction::Splt),
+                Some(ChartAction::NoAc),
+            ],
+            (0..5).map(|col| quiz.entry(0, col)).collect::<Vec<_>>()
+        );
+        assert_eq!((0, 5), quiz.cursor());
+
+        quiz.move_cursor(-1, 9);
+        assert_eq!((0, 9), quiz.cursor());
+        quiz.type_char('p');
+        assert!(quiz.finish_code());
+        assert_eq!((1, 0), quiz.cursor());
+        quiz.move_cursor(-1, 9);
+        quiz.clear();
+        assert_eq!(None, quiz.entry(0, 9));
+        assert_eq!(5, quiz.filled());
+    }
+
+    #[test]
+    fn grading_checks_every_cell() {
+        let charts = ChartSet::for_rules(&Rules::default());
+        let mut quiz = ChartQuiz::new(TableType::Soft);
+        // Fill in the chart correctly, then get soft 20 vs 6 wrong
+        for row in 0..quiz.rows() {
+            for col in 0..10 {
+                let index = quiz.index(row, col);
+                quiz.enter(charts.get(&index).unwrap());
+            }
+        }
+        quiz.set_cursor(1, 4);
+        quiz.enter(ChartAction::DblS);
+        quiz.set_cursor(9, 9);
+        quiz.clear();
+        let result = quiz.grade(&charts);
+        assert_eq!(98, result.correct());
+        let missed: Vec<String> = result.missed().map(|c| c.index.to_string()).collect();
+        assert_eq!(vec!["soft:20,6", "soft:12,1"], missed);
+        assert_eq!(ChartAction::Stnd, result.cell(1, 4).expected);
+        assert_eq!("Soft Totals: 98/100 cells right", result.summary());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::bjerror::{BjError, BjResult};
+use crate::progress::ProgressStats;
+use crate::study_set::StudySet;
+use crate::supabase::ClassMemberRow;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Work an instructor sets their class.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Assignment {
+    /// Drill a study set.
+    StudySet(StudySet),
+    /// Take an exam: every cell, or `per_table` cells from each table.
+    Exam { per_table: Option<usize> },
+}
+
+impl Assignment {
+    /// As shown in an assignment list, e.g. "Study Soft doubles (soft vs 3-6 double)".
+    pub fn label(&self) -> String {
+        match self {
+            Assignment::StudySet(set) => format!("Study {} ({})", set.name, set.filter()),
+            Assignment::Exam { per_table: None } => "Exam: every cell".to_string(),
+            Assignment::Exam { per_table: Some(n) } => {
+                format!("Short exam: {} cells from each table", n)
+            }
+        }
+    }
+}
+
+impl Display for Assignment {
+    /// e.g. "exam", "exam 10" or "Soft doubles: soft vs 3-6 double".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Assignment::StudySet(set) => write!(f, "{}", set),
+            Assignment::Exam { per_table: None } => write!(f, "exam"),
+            Assignment::Exam { per_table: Some(n) } => write!(f, "exam {}", n),
+        }
+    }
+}
+
+impl FromStr for Assignment {
+    type Err = BjError;
+
+    /// An assignment as `Display` writes it: "exam", "exam" and the cells per table, or a
+    /// study set.
+    fn from_str(s: &str) -> BjResult<Self> {
+        let s = s.trim();
+        match s.strip_prefix("exam") {
+            Some("") => Ok(Assignment::Exam { per_table: None }),
+            Some(rest) if rest.starts_with(' ') => {
+                let per_table = rest.trim().parse()?;
+                if per_table == 0 {
+                    return Err(BjError::InvalidStudySet(
+                        "an exam needs at least one cell per table".to_string(),
+                    ));
+                }
+                Ok(Assignment::Exam {
+                    per_table: Some(per_table),
+                })
+            }
+            _ => Ok(Assignment::StudySet(s.parse()?)),
+        }
+    }
+}
+
+/// A class member and their progress, as their instructor sees it.
+#[derive(Debug, Clone)]
+pub struct MemberProgress {
+    pub member: ClassMemberRow,
+    pub stats: ProgressStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assignments_parse_what_they_display() {
+        for text in ["exam", "exam 10", "Soft doubles: soft vs 3-6 double"] {
+            let assignment: Assignment = text.parse().unwrap();
+            assert_eq!(text, assignment.to_string());
+        }
+        assert_eq!(
+            Assignment::Exam { per_table: Some(5) },
+            " exam  5 ".parse().unwrap()
+        );
+        assert_eq!(
+            "Short exam: 5 cells from each table",
+            Assignment::Exam { per_table: Some(5) }.label()
+        );
+        assert!("exam 0".parse::<Assignment>().is_err());
+        assert!("exam five".parse::<Assignment>().is_err());
+        assert!("examine pairs".parse::<Assignment>().is_err());
+        assert!(matches!(
+            "exams: pairs".parse(),
+            Ok(Assignment::StudySet(set)) if set.name == "exams"
+        ));
+    }
+
+    #[test]
+    fn assignments_are_tagged_in_json() {
+        let exam = Assignment::Exam {
+            per_table: Some(10),
+        };
+        let json = serde_json::to_value(&exam).unwrap();
+        assert_eq!(serde_json::json!({ "kind": "exam", "per_table": 10 }), json);
+        let set: Assignment = "Pairs: pairs".parse().unwrap();
+        let json = serde_json::to_value(&set).unwrap();
+        assert_eq!("study_set", json["kind"]);
+        assert_eq!("Pairs", json["name"]);
+        assert_eq!(set, serde_json::from_value(json).unwrap());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::rules::{GameVariant, HoleCard, Rules, Soft17};
+use crate::strat::{ChartSet, TableType, strategy_charts};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// The strategy the player is training, as text for the coaching prompt: the legend, the
+/// hard, soft and pair tables, any extra plays, and the rules. The coaching function puts it
+/// in place of its built-in tables, so the coach teaches the charts the player is graded
+/// against.
+pub fn strategy_prompt(charts: &ChartSet, rules: &Rules) -> String {
+    let mut text = String::new();
+    text.push_str(
+        "Table index format: \"type:row,col\" where col is dealer's up card (1=Ace, 2-10).\n",
+    );
+    text.push_str(
+        "Actions: H=Hit, S=Stand, Dh=Double (Hit if can't), Ds=Double (Stand if can't), \
+         P=Split, Pd=Split (DAS only, otherwise don't split), Rh=Surrender (Hit if can't).\n",
+    );
+
+    let headings = ["## Hard Totals", "## Soft Totals", "## Pair Splitting"];
+    for (chart, heading) in strategy_charts(charts).into_iter().zip(headings) {
+        let _ = write!(text, "\n{}\nColumns: ", heading);
+        text.push_str(&row_cells(&chart.col_headers));
+        text.push('\n');
+        // Displayed charts run from the highest row down; the prompt reads top to bottom.
+        let rows: Vec<(String, &[&str])> = match heading {
+            // Hard rows at the ends that play alike are one row, "8-" or "17+"
+            "## Hard Totals" => charts
+                .hard_row_groups()
+                .iter()
+                .filter_map(|group| {
+                    let label = hard_label(group);
+                    let (_, cells) = chart
+                        .rows
+                        .iter()
+                        .find(|(l, _)| *l == group.start().to_string())?;
+                    Some((label, &cells[..]))
+                })
+                .collect(),
+            _ => chart
+                .rows
+                .iter()
+                .rev()
+                .map(|(label, cells)| (row_label(heading, label), &cells[..]))
+                .collect(),
+        };
+        for (label, cells) in rows {
+            let _ = writeln!(text, "{:<10}{}", format!("{}:", label), row_cells(cells));
+        }
+        // After a blank line, so the notes aren't read as table rows
+        if !chart.notes.is_empty() {
+            text.push('\n');
+        }
+        for note in &chart.notes {
+            let _ = writeln!(text, "Also: {}", note);
+        }
+    }
+
+    text.push_str(
+        "\n(\"-\" means do NOT split; use the hard total instead. \"Pd\" means split only with DAS.)\n",
+    );
+    text.push_str(&rules_line(rules));
+    text
+}
+
+// "8-" for the rows up to 8, "17+" for 17 and up, otherwise the total.
+fn hard_label(group: &RangeInclusive<u8>) -> String {
+    let rows = TableType::Hard.rows();
+    if group.start() == rows.start() {
+        format!("{}-", group.end())
+    } else if group.end() == rows.end() {
+        format!("{}+", group.start())
+    } else {
+        group.start().to_string()
+    }
+}
+
+// "A,7 (18)" for soft rows; other labels as the charts print them.
+fn row_label(heading: &str, label: &str) -> String {
+    match heading {
+        "## Soft Totals" => {
+            let other = match &label[2..] {
+                "A" => 1,
+                "T" => 10,
+                n => n.parse().unwrap_or(0),
+            };
+            format!("{} ({})", label, 11 + other)
+        }
+        _ => label.to_string(),
+    }
+}
+
+fn row_cells(cells: &[&str]) -> String {
+    cells
+        .iter()
+        .map(|c| format!("{:<2}", c))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end()
+        .to_string()
+}
+
+// "Rules: 6-deck shoe, dealer hits soft 17, double after split allowed (DAS), no surrender."
+fn rules_line(rules: &Rules) -> String {
+    let mut parts = Vec::new();
+    if rules.variant != GameVariant::Standard {
+        parts.push(rules.variant.to_string());
+    }
+    parts.push(format!("{}-deck shoe", rules.decks));
+    parts.push(
+        match rules.soft_17 {
+            Soft17::Hits => "dealer hits soft 17",
+            Soft17::Stands => "dealer stands on soft 17",
+        }
+        .to_string(),
+    );
+    parts.push(
+        if rules.das {
+            "double after split allowed (DAS)"
+        } else {
+            "no double after split"
+        }
+        .to_string(),
+    );
+    parts.push(
+        if rules.late_surrender() {
+            "late surrender"
+        } else {
+            "no surrender"
+        }
+        .to_string(),
+    );
+    if rules.resplit_aces {
+        parts.push("resplit aces".to_string());
+    }
+    if rules.hole_card == HoleCard::Enhc {
+        parts.push("no dealer hole card (ENHC)".to_string());
+    }
+    parts.push(format!("blackjack pays {}", rules.payout));
+    format!("Rules: {}.", parts.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::PRESETS;
+    use crate::verify::{COACHING_SOURCE, check_coaching_prompt};
+
+    #[test]
+    fn prompt_passes_the_consistency_check_for_every_preset() {
+        for preset in PRESETS.iter() {
+            let prompt = strategy_prompt(&ChartSet::for_rules(&preset.rules), &preset.rules);
+            let mismatches = check_coaching_prompt(&prompt, &preset.rules);
+            assert!(mismatches.is_empty(), "{}: {:?}", preset.key, mismatches);
+        }
+    }
+
+    #[test]
+    fn standard_prompt_matches_the_built_in_tables() {
+        let rules = Rules::standard();
+        let prompt = strategy_prompt(&ChartSet::for_rules(&rules), &rules);
+        for line in prompt
+            .lines()
+            .filter(|l| l.starts_with("16:") || l.starts_with("A,7"))
+        {
+            assert!(COACHING_SOURCE.contains(line), "{}", line);
+        }
+        assert!(prompt.ends_with(
+            "Rules: 6-deck shoe, dealer hits soft 17, double after split allowed (DAS), \
+             no surrender, blackjack pays 3:2."
+        ));
+    }
+
+    #[test]
+    fn custom_charts_are_what_the_prompt_shows() {
+        let rules = Rules::standard();
+        let mut csv = ChartSet::for_rules(&rules).to_csv();
+        csv = csv.replace("\"16\",S,S,S,S,S,H,H,H,H,H", "\"16\",S,S,S,S,S,S,H,H,H,H");
+        let charts = ChartSet::from_csv(&csv).unwrap();
+        let prompt = strategy_prompt(&charts, &rules);
+        assert!(prompt.contains("16:       S  S  S  S  S  S  H  H  H  H"));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::rules::Rules;
+use crate::strat::{Strategy, TableIndex, TableType};
+use crate::table_index_keys::{indices_for_type, table_index_to_key};
+use spaced_rep::{Deck, DeckSummary};
+
+/// Share of a lesson's cells that must be mastered before the next lesson unlocks.
+pub const MASTERY_THRESHOLD: f64 = 0.8;
+
+/// One region of the charts, taught as a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lesson {
+    pub name: &'static str,
+    table: TableType,
+    /// First and last row of each block of rows.
+    rows: &'static [(u8, u8)],
+}
+
+/// The lessons of the Curriculum mode in teaching order: the hard totals that play the
+/// same against every upcard, the stiff hands, the doubling totals, soft totals, and pairs.
+pub const LESSONS: [Lesson; 5] = [
+    Lesson {
+        name: "Hard 17+ and 8-",
+        table: TableType::Hard,
+        rows: &[(5, 8), (17, 20)],
+    },
+    Lesson {
+        name: "Stiff hands",
+        table: TableType::Hard,
+        rows: &[(12, 16)],
+    },
+    Lesson {
+        name: "Doubles",
+        table: TableType::Hard,
+        rows: &[(9, 11)],
+    },
+    Lesson {
+        name: "Soft totals",
+        table: TableType::Soft,
+        rows: &[(13, 21)],
+    },
+    Lesson {
+        name: "Splits",
+        table: TableType::Split,
+        rows: &[(1, 10)],
+    },
+];
+
+impl Lesson {
+    /// The lesson's chart cells.
+    pub fn indices(&self) -> Vec<TableIndex> {
+        indices_for_type(self.table)
+            .into_iter()
+            .filter(|ti| {
+                let row = ti.row_index();
+                self.rows
+                    .iter()
+                    .any(|(first, last)| (*first..=*last).contains(&row))
+            })
+            .collect()
+    }
+
+    /// The lesson's spaced-rep keys under the given rules and strategy.
+    pub fn keys(&self, rules: &Rules, strategy: &dyn Strategy) -> Vec<String> {
+        self.indices()
+            .iter()
+            .map(|ti| table_index_to_key(ti, rules, strategy))
+            .collect()
+    }
+
+    /// Whether enough of the lesson is mastered to go on to the next.
+    pub fn is_mastered(&self, deck: &Deck, rules: &Rules, strategy: &dyn Strategy) -> bool {
+        let keys = self.keys(rules, strategy);
+        is_mastered(&deck.summary(&keys), keys.len())
+    }
+}
+
+/// Whether a summary of `cells` cells reaches the mastery threshold.
+pub fn is_mastered(summary: &DeckSummary, cells: usize) -> bool {
+    cells > 0 && summary.mastered as f64 >= cells as f64 * MASTERY_THRESHOLD
+}
+
+/// The cells of every lesson up to and including `lesson`.
+pub fn unlocked_indices(lesson: usize) -> Vec<TableIndex> {
+    LESSONS
+        .iter()
+        .take(lesson + 1)
+        .flat_map(Lesson::indices)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strat::BasicStrategy;
+
+    #[test]
+    fn lessons_cover_every_cell_once() {
+        let mut cells: Vec<TableIndex> = unlocked_indices(LESSONS.len() - 1);
+        let total = cells.len();
+        assert_eq!(160 + 90 + 100, total);
+        cells.sort_by_key(|ti| ti.to_string());
+        cells.dedup();
+        assert_eq!(total, cells.len());
+        assert_eq!(80, LESSONS[0].indices().len());
+        assert_eq!(LESSONS[0].indices(), unlocked_indices(0));
+    }
+
+    #[test]
+    fn a_lesson_is_mastered_at_the_threshold() {
+        let rules = Rules::default();
+        let lesson = &LESSONS[2];
+        let keys = lesson.keys(&rules, &BasicStrategy);
+        let mut deck = Deck::new();
+        assert!(!lesson.is_mastered(&deck, &rules, &BasicStrategy));
+        let needed = (keys.len() as f64 * MASTERY_THRESHOLD).ceil() as usize;
+        for key in &keys[..needed] {
+            for _ in 0..spaced_rep::NUM_BOXES {
+                deck.record(key, true);
+            }
+        }
+        assert!(lesson.is_mastered(&deck, &rules, &BasicStrategy));
+        // Progress under one strategy doesn't count for another
+        assert!(!lesson.is_mastered(&deck, &rules, &crate::strat::SimpleStrategy));
+        assert!(!is_mastered(&DeckSummary::default(), 0));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::card::Card;
+use crate::hand::Hand;
+use crate::strat::{Action, ColIndex, RowIndex, TableIndex, TableType, new_table_index};
+use crate::{BjError, BjResult};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Serde support for cards, hands, actions and table indices.
+//
+// Each serializes as the string it parses from: a card as "TH", a hand as "KS 7D" (with a
+// dealer's hole card in parentheses, "KS (7D)"), an action as "Stand", a table type as
+// "hard" and a table index as "hard:16,9". Those are the forms the answer log has always
+// stored, so old rows read back as the new types. Where size matters, `Compact` gives each
+// a small binary code, and a field marked `#[serde(with = "bjsc::encoding::compact")]` is
+// written as its code instead.
+
+macro_rules! serde_as_string {
+    ($ty:ty, $to_string:expr) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&$to_string(self))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+serde_as_string!(Card, Card::code);
+serde_as_string!(Hand, Hand::code);
+serde_as_string!(Action, Action::to_string);
+serde_as_string!(TableIndex, TableIndex::to_string);
+serde_as_string!(TableType, TableType::to_string);
+
+/// A fixed-size binary form of a value, for payloads where the strings are too big.
+pub trait Compact: Sized {
+    type Code;
+
+    fn to_compact(&self) -> Self::Code;
+
+    /// Fails on a code that no value encodes to.
+    fn from_compact(code: Self::Code) -> BjResult<Self>;
+}
+
+/// A card is suit * 13 + pip, in 0..52.
+impl Compact for Card {
+    type Code = u8;
+
+    fn to_compact(&self) -> u8 {
+        u8::from(*self)
+    }
+
+    fn from_compact(code: u8) -> BjResult<Self> {
+        Card::try_from(code)
+    }
+}
+
+/// An action is its place in Hit, Stand, Split, Double, Surrender.
+impl Compact for Action {
+    type Code = u8;
+
+    fn to_compact(&self) -> u8 {
+        *self as u8
+    }
+
+    fn from_compact(code: u8) -> BjResult<Self> {
+        Action::try_from(code)
+    }
+}
+
+// The top bit marks the dealer's hole card.
+const HOLE_CARD: u8 = 0x80;
+
+/// A hand is one byte per card, then the hole card, if any, with its top bit set.
+impl Compact for Hand {
+    type Code = Vec<u8>;
+
+    fn to_compact(&self) -> Vec<u8> {
+        let cards = self.cards().iter().map(Card::to_compact);
+        let hole = self.hole_card().map(|c| c.to_compact() | HOLE_CARD);
+        cards.chain(hole).collect()
+    }
+
+    fn from_compact(code: Vec<u8>) -> BjResult<Self> {
+        let mut hand = Hand::default();
+        for byte in code {
+            let card = Card::from_compact(byte & !HOLE_CARD)?;
+            if byte & HOLE_CARD != 0 {
+                hand.set_hole_card(card);
+            } else {
+                hand.add_card(card);
+            }
+        }
+        Ok(hand)
+    }
+}
+
+/// A table index is 0xTRRC: the table (hard, soft, split, surrender) in the top four bits,
+/// then the row and the dealer's card (1 for an ace).
+impl Compact for TableIndex {
+    type Code = u16;
+
+    fn to_compact(&self) -> u16 {
+        (self.table_type() as u16) << 12 | (self.row_index() as u16) << 4 | self.col.value() as u16
+    }
+
+    fn from_compact(code: u16) -> BjResult<Self> {
+        let table_type = TableType::try_from((code >> 12) as u8)?;
+        let row = RowIndex::new(table_type, (code >> 4) as u8)?;
+        let col = ColIndex::new((code & 0xF) as u8)?;
+        Ok(new_table_index(row, col))
+    }
+}
+
+/// Serde adapter that writes a `Compact` value as its code.
+pub mod compact {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Compact,
+        T::Code: Serialize,
+        S: Serializer,
+    {
+        value.to_compact().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Compact,
+        T::Code: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::from_compact(T::Code::deserialize(deserializer)?)
+            .map_err(|e: BjError| serde::de::Error::custom(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Answer {
+        index: TableIndex,
+        action: Action,
+        #[serde(with = "compact")]
+        packed: TableIndex,
+    }
+
+    #[test]
+    fn types_serialize_as_their_strings() {
+        let answer = Answer {
+            index: "hard:16,9".parse().unwrap(),
+            action: Action::Stand,
+            packed: "soft:18,1".parse().unwrap(),
+        };
+        let json = serde_json::to_string(&answer).unwrap();
+        assert_eq!(
+            r#"{"index":"hard:16,9","action":"Stand","packed":4385}"#,
+            json
+        );
+        assert_eq!(answer, serde_json::from_str(&json).unwrap());
+
+        let card: Card = "TH".parse().unwrap();
+        assert_eq!("\"TH\"", serde_json::to_string(&card).unwrap());
+        assert!(serde_json::from_str::<Action>("\"Fold\"").is_err());
+    }
+
+    #[test]
+    fn hands_keep_their_hole_card() {
+        let mut dealer: Hand = "KS".parse().unwrap();
+        dealer.set_hole_card("7D".parse().unwrap());
+        let json = serde_json::to_string(&dealer).unwrap();
+        assert_eq!("\"KS (7D)\"", json);
+        let back: Hand = serde_json::from_str(&json).unwrap();
+        assert_eq!(dealer.hole_card(), back.hole_card());
+
+        let code = dealer.to_compact();
+        assert_eq!(2, code.len());
+        let back = Hand::from_compact(code).unwrap();
+        assert_eq!(dealer.cards(), back.cards());
+        assert_eq!(dealer.hole_card(), back.hole_card());
+    }
+
+    #[test]
+    fn every_chart_cell_has_its_own_code() {
+        let mut codes = std::collections::HashSet::new();
+        for table_type in [TableType::Hard, TableType::Soft, TableType::Split] {
+            for row in table_type.rows() {
+                for col in 1..=10 {
+                    let index = new_table_index(
+                        RowIndex::new(table_type, row).unwrap(),
+                        ColIndex::new(col).unwrap(),
+                    );
+                    let code = index.to_compact();
+                    assert!(codes.insert(code));
+                    assert_eq!(index, TableIndex::from_compact(code).unwrap());
+                }
+            }
+        }
+        assert_eq!(
+            0x0109,
+            "hard:16,9".parse::<TableIndex>().unwrap().to_compact()
+        );
+    }
+
+    #[test]
+    fn bad_codes_are_errors() {
+        assert!(TableIndex::from_compact(0x0049).is_err()); // hard 4
+        assert!(TableIndex::from_compact(0x0100).is_err()); // no dealer card
+        assert!(TableIndex::from_compact(0x5109).is_err());
+        assert!(Card::from_compact(52).is_err());
+        assert!(Action::from_compact(5).is_err());
+        for action in [Action::Hit, Action::Double, Action::Surrender] {
+            assert_eq!(action, Action::from_compact(action.to_compact()).unwrap());
+        }
+    }
+}
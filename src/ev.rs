@@ -0,0 +1,651 @@
+use crate::Hand;
+use crate::rules::{GameVariant, Rules, Soft17};
+use crate::strat::{Action, ChartAction, ChartSet, TableIndex, TableType};
+
+// Expected values for each play in a chart cell, to explain why the chart plays a cell the
+// way it does.
+//
+// The numbers are an infinite-deck estimate: every card is drawn with the same probability
+// no matter what has been dealt. That is within a few hundredths of a unit of a full shoe
+// calculation, and close enough to show how far apart the plays are. Splits are played out
+// once (no resplits), and bonus payouts (Spanish 21's 21s) are left out.
+
+// Dealer final outcomes.
+const BUST: usize = 5; // 0..5 are 17..=21
+const BLACKJACK: usize = 6;
+const PUSH_22: usize = 7;
+
+/// The play-by-play value of a chart cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellExplanation {
+    /// The player's hand, e.g. "hard 16", "soft 18", "8,8".
+    pub hand: String,
+    /// The dealer's up card, e.g. "T".
+    pub dealer: String,
+    /// Expected value per unit bet of each legal play, best first.
+    pub evs: Vec<(Action, f64)>,
+    /// Chance the dealer busts with this up card (after peeking for blackjack, where the
+    /// dealer peeks).
+    pub dealer_bust: f64,
+    /// Why the chart's play is right, or what it gives up, in a sentence or two.
+    pub rationale: String,
+}
+
+impl CellExplanation {
+    /// The explanation as display lines: the EVs, the dealer's bust chance and the rationale.
+    pub fn lines(&self) -> Vec<String> {
+        let evs: Vec<String> = self
+            .evs
+            .iter()
+            .map(|(action, ev)| format!("{} {:+.3}", action, ev))
+            .collect();
+        vec![
+            format!(
+                "EV per unit, {} vs {}: {}",
+                self.hand,
+                self.dealer,
+                evs.join(" · ")
+            ),
+            format!(
+                "Dealer busts {:.0}% of the time with {} up.",
+                self.dealer_bust * 100.0,
+                self.dealer
+            ),
+            self.rationale.clone(),
+        ]
+    }
+}
+
+// A hand's value per unit of the original bet, with the chances the original stake wins or
+// loses (needed for Free Bet's free doubles and splits, which only pay on a win).
+#[derive(Debug, Clone, Copy, Default)]
+struct Value {
+    ev: f64,
+    win: f64,
+    lose: f64,
+}
+
+const BUSTED: Value = Value {
+    ev: -1.0,
+    win: 0.0,
+    lose: 1.0,
+};
+
+impl Value {
+    fn add_weighted(&mut self, other: Value, weight: f64) {
+        self.ev += other.ev * weight;
+        self.win += other.win * weight;
+        self.lose += other.lose * weight;
+    }
+
+    fn best(self, other: Value) -> Value {
+        if other.ev > self.ev { other } else { self }
+    }
+}
+
+// Best total of a hand with `hard` points (aces as 1), counting one ace as 11 if it fits.
+fn best_total(hard: u8, has_ace: bool) -> u8 {
+    if has_ace && hard + 10 <= 21 {
+        hard + 10
+    } else {
+        hard
+    }
+}
+
+struct Calculator<'a> {
+    rules: &'a Rules,
+    // Probability of drawing each card value, 1 (ace) to 10.
+    draw: [f64; 11],
+    // Dealer final outcomes for the up card.
+    dealer: [f64; 8],
+}
+
+impl<'a> Calculator<'a> {
+    fn new(rules: &'a Rules, up: u8) -> Self {
+        let mut draw = [0.0; 11];
+        // Spanish 21 decks have no pip tens: 12 ten-values in 48 cards.
+        let (other, ten) = match rules.variant {
+            GameVariant::Spanish21 => (4.0 / 48.0, 12.0 / 48.0),
+            _ => (1.0 / 13.0, 4.0 / 13.0),
+        };
+        for (v, p) in draw.iter_mut().enumerate().skip(1) {
+            *p = if v == 10 { ten } else { other };
+        }
+        let mut calc = Calculator {
+            rules,
+            draw,
+            dealer: [0.0; 8],
+        };
+        let mut dealer = [0.0; 8];
+        calc.dealer_draw(up, up == 1, 1, 1.0, &mut dealer);
+        // A dealer who peeks has already shown a blackjack before the player acts.
+        if rules.dealer_takes_hole_card() && dealer[BLACKJACK] > 0.0 {
+            let rest = 1.0 - dealer[BLACKJACK];
+            dealer[BLACKJACK] = 0.0;
+            dealer.iter_mut().for_each(|p| *p /= rest);
+        }
+        calc.dealer = dealer;
+        calc
+    }
+
+    fn dealer_draw(&self, hard: u8, has_ace: bool, cards: u8, weight: f64, out: &mut [f64; 8]) {
+        let total = best_total(hard, has_ace);
+        if cards == 2 && total == 21 {
+            out[BLACKJACK] += weight;
+            return;
+        }
+        if total > 21 {
+            let outcome = if total == 22 && self.rules.dealer_22_pushes() {
+                PUSH_22
+            } else {
+                BUST
+            };
+            out[outcome] += weight;
+            return;
+        }
+        let soft_17 = total == 17 && total != hard;
+        let hits = total < 17 || (soft_17 && self.rules.soft_17 == Soft17::Hits);
+        if !hits {
+            out[(total - 17) as usize] += weight;
+            return;
+        }
+        for c in 1..=10u8 {
+            self.dealer_draw(
+                hard + c,
+                has_ace || c == 1,
+                cards + 1,
+                weight * self.draw[c as usize],
+                out,
+            );
+        }
+    }
+
+    fn stand(&self, total: u8) -> Value {
+        let mut win = self.dealer[BUST];
+        let mut lose = self.dealer[BLACKJACK];
+        for (i, p) in self.dealer[..BUST].iter().enumerate() {
+            let dealer_total = 17 + i as u8;
+            if total > dealer_total {
+                win += p;
+            } else if total < dealer_total {
+                lose += p;
+            }
+        }
+        Value {
+            ev: win - lose,
+            win,
+            lose,
+        }
+    }
+
+    // The value of the hand after one more card, played with `then`.
+    fn draw_then(&self, hard: u8, has_ace: bool, then: impl Fn(u8, bool) -> Value) -> Value {
+        let mut value = Value::default();
+        for c in 1..=10u8 {
+            let (h, a) = (hard + c, has_ace || c == 1);
+            let next = if best_total(h, a) > 21 {
+                BUSTED
+            } else {
+                then(h, a)
+            };
+            value.add_weighted(next, self.draw[c as usize]);
+        }
+        value
+    }
+
+    // Hitting, then playing on as well as possible without doubling.
+    fn hit(&self, hard: u8, has_ace: bool) -> Value {
+        self.draw_then(hard, has_ace, |h, a| self.hit_or_stand(h, a))
+    }
+
+    fn hit_or_stand(&self, hard: u8, has_ace: bool) -> Value {
+        let stand = self.stand(best_total(hard, has_ace));
+        if best_total(hard, has_ace) == 21 {
+            return stand;
+        }
+        stand.best(self.hit(hard, has_ace))
+    }
+
+    // Doubling: one card, twice the bet. The win and loss chances are of the original stake.
+    fn double(&self, hard: u8, has_ace: bool) -> Value {
+        let once = self.draw_then(hard, has_ace, |h, a| self.stand(best_total(h, a)));
+        Value {
+            ev: once.ev * 2.0,
+            ..once
+        }
+    }
+
+    fn free_double(&self, hard: u8, has_ace: bool) -> bool {
+        self.rules.variant == GameVariant::FreeBet && !has_ace && (9..=11).contains(&hard)
+    }
+
+    fn double_value(&self, hard: u8, has_ace: bool) -> Value {
+        let double = self.double(hard, has_ace);
+        if self.free_double(hard, has_ace) {
+            // The house's half of the bet only pays on a win
+            Value {
+                ev: double.ev / 2.0 + double.win,
+                ..double
+            }
+        } else {
+            double
+        }
+    }
+
+    // One hand of a split pair of `value`s, played out.
+    fn split_hand(&self, value: u8) -> Value {
+        if value == 1 {
+            // Split aces take one card each
+            return self.draw_then(1, true, |h, a| self.stand(best_total(h, a)));
+        }
+        self.draw_then(value, false, |h, a| {
+            let play = self.hit_or_stand(h, a);
+            if self.rules.das {
+                play.best(self.double_value(h, a))
+            } else {
+                play
+            }
+        })
+    }
+
+    fn split(&self, value: u8) -> f64 {
+        let hand = self.split_hand(value);
+        if self.rules.variant == GameVariant::FreeBet && value != 10 {
+            // The house's hand only pays on a win
+            hand.ev + hand.win
+        } else {
+            2.0 * hand.ev
+        }
+    }
+}
+
+fn card_label(value: u8) -> &'static str {
+    const LABELS: [&str; 10] = ["A", "2", "3", "4", "5", "6", "7", "8", "9", "T"];
+    LABELS[(value - 1) as usize]
+}
+
+/// Explain the chart cell a hand is graded under: the EV of each play the hand can make, the
+/// dealer's bust chance and why the play `charts` gives the hand is right, or what it gives
+/// up.
+pub fn explain_cell(
+    index: &TableIndex,
+    rules: &Rules,
+    charts: &ChartSet,
+    player: &Hand,
+    dealer: &Hand,
+) -> CellExplanation {
+    let cards = player.num_cards();
+    let can_double = cards <= 2 || rules.double_any_cards();
+    let chart_play = match charts.lookup_action(player, dealer) {
+        Ok((ChartAction::DblH, _)) if !can_double => Some(Action::Hit),
+        Ok((ChartAction::DblS, _)) if !can_double => Some(Action::Stand),
+        Ok((action, _)) => action.apply_rules(),
+        Err(_) => None,
+    };
+    explain(index, rules, chart_play, cards)
+}
+
+// Explain a cell for a hand of `cards` cards, played `chart_play`.
+fn explain(
+    index: &TableIndex,
+    rules: &Rules,
+    chart_play: Option<Action>,
+    cards: usize,
+) -> CellExplanation {
+    let up = index.col_index().value();
+    let calc = Calculator::new(rules, up);
+    let row = index.row_index();
+
+    // (hard points, has ace, pair value)
+    let (hard, has_ace, pair, hand) = match index.table_type() {
+        TableType::Soft => (row - 10, true, None, format!("soft {}", row)),
+        TableType::Split => {
+            let label = card_label(row);
+            let hard = if row == 1 { 2 } else { row * 2 };
+            (hard, row == 1, Some(row), format!("{},{}", label, label))
+        }
+        _ => (row, false, None, format!("hard {}", row)),
+    };
+    let total = best_total(hard, has_ace);
+
+    // Only the plays open to a hand of this many cards
+    let two_cards = cards <= 2;
+    let mut evs = vec![
+        (Action::Stand, calc.stand(total).ev),
+        (Action::Hit, calc.hit(hard, has_ace).ev),
+    ];
+    if two_cards || rules.double_any_cards() {
+        evs.push((Action::Double, calc.double_value(hard, has_ace).ev));
+    }
+    if let Some(value) = pair.filter(|_| two_cards) {
+        evs.push((Action::Split, calc.split(value)));
+    }
+    if two_cards && rules.late_surrender() {
+        evs.push((Action::Surrender, -0.5));
+    }
+    evs.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let dealer = card_label(up).to_string();
+    let dealer_bust = calc.dealer[BUST];
+    let soft = total != hard;
+    let rationale = chart_play.map_or_else(String::new, |play| {
+        rationale(play, &evs, (total, soft), dealer_bust, &dealer)
+    });
+    CellExplanation {
+        hand,
+        dealer,
+        evs,
+        dealer_bust,
+        rationale,
+    }
+}
+
+// How far a chart's play may fall short of the estimate's best play and still be put down to
+// the estimate leaving out the cards already dealt.
+const CLOSE_CALL: f64 = 0.02;
+
+// `(total, soft)` is the hand's best total and whether it counts an ace as 11.
+fn rationale(
+    play: Action,
+    evs: &[(Action, f64)],
+    (total, soft): (u8, bool),
+    bust: f64,
+    dealer: &str,
+) -> String {
+    let ev_of = |action: Action| evs.iter().find(|(a, _)| *a == action).map(|(_, ev)| *ev);
+    let Some(chosen) = ev_of(play) else {
+        return String::new();
+    };
+    let runner_up = evs.iter().find(|(a, _)| *a != play);
+    let margin = match runner_up {
+        Some((other, ev)) if chosen >= *ev => format!(
+            "{} is worth {:+.3} a unit, {:.3} more than {}.",
+            play,
+            chosen,
+            chosen - ev,
+            other.to_string().to_lowercase()
+        ),
+        // A chart that plays the cell worse than the estimate's best play
+        Some((other, ev)) if ev - chosen < CLOSE_CALL => format!(
+            "{} is worth {:+.3} a unit; the chart's play gives up {:.3} against {}.",
+            play,
+            chosen,
+            ev - chosen,
+            other.to_string().to_lowercase()
+        ),
+        // Too far behind to be down to the cards in the shoe: no reasons for it
+        Some((other, ev)) => {
+            return format!(
+                "{} is worth {:+.3} a unit; the chart's play loses {:.3} a unit against {}, \
+                 the better play by this estimate.",
+                play,
+                chosen,
+                ev - chosen,
+                other.to_string().to_lowercase()
+            );
+        }
+        None => format!("{} is worth {:+.3} a unit.", play, chosen),
+    };
+    let reason = match play {
+        Action::Stand if total < 17 => format!(
+            "A {} up busts {:.0}% of the time, so let the dealer take the risk rather than \
+             busting first.",
+            dealer,
+            bust * 100.0
+        ),
+        Action::Stand => format!("{} is strong enough to stand on.", total),
+        Action::Hit if soft => format!(
+            "A soft {} can't bust with one more card, so a card to improve it costs little.",
+            total
+        ),
+        Action::Hit if total < 12 => format!("{} can't bust with one more card.", total),
+        Action::Hit if total < 17 => format!(
+            "Standing on {} only wins when the dealer busts, {:.0}% of the time with {} up; \
+             a card helps more often than it hurts.",
+            total,
+            bust * 100.0,
+            dealer
+        ),
+        Action::Hit => format!(
+            "A hard {} is strong, but a card helps it against a {} more often than it hurts.",
+            total, dealer
+        ),
+        Action::Double => format!(
+            "You're the favourite after one more card against a {}, so get twice the bet out.",
+            dealer
+        ),
+        Action::Split => {
+            "Two hands starting from one of these cards are worth more than the pair's total."
+                .to_string()
+        }
+        Action::Surrender => {
+            "Every play loses more than half a bet on average, so give up half instead.".to_string()
+        }
+    };
+    let best = evs.first().map(|(a, _)| *a);
+    let note = match best {
+        Some(best) if best != play => format!(
+            " (This estimate slightly favours {}; the chart accounts for the cards in the \
+             shoe.)",
+            best.to_string().to_lowercase()
+        ),
+        _ => String::new(),
+    };
+    format!("{} {}{}", margin, reason, note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HoleCard;
+    use crate::hand_builder::build_hand_for_index;
+    use crate::strat::{ColIndex, RowIndex, new_table_index};
+
+    fn index(table_type: TableType, row: u8, col: u8) -> TableIndex {
+        let col: ColIndex = col.to_string().parse().unwrap();
+        new_table_index(RowIndex::new(table_type, row).unwrap(), col)
+    }
+
+    fn s17() -> Rules {
+        Rules {
+            soft_17: Soft17::Stands,
+            ..Default::default()
+        }
+    }
+
+    // A two-card hand in the cell, played `play`.
+    fn explain_play(index: &TableIndex, rules: &Rules, play: Action) -> CellExplanation {
+        explain(index, rules, Some(play), 2)
+    }
+
+    fn best(table_type: TableType, row: u8, col: u8, rules: &Rules) -> Action {
+        explain_play(&index(table_type, row, col), rules, Action::Stand).evs[0].0
+    }
+
+    #[test]
+    fn dealer_bust_chances_match_published_figures() {
+        // Infinite deck, S17: 2 up 35.4%, 6 up 42.3%
+        let two = explain_play(&index(TableType::Hard, 12, 2), &s17(), Action::Hit);
+        assert!(
+            (two.dealer_bust - 0.354).abs() < 0.002,
+            "{}",
+            two.dealer_bust
+        );
+        let six = explain_play(&index(TableType::Hard, 12, 6), &s17(), Action::Stand);
+        assert!(
+            (six.dealer_bust - 0.423).abs() < 0.002,
+            "{}",
+            six.dealer_bust
+        );
+        // Hitting soft 17 busts the dealer more often.
+        let h17 = explain_play(
+            &index(TableType::Hard, 12, 6),
+            &Rules::default(),
+            Action::Stand,
+        );
+        assert!(h17.dealer_bust > six.dealer_bust);
+    }
+
+    #[test]
+    fn best_plays_agree_with_the_chart_on_clear_cells() {
+        let rules = Rules::default();
+        assert_eq!(Action::Double, best(TableType::Hard, 11, 6, &rules));
+        assert_eq!(Action::Stand, best(TableType::Hard, 16, 6, &rules));
+        assert_eq!(Action::Hit, best(TableType::Hard, 12, 2, &rules));
+        assert_eq!(Action::Split, best(TableType::Split, 8, 10, &rules));
+        assert_eq!(Action::Stand, best(TableType::Split, 10, 6, &rules));
+        assert_eq!(Action::Double, best(TableType::Soft, 18, 4, &rules));
+    }
+
+    #[test]
+    fn stand_on_sixteen_against_ten_is_about_minus_half() {
+        let ex = explain_play(&index(TableType::Hard, 16, 10), &s17(), Action::Hit);
+        let stand = ex.evs.iter().find(|(a, _)| *a == Action::Stand).unwrap().1;
+        assert!((stand + 0.540).abs() < 0.005, "{}", stand);
+        // No surrender without the rule
+        assert!(ex.evs.iter().all(|(a, _)| *a != Action::Surrender));
+
+        let ls = Rules {
+            surrender: true,
+            ..Default::default()
+        };
+        assert_eq!(Action::Surrender, best(TableType::Hard, 16, 10, &ls));
+        // Nor without the peek
+        let enhc = Rules {
+            hole_card: HoleCard::Enhc,
+            ..ls
+        };
+        assert_ne!(Action::Surrender, best(TableType::Hard, 16, 10, &enhc));
+    }
+
+    #[test]
+    fn explanation_reads_well() {
+        let ex = explain_play(
+            &index(TableType::Hard, 12, 4),
+            &Rules::default(),
+            Action::Stand,
+        );
+        let lines = ex.lines();
+        assert!(lines[0].starts_with("EV per unit, hard 12 vs 4: Stand"));
+        assert!(lines[1].starts_with("Dealer busts 40%"), "{}", lines[1]);
+        assert!(lines[2].starts_with("Stand is worth"), "{}", lines[2]);
+    }
+
+    #[test]
+    fn a_chart_that_gives_up_ev_says_how_much() {
+        let ev = |ex: &CellExplanation, play| ex.evs.iter().find(|(a, _)| *a == play).unwrap().1;
+
+        // A custom chart that hits hard 20 loses far too much to be a close call
+        let ex = explain_play(&index(TableType::Hard, 20, 6), &s17(), Action::Hit);
+        let (hit, stand) = (ev(&ex, Action::Hit), ev(&ex, Action::Stand));
+        assert!(stand - hit > 1.0, "{}", stand - hit);
+        assert_eq!(
+            format!(
+                "Hit is worth {:+.3} a unit; the chart's play loses {:.3} a unit against stand, \
+                 the better play by this estimate.",
+                hit,
+                stand - hit
+            ),
+            ex.rationale
+        );
+
+        // 16 against a ten is nearly even: the estimate can't see the cards in the shoe
+        let ex = explain_play(&index(TableType::Hard, 16, 10), &s17(), Action::Stand);
+        let (hit, stand) = (ev(&ex, Action::Hit), ev(&ex, Action::Stand));
+        assert!(hit > stand && hit - stand < CLOSE_CALL, "{}", hit - stand);
+        assert_eq!(
+            format!(
+                "Stand is worth {:+.3} a unit; the chart's play gives up {:.3} against hit. \
+                 A T up busts {:.0}% of the time, so let the dealer take the risk rather than \
+                 busting first. (This estimate slightly favours hit; the chart accounts for \
+                 the cards in the shoe.)",
+                stand,
+                hit - stand,
+                ex.dealer_bust * 100.0
+            ),
+            ex.rationale
+        );
+    }
+
+    #[test]
+    fn hitting_a_soft_hand_is_not_about_the_dealer_busting() {
+        let ex = explain_play(
+            &index(TableType::Soft, 18, 9),
+            &Rules::default(),
+            Action::Hit,
+        );
+        let (hit, next) = (ex.evs[0].1, ex.evs[1]);
+        assert_eq!(Action::Hit, ex.evs[0].0);
+        assert_eq!(
+            format!(
+                "Hit is worth {:+.3} a unit, {:.3} more than {}. A soft 18 can't bust with one \
+                 more card, so a card to improve it costs little.",
+                hit,
+                hit - next.1,
+                next.0.to_string().to_lowercase()
+            ),
+            ex.rationale
+        );
+    }
+
+    #[test]
+    fn explains_the_play_of_the_chart_being_graded() {
+        let rules = Rules::default();
+        let cell = index(TableType::Hard, 12, 2);
+        let (player, dealer) = build_hand_for_index(&cell, &rules);
+        let basic = explain_cell(
+            &cell,
+            &rules,
+            &ChartSet::for_rules(&rules),
+            &player,
+            &dealer,
+        );
+        assert!(
+            basic.rationale.starts_with("Hit is worth"),
+            "{}",
+            basic.rationale
+        );
+        // The simple strategy stands on 12 against a 2
+        let simple = explain_cell(&cell, &rules, &ChartSet::simple(), &player, &dealer);
+        assert!(
+            simple.rationale.starts_with("Stand is worth"),
+            "{}",
+            simple.rationale
+        );
+    }
+
+    #[test]
+    fn a_hand_past_two_cards_only_weighs_the_plays_it_can_make() {
+        let rules = Rules {
+            surrender: true,
+            ..Default::default()
+        };
+        let cell = index(TableType::Hard, 16, 10);
+        let plays = |ex: CellExplanation| ex.evs.iter().map(|(a, _)| *a).collect::<Vec<_>>();
+        let two = plays(explain_play(&cell, &rules, Action::Surrender));
+        assert!(two.contains(&Action::Double) && two.contains(&Action::Surrender));
+
+        let player: Hand = "4S 5D 7C".parse().unwrap();
+        let dealer: Hand = "KS".parse().unwrap();
+        let ex = explain_cell(
+            &cell,
+            &rules,
+            &ChartSet::for_rules(&rules),
+            &player,
+            &dealer,
+        );
+        let mut three = plays(ex.clone());
+        three.sort_by_key(|a| a.to_string());
+        assert_eq!(vec![Action::Hit, Action::Stand], three);
+        assert!(ex.rationale.starts_with("Hit is worth"), "{}", ex.rationale);
+
+        // Spanish 21 doubles on any number of cards
+        let s21 = crate::presets::PRESETS
+            .iter()
+            .find(|p| p.rules.variant == GameVariant::Spanish21)
+            .unwrap()
+            .rules;
+        let ex = explain_cell(&cell, &s21, &ChartSet::for_rules(&s21), &player, &dealer);
+        assert!(plays(ex).contains(&Action::Double));
+    }
+}
@@ -0,0 +1,360 @@
+use crate::rules::Rules;
+use crate::strat::{Action, StrategyKind, TableIndex, TableType};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+/// Share of the questions to answer right to pass.
+pub const PASS_MARK: f64 = 0.9;
+
+const TABLES: [TableType; 3] = [TableType::Hard, TableType::Soft, TableType::Split];
+
+/// An exam in progress: each question asked once, in random order, and graded only at the
+/// end.
+#[derive(Debug, Clone)]
+pub struct Exam {
+    questions: Vec<TableIndex>,
+    answers: Vec<ExamAnswer>,
+    /// The rules and strategy the exam grades against.
+    rules: Rules,
+    strategy: StrategyKind,
+    /// Whether the player's own charts grade it instead of the strategy's.
+    custom_charts: bool,
+}
+
+/// One answered question.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExamAnswer {
+    pub index: TableIndex,
+    pub correct_action: Action,
+    pub answer: Action,
+}
+
+impl ExamAnswer {
+    pub fn is_correct(&self) -> bool {
+        self.answer == self.correct_action
+    }
+}
+
+impl Exam {
+    /// Every cell once, shuffled.
+    pub fn full(
+        mut cells: Vec<TableIndex>,
+        rules: Rules,
+        strategy: StrategyKind,
+        custom_charts: bool,
+        rng: &mut impl Rng,
+    ) -> Self {
+        cells.shuffle(rng);
+        Exam {
+            questions: cells,
+            answers: Vec::new(),
+            rules,
+            strategy,
+            custom_charts,
+        }
+    }
+
+    /// `per_table` cells drawn from each table, shuffled: a shorter exam that still covers
+    /// hard totals, soft totals and pairs.
+    pub fn sample(
+        cells: Vec<TableIndex>,
+        per_table: usize,
+        rules: Rules,
+        strategy: StrategyKind,
+        custom_charts: bool,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let sample = TABLES
+            .iter()
+            .flat_map(|tt| {
+                let table: Vec<TableIndex> = cells
+                    .iter()
+                    .filter(|ti| ti.table_type() == *tt)
+                    .copied()
+                    .collect();
+                table
+                    .choose_multiple(rng, per_table)
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Exam::full(sample, rules, strategy, custom_charts, rng)
+    }
+
+    /// The question being asked; None once all are answered.
+    pub fn current(&self) -> Option<TableIndex> {
+        self.questions.get(self.answers.len()).copied()
+    }
+
+    pub fn answered(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.questions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// Answer the current question. Does nothing once the exam is finished.
+    pub fn record(&mut self, correct_action: Action, answer: Action) {
+        if let Some(index) = self.current() {
+            self.answers.push(ExamAnswer {
+                index,
+                correct_action,
+                answer,
+            });
+        }
+    }
+
+    /// Grade the answers so far. `taken_at` is when the exam finished, as an RFC 3339
+    /// timestamp.
+    pub fn report(&self, taken_at: String, duration_secs: u64) -> ExamReport {
+        let by_table: Vec<TableScore> = TABLES
+            .iter()
+            .map(|tt| {
+                let answers = self.answers.iter().filter(|a| a.index.table_type() == *tt);
+                TableScore {
+                    table: *tt,
+                    correct: answers.clone().filter(|a| a.is_correct()).count() as u32,
+                    total: answers.count() as u32,
+                }
+            })
+            .filter(|score| score.total > 0)
+            .collect();
+        let correct = by_table.iter().map(|s| s.correct).sum();
+        let total = by_table.iter().map(|s| s.total).sum();
+        ExamReport {
+            taken_at,
+            duration_secs,
+            rules: self.rules,
+            strategy: self.strategy,
+            custom_charts: self.custom_charts,
+            correct,
+            total,
+            by_table,
+            missed: self
+                .answers
+                .iter()
+                .filter(|a| !a.is_correct())
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// The score for one table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableScore {
+    pub table: TableType,
+    pub correct: u32,
+    pub total: u32,
+}
+
+/// A graded exam, as saved locally and to the `exam_results` table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExamReport {
+    /// When the exam finished, as an RFC 3339 timestamp.
+    pub taken_at: String,
+    pub duration_secs: u64,
+    pub rules: Rules,
+    pub strategy: StrategyKind,
+    /// Graded against the player's own charts rather than `strategy`'s.
+    #[serde(default)]
+    pub custom_charts: bool,
+    pub correct: u32,
+    pub total: u32,
+    pub by_table: Vec<TableScore>,
+    /// The questions answered wrong.
+    pub missed: Vec<ExamAnswer>,
+}
+
+impl ExamReport {
+    /// Whether the score reached `PASS_MARK`. Worked out from the score rather than saved,
+    /// so a result can't claim a pass its score doesn't earn.
+    pub fn passed(&self) -> bool {
+        self.total > 0 && self.correct as f64 >= self.total as f64 * PASS_MARK
+    }
+
+    /// What the exam was graded against, e.g. "Basic" or "Custom charts".
+    pub fn strategy_label(&self) -> String {
+        if self.custom_charts {
+            "Custom charts".to_string()
+        } else {
+            self.strategy.to_string()
+        }
+    }
+
+    pub fn score_pct(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.correct as f64 / self.total as f64 * 100.0
+    }
+
+    /// e.g. "PASS 96% (336/350) in 14:05".
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {:.0}% ({}/{}) in {}:{:02}",
+            if self.passed() { "PASS" } else { "FAIL" },
+            self.score_pct(),
+            self.correct,
+            self.total,
+            self.duration_secs / 60,
+            self.duration_secs % 60
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_index_keys::indices_for_mode;
+    use crate::{BasicStrategy, StudyMode};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn cells() -> Vec<TableIndex> {
+        indices_for_mode(StudyMode::All, &Rules::default(), &BasicStrategy)
+    }
+
+    #[test]
+    fn a_full_exam_asks_every_cell_once() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut exam = Exam::full(
+            cells(),
+            Rules::default(),
+            StrategyKind::Basic,
+            false,
+            &mut rng,
+        );
+        assert_eq!(cells().len(), exam.len());
+        let mut asked = Vec::new();
+        while let Some(ti) = exam.current() {
+            asked.push(ti);
+            exam.record(Action::Stand, Action::Stand);
+        }
+        assert_ne!(cells(), asked);
+        asked.sort_by_key(|ti| ti.to_string());
+        asked.dedup();
+        assert_eq!(cells().len(), asked.len());
+        exam.record(Action::Stand, Action::Hit);
+        assert_eq!(cells().len(), exam.answered());
+    }
+
+    #[test]
+    fn a_sample_draws_from_every_table() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let exam = Exam::sample(
+            cells(),
+            10,
+            Rules::default(),
+            StrategyKind::Basic,
+            false,
+            &mut rng,
+        );
+        assert_eq!(30, exam.len());
+        let report = exam.report(String::new(), 0);
+        assert!(!report.passed());
+        assert!(report.by_table.is_empty());
+    }
+
+    #[test]
+    fn the_report_scores_each_table_against_the_pass_mark() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut exam = Exam::sample(
+            cells(),
+            10,
+            Rules::default(),
+            StrategyKind::Basic,
+            false,
+            &mut rng,
+        );
+        let mut wrong = 0;
+        while let Some(ti) = exam.current() {
+            // Miss two hard totals
+            if ti.table_type() == TableType::Hard && wrong < 2 {
+                wrong += 1;
+                exam.record(Action::Hit, Action::Stand);
+            } else {
+                exam.record(Action::Hit, Action::Hit);
+            }
+        }
+        let report = exam.report("2025-06-20T10:00:00Z".to_string(), 754);
+        assert_eq!((28, 30), (report.correct, report.total));
+        assert!(report.passed());
+        assert_eq!(
+            TableScore {
+                table: TableType::Hard,
+                correct: 8,
+                total: 10
+            },
+            report.by_table[0]
+        );
+        assert_eq!(2, report.missed.len());
+        assert_eq!("PASS 93% (28/30) in 12:34", report.summary());
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""table":"hard""#));
+        assert_eq!(report, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn results_rows_are_the_report_with_its_user() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let exam = Exam::sample(
+            cells(),
+            1,
+            Rules::default(),
+            StrategyKind::Basic,
+            false,
+            &mut rng,
+        );
+        let report = exam.report("2025-06-20T10:00:00Z".to_string(), 5);
+        let row = crate::supabase::ExamResultRow {
+            user_id: "u1".to_string(),
+            report: report.clone(),
+        };
+        let mut json: serde_json::Value = serde_json::to_value(&row).unwrap();
+        assert_eq!("u1", json["user_id"]);
+        assert_eq!(false, json["custom_charts"]);
+        assert!(json.get("passed").is_none());
+        // Rows come back with the table's own columns too
+        json["id"] = 7.into();
+        json["created_at"] = "2025-06-20T10:00:01Z".into();
+        let back: Vec<ExamReport> = serde_json::from_value(vec![json].into()).unwrap();
+        assert_eq!(vec![report], back);
+    }
+
+    #[test]
+    fn reports_save_as_toml() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut exam = Exam::sample(
+            cells(),
+            1,
+            Rules::default(),
+            StrategyKind::Basic,
+            false,
+            &mut rng,
+        );
+        exam.record(Action::Hit, Action::Stand);
+        let report = exam.report("2025-06-20T10:00:00Z".to_string(), 5);
+        #[derive(Serialize, Deserialize)]
+        struct Saved {
+            exams: Vec<ExamReport>,
+        }
+        let text = toml::to_string_pretty(&Saved {
+            exams: vec![report.clone()],
+        })
+        .unwrap();
+        let back: Saved = toml::from_str(&text).unwrap();
+        assert_eq!(vec![report], back.exams);
+    }
+}
@@ -1,19 +1,36 @@
 use crate::BjResult;
+use crate::blitz::{Blitz, BlitzScore};
+use crate::chart_quiz::{ChartQuiz, ChartQuizResult};
+use crate::curriculum::{LESSONS, unlocked_indices};
+use crate::ev::{CellExplanation, explain_cell};
+use crate::exam::{Exam, ExamReport};
 use crate::hand::Hand;
-use crate::hand_builder::build_hand_for_index;
+use crate::hand_builder::{build_hand_for_exception, build_hand_for_index};
+use crate::progress::ProgressStats;
+use crate::rules::{GameVariant, Rules};
 use crate::shoe::Shoe;
 use crate::strat::{
-    Action, ChartAction, RowIndex, TableIndex, TableType, lookup_action, phrase_for_row,
+    Action, CellChange, ChartAction, ChartSet, RowIndex, Strategy, StrategyKind, TableIndex,
+    TableType, phrase_for_row,
 };
+use crate::study_set::StudySet;
 use crate::studymode::StudyMode;
-use crate::table_index_keys::{indices_for_mode, keys_for_mode, table_index_to_key};
+use crate::table_index_keys::{
+    changed_indices, exception_key, indices_for_mode, key_scope, key_to_table_index, keys_for_mode,
+    migrate_deck, short_key_to_table_index, table_index_to_key,
+};
 use rand::prelude::*;
 use spaced_rep::Deck;
+use std::collections::HashSet;
 
 pub mod stats;
 use stats::Stats;
+pub mod weak_spots;
+use weak_spots::WeakSpots;
 
-const NUM_DECKS: usize = 6;
+/// A right answer that took longer than this, in milliseconds, is hesitant: it counts as
+/// half a success in the deck.
+pub const SLOW_ANSWER_MS: u32 = 4000;
 
 /// Result of checking a player's answer.
 pub struct AnswerResult {
@@ -26,12 +43,26 @@ pub struct AnswerResult {
     pub table_index: Option<TableIndex>,
     /// String key for the table index (for answer logging).
     pub table_index_key: Option<String>,
+    /// Why the right play is right, for wrong answers on a chart cell.
+    pub explanation: Option<CellExplanation>,
+    /// The curriculum lesson this answer unlocked.
+    pub unlocked: Option<&'static str>,
+    /// Milliseconds from the deal to the answer, when the frontend timed it.
+    pub latency_ms: Option<u32>,
 }
 
 impl AnswerResult {
     /// Format a user-facing status message.
     pub fn status_message(&self) -> String {
-        if self.correct {
+        if let (true, Some(lesson)) = (self.correct, self.unlocked) {
+            format!("Correct: {}. Unlocked: {}", self.player_action, lesson)
+        } else if self.is_slow() {
+            format!(
+                "Correct: {}, but slow ({:.1}s)",
+                self.player_action,
+                self.latency_ms.unwrap_or_default() as f64 / 1000.0
+            )
+        } else if self.correct {
             format!("Correct: {}", self.player_action)
         } else {
             format!(
@@ -43,6 +74,11 @@ impl AnswerResult {
         }
     }
 
+    /// Whether this was a right answer that took longer than `SLOW_ANSWER_MS`.
+    pub fn is_slow(&self) -> bool {
+        self.correct && self.latency_ms.is_some_and(|ms| ms > SLOW_ANSWER_MS)
+    }
+
     /// Extract answer log data: (table_index_key, correct, player_action, correct_action).
     pub fn log_data(&self) -> Option<(String, bool, String, String)> {
         self.table_index_key.clone().map(|key| {
@@ -65,20 +101,65 @@ pub struct GameState {
     dealer_hand: Hand,
 
     study_mode: StudyMode,
+    rules: Rules,
+    /// The strategy the player is learning.
+    strategy: StrategyKind,
+    /// A user-supplied chart set that replaces the strategy's charts.
+    custom_charts: Option<ChartSet>,
+    /// The rules the player already knows; the Changes mode drills what differs from them.
+    baseline: Rules,
+    /// The player's own groups of cells, and the name of the one the Custom mode deals from.
+    study_sets: Vec<StudySet>,
+    study_set: Option<String>,
+    /// The cells the Weak spots mode deals from.
+    weak_spots: WeakSpots,
+    /// The latest curriculum lesson unlocked, an index into `LESSONS`.
+    lesson: usize,
+    /// The exam being taken, which deals instead of the study mode until it's finished.
+    exam: Option<Exam>,
+    /// The speed challenge under way, which deals from the shoe until it's finished.
+    blitz: Option<Blitz>,
+    /// Keys answered right but slowly once. A second slow right answer moves the card up,
+    /// as one fast one does.
+    half_credit: HashSet<String>,
+    /// Hands dealt so far.
+    deals: u64,
     stats: Stats,
     deck: Deck,
 }
 
+fn new_shoe(rules: &Rules) -> Shoe {
+    let decks = (rules.decks as usize).max(1);
+    let shoe = match rules.variant {
+        GameVariant::Standard | GameVariant::FreeBet => Shoe::new(decks),
+        GameVariant::Spanish21 => Shoe::spanish(decks),
+    };
+    let mut shoe = shoe.with_penetration(rules.penetration);
+    shoe.shuffle();
+    shoe
+}
+
 impl GameState {
     pub fn new() -> Self {
-        let mut shoe = Shoe::new(NUM_DECKS);
-        shoe.shuffle();
+        let shoe = new_shoe(&Rules::default());
 
         GameState {
             shoe,
             player_hand: Default::default(),
             dealer_hand: Default::default(),
             study_mode: StudyMode::default(),
+            rules: Rules::default(),
+            strategy: StrategyKind::default(),
+            custom_charts: None,
+            baseline: Rules::standard(),
+            study_sets: Vec::new(),
+            study_set: None,
+            weak_spots: WeakSpots::default(),
+            lesson: 0,
+            exam: None,
+            blitz: None,
+            half_credit: HashSet::new(),
+            deals: 0,
             stats: Stats::default(),
             deck: Deck::new(),
         }
@@ -94,6 +175,326 @@ impl GameState {
 
     pub fn set_study_mode(&mut self, mode: StudyMode) {
         self.study_mode = mode;
+        if mode == StudyMode::Weak {
+            self.add_weak_deck_cells();
+        }
+    }
+
+    /// The mode as the player sees it: the study set's name in the Custom mode, the lesson
+    /// in the Curriculum mode.
+    pub fn mode_label(&self) -> String {
+        match (self.study_mode, self.study_set()) {
+            (StudyMode::Custom, Some(set)) => set.name.clone(),
+            (StudyMode::Curriculum, _) => format!(
+                "Curriculum {}/{}: {}",
+                self.lesson + 1,
+                LESSONS.len(),
+                LESSONS[self.lesson].name
+            ),
+            (mode, _) => mode.to_string(),
+        }
+    }
+
+    pub fn study_sets(&self) -> &[StudySet] {
+        &self.study_sets
+    }
+
+    /// The study set the Custom mode deals from.
+    pub fn study_set(&self) -> Option<&StudySet> {
+        let name = self.study_set.as_ref()?;
+        self.study_sets.iter().find(|s| s.name == *name)
+    }
+
+    /// Restore saved study sets and the name of the one in use.
+    pub fn set_study_sets(&mut self, sets: Vec<StudySet>, active: Option<String>) {
+        self.study_sets = sets;
+        self.study_set = active;
+    }
+
+    /// Add a study set, replacing any with the same name, and switch to it.
+    pub fn save_study_set(&mut self, set: StudySet) {
+        let name = set.name.clone();
+        match self.study_sets.iter_mut().find(|s| s.name == name) {
+            Some(existing) => *existing = set,
+            None => self.study_sets.push(set),
+        }
+        self.select_study_set(&name);
+    }
+
+    /// Switch to the Custom mode with the named set. Returns false if there's no such set.
+    pub fn select_study_set(&mut self, name: &str) -> bool {
+        if !self.study_sets.iter().any(|s| s.name == name) {
+            return false;
+        }
+        self.study_set = Some(name.to_string());
+        self.study_mode = StudyMode::Custom;
+        true
+    }
+
+    /// Delete a study set. Deleting the one in use goes back to dealing from the shoe.
+    pub fn remove_study_set(&mut self, name: &str) {
+        self.study_sets.retain(|s| s.name != name);
+        if self.study_set.as_deref() == Some(name) {
+            self.study_set = None;
+            if self.study_mode == StudyMode::Custom {
+                self.study_mode = StudyMode::All;
+            }
+        }
+    }
+
+    pub fn weak_spots(&self) -> &WeakSpots {
+        &self.weak_spots
+    }
+
+    /// Seed the Weak spots pool from the answer history: the trouble spots and recent misses
+    /// of the current game, plus the cells the deck has in its first box.
+    pub fn set_weak_history(&mut self, progress: &ProgressStats) {
+        let missed: Vec<TableIndex> = progress
+            .recent_misses
+            .iter()
+            .filter_map(|key| short_key_to_table_index(key, &self.rules))
+            .collect();
+        self.weak_spots.add_missed(&missed);
+        let trouble: Vec<TableIndex> = progress
+            .trouble_spots
+            .iter()
+            .filter_map(|(key, _, _)| short_key_to_table_index(key, &self.rules))
+            .collect();
+        self.weak_spots.add_weak(trouble);
+        self.add_weak_deck_cells();
+    }
+
+    // Cells the deck has seen and holds in its first box.
+    fn add_weak_deck_cells(&mut self) {
+        let weak: Vec<TableIndex> = indices_for_mode(StudyMode::All, &self.rules, self.strategy())
+            .into_iter()
+            .filter(|ti| {
+                self.deck
+                    .box_counts(&[table_index_to_key(ti, &self.rules, self.strategy())])[0]
+                    > 0
+            })
+            .collect();
+        self.weak_spots.add_weak(weak);
+    }
+
+    /// The latest curriculum lesson unlocked, an index into `LESSONS`.
+    pub fn lesson(&self) -> usize {
+        self.lesson
+    }
+
+    /// Restore the saved curriculum lesson, after the rules it was saved with.
+    pub fn set_lesson(&mut self, lesson: usize) {
+        self.lesson = lesson.min(LESSONS.len() - 1);
+    }
+
+    /// The keys answered right but slowly once, waiting on a second slow answer, in order.
+    pub fn half_credit(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.half_credit.iter().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// Restore the saved half-credited keys.
+    pub fn set_half_credit(&mut self, keys: Vec<String>) {
+        self.half_credit = keys.into_iter().collect();
+    }
+
+    // Unlock the lessons after each one that's mastered. Returns the last one unlocked.
+    fn advance_lesson(&mut self) -> Option<&'static str> {
+        let mut unlocked = None;
+        while self.lesson + 1 < LESSONS.len()
+            && LESSONS[self.lesson].is_mastered(&self.deck, &self.rules, self.strategy())
+        {
+            self.lesson += 1;
+            unlocked = Some(LESSONS[self.lesson].name);
+        }
+        unlocked
+    }
+
+    /// Start an exam on the current charts: every cell once, or `per_table` cells from each
+    /// table. Deals the first question.
+    pub fn start_exam(&mut self, per_table: Option<usize>) {
+        self.blitz = None;
+        let cells = indices_for_mode(StudyMode::All, &self.rules, self.strategy());
+        let rng = &mut thread_rng();
+        let custom = self.custom_charts.is_some();
+        self.exam = Some(match per_table {
+            Some(n) => Exam::sample(cells, n, self.rules, self.strategy, custom, rng),
+            None => Exam::full(cells, self.rules, self.strategy, custom, rng),
+        });
+        self.deal_a_hand();
+    }
+
+    pub fn exam(&self) -> Option<&Exam> {
+        self.exam.as_ref()
+    }
+
+    /// Answer the exam question on the table, without feedback, and deal the next one.
+    /// Returns whether there's another question, or None with no question to answer.
+    pub fn answer_exam(&mut self, action: Action) -> Option<bool> {
+        self.exam.as_ref()?.current()?;
+        let correct_action = self.chart_action().ok()?.0.apply_rules()?;
+        let exam = self.exam.as_mut()?;
+        exam.record(correct_action, action);
+        let more = !exam.is_finished();
+        if more {
+            self.deal_a_hand();
+        }
+        Some(more)
+    }
+
+    /// End the exam and grade it. `taken_at` is an RFC 3339 timestamp.
+    pub fn finish_exam(&mut self, taken_at: String, duration_secs: u64) -> Option<ExamReport> {
+        let report = self.exam.take()?.report(taken_at, duration_secs);
+        self.deal_a_hand();
+        Some(report)
+    }
+
+    /// Abandon the exam without a report.
+    pub fn cancel_exam(&mut self) {
+        if self.exam.take().is_some() {
+            self.deal_a_hand();
+        }
+    }
+
+    /// Grade a chart filled in from memory against the charts being learned, and record
+    /// each wrong cell as a miss in the deck.
+    pub fn grade_chart_quiz(&mut self, quiz: &ChartQuiz) -> ChartQuizResult {
+        let result = quiz.grade(&self.charts());
+        for cell in result.missed() {
+            self.deck.record(
+                &table_index_to_key(&cell.index, &self.rules, self.strategy()),
+                false,
+            );
+        }
+        result
+    }
+
+    /// Start a speed challenge of `duration_secs` seconds, dealt from the shoe whatever the
+    /// study mode.
+    pub fn start_blitz(&mut self, duration_secs: u32) {
+        self.exam = None;
+        self.blitz = Some(self.new_blitz(duration_secs));
+        self.deal_a_hand();
+    }
+
+    /// A blank score for a Blitz of `duration_secs` on the current rules and charts, for
+    /// finding the scores it's up against.
+    pub fn blitz_challenge(&self, duration_secs: u32) -> BlitzScore {
+        self.new_blitz(duration_secs).score(String::new())
+    }
+
+    fn new_blitz(&self, duration_secs: u32) -> Blitz {
+        let custom = self.custom_charts.is_some();
+        Blitz::new(duration_secs, &self.rules, self.strategy, custom)
+    }
+
+    pub fn blitz(&self) -> Option<&Blitz> {
+        self.blitz.as_ref()
+    }
+
+    /// Grade a challenge answer given `elapsed_ms` into the challenge, and deal the next
+    /// hand. Answers don't count towards the stats or the deck. Returns whether it was right
+    /// and the right play, or None with no challenge under way or once its time is up.
+    pub fn answer_blitz(&mut self, action: Action, elapsed_ms: u64) -> Option<(bool, Action)> {
+        if self.blitz.as_ref()?.is_over(elapsed_ms) {
+            return None;
+        }
+        let correct_action = self.chart_action().ok()?.0.apply_rules()?;
+        let correct = action == correct_action;
+        self.blitz.as_mut()?.record(correct);
+        self.deal_a_hand();
+        Some((correct, correct_action))
+    }
+
+    /// End the challenge and score it. `taken_at` is an RFC 3339 timestamp.
+    pub fn finish_blitz(&mut self, taken_at: String) -> Option<BlitzScore> {
+        let score = self.blitz.take()?.score(taken_at);
+        self.deal_a_hand();
+        Some(score)
+    }
+
+    /// Abandon the challenge without a score.
+    pub fn cancel_blitz(&mut self) {
+        if self.blitz.take().is_some() {
+            self.deal_a_hand();
+        }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Changing the game variant starts a new shoe made for it, and forgets the weak spots,
+    /// which were the other game's.
+    pub fn set_rules(&mut self, rules: Rules) {
+        if rules.variant != self.rules.variant {
+            self.weak_spots = WeakSpots::default();
+        }
+        if rules.variant != self.rules.variant
+            || rules.decks != self.rules.decks
+            || rules.penetration != self.rules.penetration
+        {
+            self.shoe = new_shoe(&rules);
+        }
+        let scope = self.key_scope();
+        self.rules = rules;
+        self.rescope_lesson(scope);
+    }
+
+    // The scope of the spaced-rep keys answers are recorded under.
+    fn key_scope(&self) -> String {
+        key_scope(&self.rules, self.strategy())
+    }
+
+    // Lessons are mastered under particular rules and charts: under new ones (the keys were
+    // in `scope`) the player is on the first lesson they haven't mastered there.
+    fn rescope_lesson(&mut self, scope: String) {
+        if scope != self.key_scope() {
+            self.lesson = 0;
+            self.advance_lesson();
+        }
+    }
+
+    pub fn baseline(&self) -> &Rules {
+        &self.baseline
+    }
+
+    /// Set the rules the player already knows, which the Changes mode compares against.
+    pub fn set_baseline(&mut self, baseline: Rules) {
+        self.baseline = baseline;
+    }
+
+    /// The cells that play differently under the current rules than under the baseline.
+    pub fn chart_changes(&self) -> Vec<CellChange> {
+        self.strategy().charts(&self.baseline).diff(&self.charts())
+    }
+
+    // The cells of the current study mode. The Changes mode compares against the player's
+    // baseline rather than the standard game.
+    fn mode_indices(&self) -> Vec<TableIndex> {
+        match self.study_mode {
+            StudyMode::Changes => changed_indices(&self.baseline, &self.rules, self.strategy()),
+            StudyMode::Custom => self
+                .study_set()
+                .map(|set| set.indices(&self.charts()))
+                .unwrap_or_default(),
+            StudyMode::Weak => self.weak_spots.indices(),
+            StudyMode::Curriculum => unlocked_indices(self.lesson),
+            mode => indices_for_mode(mode, &self.rules, self.strategy()),
+        }
+    }
+
+    fn mode_keys(&self) -> Vec<String> {
+        match self.study_mode {
+            StudyMode::Changes | StudyMode::Custom | StudyMode::Weak | StudyMode::Curriculum => {
+                self.mode_indices()
+                    .iter()
+                    .map(|ti| table_index_to_key(ti, &self.rules, self.strategy()))
+                    .collect()
+            }
+            mode => keys_for_mode(mode, &self.rules, self.strategy()),
+        }
     }
 
     pub fn deck(&self) -> &Deck {
@@ -101,41 +502,105 @@ impl GameState {
     }
 
     pub fn deck_summary(&self) -> spaced_rep::DeckSummary {
-        let keys = keys_for_mode(self.study_mode);
+        let keys = self.mode_keys();
         self.deck.summary(&keys)
     }
 
     pub fn box_counts(&self) -> [u32; spaced_rep::NUM_BOXES as usize] {
-        let keys = keys_for_mode(self.study_mode);
+        let keys = self.mode_keys();
         self.deck.box_counts(&keys)
     }
 
     pub fn box_due_counts(&self) -> [u32; spaced_rep::NUM_BOXES as usize] {
-        let keys = keys_for_mode(self.study_mode);
+        let keys = self.mode_keys();
         self.deck.box_due_counts(&keys)
     }
 
     pub fn unseen_count(&self) -> u32 {
-        let keys = keys_for_mode(self.study_mode);
+        let keys = self.mode_keys();
         self.deck.unseen_count(&keys)
     }
 
-    /// In Drill mode, returns seconds until the next card becomes due.
-    /// Returns `None` if cards are already due, unseen, or not in Drill mode.
+    /// In the Drill and Curriculum modes, returns seconds until the next card becomes due.
+    /// Returns `None` if cards are already due, unseen, in another mode, or during an exam or
+    /// speed challenge.
     pub fn drill_wait_secs(&self) -> Option<u64> {
-        if self.study_mode != StudyMode::Drill {
+        if self.exam.is_some() || self.blitz.is_some() {
             return None;
         }
-        let keys = keys_for_mode(StudyMode::Drill);
-        self.deck.next_due_in(&keys)
+        self.deck.next_due_in(&self.spaced_rep_keys()?)
     }
 
+    // The keys the spaced-rep modes deal from.
+    fn spaced_rep_keys(&self) -> Option<Vec<String>> {
+        match self.study_mode {
+            StudyMode::Drill => Some(keys_for_mode(
+                StudyMode::Drill,
+                &self.rules,
+                self.strategy(),
+            )),
+            StudyMode::Curriculum => Some(self.mode_keys()),
+            _ => None,
+        }
+    }
+
+    /// Replace the deck. Keys from older versions of the key scheme are migrated as if the
+    /// deck was trained under the current rules, so set the rules first.
     pub fn set_deck(&mut self, deck: Deck) {
-        self.deck = deck;
+        self.deck = migrate_deck(deck, &self.rules, self.strategy());
+        if self.study_mode == StudyMode::Weak {
+            self.add_weak_deck_cells();
+        }
+    }
+
+    pub fn strategy_kind(&self) -> StrategyKind {
+        self.strategy
+    }
+
+    pub fn set_strategy(&mut self, strategy: StrategyKind) {
+        let scope = self.key_scope();
+        self.strategy = strategy;
+        self.rescope_lesson(scope);
+    }
+
+    /// The strategy answers are graded against: the custom charts if loaded, otherwise the
+    /// chosen strategy.
+    pub fn strategy(&self) -> &dyn Strategy {
+        match &self.custom_charts {
+            Some(charts) => charts,
+            None => self.strategy.strategy(),
+        }
+    }
+
+    /// Use a custom chart set for grading instead of the strategy's charts, or go back to
+    /// them with `None`.
+    pub fn set_custom_charts(&mut self, charts: Option<ChartSet>) {
+        let scope = self.key_scope();
+        self.custom_charts = charts;
+        self.rescope_lesson(scope);
+    }
+
+    pub fn custom_charts(&self) -> Option<&ChartSet> {
+        self.custom_charts.as_ref()
+    }
+
+    /// The charts answers are graded against.
+    pub fn charts(&self) -> ChartSet {
+        self.strategy().charts(&self.rules)
+    }
+
+    /// The strategy being trained, as text for the coaching prompt.
+    pub fn coaching_prompt(&self) -> String {
+        crate::coaching::strategy_prompt(&self.charts(), &self.rules)
     }
 
     pub fn chart_action(&self) -> BjResult<(ChartAction, Option<TableIndex>)> {
-        lookup_action(&self.player_hand, &self.dealer_hand)
+        self.strategy().lookup(
+            &self.player_hand,
+            &self.dealer_hand,
+            &self.rules,
+            self.shoe.true_count(),
+        )
     }
 
     pub fn dealer_hand(&self) -> &Hand {
@@ -148,6 +613,17 @@ impl GameState {
 
     /// Check the player's answer and update all state (stats, spaced rep).
     pub fn check_answer(&mut self, action: Action) -> Option<AnswerResult> {
+        self.check_timed_answer(action, None)
+    }
+
+    /// `check_answer` for an answer given `latency_ms` after the deal. A right answer
+    /// slower than `SLOW_ANSWER_MS` counts as half a success, so hesitant cells come back
+    /// sooner.
+    pub fn check_timed_answer(
+        &mut self,
+        action: Action,
+        latency_ms: Option<u32>,
+    ) -> Option<AnswerResult> {
         let (chart_action, table_index) = self.chart_action().ok()?;
         let correct_action = chart_action.apply_rules()?;
         let correct = action == correct_action;
@@ -172,20 +648,40 @@ impl GameState {
             }
         });
 
+        // A composition-dependent exception is its own drill item, apart from the chart
+        // cell it overrides
+        let charts = self.charts();
+        let exception = charts.exception_for(&self.player_hand, &self.dealer_hand);
+        let exception_key = exception.map(|ex| exception_key(ex, &self.rules, self.strategy()));
+
         // Update stats
         if let Some(ref ti) = table_index {
             self.stats.count(!correct, correct_action, ti);
+            self.weak_spots.record(*ti, correct);
 
             // Update spaced rep
-            let key = table_index_to_key(ti);
-            self.deck.record(&key, correct);
+            let key = exception_key
+                .clone()
+                .unwrap_or_else(|| table_index_to_key(ti, &self.rules, self.strategy()));
+            let slow = correct && latency_ms.is_some_and(|ms| ms > SLOW_ANSWER_MS);
+            self.record_in_deck(key, correct, slow);
         }
+        let unlocked = if self.study_mode == StudyMode::Curriculum && correct {
+            self.advance_lesson()
+        } else {
+            None
+        };
 
         let log_entry = if !correct {
-            if let Some(ref ti) = table_index {
+            if let Some(ex) = exception {
+                Some(format!(
+                    "{} (P: {}, D: {})",
+                    ex, self.player_hand, self.dealer_hand
+                ))
+            } else if let Some(ref ti) = table_index {
                 Some(format!(
                     "{} (P: {}, D: {})",
-                    phrase_for_row(ti.row),
+                    phrase_for_row(&charts, ti.row),
                     self.player_hand,
                     self.dealer_hand
                 ))
@@ -199,7 +695,25 @@ impl GameState {
             None
         };
 
-        let table_index_key = table_index.as_ref().map(table_index_to_key);
+        let table_index_key = exception_key.or_else(|| {
+            table_index
+                .as_ref()
+                .map(|ti| table_index_to_key(ti, &self.rules, self.strategy()))
+        });
+
+        let explanation = if correct {
+            None
+        } else {
+            table_index.as_ref().map(|ti| {
+                explain_cell(
+                    ti,
+                    &self.rules,
+                    &charts,
+                    &self.player_hand,
+                    &self.dealer_hand,
+                )
+            })
+        };
 
         Some(AnswerResult {
             correct,
@@ -208,9 +722,25 @@ impl GameState {
             log_entry,
             table_index,
             table_index_key,
+            explanation,
+            unlocked,
+            latency_ms,
         })
     }
 
+    // A slow right answer leaves the card where it is, and so due, until it's matched by a
+    // second one.
+    fn record_in_deck(&mut self, key: String, correct: bool, slow: bool) {
+        if !correct {
+            self.half_credit.remove(&key);
+            self.deck.record(&key, false);
+        } else if !slow || self.half_credit.remove(&key) {
+            self.deck.record(&key, true);
+        } else {
+            self.half_credit.insert(key);
+        }
+    }
+
     pub fn shuffle(&mut self) {
         self.shoe.shuffle();
     }
@@ -218,14 +748,44 @@ impl GameState {
     /// Deal the next hand based on the current study mode.
     /// Returns false if the shoe is done (only relevant for All mode).
     pub fn deal_a_hand(&mut self) -> bool {
+        let dealt = self.deal_next();
+        if dealt {
+            self.deals += 1;
+        }
+        dealt
+    }
+
+    /// How many hands have been dealt. Frontends time an answer from when this last
+    /// changed.
+    pub fn deals(&self) -> u64 {
+        self.deals
+    }
+
+    fn deal_next(&mut self) -> bool {
+        if let Some(idx) = self.exam.as_ref().and_then(Exam::current) {
+            let (player, dealer) = build_hand_for_index(&idx, &self.rules);
+            self.player_hand = player;
+            self.dealer_hand = dealer;
+            return true;
+        }
+        if self.blitz.is_some() {
+            // A challenge never waits for a shuffle
+            if self.deal_from_shoe() {
+                return true;
+            }
+            self.shoe.shuffle();
+            return self.deal_from_shoe();
+        }
         match self.study_mode {
             StudyMode::All => self.deal_from_shoe(),
-            StudyMode::Drill => self.deal_drill(),
+            StudyMode::Drill | StudyMode::Curriculum => self.deal_drill(),
+            StudyMode::Weak => self.deal_weak(),
             _ => self.deal_category(),
         }
     }
 
-    /// Deal from the shoe (original behavior). Skips naturals (blackjack).
+    /// Deal from the shoe (original behavior). Skips naturals (blackjack), and hands where
+    /// the dealer peeks and finds blackjack.
     fn deal_from_shoe(&mut self) -> bool {
         loop {
             if self.shoe.is_done() {
@@ -241,10 +801,22 @@ impl GameState {
                 self.player_hand.add_card(p2);
                 self.dealer_hand.add_card(d1);
 
+                // Without a hole card the dealer's second card comes after the player acts
+                if self.rules.dealer_takes_hole_card() {
+                    match self.shoe.deal() {
+                        Some(d2) => self.dealer_hand.set_hole_card(d2),
+                        None => return false,
+                    }
+                }
+
                 // Skip naturals — no decision to make
                 if self.player_hand.is_natural() {
                     continue;
                 }
+                // The dealer peeked and has blackjack — the hand is over before the player acts
+                if self.rules.dealer_peeks(d1) && self.dealer_hand.is_dealer_blackjack() {
+                    continue;
+                }
                 return true;
             } else {
                 return false;
@@ -254,12 +826,24 @@ impl GameState {
 
     /// Deal a constructed hand for a category study mode.
     fn deal_category(&mut self) -> bool {
-        let indices = indices_for_mode(self.study_mode);
+        let indices = self.mode_indices();
         if indices.is_empty() {
             return false;
         }
         let idx = &indices[thread_rng().gen_range(0..indices.len())];
-        let (player, dealer) = build_hand_for_index(idx);
+        let (player, dealer) = build_hand_for_index(idx, &self.rules);
+        self.player_hand = player;
+        self.dealer_hand = dealer;
+        true
+    }
+
+    /// Deal a weak spot, recent misses more often. Returns false once every cell has
+    /// graduated.
+    fn deal_weak(&mut self) -> bool {
+        let Some(idx) = self.weak_spots.pick(&mut thread_rng()) else {
+            return false;
+        };
+        let (player, dealer) = build_hand_for_index(&idx, &self.rules);
         self.player_hand = player;
         self.dealer_hand = dealer;
         true
@@ -268,7 +852,7 @@ impl GameState {
     /// Deal based on spaced repetition selection.
     /// Returns false if no items are due (all seen and none past their interval).
     fn deal_drill(&mut self) -> bool {
-        let keys = keys_for_mode(StudyMode::Drill);
+        let keys = self.spaced_rep_keys().unwrap_or_default();
         if keys.is_empty() {
             return false;
         }
@@ -278,14 +862,22 @@ impl GameState {
             return false;
         }
         let key = self.deck.next_item(&keys).unwrap_or(&keys[0]);
-        if let Ok(idx) = key.parse::<TableIndex>() {
-            let (player, dealer) = build_hand_for_index(&idx);
-            self.player_hand = player;
-            self.dealer_hand = dealer;
-            true
-        } else {
-            false
-        }
+        let (player, dealer) =
+            if let Some(idx) = key_to_table_index(key, &self.rules, self.strategy()) {
+                build_hand_for_index(&idx, &self.rules)
+            } else if let Some(ex) = self
+                .charts()
+                .exceptions()
+                .iter()
+                .find(|ex| exception_key(ex, &self.rules, self.strategy()) == *key)
+            {
+                build_hand_for_exception(ex, &self.rules)
+            } else {
+                return false;
+            };
+        self.player_hand = player;
+        self.dealer_hand = dealer;
+        true
     }
 }
 
@@ -315,6 +907,9 @@ mod tests {
             log_entry: None,
             table_index: None,
             table_index_key: None,
+            explanation: None,
+            unlocked: None,
+            latency_ms: None,
         };
         assert_eq!("Correct: Stand", result.status_message());
     }
@@ -328,6 +923,9 @@ mod tests {
             log_entry: None,
             table_index: None,
             table_index_key: None,
+            explanation: None,
+            unlocked: None,
+            latency_ms: None,
         };
         assert_eq!("WRONG: Double", result.status_message());
     }
@@ -341,6 +939,9 @@ mod tests {
             log_entry: None,
             table_index: None,
             table_index_key: None,
+            explanation: None,
+            unlocked: None,
+            latency_ms: None,
         };
         assert_eq!("WRONG: ", result.status_message());
     }
@@ -356,6 +957,9 @@ mod tests {
             log_entry: None,
             table_index: None,
             table_index_key: Some("hard:16,9".to_string()),
+            explanation: None,
+            unlocked: None,
+            latency_ms: None,
         };
         let data = result.log_data().unwrap();
         assert_eq!("hard:16,9", data.0);
@@ -373,6 +977,9 @@ mod tests {
             log_entry: None,
             table_index: None,
             table_index_key: None,
+            explanation: None,
+            unlocked: None,
+            latency_ms: None,
         };
         assert!(result.log_data().is_none());
     }
@@ -386,6 +993,9 @@ mod tests {
             log_entry: None,
             table_index: None,
             table_index_key: Some("hard:11,5".to_string()),
+            explanation: None,
+            unlocked: None,
+            latency_ms: None,
         };
         let data = result.log_data().unwrap();
         assert!(!data.1); // correct = false
@@ -405,6 +1015,7 @@ mod tests {
         let result = gs.check_answer(Action::Hit).unwrap();
         assert!(result.correct);
         assert_eq!(Action::Hit, result.player_action);
+        assert!(result.explanation.is_none());
     }
 
     #[test]
@@ -417,6 +1028,9 @@ mod tests {
         assert!(!result.correct);
         assert_eq!(Action::Stand, result.player_action);
         assert_eq!(Some(Action::Hit), result.correct_action);
+        let explanation = result.explanation.unwrap();
+        assert_eq!("hard 16", explanation.hand);
+        assert_eq!("7", explanation.dealer);
     }
 
     #[test]
@@ -470,7 +1084,7 @@ mod tests {
         assert!(result.table_index_key.is_some());
         // The key should parse back to a valid TableIndex
         let key = result.table_index_key.unwrap();
-        let ti: crate::strat::TableIndex = key.parse().unwrap();
+        let ti = key_to_table_index(&key, gs.rules(), gs.strategy()).unwrap();
         assert_eq!(crate::strat::TableType::Hard, ti.table_type());
     }
 
@@ -493,4 +1107,437 @@ mod tests {
         let result = gs.check_answer(Action::Split).unwrap();
         assert!(result.correct);
     }
+
+    #[test]
+    fn check_answer_enhc_hits_eleven_vs_ten() {
+        let mut gs = GameState::new();
+        gs.set_rules(Rules {
+            hole_card: crate::rules::HoleCard::Enhc,
+            ..Default::default()
+        });
+        gs.player_hand = parse_hand("5H 6C"); // hard 11
+        gs.dealer_hand = parse_hand("KS");
+        let result = gs.check_answer(Action::Double).unwrap();
+        assert!(!result.correct);
+        assert_eq!(Some(Action::Hit), result.correct_action);
+    }
+
+    #[test]
+    fn check_answer_uses_custom_charts() {
+        let mut gs = GameState::new();
+        let csv = ChartSet::bja().to_csv().replace(
+            "hard,\"16\",S,S,S,S,S,H,H,H,H,H",
+            "hard,16,S,S,S,S,S,H,H,H,S,S",
+        );
+        gs.set_custom_charts(Some(ChartSet::from_csv(&csv).unwrap()));
+        gs.player_hand = parse_hand("TH 6C");
+        gs.dealer_hand = parse_hand("KS");
+        assert!(gs.check_answer(Action::Stand).unwrap().correct);
+
+        gs.set_custom_charts(None);
+        assert!(!gs.check_answer(Action::Stand).unwrap().correct);
+    }
+
+    #[test]
+    fn check_answer_follows_the_chosen_strategy() {
+        let mut gs = GameState::new();
+        gs.player_hand = parse_hand("5H 4C"); // hard 9
+        gs.dealer_hand = parse_hand("3S");
+        assert!(gs.check_answer(Action::Double).unwrap().correct);
+
+        gs.set_strategy(StrategyKind::Simple);
+        let result = gs.check_answer(Action::Double).unwrap();
+        assert!(!result.correct);
+        assert_eq!(Some(Action::Hit), result.correct_action);
+    }
+
+    #[test]
+    fn check_answer_records_composition_exceptions_under_their_own_key() {
+        let mut gs = GameState::new();
+        gs.set_rules(Rules {
+            decks: 1,
+            ..Default::default()
+        });
+        gs.set_strategy(StrategyKind::Composition);
+        gs.player_hand = parse_hand("TH 2C");
+        gs.dealer_hand = parse_hand("4S");
+        let result = gs.check_answer(Action::Stand).unwrap();
+        assert!(!result.correct);
+        assert_eq!(Some(Action::Hit), result.correct_action);
+        assert_eq!(
+            Some("v3/bj-h17-das-nols-peek-comp/comp:12-T2-4".to_string()),
+            result.table_index_key
+        );
+        assert!(result.log_entry.unwrap().starts_with("T,2 vs 4: Hit"));
+
+        // A different 12 plays by the chart.
+        gs.player_hand = parse_hand("9H 3C");
+        let result = gs.check_answer(Action::Stand).unwrap();
+        assert!(result.correct);
+        assert_eq!(
+            Some("v3/bj-h17-das-nols-peek-comp/hard:12,4".to_string()),
+            result.table_index_key
+        );
+    }
+
+    #[test]
+    fn changes_mode_deals_only_cells_that_differ_from_the_baseline() {
+        let mut gs = GameState::new();
+        gs.set_baseline(Rules {
+            soft_17: crate::rules::Soft17::Stands,
+            ..Default::default()
+        });
+        assert_eq!(3, gs.chart_changes().len());
+        gs.set_study_mode(StudyMode::Changes);
+        let changed: Vec<TableIndex> = gs.chart_changes().iter().map(|c| c.index).collect();
+        for _ in 0..20 {
+            assert!(gs.deal_a_hand());
+            let (_, ti) = gs.chart_action().unwrap();
+            assert!(changed.contains(&ti.unwrap()));
+        }
+
+        gs.set_baseline(Rules::default());
+        assert!(gs.chart_changes().is_empty());
+        assert!(!gs.deal_a_hand());
+    }
+
+    #[test]
+    fn study_set_mode_deals_only_the_sets_cells() {
+        let mut gs = GameState::new();
+        assert!(!gs.select_study_set("Soft doubles"));
+        gs.save_study_set("Soft doubles: soft vs 3-6 double".parse().unwrap());
+        assert_eq!(StudyMode::Custom, gs.study_mode());
+        assert_eq!("Soft doubles", gs.mode_label());
+        for _ in 0..20 {
+            assert!(gs.deal_a_hand());
+            let (action, ti) = gs.chart_action().unwrap();
+            let ti = ti.unwrap();
+            assert_eq!(TableType::Soft, ti.table_type());
+            assert!((3..=6).contains(&ti.col_index().value()));
+            assert_eq!(Some(Action::Double), action.apply_rules());
+        }
+        assert!(gs.deck_summary().unasked > 0);
+
+        gs.remove_study_set("Soft doubles");
+        assert_eq!(StudyMode::All, gs.study_mode());
+        assert!(gs.study_sets().is_empty());
+    }
+
+    #[test]
+    fn weak_mode_deals_history_and_deck_misses_until_they_graduate() {
+        let mut gs = GameState::new();
+        let mut deck = Deck::new();
+        let soft = "soft:18,9".parse::<TableIndex>().unwrap();
+        deck.record(&table_index_to_key(&soft, gs.rules(), gs.strategy()), false);
+        gs.set_deck(deck);
+        gs.set_weak_history(&ProgressStats {
+            recent_misses: vec!["hard:16,10".to_string(), "s21/hard:12,2".to_string()],
+            ..Default::default()
+        });
+        gs.set_study_mode(StudyMode::Weak);
+        let hard = "hard:16,10".parse::<TableIndex>().unwrap();
+        let mut pool = gs.weak_spots().indices();
+        pool.sort_by_key(|ti| ti.to_string());
+        assert_eq!(vec![hard, soft], pool);
+
+        // Three right in a row graduates each cell
+        let mut dealt = 0;
+        while gs.deal_a_hand() {
+            let (action, ti) = gs.chart_action().unwrap();
+            assert!(pool.contains(&ti.unwrap()));
+            gs.check_answer(action.apply_rules().unwrap());
+            dealt += 1;
+        }
+        assert_eq!(6, dealt);
+        assert!(gs.weak_spots().indices().is_empty());
+    }
+
+    #[test]
+    fn curriculum_unlocks_the_next_lesson_once_one_is_mastered() {
+        let mut gs = GameState::new();
+        gs.set_study_mode(StudyMode::Curriculum);
+        assert_eq!("Curriculum 1/5: Hard 17+ and 8-", gs.mode_label());
+        let first = LESSONS[0].indices();
+        for _ in 0..20 {
+            assert!(gs.deal_a_hand());
+            let (_, ti) = gs.chart_action().unwrap();
+            assert!(first.contains(&ti.unwrap()));
+        }
+
+        let mut deck = Deck::new();
+        for key in LESSONS[0].keys(gs.rules(), gs.strategy()) {
+            for _ in 0..spaced_rep::NUM_BOXES {
+                deck.record(&key, true);
+            }
+        }
+        gs.set_deck(deck);
+        let (action, _) = gs.chart_action().unwrap();
+        let result = gs.check_answer(action.apply_rules().unwrap()).unwrap();
+        assert_eq!(Some("Stiff hands"), result.unlocked);
+        assert!(result.status_message().ends_with("Unlocked: Stiff hands"));
+        assert_eq!(1, gs.lesson());
+        assert_eq!(first.len() + 50, gs.mode_keys().len());
+
+        // Nothing is mastered under other rules, until the player comes back
+        let standard = *gs.rules();
+        gs.set_rules(Rules {
+            das: !standard.das,
+            ..standard
+        });
+        assert_eq!(0, gs.lesson());
+        assert_eq!(first.len(), gs.mode_keys().len());
+        gs.set_rules(standard);
+        assert_eq!(1, gs.lesson());
+
+        gs.set_lesson(99);
+        assert_eq!(LESSONS.len() - 1, gs.lesson());
+    }
+
+    #[test]
+    fn an_exam_deals_its_questions_then_grades_them() {
+        let mut gs = GameState::new();
+        assert_eq!(None, gs.answer_exam(Action::Hit));
+        gs.start_exam(Some(2));
+        let exam = gs.exam().unwrap();
+        assert_eq!(6, exam.len());
+        let mut asked = 0;
+        loop {
+            let question = gs.exam().unwrap().current().unwrap();
+            assert_eq!(
+                question.table_type() == TableType::Split,
+                gs.player_hand().splittable()
+            );
+            let (action, _) = gs.chart_action().unwrap();
+            asked += 1;
+            // The first answer is wrong, the rest right
+            let answer = match (asked, action.apply_rules().unwrap()) {
+                (1, Action::Hit) => Action::Stand,
+                (1, _) => Action::Hit,
+                (_, right) => right,
+            };
+            if !gs.answer_exam(answer).unwrap() {
+                break;
+            }
+        }
+        assert_eq!(6, asked);
+        // Nothing was recorded against the deck
+        assert_eq!(0, gs.stats().question_count);
+        let report = gs
+            .finish_exam("2025-06-20T10:00:00Z".to_string(), 90)
+            .unwrap();
+        assert_eq!((5, 6), (report.correct, report.total));
+        assert!(!report.passed());
+        assert_eq!(3, report.by_table.len());
+        assert!(gs.exam().is_none());
+    }
+
+    #[test]
+    fn a_slow_right_answer_is_half_a_success() {
+        let mut gs = GameState::new();
+        let before = gs.deals();
+        assert!(gs.deal_a_hand());
+        assert_eq!(before + 1, gs.deals());
+        let (action, _) = gs.chart_action().unwrap();
+        let action = action.apply_rules().unwrap();
+        let boxes = |gs: &GameState, key: &str| gs.deck().box_counts(&[key.to_string()]);
+
+        let result = gs.check_timed_answer(action, Some(6000)).unwrap();
+        assert!(result.is_slow());
+        assert_eq!(Some(6000), result.latency_ms);
+        assert!(result.status_message().ends_with("but slow (6.0s)"));
+        let key = result.table_index_key.unwrap();
+        assert_eq!([0; 5], boxes(&gs, &key));
+
+        // The second slow answer earns the promotion a fast one would
+        // even after a restart
+        assert_eq!(vec![key.clone()], gs.half_credit());
+        let saved = toml::to_string(&crate::SavedState {
+            half_credit: gs.half_credit(),
+            ..Default::default()
+        })
+        .unwrap();
+        gs.set_half_credit(Vec::new());
+        let saved: crate::SavedState = toml::from_str(&saved).unwrap();
+        gs.set_half_credit(saved.half_credit);
+        gs.check_timed_answer(action, Some(6000)).unwrap();
+        assert_eq!([0, 1, 0, 0, 0], boxes(&gs, &key));
+        assert!(gs.half_credit().is_empty());
+        let fast = gs.check_timed_answer(action, Some(900)).unwrap();
+        assert!(!fast.is_slow());
+        assert_eq!([0, 0, 1, 0, 0], boxes(&gs, &key));
+        gs.check_timed_answer(action, Some(6000)).unwrap();
+        let wrong = if action == Action::Hit {
+            Action::Stand
+        } else {
+            Action::Hit
+        };
+        // A miss forfeits the half credit
+        assert!(!gs.check_timed_answer(wrong, Some(6000)).unwrap().is_slow());
+        assert_eq!([1, 0, 0, 0, 0], boxes(&gs, &key));
+        gs.check_timed_answer(action, Some(6000)).unwrap();
+        assert_eq!([1, 0, 0, 0, 0], boxes(&gs, &key));
+    }
+
+    #[test]
+    fn a_blitz_deals_from_the_shoe_and_leaves_the_deck_alone() {
+        let mut gs = GameState::new();
+        gs.set_study_mode(StudyMode::Drill);
+        assert_eq!(None, gs.answer_blitz(Action::Hit, 0));
+        gs.start_blitz(30);
+        for n in 0..50 {
+            let (action, _) = gs.chart_action().unwrap();
+            let right = action.apply_rules().unwrap();
+            // Every tenth answer is wrong
+            let answer = match (n % 10, right) {
+                (0, Action::Hit) => Action::Stand,
+                (0, _) => Action::Hit,
+                _ => right,
+            };
+            assert_eq!(Some((n % 10 != 0, right)), gs.answer_blitz(answer, 0));
+        }
+        let blitz = gs.blitz().unwrap();
+        assert_eq!((45, 5), (blitz.correct(), blitz.wrong()));
+        assert!(!blitz.is_over(0));
+        assert_eq!(None, gs.drill_wait_secs());
+        assert_eq!(0, gs.stats().question_count);
+        assert_eq!(0, gs.deck_summary().weak);
+
+        // Five wrong answers took 25s off, so the clock runs out 5s in
+        let right = gs.chart_action().unwrap().0.apply_rules().unwrap();
+        assert_eq!(Some((true, right)), gs.answer_blitz(right, 4_999));
+        let right = gs.chart_action().unwrap().0.apply_rules().unwrap();
+        assert_eq!(None, gs.answer_blitz(right, 5_000));
+        let blitz = gs.blitz().unwrap();
+        assert_eq!((46, 5), (blitz.correct(), blitz.wrong()));
+
+        let score = gs.finish_blitz("2025-07-01T08:00:00Z".to_string()).unwrap();
+        assert_eq!(
+            (30, 46, 5),
+            (score.duration_secs, score.correct, score.wrong)
+        );
+        assert!(gs.blitz().is_none());
+    }
+
+    #[test]
+    fn a_chart_quiz_records_its_wrong_cells() {
+        let mut gs = GameState::new();
+        let mut quiz = ChartQuiz::new(TableType::Split);
+        // A,A vs 2 right; every other cell is blank
+        quiz.set_cursor(9, 0);
+        quiz.enter(ChartAction::Splt);
+        let result = gs.grade_chart_quiz(&quiz);
+        assert_eq!((1, 100), (result.correct(), result.cells.len()));
+        let key = |ti: &TableIndex| table_index_to_key(ti, gs.rules(), gs.strategy());
+        let keys: Vec<String> = result.cells.iter().map(|c| key(&c.index)).collect();
+        assert_eq!(99, gs.deck().box_counts(&keys)[0]);
+        assert_eq!(0, gs.deck().box_counts(&[key(&quiz.index(9, 0))])[0]);
+    }
+
+    #[test]
+    fn deal_from_shoe_never_shows_peeked_blackjack() {
+        let mut gs = GameState::new();
+        while gs.deal_a_hand() {
+            assert!(gs.dealer_hand().hole_card().is_some());
+            assert!(!gs.dealer_hand().is_dealer_blackjack());
+        }
+    }
+
+    #[test]
+    fn deal_from_shoe_enhc_has_no_hole_card() {
+        let mut gs = GameState::new();
+        gs.set_rules(Rules {
+            hole_card: crate::rules::HoleCard::Enhc,
+            ..Default::default()
+        });
+        assert!(gs.deal_a_hand());
+        assert!(gs.dealer_hand().hole_card().is_none());
+    }
+
+    #[test]
+    fn check_answer_spanish21_uses_its_chart_and_keys() {
+        let mut gs = GameState::new();
+        gs.set_rules(Rules {
+            variant: GameVariant::Spanish21,
+            ..Default::default()
+        });
+        gs.player_hand = parse_hand("9H 7C"); // hard 16
+        gs.dealer_hand = parse_hand("KS");
+        let result = gs.check_answer(Action::Surrender).unwrap();
+        assert!(result.correct);
+        assert_eq!(
+            Some("v3/s21-basic/hard:16,10".to_string()),
+            result.table_index_key
+        );
+        let seen = |key: &str| gs.deck().unseen_count(&[key.to_string()]) == 0;
+        assert!(seen("v3/s21-basic/hard:16,10"));
+        assert!(!seen("v3/bj-h17-das-nols-peek-basic/hard:16,10"));
+    }
+
+    #[test]
+    fn set_deck_migrates_old_keys_to_the_current_rules() {
+        let mut gs = GameState::new();
+        gs.set_rules(Rules {
+            soft_17: crate::rules::Soft17::Stands,
+            ..Default::default()
+        });
+        let mut deck = Deck::new();
+        deck.record("hard:16,10", true);
+        gs.set_deck(deck);
+        let seen = |key: &str| gs.deck().unseen_count(&[key.to_string()]) == 0;
+        assert!(seen("v3/bj-s17-das-nols-peek-basic/hard:16,10"));
+        assert!(!seen("hard:16,10"));
+    }
+
+    #[test]
+    fn switching_strategy_starts_the_cells_afresh() {
+        let mut gs = GameState::new();
+        gs.set_strategy(StrategyKind::Simple);
+        let mut deck = Deck::new();
+        deck.record("v2/bj-h17-das-nols-peek/hard:9,3", true);
+        gs.set_deck(deck);
+        let ti: TableIndex = "hard:9,3".parse().unwrap();
+        let seen = |gs: &GameState| {
+            let key = table_index_to_key(&ti, gs.rules(), gs.strategy());
+            gs.deck().unseen_count(&[key]) == 0
+        };
+        assert!(seen(&gs));
+        // Hit under the simple strategy, a double under basic strategy
+        gs.set_strategy(StrategyKind::Basic);
+        assert!(!seen(&gs));
+        gs.set_custom_charts(Some(ChartSet::simple()));
+        assert!(!seen(&gs));
+        gs.set_custom_charts(None);
+        gs.set_strategy(StrategyKind::Simple);
+        assert!(seen(&gs));
+    }
+
+    #[test]
+    fn spanish21_shoe_has_no_pip_tens() {
+        let mut gs = GameState::new();
+        gs.set_rules(Rules {
+            variant: GameVariant::Spanish21,
+            ..Default::default()
+        });
+        while gs.deal_a_hand() {
+            let mut cards = gs
+                .player_hand()
+                .cards()
+                .iter()
+                .chain(gs.dealer_hand().cards());
+            assert!(cards.all(|c| c.pip != crate::card::Pip::Ten));
+        }
+    }
+
+    #[test]
+    fn spanish21_drill_deals_namespaced_keys() {
+        let mut gs = GameState::new();
+        gs.set_rules(Rules {
+            variant: GameVariant::Spanish21,
+            ..Default::default()
+        });
+        gs.set_study_mode(StudyMode::Drill);
+        assert!(gs.deal_a_hand());
+        let (_, ti) = gs.chart_action().unwrap();
+        assert!(ti.is_some());
+    }
 }
@@ -0,0 +1,159 @@
+use crate::strat::TableIndex;
+use rand::Rng;
+use std::collections::HashSet;
+
+// How much more often the latest miss comes up than a cell with no recent miss. The bonus
+// halves with each miss since.
+const RECENT_BONUS: f64 = 8.0;
+
+#[derive(Debug, Clone, Copy)]
+struct WeakCell {
+    index: TableIndex,
+    /// Correct answers since the last miss.
+    streak: u32,
+    /// When the cell was last missed, counted in misses; `None` if not known.
+    missed_at: Option<u32>,
+}
+
+/// The cells the Weak spots mode deals from: trouble spots from the answer history, cells
+/// the deck has in its first box, and anything missed since. Cells answered right
+/// `GRADUATE_AFTER` times in a row leave the pool until they're missed again.
+#[derive(Debug, Clone, Default)]
+pub struct WeakSpots {
+    cells: Vec<WeakCell>,
+    graduated: HashSet<TableIndex>,
+    misses: u32,
+}
+
+impl WeakSpots {
+    /// Correct answers in a row that take a cell out of the pool.
+    pub const GRADUATE_AFTER: u32 = 3;
+
+    pub fn indices(&self) -> Vec<TableIndex> {
+        self.cells.iter().map(|c| c.index).collect()
+    }
+
+    /// Add cells from the answer history, most recently missed first.
+    pub fn add_missed(&mut self, recent_first: &[TableIndex]) {
+        for &index in recent_first.iter().rev() {
+            self.misses += 1;
+            let missed_at = Some(self.misses);
+            if !self.graduated.contains(&index) {
+                match self.cells.iter_mut().find(|c| c.index == index) {
+                    Some(cell) => cell.missed_at = missed_at,
+                    None => self.cells.push(WeakCell {
+                        index,
+                        streak: 0,
+                        missed_at,
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Add cells that are weak for another reason, such as a low Leitner box.
+    pub fn add_weak(&mut self, cells: impl IntoIterator<Item = TableIndex>) {
+        for index in cells {
+            if !self.graduated.contains(&index) && !self.cells.iter().any(|c| c.index == index) {
+                self.cells.push(WeakCell {
+                    index,
+                    streak: 0,
+                    missed_at: None,
+                });
+            }
+        }
+    }
+
+    /// Count an answer. A miss puts the cell (back) in the pool as the most recent failure.
+    pub fn record(&mut self, index: TableIndex, correct: bool) {
+        if !correct {
+            self.graduated.remove(&index);
+            self.add_missed(&[index]);
+            if let Some(cell) = self.cells.iter_mut().find(|c| c.index == index) {
+                cell.streak = 0;
+            }
+            return;
+        }
+        if let Some(at) = self.cells.iter().position(|c| c.index == index) {
+            self.cells[at].streak += 1;
+            if self.cells[at].streak >= Self::GRADUATE_AFTER {
+                self.cells.remove(at);
+                self.graduated.insert(index);
+            }
+        }
+    }
+
+    /// A cell from the pool, recent misses more likely than the rest.
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<TableIndex> {
+        let weight = |cell: &WeakCell| match cell.missed_at {
+            Some(at) => 1.0 + RECENT_BONUS * 0.5f64.powi((self.misses - at) as i32),
+            None => 1.0,
+        };
+        let total: f64 = self.cells.iter().map(weight).sum();
+        let mut target = rng.gen_range(0.0..total.max(f64::MIN_POSITIVE));
+        for cell in &self.cells {
+            target -= weight(cell);
+            if target < 0.0 {
+                return Some(cell.index);
+            }
+        }
+        self.cells.last().map(|c| c.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn ti(s: &str) -> TableIndex {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn cells_graduate_after_a_streak() {
+        let mut weak = WeakSpots::default();
+        weak.add_missed(&[ti("hard:16,10")]);
+        for _ in 0..WeakSpots::GRADUATE_AFTER - 1 {
+            weak.record(ti("hard:16,10"), true);
+        }
+        assert_eq!(1, weak.indices().len());
+        weak.record(ti("hard:16,10"), true);
+        assert!(weak.indices().is_empty());
+
+        // Graduated cells don't come back from the history or the deck...
+        weak.add_missed(&[ti("hard:16,10")]);
+        weak.add_weak([ti("hard:16,10")]);
+        assert!(weak.indices().is_empty());
+        // ...but a new miss brings them back.
+        weak.record(ti("hard:16,10"), false);
+        assert_eq!(vec![ti("hard:16,10")], weak.indices());
+    }
+
+    #[test]
+    fn a_miss_resets_the_streak() {
+        let mut weak = WeakSpots::default();
+        weak.add_weak([ti("soft:18,9")]);
+        weak.record(ti("soft:18,9"), true);
+        weak.record(ti("soft:18,9"), true);
+        weak.record(ti("soft:18,9"), false);
+        weak.record(ti("soft:18,9"), true);
+        weak.record(ti("soft:18,9"), true);
+        assert_eq!(1, weak.indices().len());
+    }
+
+    #[test]
+    fn recent_misses_come_up_more_often() {
+        let mut weak = WeakSpots::default();
+        weak.add_weak([ti("hard:12,2"), ti("hard:12,3"), ti("hard:12,4")]);
+        weak.add_missed(&[ti("split:9,7")]);
+        let mut rng = StdRng::seed_from_u64(7);
+        let recent = (0..1000)
+            .filter(|_| weak.pick(&mut rng) == Some(ti("split:9,7")))
+            .count();
+        // Weight 9 of 12
+        assert!((650..850).contains(&recent), "{}", recent);
+        assert_eq!(None, WeakSpots::default().pick(&mut rng));
+    }
+}
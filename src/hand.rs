@@ -1,5 +1,5 @@
+use crate::BjError;
 use crate::card::{Card, Pip};
-use crate::{BjError, BjResult};
 use itertools::Itertools;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -9,6 +9,8 @@ pub struct Hand {
     cards: Vec<Card>,
     total: u8,
     soft: bool,
+    // The dealer's face-down card. It is not part of `cards` or the total until revealed.
+    hole_card: Option<Card>,
 }
 
 impl Hand {
@@ -29,6 +31,30 @@ impl Hand {
         self.cards.len() == 2 && self.total == 21
     }
 
+    /// The dealer's face-down card, if one was dealt.
+    pub fn hole_card(&self) -> Option<Card> {
+        self.hole_card
+    }
+
+    pub fn set_hole_card(&mut self, card: Card) {
+        self.hole_card = Some(card);
+    }
+
+    /// Turn the hole card over, making it part of the hand.
+    pub fn reveal_hole_card(&mut self) {
+        if let Some(card) = self.hole_card.take() {
+            self.add_card(card);
+        }
+    }
+
+    /// A dealer blackjack: the upcard and the hole card total 21.
+    pub fn is_dealer_blackjack(&self) -> bool {
+        match (self.cards.as_slice(), self.hole_card) {
+            ([up], Some(hole)) => up.value() + hole.value() == 21,
+            _ => false,
+        }
+    }
+
     pub fn splittable(&self) -> bool {
         if self.cards.len() != 2 {
             false
@@ -37,6 +63,13 @@ impl Hand {
         }
     }
 
+    /// The hand as it's typed, e.g. "KS (7D)", which parses back to the hand.
+    pub fn code(&self) -> String {
+        let cards = self.cards.iter().map(Card::code);
+        let hole = self.hole_card.map(|c| format!("({})", c.code()));
+        cards.chain(hole).join(" ")
+    }
+
     pub fn cards(&self) -> &[Card] {
         &self.cards
     }
@@ -77,13 +110,20 @@ impl Display for Hand {
 impl FromStr for Hand {
     type Err = BjError;
 
+    /// Cards separated by spaces, e.g. "KS 7D". A card in parentheses is the dealer's hole
+    /// card: "KS (7D)".
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cards: Vec<Card> = s
-            .split_whitespace()
-            .map(|s| s.parse())
-            .collect::<BjResult<Vec<Card>>>()?;
+        let mut cards = Vec::new();
+        let mut hole_card = None;
+        for token in s.split_whitespace() {
+            match token.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+                Some(hole) => hole_card = Some(hole.parse()?),
+                None => cards.push(token.parse()?),
+            }
+        }
         let mut hand = Hand {
             cards,
+            hole_card,
             ..Default::default()
         };
         hand.compute_total();
@@ -115,6 +155,17 @@ mod tests {
         assert_eq!(h.total(), 22);
     }
 
+    #[test]
+    fn test_code_round_trips_the_hole_card() {
+        let mut h = parse("KS");
+        h.set_hole_card("7D".parse().unwrap());
+        assert_eq!("KS (7D)", h.code());
+        let back = parse(&h.code());
+        assert_eq!(h.cards(), back.cards());
+        assert_eq!(h.hole_card(), back.hole_card());
+        assert_eq!(10, back.total());
+    }
+
     // --- total() ---
 
     #[test]
@@ -276,6 +327,47 @@ mod tests {
         assert_eq!(11, card.value()); // Ace = 11
     }
 
+    // --- hole card ---
+
+    #[test]
+    fn hole_card_not_counted_in_total() {
+        let mut h = parse("TS");
+        h.set_hole_card("AH".parse().unwrap());
+        assert_eq!(10, h.total());
+        assert_eq!(1, h.num_cards());
+        assert_eq!("T♠", h.to_string());
+    }
+
+    #[test]
+    fn reveal_hole_card_adds_it_to_total() {
+        let mut h = parse("TS");
+        h.set_hole_card("7H".parse().unwrap());
+        h.reveal_hole_card();
+        assert_eq!(17, h.total());
+        assert_eq!(2, h.num_cards());
+        assert!(h.hole_card().is_none());
+    }
+
+    #[test]
+    fn is_dealer_blackjack_ten_under_ace() {
+        let mut h = parse("AS");
+        h.set_hole_card("KH".parse().unwrap());
+        assert!(h.is_dealer_blackjack());
+    }
+
+    #[test]
+    fn is_dealer_blackjack_false_without_hole_card() {
+        let h = parse("AS");
+        assert!(!h.is_dealer_blackjack());
+    }
+
+    #[test]
+    fn is_dealer_blackjack_false_for_non_21() {
+        let mut h = parse("TS");
+        h.set_hole_card("9H".parse().unwrap());
+        assert!(!h.is_dealer_blackjack());
+    }
+
     // --- from_str / Display round-trip ---
 
     #[test]
@@ -1,6 +1,7 @@
 use crate::card::{Card, Pip, Suit};
 use crate::hand::Hand;
-use crate::strat::{TableIndex, TableType};
+use crate::rules::{GameVariant, Rules};
+use crate::strat::{CompositionException, Makeup, TableIndex, TableType};
 use rand::prelude::*;
 
 const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
@@ -10,8 +11,9 @@ fn random_suit() -> Suit {
 }
 
 /// Map a card value (1-10, where 1=Ace) to a Pip.
-/// For value 10, randomly picks Ten/Jack/Queen/King.
-fn pip_for_value(val: u8) -> Pip {
+/// For value 10, randomly picks Ten/Jack/Queen/King (Jack/Queen/King in Spanish 21, whose
+/// decks have no pip tens).
+fn pip_for_value(val: u8, variant: GameVariant) -> Pip {
     match val {
         1 => Pip::Ace,
         2 => Pip::Two,
@@ -23,45 +25,114 @@ fn pip_for_value(val: u8) -> Pip {
         8 => Pip::Eight,
         9 => Pip::Nine,
         10 => {
-            let faces = [Pip::Ten, Pip::Jack, Pip::Queen, Pip::King];
-            faces[thread_rng().gen_range(0..4)]
+            let faces: &[Pip] = match variant {
+                GameVariant::Standard | GameVariant::FreeBet => {
+                    &[Pip::Ten, Pip::Jack, Pip::Queen, Pip::King]
+                }
+                GameVariant::Spanish21 => &[Pip::Jack, Pip::Queen, Pip::King],
+            };
+            faces[thread_rng().gen_range(0..faces.len())]
         }
         11 => Pip::Ace,
         _ => unreachable!("invalid card value: {}", val),
     }
 }
 
-fn make_card(val: u8) -> Card {
+fn make_card(val: u8, variant: GameVariant) -> Card {
     Card {
-        pip: pip_for_value(val),
+        pip: pip_for_value(val, variant),
         suit: random_suit(),
     }
 }
 
+/// A hole card that doesn't complete a dealer blackjack. With a hole card the dealer has
+/// already peeked by the time the player acts, so a question never hides a blackjack.
+fn make_hole_card(upcard: Card, variant: GameVariant) -> Card {
+    let mut rng = thread_rng();
+    loop {
+        let card = make_card(rng.gen_range(1..=10), variant);
+        if upcard.value() + card.value() != 21 {
+            return card;
+        }
+    }
+}
+
 /// Build a (player_hand, dealer_hand) for a given TableIndex.
-pub fn build_hand_for_index(index: &TableIndex) -> (Hand, Hand) {
+pub fn build_hand_for_index(index: &TableIndex, rules: &Rules) -> (Hand, Hand) {
     let row = index.row_index();
     let col = index.col_index().value(); // 1=Ace, 2-10
 
-    let dealer_card = make_card(col);
-    let mut dealer = Hand::default();
-    dealer.add_card(dealer_card);
+    let variant = rules.variant;
+    let dealer = build_dealer_hand(col, rules);
 
     let mut player = Hand::default();
 
     match index.table_type() {
-        TableType::Hard => build_hard_hand(&mut player, row),
-        TableType::Soft => build_soft_hand(&mut player, row),
-        TableType::Split => build_split_hand(&mut player, row),
-        TableType::Surrender => build_hard_hand(&mut player, row),
+        TableType::Hard => build_hard_hand(&mut player, row, variant),
+        TableType::Soft => build_soft_hand(&mut player, row, variant),
+        TableType::Split => build_split_hand(&mut player, row, variant),
+        TableType::Surrender => build_hard_hand(&mut player, row, variant),
+    }
+
+    (player, dealer)
+}
+
+/// Build a (player_hand, dealer_hand) that a composition-dependent exception applies to.
+pub fn build_hand_for_exception(exception: &CompositionException, rules: &Rules) -> (Hand, Hand) {
+    let variant = rules.variant;
+    let dealer = build_dealer_hand(exception.dealer, rules);
+
+    let mut player = Hand::default();
+    match exception.makeup {
+        Makeup::TwoCards(a, b) => {
+            player.add_card(make_card(a, variant));
+            player.add_card(make_card(b, variant));
+        }
+        Makeup::AtLeast(n) => build_multi_card_hand(&mut player, exception.total, n, variant),
     }
 
     (player, dealer)
 }
 
+/// The dealer's up card for chart column `col` (1=Ace), plus a hole card when the rules
+/// deal one.
+fn build_dealer_hand(col: u8, rules: &Rules) -> Hand {
+    let dealer_card = make_card(col, rules.variant);
+    let mut dealer = Hand::default();
+    dealer.add_card(dealer_card);
+    if rules.dealer_takes_hole_card() {
+        dealer.set_hole_card(make_hole_card(dealer_card, rules.variant));
+    }
+    dealer
+}
+
+/// Build a hard hand of `count` non-ace cards totaling `total`.
+fn build_multi_card_hand(hand: &mut Hand, total: u8, count: u8, variant: GameVariant) {
+    let mut rng = thread_rng();
+    loop {
+        let mut values: Vec<u8> = (1..count).map(|_| rng.gen_range(2..=10)).collect();
+        let sum: u8 = values.iter().sum();
+        if let Some(last) = total.checked_sub(sum)
+            && (2..=10).contains(&last)
+        {
+            values.push(last);
+            for v in values {
+                hand.add_card(make_card(v, variant));
+            }
+            return;
+        }
+    }
+}
+
 /// Build a hard hand totaling `total`.
-/// Picks two non-ace cards that sum to `total`, avoiding pairs (to not trigger split).
-fn build_hard_hand(hand: &mut Hand, total: u8) {
+/// Picks two non-ace cards that sum to `total`, avoiding pairs (to not trigger split). A
+/// hard 20 can only be made without a pair from three cards.
+fn build_hard_hand(hand: &mut Hand, total: u8, variant: GameVariant) {
+    if total >= 20 {
+        build_multi_card_hand(hand, total, 3, variant);
+        return;
+    }
+
     let mut rng = thread_rng();
 
     // Valid first card range: 2..=10, second card = total - first, also 2..=10
@@ -71,8 +142,8 @@ fn build_hard_hand(hand: &mut Hand, total: u8) {
     if min_first > max_first {
         // Fallback for very low totals (e.g., total=4 only option is 2+2)
         let half = total / 2;
-        hand.add_card(make_card(half));
-        hand.add_card(make_card(total - half));
+        hand.add_card(make_card(half, variant));
+        hand.add_card(make_card(total - half, variant));
         return;
     }
 
@@ -90,23 +161,28 @@ fn build_hard_hand(hand: &mut Hand, total: u8) {
         second = total - first;
     }
 
-    hand.add_card(make_card(first));
-    hand.add_card(make_card(second));
+    hand.add_card(make_card(first, variant));
+    hand.add_card(make_card(second, variant));
 }
 
-/// Build a soft hand totaling `total` (e.g., soft 17 = Ace + 6).
-fn build_soft_hand(hand: &mut Hand, total: u8) {
-    hand.add_card(make_card(1)); // Ace (will count as 11)
+/// Build a soft hand totaling `total` (e.g., soft 17 = Ace + 6). Soft 21 gets three cards,
+/// since two would be a blackjack.
+fn build_soft_hand(hand: &mut Hand, total: u8, variant: GameVariant) {
+    hand.add_card(make_card(1, variant)); // Ace (will count as 11)
     let other = total - 11;
-    hand.add_card(make_card(other));
+    if other == 10 {
+        build_multi_card_hand(hand, other, 2, variant);
+    } else {
+        hand.add_card(make_card(other, variant));
+    }
 }
 
 /// Build a split hand (pair) for the given row value.
 /// Row 1 = Aces, Row 2-10 = that pip value.
-fn build_split_hand(hand: &mut Hand, row: u8) {
+fn build_split_hand(hand: &mut Hand, row: u8, variant: GameVariant) {
     let val = if row == 1 { 11 } else { row }; // Ace has value 11 for card creation
-    hand.add_card(make_card(val));
-    hand.add_card(make_card(val));
+    hand.add_card(make_card(val, variant));
+    hand.add_card(make_card(val, variant));
 }
 
 #[cfg(test)]
@@ -122,16 +198,18 @@ mod tests {
 
     #[test]
     fn test_hard_hand_total() {
-        for total in 8..=17 {
+        for total in 5..=20 {
             let idx = make_index(TableType::Hard, total, 5);
-            let (player, dealer) = build_hand_for_index(&idx);
+            let (player, dealer) = build_hand_for_index(&idx, &Rules::default());
             assert_eq!(player.total(), total, "hard total mismatch for {}", total);
             assert!(
                 !player.is_soft(),
                 "hard hand should not be soft for {}",
                 total
             );
-            assert_eq!(player.num_cards(), 2);
+            assert!(!player.splittable(), "hard {} dealt as a pair", total);
+            let cards = if total == 20 { 3 } else { 2 };
+            assert_eq!(player.num_cards(), cards);
             assert_eq!(dealer.num_cards(), 1);
         }
     }
@@ -140,9 +218,10 @@ mod tests {
     fn test_soft_hand_total() {
         for total in 13..=21 {
             let idx = make_index(TableType::Soft, total, 3);
-            let (player, _) = build_hand_for_index(&idx);
+            let (player, _) = build_hand_for_index(&idx, &Rules::default());
             assert_eq!(player.total(), total, "soft total mismatch for {}", total);
             assert!(player.is_soft(), "soft hand should be soft for {}", total);
+            assert!(!player.is_natural(), "soft {} dealt as a blackjack", total);
         }
     }
 
@@ -150,7 +229,7 @@ mod tests {
     fn test_split_hand() {
         for row in 1..=10 {
             let idx = make_index(TableType::Split, row, 7);
-            let (player, _) = build_hand_for_index(&idx);
+            let (player, _) = build_hand_for_index(&idx, &Rules::default());
             assert!(
                 player.splittable(),
                 "split hand should be splittable for row {}",
@@ -160,12 +239,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_exception_hands_match_their_exception() {
+        let rules = Rules {
+            decks: 1,
+            ..Default::default()
+        };
+        for ex in crate::ChartSet::composition_dependent(&rules).exceptions() {
+            for _ in 0..20 {
+                let (player, dealer) = build_hand_for_exception(ex, &rules);
+                assert!(
+                    ex.matches(&player, &dealer),
+                    "{} from {} vs {}",
+                    ex,
+                    player,
+                    dealer
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_dealer_hand() {
         let idx = make_index(TableType::Hard, 12, 1); // dealer Ace
-        let (_, dealer) = build_hand_for_index(&idx);
+        let (_, dealer) = build_hand_for_index(&idx, &Rules::default());
         assert_eq!(dealer.num_cards(), 1);
         // ColIndex 1 = Ace, card value = 11
         assert_eq!(dealer.first_card().unwrap().value(), 11);
     }
+
+    #[test]
+    fn test_dealer_hole_card_never_blackjack() {
+        for col in [1u8, 10] {
+            let idx = make_index(TableType::Hard, 16, col);
+            for _ in 0..50 {
+                let (_, dealer) = build_hand_for_index(&idx, &Rules::default());
+                assert!(dealer.hole_card().is_some());
+                assert!(!dealer.is_dealer_blackjack());
+            }
+        }
+    }
+
+    #[test]
+    fn test_enhc_dealer_has_no_hole_card() {
+        let rules = Rules {
+            hole_card: crate::rules::HoleCard::Enhc,
+            ..Default::default()
+        };
+        let idx = make_index(TableType::Hard, 16, 10);
+        let (_, dealer) = build_hand_for_index(&idx, &rules);
+        assert!(dealer.hole_card().is_none());
+        assert_eq!(dealer.num_cards(), 1);
+    }
+
+    #[test]
+    fn test_spanish21_has_no_pip_tens() {
+        let rules = Rules {
+            variant: GameVariant::Spanish21,
+            ..Default::default()
+        };
+        let idx = make_index(TableType::Split, 10, 10);
+        for _ in 0..50 {
+            let (player, dealer) = build_hand_for_index(&idx, &rules);
+            let cards = player.cards().iter().chain(dealer.cards());
+            assert!(
+                cards
+                    .chain(dealer.hole_card().iter())
+                    .all(|c| c.pip != Pip::Ten)
+            );
+        }
+    }
 }
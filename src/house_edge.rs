@@ -0,0 +1,188 @@
+use crate::rules::{BlackjackPayout, GameVariant, HoleCard, Rules, Soft17};
+
+// Rule-of-thumb house edge, in percent of the original bet, for a player using basic strategy.
+//
+// Each game starts from a published base edge for one reference rule set, then adds the usual
+// effect of each rule that differs from it. The effects are close to additive for the rules
+// modelled here, so the total is within a few hundredths of a percent of a full calculation.
+
+// Six decks, dealer stands on soft 17, DAS, no surrender, no resplitting aces, 3:2, peek.
+const STANDARD_BASE: f64 = 0.40;
+// Spanish 21 with the rules in its charts: six decks, S17, late surrender, rescue.
+const SPANISH21_BASE: f64 = 0.40;
+// Free Bet: six decks, H17, dealer 22 pushes.
+const FREE_BET_BASE: f64 = 1.02;
+
+const H17: f64 = 0.22;
+const SPANISH21_H17: f64 = 0.36;
+const FREE_BET_S17: f64 = -0.20;
+const NO_DAS: f64 = 0.14;
+const LATE_SURRENDER: f64 = -0.08;
+const RESPLIT_ACES: f64 = -0.08;
+const SIX_TO_FIVE: f64 = 1.39;
+const ENHC: f64 = 0.11;
+
+/// An estimated house edge, with the cost of each rule that moves it away from the base game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HouseEdge {
+    /// Edge of the reference game for this variant, in percent.
+    pub base: f64,
+    /// (rule, change in percent). Positive numbers favour the house.
+    pub adjustments: Vec<(String, f64)>,
+}
+
+impl HouseEdge {
+    /// The estimated edge in percent.
+    pub fn total(&self) -> f64 {
+        self.base + self.adjustments.iter().map(|(_, a)| a).sum::<f64>()
+    }
+
+    /// One line per rule, e.g. "H17 +0.22%".
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = vec![format!("Base {:.2}%", self.base)];
+        lines.extend(
+            self.adjustments
+                .iter()
+                .map(|(rule, a)| format!("{} {:+.2}%", rule, a)),
+        );
+        lines
+    }
+}
+
+/// Effect of the number of decks, relative to six.
+fn decks_adjustment(decks: u8) -> f64 {
+    match decks {
+        0 | 1 => -0.48,
+        2 => -0.19,
+        3 => -0.10,
+        4 => -0.06,
+        5 => -0.03,
+        6 => 0.0,
+        7 => 0.01,
+        _ => 0.02,
+    }
+}
+
+/// Estimate the house edge for basic strategy play under these rules.
+pub fn house_edge(rules: &Rules) -> HouseEdge {
+    let mut adjustments = Vec::new();
+    let mut push = |rule: String, a: f64| {
+        if a != 0.0 {
+            adjustments.push((rule, a));
+        }
+    };
+
+    push(
+        format!("{} decks", rules.decks),
+        decks_adjustment(rules.decks),
+    );
+
+    let base = match rules.variant {
+        GameVariant::Standard => {
+            if rules.soft_17 == Soft17::Hits {
+                push("H17".to_string(), H17);
+            }
+            if !rules.das {
+                push("No DAS".to_string(), NO_DAS);
+            }
+            if rules.late_surrender() {
+                push("Late surrender".to_string(), LATE_SURRENDER);
+            }
+            if rules.hole_card == HoleCard::Enhc {
+                push("ENHC".to_string(), ENHC);
+            }
+            STANDARD_BASE
+        }
+        // Surrender and DAS are part of the game; the charts don't change with them.
+        GameVariant::Spanish21 => {
+            if rules.soft_17 == Soft17::Hits {
+                push("H17".to_string(), SPANISH21_H17);
+            }
+            SPANISH21_BASE
+        }
+        GameVariant::FreeBet => {
+            if rules.soft_17 == Soft17::Stands {
+                push("S17".to_string(), FREE_BET_S17);
+            }
+            FREE_BET_BASE
+        }
+    };
+
+    if rules.resplit_aces {
+        push("Resplit aces".to_string(), RESPLIT_ACES);
+    }
+    if rules.payout == BlackjackPayout::SixToFive {
+        push("Blackjack pays 6:5".to_string(), SIX_TO_FIVE);
+    }
+
+    HouseEdge { base, adjustments }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn default_rules_pay_for_h17() {
+        let edge = house_edge(&Rules::default());
+        assert!(close(0.62, edge.total()), "{}", edge.total());
+        assert_eq!(vec![("H17".to_string(), H17)], edge.adjustments);
+    }
+
+    #[test]
+    fn good_and_bad_rules_add_up() {
+        let rules = Rules {
+            decks: 2,
+            soft_17: Soft17::Stands,
+            surrender: true,
+            resplit_aces: true,
+            ..Default::default()
+        };
+        assert!(close(0.40 - 0.19 - 0.08 - 0.08, house_edge(&rules).total()));
+
+        let rules = Rules {
+            decks: 1,
+            das: false,
+            payout: BlackjackPayout::SixToFive,
+            ..Default::default()
+        };
+        assert!(close(
+            0.40 - 0.48 + 0.22 + 0.14 + 1.39,
+            house_edge(&rules).total()
+        ));
+    }
+
+    #[test]
+    fn enhc_gets_no_credit_for_late_surrender() {
+        let rules = Rules {
+            surrender: true,
+            hole_card: HoleCard::Enhc,
+            ..Default::default()
+        };
+        assert_eq!(
+            vec!["Base 0.40%", "H17 +0.22%", "ENHC +0.11%"],
+            house_edge(&rules).describe()
+        );
+    }
+
+    #[test]
+    fn variants_have_their_own_base() {
+        let rules = Rules {
+            variant: GameVariant::FreeBet,
+            ..Default::default()
+        };
+        let edge = house_edge(&rules);
+        assert_eq!(FREE_BET_BASE, edge.base);
+        assert!(edge.adjustments.is_empty());
+    }
+
+    #[test]
+    fn describe_lists_each_rule() {
+        let lines = house_edge(&Rules::default()).describe();
+        assert_eq!(vec!["Base 0.40%", "H17 +0.22%"], lines);
+    }
+}
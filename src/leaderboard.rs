@@ -0,0 +1,319 @@
+use crate::blitz::BlitzScore;
+use crate::progress::day_number;
+use crate::supabase::{BlitzScoreRow, ExamResultRow, GroupMemberRow, StreakRow};
+use crate::table_index_keys::rules_scope;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// What a group's leaderboard ranks its members by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Board {
+    /// Best Blitz score for the rules, strategy and length being played.
+    #[default]
+    Blitz,
+    /// Days in a row with answers, while the run is still going.
+    Streak,
+    /// Best exam score for the rules and strategy being played.
+    Exam,
+}
+
+impl Board {
+    pub const ALL: [Board; 3] = [Board::Blitz, Board::Streak, Board::Exam];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Board::Blitz => "Blitz",
+            Board::Streak => "Streak",
+            Board::Exam => "Exam",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Board::Blitz => Board::Streak,
+            Board::Streak => Board::Exam,
+            Board::Exam => Board::Blitz,
+        }
+    }
+}
+
+/// A member's place on a leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    /// 1 for the top; members with the same score share a rank.
+    pub rank: usize,
+    pub user_id: String,
+    pub display_name: String,
+    /// The score as shown, e.g. "23 right, 1 wrong".
+    pub score: String,
+}
+
+/// Rank the members with a score, highest key first. Members with no score are left off.
+fn standings<K: Ord>(
+    members: &[GroupMemberRow],
+    mut scores: HashMap<&str, (K, String)>,
+) -> Vec<Standing> {
+    let mut ranked: Vec<(K, &GroupMemberRow, String)> = members
+        .iter()
+        .filter_map(|m| {
+            let (key, score) = scores.remove(m.user_id.as_str())?;
+            Some((key, m, score))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.display_name.cmp(&b.1.display_name)));
+
+    let mut result: Vec<Standing> = Vec::with_capacity(ranked.len());
+    for (i, (key, member, score)) in ranked.iter().enumerate() {
+        let rank = match i.checked_sub(1) {
+            Some(prev) if ranked[prev].0 == *key => result[prev].rank,
+            _ => i + 1,
+        };
+        result.push(Standing {
+            rank,
+            user_id: member.user_id.clone(),
+            display_name: member.display_name.clone(),
+            score: score.clone(),
+        });
+    }
+    result
+}
+
+/// Each member's best Blitz score: most right, then fewest wrong.
+pub fn blitz_standings(members: &[GroupMemberRow], scores: &[BlitzScoreRow]) -> Vec<Standing> {
+    let mut best: HashMap<&str, &BlitzScoreRow> = HashMap::new();
+    for row in scores {
+        let entry = best.entry(row.user_id.as_str()).or_insert(row);
+        if row.score.beats(&entry.score) {
+            *entry = row;
+        }
+    }
+    let scores = best
+        .into_iter()
+        .map(|(user, row)| {
+            let key = (row.score.correct, Reverse(row.score.wrong));
+            let text = format!("{} right, {} wrong", row.score.correct, row.score.wrong);
+            (user, (key, text))
+        })
+        .collect();
+    standings(members, scores)
+}
+
+/// Each member's best exam: highest score, then the most questions. Only exams taken under
+/// the same rule set and strategy as `challenge` count, and exams graded against the
+/// player's own charts never do.
+pub fn exam_standings(
+    members: &[GroupMemberRow],
+    results: &[ExamResultRow],
+    challenge: &BlitzScore,
+) -> Vec<Standing> {
+    let mut scores: HashMap<&str, ((u32, u32), String)> = HashMap::new();
+    let counted = results.iter().filter(|r| {
+        r.report.total > 0
+            && !r.report.custom_charts
+            && r.report.strategy == challenge.strategy
+            && rules_scope(&r.report.rules) == challenge.rule_set
+    });
+    for row in counted {
+        let report = &row.report;
+        let key = (report.correct * 1000 / report.total, report.total);
+        let text = format!(
+            "{:.0}% ({}/{})",
+            report.score_pct(),
+            report.correct,
+            report.total
+        );
+        let entry = scores
+            .entry(row.user_id.as_str())
+            .or_insert((key, text.clone()));
+        if key > entry.0 {
+            *entry = (key, text);
+        }
+    }
+    standings(members, scores)
+}
+
+/// Each member's practice streak, if it's still going: their last day was `today` or the
+/// day before. Days are "YYYY-MM-DD".
+pub fn streak_standings(
+    members: &[GroupMemberRow],
+    streaks: &[StreakRow],
+    today: &str,
+) -> Vec<Standing> {
+    let Some(today) = day_number(today) else {
+        return Vec::new();
+    };
+    let scores = streaks
+        .iter()
+        .filter(|row| day_number(&row.last_day).is_some_and(|day| today - day <= 1))
+        .map(|row| {
+            let text = format!("{} day{}", row.days, if row.days == 1 { "" } else { "s" });
+            (row.user_id.as_str(), (row.days, text))
+        })
+        .collect();
+    standings(members, scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rules;
+    use crate::blitz::Blitz;
+    use crate::exam::{Exam, ExamReport};
+    use crate::strat::{Action, StrategyKind};
+    use crate::table_index_keys::indices_for_mode;
+    use crate::{BasicStrategy, StudyMode};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn members() -> Vec<GroupMemberRow> {
+        ["ann", "bob", "cat"]
+            .iter()
+            .map(|name| GroupMemberRow {
+                group_id: "g1".to_string(),
+                user_id: format!("u-{}", name),
+                display_name: name.to_string(),
+            })
+            .collect()
+    }
+
+    fn blitz(user: &str, correct: u32, wrong: u32) -> BlitzScoreRow {
+        let mut blitz = Blitz::new(60, &Rules::default(), StrategyKind::Basic, false);
+        (0..correct).for_each(|_| blitz.record(true));
+        (0..wrong).for_each(|_| blitz.record(false));
+        BlitzScoreRow {
+            user_id: user.to_string(),
+            score: blitz.score(String::new()),
+        }
+    }
+
+    fn ranks(standings: &[Standing]) -> Vec<(usize, &str, &str)> {
+        standings
+            .iter()
+            .map(|s| (s.rank, s.display_name.as_str(), s.score.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn blitz_ranks_each_members_best() {
+        let scores = vec![
+            blitz("u-ann", 20, 2),
+            blitz("u-bob", 20, 1),
+            blitz("u-ann", 18, 0),
+            blitz("u-cat", 20, 1),
+            // Not in the group
+            blitz("u-dan", 40, 0),
+        ];
+        assert_eq!(
+            vec![
+                (1, "bob", "20 right, 1 wrong"),
+                (1, "cat", "20 right, 1 wrong"),
+                (3, "ann", "20 right, 2 wrong"),
+            ],
+            ranks(&blitz_standings(&members(), &scores))
+        );
+    }
+
+    /// An exam of six questions with the first `wrong` answered wrongly.
+    fn exam(wrong: usize, rules: Rules, strategy: StrategyKind, rng: &mut StdRng) -> ExamReport {
+        let cells = indices_for_mode(StudyMode::All, &rules, &BasicStrategy);
+        let mut exam = Exam::sample(cells, 2, rules, strategy, false, rng);
+        for i in 0..exam.len() {
+            let answer = if i < wrong {
+                Action::Stand
+            } else {
+                Action::Hit
+            };
+            exam.record(Action::Hit, answer);
+        }
+        exam.report(String::new(), 60)
+    }
+
+    fn exam_row(user: &str, report: ExamReport) -> ExamResultRow {
+        ExamResultRow {
+            user_id: user.to_string(),
+            report,
+        }
+    }
+
+    #[test]
+    fn exams_rank_by_best_score() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let basic = StrategyKind::Basic;
+        let mut own_charts = exam(0, Rules::default(), basic, &mut rng);
+        own_charts.custom_charts = true;
+        let results = vec![
+            exam_row("u-cat", exam(3, Rules::default(), basic, &mut rng)),
+            exam_row("u-cat", own_charts),
+            exam_row("u-cat", exam(1, Rules::default(), basic, &mut rng)),
+            exam_row("u-ann", exam(0, Rules::default(), basic, &mut rng)),
+        ];
+        assert_eq!(
+            vec![(1, "ann", "100% (6/6)"), (2, "cat", "83% (5/6)")],
+            ranks(&exam_standings(
+                &members(),
+                &results,
+                &blitz("", 0, 0).score
+            ))
+        );
+    }
+
+    #[test]
+    fn exams_only_rank_against_the_same_rules_and_strategy() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let no_das = Rules {
+            das: false,
+            ..Rules::default()
+        };
+        let basic = StrategyKind::Basic;
+        let results = vec![
+            exam_row("u-ann", exam(0, no_das, basic, &mut rng)),
+            exam_row("u-ann", exam(2, Rules::default(), basic, &mut rng)),
+            exam_row(
+                "u-bob",
+                exam(0, Rules::default(), StrategyKind::Simple, &mut rng),
+            ),
+            exam_row("u-cat", exam(1, Rules::default(), basic, &mut rng)),
+        ];
+
+        let challenge = blitz("", 0, 0).score;
+        assert_eq!(
+            vec![(1, "cat", "83% (5/6)"), (2, "ann", "67% (4/6)")],
+            ranks(&exam_standings(&members(), &results, &challenge))
+        );
+
+        let no_das_challenge = Blitz::new(60, &no_das, basic, false).score(String::new());
+        assert_eq!(
+            vec![(1, "ann", "100% (6/6)")],
+            ranks(&exam_standings(&members(), &results, &no_das_challenge))
+        );
+
+        let simple = Blitz::new(60, &Rules::default(), StrategyKind::Simple, false);
+        assert_eq!(
+            vec![(1, "bob", "100% (6/6)")],
+            ranks(&exam_standings(
+                &members(),
+                &results,
+                &simple.score(String::new())
+            ))
+        );
+    }
+
+    #[test]
+    fn only_running_streaks_count() {
+        let streak = |user: &str, last_day: &str, days| StreakRow {
+            user_id: user.to_string(),
+            last_day: last_day.to_string(),
+            days,
+        };
+        let streaks = vec![
+            streak("u-ann", "2025-03-01", 1),
+            streak("u-bob", "2025-02-28", 12),
+            streak("u-cat", "2025-02-27", 30),
+        ];
+        assert_eq!(
+            vec![(1, "bob", "12 days"), (2, "ann", "1 day")],
+            ranks(&streak_standings(&members(), &streaks, "2025-03-01"))
+        );
+        assert!(streak_standings(&members(), &streaks, "today").is_empty());
+    }
+}
@@ -1,32 +1,61 @@
 pub mod api;
 mod bjerror;
+pub mod blitz;
 pub mod card;
+pub mod chart_quiz;
+pub mod class;
+mod coaching;
+pub mod curriculum;
+pub mod encoding;
+mod ev;
+pub mod exam;
 mod gamestate;
 pub mod hand;
 mod shoe;
 mod strat;
 
 mod hand_builder;
+mod house_edge;
+pub mod leaderboard;
 pub mod persistence;
+mod presets;
 pub mod progress;
+mod rules;
+mod settlement;
+mod study_set;
 mod studymode;
 pub mod supabase;
 mod table_index_keys;
+pub mod verify;
 
 pub use bjerror::*;
+pub use coaching::strategy_prompt;
+pub use encoding::Compact;
+pub use ev::{CellExplanation, explain_cell};
 pub use gamestate::stats::Stats;
-pub use gamestate::{AnswerResult, GameState};
+pub use gamestate::weak_spots::WeakSpots;
+pub use gamestate::{AnswerResult, GameState, SLOW_ANSWER_MS};
 pub use hand::Hand;
 pub use hand_builder::build_hand_for_index;
+pub use house_edge::{HouseEdge, house_edge};
 pub use persistence::SavedState;
+pub use presets::{PRESETS, Preset, next_preset, preset_by_key, preset_for_rules, resolve_rules};
+pub use rules::{BlackjackPayout, GameVariant, HoleCard, Rules, Soft17};
+pub use settlement::{Outcome, Stake, settle};
 pub use spaced_rep::{BOX_LABELS, DeckSummary};
 pub use strat::{
-    Action, ChartAction, StrategyChart, TableIndex, TableType, all_charts, all_phrases,
-    phrase_for_row,
+    Action, BasicStrategy, CellChange, ChartAction, ChartSet, CompositionException,
+    CompositionStrategy, Makeup, SimpleStrategy, Strategy, StrategyChart, StrategyKind, TableIndex,
+    TableType, all_charts, all_phrases, lookup_action, lookup_by_index, phrase_for_row,
+    rescue_action, strategy_charts,
 };
+pub use study_set::StudySet;
 pub use studymode::StudyMode;
 pub use supabase::{AuthSession, SupabaseConfig};
-pub use table_index_keys::{indices_for_mode, keys_for_mode};
+pub use table_index_keys::{
+    KEY_VERSION, changed_indices, exception_key, indices_for_mode, key_scope, key_to_table_index,
+    keys_for_mode, migrate_deck, migrate_key, rules_scope, short_key, table_index_to_key,
+};
 
 /// Format a duration in seconds as a human-readable string (e.g. "2m 30s", "1h 5m").
 pub fn format_wait_time(secs: u64) -> String {
@@ -1,3 +1,8 @@
+use crate::blitz::{BlitzScore, record_best};
+use crate::exam::ExamReport;
+use crate::rules::Rules;
+use crate::strat::{ChartSet, StrategyKind};
+use crate::study_set::StudySet;
 use crate::studymode::StudyMode;
 use serde::{Deserialize, Serialize};
 use spaced_rep::Deck;
@@ -10,6 +15,32 @@ pub struct SavedState {
     pub mode: StudyMode,
     #[serde(default)]
     pub deck: Deck,
+    #[serde(default)]
+    pub rules: Rules,
+    /// Key of the preset the rules came from; `None` for custom rules.
+    #[serde(default)]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub strategy: StrategyKind,
+    /// Path of a custom chart file (TOML or CSV) to grade against instead of the charts for
+    /// the rules.
+    #[serde(default)]
+    pub chart_file: Option<String>,
+    /// The rules the player already knows, for the Changes mode. `None` means the standard
+    /// game.
+    #[serde(default)]
+    pub baseline: Option<Rules>,
+    #[serde(default)]
+    pub study_sets: Vec<StudySet>,
+    /// Name of the study set the Custom mode deals from.
+    #[serde(default)]
+    pub study_set: Option<String>,
+    /// The latest lesson the Curriculum mode has unlocked.
+    #[serde(default)]
+    pub lesson: usize,
+    /// Keys answered right but slowly once, which a second slow answer moves up.
+    #[serde(default)]
+    pub half_credit: Vec<String>,
 }
 
 fn state_path() -> PathBuf {
@@ -27,6 +58,83 @@ pub fn load_state() -> SavedState {
     }
 }
 
+fn exams_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".bjsc").join("exams.toml")
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedExams {
+    #[serde(default)]
+    exams: Vec<ExamReport>,
+}
+
+/// Every exam taken on this machine, oldest first.
+pub fn load_exam_reports() -> Vec<ExamReport> {
+    let content = fs::read_to_string(exams_path()).unwrap_or_default();
+    toml::from_str::<SavedExams>(&content)
+        .unwrap_or_default()
+        .exams
+}
+
+/// Add an exam to the saved ones.
+pub fn save_exam_report(report: &ExamReport) {
+    let path = exams_path();
+    let mut saved = SavedExams {
+        exams: load_exam_reports(),
+    };
+    saved.exams.push(report.clone());
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(content) = toml::to_string_pretty(&saved) {
+        let _ = fs::write(&path, content);
+    }
+}
+
+fn blitz_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".bjsc").join("blitz.toml")
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedBlitz {
+    #[serde(default)]
+    bests: Vec<BlitzScore>,
+}
+
+/// The best Blitz score on this machine for each rule set, strategy and length.
+pub fn load_blitz_bests() -> Vec<BlitzScore> {
+    let content = fs::read_to_string(blitz_path()).unwrap_or_default();
+    toml::from_str::<SavedBlitz>(&content)
+        .unwrap_or_default()
+        .bests
+}
+
+/// Keep a Blitz score if it's a personal best. Returns whether it was.
+pub fn save_blitz_score(score: &BlitzScore) -> bool {
+    let mut saved = SavedBlitz {
+        bests: load_blitz_bests(),
+    };
+    if !record_best(&mut saved.bests, score) {
+        return false;
+    }
+    let path = blitz_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(content) = toml::to_string_pretty(&saved) {
+        let _ = fs::write(&path, content);
+    }
+    true
+}
+
+/// Read and validate a custom chart file.
+pub fn load_chart(path: &str) -> Result<ChartSet, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    ChartSet::from_text(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
 pub fn save_state(state: &SavedState) {
     let path = state_path();
     if let Some(parent) = path.parent() {
@@ -0,0 +1,165 @@
+use crate::rules::{BlackjackPayout, GameVariant, HoleCard, Rules, Soft17};
+
+/// A named set of table rules for a common game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preset {
+    /// Stable identifier, stored in saved state and in the cloud.
+    pub key: &'static str,
+    pub name: &'static str,
+    pub rules: Rules,
+}
+
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        key: "standard",
+        name: "Standard (BJA)",
+        rules: Rules::standard(),
+    },
+    Preset {
+        key: "vegas-strip",
+        name: "Vegas Strip",
+        rules: Rules {
+            soft_17: Soft17::Stands,
+            surrender: true,
+            ..Rules::standard()
+        },
+    },
+    Preset {
+        key: "downtown",
+        name: "Downtown Vegas",
+        rules: Rules {
+            decks: 2,
+            penetration: 65,
+            das: false,
+            ..Rules::standard()
+        },
+    },
+    Preset {
+        key: "atlantic-city",
+        name: "Atlantic City",
+        rules: Rules {
+            decks: 8,
+            soft_17: Soft17::Stands,
+            surrender: true,
+            ..Rules::standard()
+        },
+    },
+    Preset {
+        key: "european",
+        name: "European (ENHC)",
+        rules: Rules {
+            hole_card: HoleCard::Enhc,
+            soft_17: Soft17::Stands,
+            ..Rules::standard()
+        },
+    },
+    Preset {
+        key: "single-deck-6-5",
+        name: "Single Deck 6:5",
+        rules: Rules {
+            decks: 1,
+            penetration: 60,
+            das: false,
+            payout: BlackjackPayout::SixToFive,
+            ..Rules::standard()
+        },
+    },
+    Preset {
+        key: "spanish21",
+        name: "Spanish 21",
+        rules: Rules {
+            variant: GameVariant::Spanish21,
+            soft_17: Soft17::Stands,
+            ..Rules::standard()
+        },
+    },
+    Preset {
+        key: "free-bet",
+        name: "Free Bet",
+        rules: Rules {
+            variant: GameVariant::FreeBet,
+            ..Rules::standard()
+        },
+    },
+];
+
+pub fn preset_by_key(key: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|p| p.key == key)
+}
+
+/// The preset whose rules match exactly, if any.
+pub fn preset_for_rules(rules: &Rules) -> Option<&'static Preset> {
+    PRESETS.iter().find(|p| p.rules == *rules)
+}
+
+/// The preset after the one matching these rules. Custom rules go back to the first preset.
+pub fn next_preset(rules: &Rules) -> &'static Preset {
+    let next = PRESETS
+        .iter()
+        .position(|p| p.rules == *rules)
+        .map_or(0, |i| (i + 1) % PRESETS.len());
+    &PRESETS[next]
+}
+
+/// The rules to play given a stored preset key and stored rules. A known preset wins, so
+/// fixes to a preset reach everyone using it; otherwise the stored rules are used as-is.
+pub fn resolve_rules(preset: Option<&str>, rules: Rules) -> Rules {
+    preset.and_then(preset_by_key).map_or(rules, |p| p.rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_unique() {
+        for (i, p) in PRESETS.iter().enumerate() {
+            assert!(PRESETS[i + 1..].iter().all(|q| q.key != p.key), "{}", p.key);
+            assert!(
+                PRESETS[i + 1..].iter().all(|q| q.rules != p.rules),
+                "{}",
+                p.key
+            );
+        }
+    }
+
+    #[test]
+    fn default_rules_are_the_standard_preset() {
+        assert_eq!(
+            Some("standard"),
+            preset_for_rules(&Rules::default()).map(|p| p.key)
+        );
+    }
+
+    #[test]
+    fn lookup_by_key() {
+        let p = preset_by_key("atlantic-city").unwrap();
+        assert_eq!(8, p.rules.decks);
+        assert!(p.rules.late_surrender());
+        assert!(preset_by_key("nope").is_none());
+    }
+
+    #[test]
+    fn next_preset_cycles() {
+        assert_eq!("vegas-strip", next_preset(&Rules::default()).key);
+        let last = PRESETS.last().unwrap();
+        assert_eq!("standard", next_preset(&last.rules).key);
+        let custom = Rules {
+            decks: 3,
+            ..Default::default()
+        };
+        assert_eq!("standard", next_preset(&custom).key);
+    }
+
+    #[test]
+    fn resolve_prefers_known_preset() {
+        let custom = Rules {
+            decks: 3,
+            ..Default::default()
+        };
+        let rules = resolve_rules(Some("downtown"), custom);
+        assert_eq!(2, rules.decks);
+        assert_eq!(custom, resolve_rules(Some("unknown"), custom));
+        assert_eq!(custom, resolve_rules(None, custom));
+    }
+}
@@ -1,4 +1,6 @@
+use crate::strat::TableType;
 use crate::supabase::AnswerLogEntry;
+use crate::table_index_keys::short_key;
 use std::collections::HashMap;
 
 /// Computed progress stats from answer log entries.
@@ -20,8 +22,20 @@ pub struct ProgressStats {
     /// Top trouble spots: (table_index, times_wrong, times_seen)
     pub trouble_spots: Vec<(String, u32, u32)>,
 
+    /// Table indices answered wrong, most recent first, each once.
+    pub recent_misses: Vec<String>,
+
+    /// Median milliseconds from deal to answer for each table index with timed answers.
+    pub median_latency_ms: HashMap<String, u32>,
+
+    /// The slowest table indices by median latency: (table_index, median_ms), slowest first.
+    pub slow_spots: Vec<(String, u32)>,
+
     /// Recent sessions: (date_string, total, correct)
     pub sessions: Vec<(String, u32, u32)>,
+
+    /// The last day practised and how many days in a row, ending on it, had answers.
+    pub practice_streak: Option<(String, u32)>,
 }
 
 impl ProgressStats {
@@ -48,6 +62,12 @@ impl ProgressStats {
         // Per-day tracking: (total, correct)
         let mut per_day: HashMap<String, (u32, u32)> = HashMap::new();
 
+        // Misses: (created_at, table_index)
+        let mut misses: Vec<(&str, String)> = Vec::new();
+
+        // Per-index answer times
+        let mut latencies: HashMap<String, Vec<u32>> = HashMap::new();
+
         for log in logs {
             total += 1;
             if log.correct {
@@ -55,21 +75,20 @@ impl ProgressStats {
             }
 
             // Category breakdown
-            let category = log.table_index.split(':').next().unwrap_or("");
-            match category {
-                "hard" => {
+            match log.cell().map(|ti| ti.table_type()) {
+                Some(TableType::Hard) => {
                     hard_total += 1;
                     if log.correct {
                         hard_correct += 1;
                     }
                 }
-                "soft" => {
+                Some(TableType::Soft) => {
                     soft_total += 1;
                     if log.correct {
                         soft_correct += 1;
                     }
                 }
-                "split" => {
+                Some(TableType::Split) => {
                     split_total += 1;
                     if log.correct {
                         split_correct += 1;
@@ -86,11 +105,18 @@ impl ProgressStats {
                 }
             }
 
-            // Per-index
-            let entry = per_index.entry(log.table_index.clone()).or_default();
+            // Per-index, with logs from before and after the key change together
+            let entry = per_index.entry(short_key(&log.table_index)).or_default();
             entry.1 += 1;
             if !log.correct {
                 entry.0 += 1;
+                misses.push((&log.created_at, short_key(&log.table_index)));
+            }
+            if let Some(ms) = log.latency_ms {
+                latencies
+                    .entry(short_key(&log.table_index))
+                    .or_default()
+                    .push(ms);
             }
 
             // Per-day (extract date from created_at)
@@ -118,12 +144,51 @@ impl ProgressStats {
         });
         trouble.truncate(10);
 
+        // Recent misses: newest first, each index at its latest miss
+        misses.sort_by(|a, b| b.0.cmp(a.0));
+        let mut recent_misses: Vec<String> = Vec::new();
+        for (_, idx) in misses {
+            if !recent_misses.contains(&idx) {
+                recent_misses.push(idx);
+            }
+        }
+        recent_misses.truncate(20);
+
+        let median_latency_ms: HashMap<String, u32> = latencies
+            .into_iter()
+            .map(|(idx, mut times)| {
+                times.sort_unstable();
+                let mid = times.len() / 2;
+                let median = if times.len() % 2 == 0 {
+                    (times[mid - 1] + times[mid]) / 2
+                } else {
+                    times[mid]
+                };
+                (idx, median)
+            })
+            .collect();
+
+        // Slow spots: slowest median first, ties by index, top 10
+        let mut slow_spots: Vec<(String, u32)> = median_latency_ms
+            .iter()
+            .map(|(idx, ms)| (idx.clone(), *ms))
+            .collect();
+        slow_spots.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        slow_spots.truncate(10);
+
         // Sessions: sort by date descending, take last 14 days
         let mut sessions: Vec<(String, u32, u32)> = per_day
             .into_iter()
             .map(|(day, (t, c))| (day, t, c))
             .collect();
         sessions.sort_by(|a, b| b.0.cmp(&a.0));
+        let mut days = sessions
+            .iter()
+            .filter_map(|(day, _, _)| Some((day, day_number(day)?)));
+        let practice_streak = days.next().map(|(last, n)| {
+            let before = days.zip(1..).take_while(|((_, day), i)| *day == n - i);
+            (last.clone(), before.count() as u32 + 1)
+        });
         sessions.truncate(14);
 
         let accuracy_pct = if total > 0 {
@@ -145,7 +210,11 @@ impl ProgressStats {
             double_total,
             double_correct,
             trouble_spots: trouble,
+            recent_misses,
+            median_latency_ms,
+            slow_spots,
             sessions,
+            practice_streak,
         }
     }
 
@@ -162,6 +231,22 @@ impl ProgressStats {
     }
 }
 
+/// Days since 1970-01-01 for a "YYYY-MM-DD" date, or None if it isn't one.
+pub(crate) fn day_number(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (y, m, d) = (parts.next()??, parts.next()??, parts.next()??);
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    // Count years from March, so the leap day ends the year
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,6 +265,7 @@ mod tests {
             player_action: player_action.to_string(),
             correct_action: correct_action.to_string(),
             created_at: created_at.to_string(),
+            latency_ms: None,
         }
     }
 
@@ -302,6 +388,52 @@ mod tests {
         assert_eq!(0, stats.soft_total);
     }
 
+    #[test]
+    fn from_logs_namespaced_key_counted_in_category() {
+        let logs = vec![make_entry(
+            "s21/split:8,5",
+            true,
+            "Split",
+            "Split",
+            "2024-01-01T10:00:00Z",
+        )];
+        let stats = ProgressStats::from_logs(&logs);
+        assert_eq!(1, stats.split_total);
+        assert_eq!(1, stats.split_correct);
+    }
+
+    #[test]
+    fn from_logs_trouble_spots_join_old_and_versioned_keys() {
+        let logs = vec![
+            make_entry("hard:16,10", false, "Stand", "Hit", "2024-01-01T10:00:00Z"),
+            make_entry(
+                "v2/bj-h17-das-nols-peek/hard:16,10",
+                false,
+                "Stand",
+                "Hit",
+                "2024-01-02T10:00:00Z",
+            ),
+        ];
+        let stats = ProgressStats::from_logs(&logs);
+        assert_eq!(2, stats.hard_total);
+        assert_eq!(vec![("hard:16,10".to_string(), 2, 2)], stats.trouble_spots);
+    }
+
+    #[test]
+    fn from_logs_exception_keys_are_in_no_table() {
+        let logs = vec![make_entry(
+            "v2/bj-h17-das-nols-peek/comp:12-T2-4",
+            true,
+            "Hit",
+            "Hit",
+            "2024-01-01T10:00:00Z",
+        )];
+        let stats = ProgressStats::from_logs(&logs);
+        assert_eq!(1, stats.total_answers);
+        assert_eq!(0, stats.hard_total + stats.soft_total + stats.split_total);
+        assert_eq!(None, logs[0].cell());
+    }
+
     #[test]
     fn from_logs_unknown_category_prefix_ignored_in_buckets() {
         // A table_index that doesn't start with hard/soft/split
@@ -420,6 +552,55 @@ mod tests {
         assert!(stats.trouble_spots.len() <= 10);
     }
 
+    #[test]
+    fn from_logs_recent_misses_newest_first_once_each() {
+        let logs = vec![
+            make_entry("hard:16,10", false, "Stand", "Hit", "2024-01-01T10:00:00Z"),
+            make_entry("soft:18,9", false, "Stand", "Hit", "2024-01-01T10:02:00Z"),
+            make_entry("hard:12,5", true, "Stand", "Stand", "2024-01-01T10:03:00Z"),
+            make_entry("hard:16,10", false, "Stand", "Hit", "2024-01-01T10:04:00Z"),
+        ];
+        let stats = ProgressStats::from_logs(&logs);
+        assert_eq!(vec!["hard:16,10", "soft:18,9"], stats.recent_misses);
+    }
+
+    // --- from_logs(): latency ---
+
+    #[test]
+    fn from_logs_median_latency_per_cell() {
+        let timed = |idx: &str, ms: Option<u32>| AnswerLogEntry {
+            latency_ms: ms,
+            ..make_entry(idx, true, "Hit", "Hit", "2024-01-01T10:00:00Z")
+        };
+        let logs = vec![
+            timed("hard:16,10", Some(900)),
+            timed("v2/bj-h17-das-nols-peek/hard:16,10", Some(5000)),
+            timed("hard:16,10", Some(1200)),
+            timed("hard:12,2", Some(3000)),
+            timed("hard:12,2", Some(2000)),
+            timed("soft:18,9", None),
+        ];
+        let stats = ProgressStats::from_logs(&logs);
+        assert_eq!(Some(&1200), stats.median_latency_ms.get("hard:16,10"));
+        assert_eq!(Some(&2500), stats.median_latency_ms.get("hard:12,2"));
+        assert_eq!(None, stats.median_latency_ms.get("soft:18,9"));
+        assert_eq!(
+            vec![
+                ("hard:12,2".to_string(), 2500),
+                ("hard:16,10".to_string(), 1200)
+            ],
+            stats.slow_spots
+        );
+    }
+
+    #[test]
+    fn log_entries_without_latency_still_parse() {
+        let json = r#"[{"table_index":"hard:16,10","correct":true,"player_action":"Hit",
+            "correct_action":"Hit","created_at":"2024-01-01T10:00:00Z"}]"#;
+        let logs: Vec<AnswerLogEntry> = serde_json::from_str(json).unwrap();
+        assert_eq!(None, logs[0].latency_ms);
+    }
+
     // --- from_logs(): session grouping by date ---
 
     #[test]
@@ -433,6 +614,30 @@ mod tests {
         assert_eq!(2, stats.sessions.len());
     }
 
+    #[test]
+    fn day_numbers_count_from_the_epoch() {
+        assert_eq!(Some(0), day_number("1970-01-01"));
+        assert_eq!(Some(59), day_number("1970-03-01"));
+        assert_eq!(Some(19_782), day_number("2024-02-29"));
+        assert_eq!(Some(19_783), day_number("2024-03-01"));
+        assert_eq!(None, day_number("2024-13-01"));
+        assert_eq!(None, day_number("unknown"));
+    }
+
+    #[test]
+    fn from_logs_practice_streak_counts_days_in_a_row() {
+        let logs = vec![
+            make_entry("hard:12,5", true, "Stand", "Stand", "2024-02-26T10:00:00Z"),
+            make_entry("hard:12,5", true, "Stand", "Stand", "2024-02-28T10:00:00Z"),
+            make_entry("hard:14,7", true, "Stand", "Stand", "2024-02-29T10:00:00Z"),
+            make_entry("hard:13,6", true, "Stand", "Stand", "2024-03-01T09:00:00Z"),
+            make_entry("hard:13,6", false, "Hit", "Stand", "2024-03-01T10:00:00Z"),
+        ];
+        let stats = ProgressStats::from_logs(&logs);
+        assert_eq!(Some(("2024-03-01".to_string(), 3)), stats.practice_streak);
+        assert_eq!(None, ProgressStats::default().practice_streak);
+    }
+
     #[test]
     fn from_logs_sessions_sorted_descending_by_date() {
         let logs = vec![
@@ -0,0 +1,385 @@
+use crate::card::Card;
+use crate::hand::Hand;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// How the dealer handles the second card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HoleCard {
+    /// American rules: the dealer takes a hole card and peeks for blackjack under an Ace
+    /// or ten-valued upcard, so a hand that reaches the player has no dealer blackjack.
+    #[default]
+    Peek,
+    /// European No Hole Card: the dealer's second card is drawn after the player acts,
+    /// so doubles and splits are lost in full to a dealer blackjack.
+    Enhc,
+}
+
+impl HoleCard {
+    pub fn next(self) -> Self {
+        match self {
+            HoleCard::Peek => HoleCard::Enhc,
+            HoleCard::Enhc => HoleCard::Peek,
+        }
+    }
+}
+
+impl Display for HoleCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            HoleCard::Peek => "Hole card (peek)",
+            HoleCard::Enhc => "No hole card (ENHC)",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The game being played. Each variant has its own charts and its own spaced-rep keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameVariant {
+    #[default]
+    Standard,
+    /// 48-card decks (no tens), late surrender and double-down rescue.
+    Spanish21,
+    /// Free doubles on hard 9-11 and free splits (except tens); a dealer 22 pushes.
+    FreeBet,
+}
+
+impl GameVariant {
+    pub fn next(self) -> Self {
+        match self {
+            GameVariant::Standard => GameVariant::Spanish21,
+            GameVariant::Spanish21 => GameVariant::FreeBet,
+            GameVariant::FreeBet => GameVariant::Standard,
+        }
+    }
+
+    /// Short name in spaced-rep keys, so progress in one variant doesn't count for another.
+    pub fn key_namespace(self) -> &'static str {
+        match self {
+            GameVariant::Standard => "bj",
+            GameVariant::Spanish21 => "s21",
+            GameVariant::FreeBet => "fb",
+        }
+    }
+}
+
+impl Display for GameVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            GameVariant::Standard => "Blackjack",
+            GameVariant::Spanish21 => "Spanish 21",
+            GameVariant::FreeBet => "Free Bet",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// What the dealer does with a soft 17.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Soft17 {
+    Stands,
+    #[default]
+    Hits,
+}
+
+impl Display for Soft17 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Soft17::Stands => "S17",
+            Soft17::Hits => "H17",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// What a player blackjack pays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BlackjackPayout {
+    #[default]
+    ThreeToTwo,
+    SixToFive,
+}
+
+impl BlackjackPayout {
+    /// Winnings per unit bet.
+    pub fn multiplier(self) -> f64 {
+        match self {
+            BlackjackPayout::ThreeToTwo => 1.5,
+            BlackjackPayout::SixToFive => 1.2,
+        }
+    }
+}
+
+impl Display for BlackjackPayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            BlackjackPayout::ThreeToTwo => "3:2",
+            BlackjackPayout::SixToFive => "6:5",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The table rules in effect. These select which strategy cells apply.
+///
+/// The defaults are the game the BJA charts were written for: six decks, dealer hits soft 17,
+/// double after split, no surrender, blackjack pays 3:2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    pub variant: GameVariant,
+    pub hole_card: HoleCard,
+    pub decks: u8,
+    /// How much of the shoe is dealt before the shuffle, in percent.
+    pub penetration: u8,
+    pub soft_17: Soft17,
+    /// Double after split.
+    pub das: bool,
+    /// Late surrender (after the dealer peeks).
+    pub surrender: bool,
+    /// Aces may be split again.
+    pub resplit_aces: bool,
+    pub payout: BlackjackPayout,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::standard()
+    }
+}
+
+impl Rules {
+    /// The default rules, usable in constants.
+    pub const fn standard() -> Self {
+        Rules {
+            variant: GameVariant::Standard,
+            hole_card: HoleCard::Peek,
+            decks: 6,
+            penetration: 75,
+            soft_17: Soft17::Hits,
+            das: true,
+            surrender: false,
+            resplit_aces: false,
+            payout: BlackjackPayout::ThreeToTwo,
+        }
+    }
+
+    /// Whether the player may surrender the first two cards after the dealer peeks.
+    /// Spanish 21 always offers it. Otherwise it needs the peek: with no hole card there is
+    /// nothing to surrender after.
+    pub fn late_surrender(&self) -> bool {
+        self.variant == GameVariant::Spanish21
+            || (self.surrender && self.hole_card == HoleCard::Peek)
+    }
+
+    /// A short description, e.g. "6D H17 DAS 3:2".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.variant != GameVariant::Standard {
+            parts.push(self.variant.to_string());
+        }
+        parts.push(format!("{}D", self.decks));
+        parts.push(self.soft_17.to_string());
+        if self.das {
+            parts.push("DAS".to_string());
+        }
+        if self.surrender && self.hole_card == HoleCard::Peek {
+            parts.push("LS".to_string());
+        }
+        if self.resplit_aces {
+            parts.push("RSA".to_string());
+        }
+        if self.hole_card == HoleCard::Enhc {
+            parts.push("ENHC".to_string());
+        }
+        parts.push(self.payout.to_string());
+        parts.join(" ")
+    }
+
+    /// Whether the player may double on any number of cards, not only the first two.
+    pub fn double_any_cards(&self) -> bool {
+        self.variant == GameVariant::Spanish21
+    }
+
+    /// Whether the player may take back a double by surrendering the original bet.
+    pub fn double_down_rescue(&self) -> bool {
+        self.variant == GameVariant::Spanish21
+    }
+
+    /// Whether a dealer total of exactly 22 pushes every hand that hasn't busted.
+    pub fn dealer_22_pushes(&self) -> bool {
+        self.variant == GameVariant::FreeBet
+    }
+
+    /// Whether the house puts up the double on this hand: any hard 9, 10 or 11 on two cards.
+    pub fn free_double(&self, hand: &Hand) -> bool {
+        self.variant == GameVariant::FreeBet
+            && hand.num_cards() == 2
+            && !hand.is_soft()
+            && (9..=11).contains(&hand.total())
+    }
+
+    /// Whether the house puts up the bet for splitting this pair: any pair except tens.
+    pub fn free_split(&self, hand: &Hand) -> bool {
+        self.variant == GameVariant::FreeBet
+            && hand.splittable()
+            && hand.first_card().is_some_and(|c| c.value() != 10)
+    }
+
+    /// Whether the dealer is dealt a face-down second card before the player acts.
+    pub fn dealer_takes_hole_card(&self) -> bool {
+        self.hole_card == HoleCard::Peek
+    }
+
+    /// Whether the dealer checks for blackjack before the player acts on this upcard.
+    pub fn dealer_peeks(&self, upcard: Card) -> bool {
+        self.hole_card == HoleCard::Peek && upcard.value() >= 10
+    }
+
+    /// Whether a dealer blackjack takes doubled and split bets as well as the original bet.
+    pub fn loses_extra_bets_to_blackjack(&self) -> bool {
+        self.hole_card == HoleCard::Enhc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(s: &str) -> Card {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn default_rules_peek() {
+        assert_eq!(HoleCard::Peek, Rules::default().hole_card);
+    }
+
+    #[test]
+    fn hole_card_next_toggles() {
+        assert_eq!(HoleCard::Enhc, HoleCard::Peek.next());
+        assert_eq!(HoleCard::Peek, HoleCard::Enhc.next());
+    }
+
+    #[test]
+    fn dealer_peeks_under_ace_and_tens() {
+        let rules = Rules::default();
+        assert!(rules.dealer_peeks(card("AS")));
+        assert!(rules.dealer_peeks(card("TS")));
+        assert!(rules.dealer_peeks(card("KH")));
+        assert!(!rules.dealer_peeks(card("9S")));
+        assert!(!rules.dealer_peeks(card("2S")));
+    }
+
+    #[test]
+    fn enhc_dealer_never_peeks() {
+        let rules = Rules {
+            hole_card: HoleCard::Enhc,
+            ..Default::default()
+        };
+        assert!(!rules.dealer_peeks(card("AS")));
+        assert!(!rules.dealer_peeks(card("TS")));
+        assert!(!rules.dealer_takes_hole_card());
+        assert!(rules.loses_extra_bets_to_blackjack());
+    }
+
+    #[test]
+    fn enhc_has_no_late_surrender() {
+        let rules = Rules {
+            surrender: true,
+            hole_card: HoleCard::Enhc,
+            ..Default::default()
+        };
+        assert!(!rules.late_surrender());
+        assert_eq!("6D H17 DAS ENHC 3:2", rules.summary());
+        let peek = Rules {
+            surrender: true,
+            ..Default::default()
+        };
+        assert!(peek.late_surrender());
+        assert_eq!("6D H17 DAS LS 3:2", peek.summary());
+    }
+
+    #[test]
+    fn spanish21_allows_surrender_and_rescue() {
+        let rules = Rules {
+            variant: GameVariant::Spanish21,
+            ..Default::default()
+        };
+        assert!(rules.late_surrender());
+        assert!(rules.double_down_rescue());
+        assert!(!Rules::default().late_surrender());
+        assert_eq!("s21", rules.variant.key_namespace());
+        assert_eq!("bj", GameVariant::Standard.key_namespace());
+    }
+
+    #[test]
+    fn free_bet_doubles_and_splits() {
+        let rules = Rules {
+            variant: GameVariant::FreeBet,
+            ..Default::default()
+        };
+        let hand = |s: &str| -> Hand { s.parse().unwrap() };
+        assert!(rules.dealer_22_pushes());
+        assert_eq!("fb", rules.variant.key_namespace());
+        assert!(rules.free_double(&hand("5H 6C")));
+        assert!(rules.free_double(&hand("5H 4C")));
+        assert!(!rules.free_double(&hand("6H 6C")));
+        assert!(!rules.free_double(&hand("AH 9C"))); // soft 20
+        assert!(!rules.free_double(&hand("2H 3C 5D"))); // three cards
+        assert!(rules.free_split(&hand("8H 8C")));
+        assert!(rules.free_split(&hand("AH AC")));
+        assert!(!rules.free_split(&hand("KH QC")));
+        assert!(!Rules::default().free_split(&hand("8H 8C")));
+    }
+
+    #[test]
+    fn rules_roundtrip_through_toml() {
+        let rules = Rules {
+            variant: GameVariant::Spanish21,
+            hole_card: HoleCard::Enhc,
+            surrender: true,
+            ..Default::default()
+        };
+        let s = toml::to_string(&rules).unwrap();
+        let back: Rules = toml::from_str(&s).unwrap();
+        assert_eq!(rules, back);
+    }
+
+    #[test]
+    fn default_rules_summary() {
+        assert_eq!("6D H17 DAS 3:2", Rules::default().summary());
+        let rules = Rules {
+            variant: GameVariant::Spanish21,
+            decks: 8,
+            soft_17: Soft17::Stands,
+            das: false,
+            surrender: true,
+            payout: BlackjackPayout::SixToFive,
+            ..Default::default()
+        };
+        assert_eq!("Spanish 21 8D S17 LS 6:5", rules.summary());
+    }
+
+    #[test]
+    fn payout_multiplier() {
+        assert_eq!(1.5, BlackjackPayout::ThreeToTwo.multiplier());
+        assert_eq!(1.2, BlackjackPayout::SixToFive.multiplier());
+    }
+
+    #[test]
+    fn rules_partial_toml_fills_in_defaults() {
+        let rules: Rules = toml::from_str("decks = 2\nsoft_17 = \"Stands\"").unwrap();
+        assert_eq!(2, rules.decks);
+        assert_eq!(Soft17::Stands, rules.soft_17);
+        assert!(rules.das);
+        assert_eq!(75, rules.penetration);
+    }
+
+    #[test]
+    fn rules_missing_fields_use_defaults() {
+        let rules: Rules = toml::from_str("").unwrap();
+        assert_eq!(Rules::default(), rules);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::hand::Hand;
+use crate::rules::{BlackjackPayout, Rules};
+
+/// How a finished player hand fared against the dealer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Blackjack,
+    Win,
+    Push,
+    Lose,
+    Surrender,
+}
+
+/// What is riding on one player hand, in units of the original bet.
+///
+/// `free` is the part the house put up (Free Bet doubles and splits). It is paid out when the
+/// hand wins but is never lost.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Stake {
+    pub own: f64,
+    pub free: f64,
+}
+
+impl Stake {
+    /// A plain bet with nothing free.
+    pub fn new(own: f64) -> Self {
+        Stake { own, free: 0.0 }
+    }
+}
+
+impl Outcome {
+    /// The player's net win (negative for a loss) for a hand with the given stake.
+    pub fn net(self, stake: Stake, payout: BlackjackPayout) -> f64 {
+        match self {
+            Outcome::Blackjack => payout.multiplier() * stake.own,
+            Outcome::Win => stake.own + stake.free,
+            Outcome::Push => 0.0,
+            Outcome::Lose => -stake.own,
+            Outcome::Surrender => -stake.own / 2.0,
+        }
+    }
+}
+
+/// Settle a finished player hand against the dealer's finished hand (hole card revealed).
+///
+/// A two-card 21 counts as a blackjack for either side, so pass split hands through only
+/// after they have drawn. Under Free Bet rules a dealer 22 pushes every hand that is still
+/// live; blackjacks are settled before the dealer draws, so they still win.
+pub fn settle(player: &Hand, dealer: &Hand, rules: &Rules) -> Outcome {
+    match (player.is_natural(), dealer.is_natural()) {
+        (true, true) => return Outcome::Push,
+        (true, false) => return Outcome::Blackjack,
+        (false, true) => return Outcome::Lose,
+        (false, false) => {}
+    }
+
+    let player_total = player.total();
+    let dealer_total = dealer.total();
+    if player_total > 21 {
+        Outcome::Lose
+    } else if dealer_total == 22 && rules.dealer_22_pushes() {
+        Outcome::Push
+    } else if dealer_total > 21 || player_total > dealer_total {
+        Outcome::Win
+    } else if player_total == dealer_total {
+        Outcome::Push
+    } else {
+        Outcome::Lose
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::GameVariant;
+
+    fn hand(s: &str) -> Hand {
+        s.parse().unwrap()
+    }
+
+    fn free_bet() -> Rules {
+        Rules {
+            variant: GameVariant::FreeBet,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn higher_total_wins() {
+        let rules = Rules::default();
+        assert_eq!(Outcome::Win, settle(&hand("TH 9C"), &hand("TS 8D"), &rules));
+        assert_eq!(
+            Outcome::Lose,
+            settle(&hand("TH 7C"), &hand("TS 8D"), &rules)
+        );
+        assert_eq!(
+            Outcome::Push,
+            settle(&hand("TH 8C"), &hand("TS 8D"), &rules)
+        );
+    }
+
+    #[test]
+    fn player_bust_loses_even_if_dealer_busts() {
+        let rules = Rules::default();
+        let outcome = settle(&hand("TH 6C 9D"), &hand("TS 6D 8C"), &rules);
+        assert_eq!(Outcome::Lose, outcome);
+    }
+
+    #[test]
+    fn blackjacks() {
+        let rules = Rules::default();
+        assert_eq!(
+            Outcome::Blackjack,
+            settle(&hand("AH KC"), &hand("TS 9D"), &rules)
+        );
+        assert_eq!(
+            Outcome::Push,
+            settle(&hand("AH KC"), &hand("AS TD"), &rules)
+        );
+        assert_eq!(
+            Outcome::Lose,
+            settle(&hand("TH 9C 2D"), &hand("AS TD"), &rules)
+        );
+    }
+
+    #[test]
+    fn dealer_22_busts_in_standard_blackjack() {
+        let outcome = settle(&hand("TH 8C"), &hand("TS 6D 6C"), &Rules::default());
+        assert_eq!(Outcome::Win, outcome);
+    }
+
+    #[test]
+    fn dealer_22_pushes_in_free_bet() {
+        let rules = free_bet();
+        assert_eq!(
+            Outcome::Push,
+            settle(&hand("TH 8C"), &hand("TS 6D 6C"), &rules)
+        );
+        // Other dealer busts still pay.
+        assert_eq!(
+            Outcome::Win,
+            settle(&hand("TH 8C"), &hand("TS 6D 7C"), &rules)
+        );
+        // A player blackjack is paid before the dealer draws.
+        let outcome = settle(&hand("AH KC"), &hand("TS 6D 6C"), &rules);
+        assert_eq!(Outcome::Blackjack, outcome);
+    }
+
+    #[test]
+    fn net_pays_free_bet_on_win_only() {
+        let doubled = Stake {
+            own: 1.0,
+            free: 1.0,
+        };
+        let payout = BlackjackPayout::default();
+        assert_eq!(2.0, Outcome::Win.net(doubled, payout));
+        assert_eq!(-1.0, Outcome::Lose.net(doubled, payout));
+        assert_eq!(0.0, Outcome::Push.net(doubled, payout));
+    }
+
+    #[test]
+    fn net_for_blackjack_and_surrender() {
+        let payout = BlackjackPayout::ThreeToTwo;
+        assert_eq!(1.5, Outcome::Blackjack.net(Stake::new(1.0), payout));
+        assert_eq!(-0.5, Outcome::Surrender.net(Stake::new(1.0), payout));
+        assert_eq!(-2.0, Outcome::Lose.net(Stake::new(2.0), payout));
+        let six_five = BlackjackPayout::SixToFive;
+        assert_eq!(1.2, Outcome::Blackjack.net(Stake::new(1.0), six_five));
+    }
+}
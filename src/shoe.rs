@@ -1,6 +1,6 @@
 use rand::prelude::*;
 
-use crate::card::Card;
+use crate::card::{Card, Pip};
 
 const CARDS_IN_A_DECK: usize = 52;
 const PEN: usize = 26;
@@ -14,22 +14,42 @@ pub struct Shoe {
 
 impl Shoe {
     pub fn new(num_decks: usize) -> Shoe {
+        Shoe::from_decks(num_decks, |_| true)
+    }
+
+    /// A Spanish 21 shoe: 48-card decks with the pip tens removed (J, Q and K stay in).
+    pub fn spanish(num_decks: usize) -> Shoe {
+        Shoe::from_decks(num_decks, |card| card.pip != Pip::Ten)
+    }
+
+    fn from_decks(num_decks: usize, keep: impl Fn(&Card) -> bool) -> Shoe {
         let mut cards = Vec::with_capacity(CARDS_IN_A_DECK * num_decks);
 
         for _ in 0..num_decks {
             for c in 0..CARDS_IN_A_DECK {
                 // unwrap: we know the indices are in range.
-                cards.push((c as u8).try_into().unwrap());
+                let card: Card = (c as u8).try_into().unwrap();
+                if keep(&card) {
+                    cards.push(card);
+                }
             }
         }
 
+        let pen = cards.len() - PEN;
         Shoe {
             cards,
             next: 0,
-            pen: num_decks * CARDS_IN_A_DECK - PEN,
+            pen,
         }
     }
 
+    /// Move the cut card so that this percentage of the shoe is dealt before a reshuffle.
+    pub fn with_penetration(mut self, percent: u8) -> Shoe {
+        let percent = percent.clamp(1, 100) as usize;
+        self.pen = self.cards.len() * percent / 100;
+        self
+    }
+
     pub fn shuffle(&mut self) {
         self.cards.shuffle(&mut thread_rng());
         self.next = 0;
@@ -39,6 +59,21 @@ impl Shoe {
         self.next >= self.pen
     }
 
+    /// The Hi-Lo true count of the cards dealt so far: the running count (+1 for 2-6, -1 for
+    /// tens and Aces) per deck left in the shoe, rounded toward zero.
+    pub fn true_count(&self) -> i32 {
+        let running: i32 = self.cards[..self.next]
+            .iter()
+            .map(|c| match c.value() {
+                2..=6 => 1,
+                10 | 11 => -1,
+                _ => 0,
+            })
+            .sum();
+        let decks_left = (self.cards.len() - self.next) as f64 / CARDS_IN_A_DECK as f64;
+        (running as f64 / decks_left.max(0.5)) as i32
+    }
+
     pub fn deal(&mut self) -> Option<Card> {
         if self.next >= self.cards.len() {
             return None;
@@ -75,6 +110,15 @@ mod tests {
         assert_eq!(312, shoe.cards.len());
     }
 
+    #[test]
+    fn spanish_six_deck_shoe_has_288_cards_and_no_tens() {
+        let shoe = Shoe::spanish(6);
+        assert_eq!(288, shoe.cards.len());
+        assert!(shoe.cards.iter().all(|c| c.pip != Pip::Ten));
+        assert_eq!(72, shoe.cards.iter().filter(|c| c.value() == 10).count());
+        assert_eq!(288 - PEN, shoe.pen);
+    }
+
     #[test]
     fn new_two_deck_shoe_has_104_cards() {
         let shoe = Shoe::new(2);
@@ -202,6 +246,31 @@ mod tests {
         assert_eq!(26, shoe.pen);
     }
 
+    #[test]
+    fn true_count_per_deck_left() {
+        let mut shoe = Shoe::new(2);
+        assert_eq!(0, shoe.true_count());
+        // Unshuffled, the shoe starts A, 2, 3, 4, 5, 6: a running count of +4.
+        for _ in 0..6 {
+            shoe.deal();
+        }
+        // 4 / (98 / 52) = 2.1
+        assert_eq!(2, shoe.true_count());
+        // The rest of the suit (7-K) brings it to -1.
+        for _ in 0..7 {
+            shoe.deal();
+        }
+        assert_eq!(0, shoe.true_count());
+    }
+
+    #[test]
+    fn penetration_moves_the_cut_card() {
+        let shoe = Shoe::new(6).with_penetration(75);
+        assert_eq!(234, shoe.pen);
+        let shoe = Shoe::new(2).with_penetration(50);
+        assert_eq!(52, shoe.pen);
+    }
+
     #[test]
     fn is_done_exactly_at_pen_is_true() {
         let mut shoe = Shoe::new(1);
@@ -1,4 +1,7 @@
+use crate::BjError::{UnknownAction, ValueOutOfRange};
+use crate::{BjError, BjResult};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum Action {
@@ -22,6 +25,21 @@ impl Action {
     }
 }
 
+impl TryFrom<u8> for Action {
+    type Error = BjError;
+
+    fn try_from(value: u8) -> BjResult<Self> {
+        match value {
+            0 => Ok(Action::Hit),
+            1 => Ok(Action::Stand),
+            2 => Ok(Action::Split),
+            3 => Ok(Action::Double),
+            4 => Ok(Action::Surrender),
+            _ => Err(ValueOutOfRange(value, 0, 4)),
+        }
+    }
+}
+
 impl Display for Action {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -35,6 +53,21 @@ impl Display for Action {
     }
 }
 
+impl FromStr for Action {
+    type Err = BjError;
+
+    fn from_str(s: &str) -> BjResult<Self> {
+        match s {
+            "Hit" => Ok(Action::Hit),
+            "Stand" => Ok(Action::Stand),
+            "Split" => Ok(Action::Split),
+            "Double" => Ok(Action::Double),
+            "Surrender" => Ok(Action::Surrender),
+            _ => Err(UnknownAction(s.to_string())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
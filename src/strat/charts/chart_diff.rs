@@ -0,0 +1,110 @@
+use crate::strat::charts::{
+    COL_LABELS, ChartAction, ChartSet, HARD_LABELS, SOFT_LABELS, SPLIT_LABELS,
+};
+use crate::strat::tableindex::{ColIndex, RowIndex, TableIndex, TableType, new_table_index};
+use std::fmt::Display;
+
+/// A chart cell that plays differently in two chart sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub index: TableIndex,
+    /// Row label as the charts display it (e.g. "16", "A,7", "8,8").
+    pub hand: &'static str,
+    /// Dealer column label (e.g. "T", "A").
+    pub dealer: &'static str,
+    pub from: ChartAction,
+    pub to: ChartAction,
+}
+
+impl ChartSet {
+    /// The cells whose play differs between `self` and `other`, hard then soft then splits,
+    /// in chart order. `from` is this chart set's play, `to` is the other's.
+    pub fn diff(&self, other: &ChartSet) -> Vec<CellChange> {
+        let mut changes = Vec::new();
+        let tables: [(TableType, &[&'static str]); 3] = [
+            (TableType::Hard, &HARD_LABELS),
+            (TableType::Soft, &SOFT_LABELS),
+            (TableType::Split, &SPLIT_LABELS),
+        ];
+        for (table_type, labels) in tables {
+            let first_row = *table_type.rows().start();
+            for (r, &hand) in labels.iter().enumerate() {
+                for (c, &dealer) in COL_LABELS.iter().enumerate() {
+                    let (from, to) = match table_type {
+                        TableType::Hard => (self.hard[r][c], other.hard[r][c]),
+                        TableType::Soft => (self.soft[r][c], other.soft[r][c]),
+                        _ => (self.split[r][c], other.split[r][c]),
+                    };
+                    if from == to {
+                        continue;
+                    }
+                    // Chart columns run 2..T then A; the table index counts the ace as 1.
+                    let col = if c == 9 { 1 } else { c as u8 + 2 };
+                    let (Ok(row), Ok(col)) = (
+                        RowIndex::new(table_type, first_row + r as u8),
+                        col.to_string().parse::<ColIndex>(),
+                    ) else {
+                        continue;
+                    };
+                    changes.push(CellChange {
+                        index: new_table_index(row, col),
+                        hand,
+                        dealer,
+                        from,
+                        to,
+                    });
+                }
+            }
+        }
+        changes
+    }
+}
+
+impl Display for CellChange {
+    /// e.g. "16 vs T: Surrender → Hit".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let play = |action: ChartAction| {
+            action
+                .apply_rules()
+                .map_or_else(|| "-".to_string(), |a| a.to_string())
+        };
+        write!(
+            f,
+            "{} vs {}: {} → {}",
+            self.hand,
+            self.dealer,
+            play(self.from),
+            play(self.to)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rules::{Rules, Soft17};
+    use crate::strat::charts::ChartSet;
+
+    #[test]
+    fn same_charts_have_no_changes() {
+        assert!(ChartSet::bja().diff(&ChartSet::bja()).is_empty());
+    }
+
+    #[test]
+    fn h17_to_s17_changes_a_few_cells() {
+        let h17 = ChartSet::for_rules(&Rules::default());
+        let s17 = ChartSet::for_rules(&Rules {
+            soft_17: Soft17::Stands,
+            ..Default::default()
+        });
+        let changes: Vec<String> = h17.diff(&s17).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            vec![
+                "11 vs A: Double → Hit",
+                "A,7 vs 2: Double → Stand",
+                "A,8 vs 6: Double → Stand",
+            ],
+            changes
+        );
+        assert_eq!("hard:11,1", h17.diff(&s17)[0].index.to_string());
+    }
+}
@@ -0,0 +1,724 @@
+use crate::rules::{GameVariant, HoleCard, Rules, Soft17};
+use crate::strat::charts::composition::{COMPOSITION_EXCEPTIONS, CompositionException};
+use crate::strat::charts::free_bet_chart::{FB_HARD_CHART, FB_SOFT_CHART, FB_SPLIT_CHART};
+use crate::strat::charts::hard_chart::{HARD_CHART, HardChart};
+use crate::strat::charts::overrides::{
+    CardCountOverride, ENHC_OVERRIDES, H17_LATE_SURRENDER_CARD_COUNT, H17_LATE_SURRENDER_OVERRIDES,
+    LATE_SURRENDER_OVERRIDES, Override, S17_LATE_SURRENDER_OVERRIDES, S17_OVERRIDES,
+};
+use crate::strat::charts::simple_chart::{
+    SIMPLE_HARD_CHART, SIMPLE_SOFT_CHART, SIMPLE_SPLIT_CHART,
+};
+use crate::strat::charts::soft_chart::{SOFT_CHART, SoftChart};
+use crate::strat::charts::spanish21_chart::{
+    S21_CARD_COUNT, S21_HARD_CHART, S21_SOFT_CHART, S21_SPLIT_CHART,
+};
+use crate::strat::charts::split_chart::{SPLIT_CHART, SplitChart};
+use crate::strat::charts::surrender_chart::SurrenderChart;
+use crate::strat::charts::{Chart, ChartAction, HardTable, SoftTable, SplitTable, as_chart_column};
+use crate::strat::tableindex::{ColIndex, TableIndex, TableType};
+use crate::{BjError, BjResult, Hand};
+use std::ops::RangeInclusive;
+
+// A complete set of strategy tables for one set of Rules.
+//
+// The published BJA charts are the starting point. Rules that change the correct play in a
+// handful of cells (e.g., ENHC) are applied as overrides on top of them, so everything that
+// reads a cell (grading, drills, chart display) sees the same strategy.
+//
+// Other games (e.g., Spanish 21) have their own charts, and may have cells whose play depends
+// on how many cards are in the hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartSet {
+    pub(crate) hard: HardTable,
+    pub(crate) soft: SoftTable,
+    pub(crate) split: SplitTable,
+    pub(crate) card_count: &'static [CardCountOverride],
+    /// Composition-dependent plays consulted by `lookup_action`. Empty unless the strategy
+    /// uses them.
+    pub(crate) exceptions: Vec<CompositionException>,
+}
+
+impl ChartSet {
+    /// The Blackjack Apprenticeship charts as published.
+    pub fn bja() -> Self {
+        ChartSet {
+            hard: HARD_CHART,
+            soft: SOFT_CHART,
+            split: SPLIT_CHART,
+            card_count: &[],
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// The Spanish 21 charts.
+    pub fn spanish21() -> Self {
+        ChartSet {
+            hard: S21_HARD_CHART,
+            soft: S21_SOFT_CHART,
+            split: S21_SPLIT_CHART,
+            card_count: &S21_CARD_COUNT,
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// The Free Bet Blackjack charts.
+    pub fn free_bet() -> Self {
+        ChartSet {
+            hard: FB_HARD_CHART,
+            soft: FB_SOFT_CHART,
+            split: FB_SPLIT_CHART,
+            card_count: &[],
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// A simplified beginner strategy with one short rule per decision.
+    pub fn simple() -> Self {
+        ChartSet {
+            hard: SIMPLE_HARD_CHART,
+            soft: SIMPLE_SOFT_CHART,
+            split: SIMPLE_SPLIT_CHART,
+            card_count: &[],
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// The charts adjusted for the given rules.
+    pub fn for_rules(rules: &Rules) -> Self {
+        match rules.variant {
+            GameVariant::Standard => {
+                let mut charts = ChartSet::bja();
+                let s17 = rules.soft_17 == Soft17::Stands;
+                if s17 {
+                    charts.apply(&S17_OVERRIDES);
+                }
+                if rules.late_surrender() {
+                    charts.apply(&LATE_SURRENDER_OVERRIDES);
+                    if s17 {
+                        charts.apply(&S17_LATE_SURRENDER_OVERRIDES);
+                    } else {
+                        charts.apply(&H17_LATE_SURRENDER_OVERRIDES);
+                        charts.card_count = &H17_LATE_SURRENDER_CARD_COUNT;
+                    }
+                }
+                if !rules.das {
+                    charts.without_das();
+                }
+                // ENHC goes last: it removes plays that the other rules may have added.
+                if rules.hole_card == HoleCard::Enhc {
+                    charts.apply(&ENHC_OVERRIDES);
+                }
+                charts
+            }
+            // The Spanish 21 charts assume the dealer peeks; there is no ENHC version.
+            GameVariant::Spanish21 => ChartSet::spanish21(),
+            GameVariant::FreeBet => ChartSet::free_bet(),
+        }
+    }
+
+    /// The charts for the rules, with the composition-dependent exceptions for the number
+    /// of decks. The other games' charts already include their card-count plays.
+    pub fn composition_dependent(rules: &Rules) -> Self {
+        let mut charts = ChartSet::for_rules(rules);
+        if rules.variant == GameVariant::Standard {
+            charts.exceptions = COMPOSITION_EXCEPTIONS
+                .iter()
+                .filter(|e| rules.decks <= e.max_decks)
+                .copied()
+                .collect();
+        }
+        charts
+    }
+
+    pub fn exceptions(&self) -> &[CompositionException] {
+        &self.exceptions
+    }
+
+    /// The composition-dependent exception that decides the play for these hands, if any.
+    pub fn exception_for(
+        &self,
+        player_hand: &Hand,
+        dealer_hand: &Hand,
+    ) -> Option<&CompositionException> {
+        self.exceptions
+            .iter()
+            .find(|e| e.matches(player_hand, dealer_hand))
+    }
+
+    fn apply(&mut self, overrides: &[Override]) {
+        for &(table_type, row, col, action) in overrides {
+            // Overrides are compile-time constants, so a bad cell is a programming error.
+            let cell = self
+                .cell_mut(table_type, row, col)
+                .expect("override refers to a cell outside the charts");
+            *cell = action;
+        }
+    }
+
+    // Without double after split, the pairs that are only worth splitting because of the
+    // doubles afterwards are played as ordinary hands instead.
+    fn without_das(&mut self) {
+        for cell in self.split.iter_mut().flatten() {
+            if *cell == ChartAction::SDas {
+                *cell = ChartAction::NoAc;
+            }
+        }
+    }
+
+    fn cell_mut(&mut self, table_type: TableType, row: u8, col: u8) -> Option<&mut ChartAction> {
+        let chart_col = as_chart_column(col.to_string().parse::<ColIndex>().ok()?);
+        match table_type {
+            TableType::Hard => Some(&mut self.hard[hard_chart_row(row)][chart_col]),
+            TableType::Soft => self.soft.get_mut(soft_chart_row(row)?)?.get_mut(chart_col),
+            TableType::Split => self
+                .split
+                .get_mut(split_chart_row(row)?)?
+                .get_mut(chart_col),
+            TableType::Surrender => None,
+        }
+    }
+
+    /// Look up the ChartAction for a given TableIndex directly (without needing actual hands).
+    pub fn get(&self, index: &TableIndex) -> BjResult<ChartAction> {
+        let chart_col = as_chart_column(index.col_index());
+        let row = index.row_index();
+
+        match index.table_type() {
+            TableType::Hard => Ok(self.hard[hard_chart_row(row)][chart_col]),
+            TableType::Soft => {
+                let r = soft_chart_row(row).ok_or(BjError::ValueOutOfRange(row, 12, 21))?;
+                Ok(self.soft[r][chart_col])
+            }
+            TableType::Split => {
+                let r = split_chart_row(row).ok_or(BjError::ValueOutOfRange(row, 1, 10))?;
+                Ok(self.split[r][chart_col])
+            }
+            TableType::Surrender => {
+                // Surrender chart is stubbed — always NoAc
+                Ok(ChartAction::NoAc)
+            }
+        }
+    }
+
+    // Returns a pair of the ChartAction and the index of the cell in the strategy table it came
+    // from. See `lookup_action` for details.
+    pub fn lookup_action(
+        &self,
+        player_hand: &Hand,
+        dealer_hand: &Hand,
+    ) -> BjResult<(ChartAction, Option<TableIndex>)> {
+        let (chart_action, table_index) = self.lookup_two_card_action(player_hand, dealer_hand)?;
+        let num_cards = player_hand.num_cards();
+        let chart_action = match table_index {
+            Some(ti) => self.for_card_count(chart_action, &ti, num_cards),
+            None => chart_action,
+        };
+        let chart_action = self
+            .exception_for(player_hand, dealer_hand)
+            .map_or(chart_action, |e| e.action);
+        // Surrender is only offered on the first two cards.
+        let chart_action = match chart_action {
+            ChartAction::SurH if num_cards > 2 => ChartAction::Hit_,
+            other => other,
+        };
+        Ok((chart_action, table_index))
+    }
+
+    /// The play for a cell when surrender isn't on offer, as for a hand past two cards: what
+    /// the card-count plays say for a surrender cell, or else the hit it falls back to.
+    pub fn without_surrender(&self, index: &TableIndex) -> BjResult<ChartAction> {
+        Ok(match self.get(index)? {
+            ChartAction::SurH => match self.for_card_count(ChartAction::SurH, index, 3) {
+                ChartAction::SurH => ChartAction::Hit_,
+                other => other,
+            },
+            other => other,
+        })
+    }
+
+    /// A short tag, e.g. "1a2b3c4d", that's the same for charts that play every cell, every
+    /// card-count play and every composition-dependent play alike. Progress learned from a
+    /// custom chart is kept under it.
+    pub fn fingerprint(&self) -> String {
+        // FNV-1a, which unlike the std hasher stays the same from one build to the next
+        let mut hash: u32 = 0x811c_9dc5;
+        let mut add = |text: &str| {
+            for byte in text.bytes().chain([b';']) {
+                hash ^= byte as u32;
+                hash = hash.wrapping_mul(0x0100_0193);
+            }
+        };
+        let rows = self.hard.iter().chain(&self.soft).chain(&self.split);
+        for action in rows.flatten() {
+            add(action.code());
+        }
+        for &(tt, row, col, min_cards, action) in self.card_count {
+            add(&format!(
+                "{:?}{},{}+{}{}",
+                tt,
+                row,
+                col,
+                min_cards,
+                action.code()
+            ));
+        }
+        for exception in &self.exceptions {
+            add(&exception.key());
+            add(exception.action.code());
+        }
+        format!("{:08x}", hash)
+    }
+
+    // The action for a cell given the number of cards in the hand. Entries for a cell are
+    // listed from fewest to most cards, so the last one that applies wins (searched from the
+    // end).
+    fn for_card_count(
+        &self,
+        action: ChartAction,
+        index: &TableIndex,
+        num_cards: usize,
+    ) -> ChartAction {
+        self.card_count
+            .iter()
+            .rev()
+            .find(|&&(tt, row, col, min_cards, _)| {
+                tt == index.table_type()
+                    && row == index.row_index()
+                    && col == index.col_index().value()
+                    && num_cards >= min_cards as usize
+            })
+            .map_or(action, |&(_, _, _, _, a)| a)
+    }
+
+    /// One line per card-count-dependent cell of the given table, e.g.
+    /// "16 vs 2: Hit with 4+ cards".
+    pub fn card_count_notes(&self, table_type: TableType) -> Vec<String> {
+        self.card_count
+            .iter()
+            .filter(|(tt, ..)| *tt == table_type)
+            .map(|&(tt, row, col, min_cards, action)| {
+                let hand = match tt {
+                    TableType::Soft => format!("A,{}", row - 11),
+                    _ => row.to_string(),
+                };
+                let dealer = match col {
+                    1 => "A".to_string(),
+                    10 => "T".to_string(),
+                    c => c.to_string(),
+                };
+                let play = action
+                    .apply_rules()
+                    .map(|a| a.to_string())
+                    .unwrap_or_default();
+                format!("{} vs {}: {} with {}+ cards", hand, dealer, play, min_cards)
+            })
+            .chain(
+                self.exceptions
+                    .iter()
+                    .filter(|_| table_type == TableType::Hard)
+                    .map(|e| e.to_string()),
+            )
+            .collect()
+    }
+
+    /// The hard rows grouped for compact display: the rows at each end of the table that
+    /// play the same as the first or last row are one group ("8 and below", "17 and up"),
+    /// every other row is a group of its own.
+    pub(crate) fn hard_row_groups(&self) -> Vec<RangeInclusive<u8>> {
+        let rows = TableType::Hard.rows();
+        let (first, last) = (*rows.start(), *rows.end());
+        let cells = |row: u8| self.hard[hard_chart_row(row)];
+        let low_end = rows
+            .clone()
+            .take_while(|&r| cells(r) == cells(first))
+            .last()
+            .unwrap_or(first);
+        let high_start = rows
+            .clone()
+            .rev()
+            .take_while(|&r| cells(r) == cells(last))
+            .last()
+            .unwrap_or(last);
+        if low_end >= high_start {
+            return vec![rows];
+        }
+        let mut groups = vec![first..=low_end];
+        groups.extend((low_end + 1..high_start).map(|r| r..=r));
+        groups.push(high_start..=last);
+        groups
+    }
+
+    fn lookup_two_card_action(
+        &self,
+        player_hand: &Hand,
+        dealer_hand: &Hand,
+    ) -> BjResult<(ChartAction, Option<TableIndex>)> {
+        // order of ops:
+        // 1. should I surrender
+        // 2. should I split
+        // 3. should I double
+        // 4. should I hit
+        // 5. stand
+
+        let (chart_action, table_index) =
+            SurrenderChart::lookup_action(self, player_hand, dealer_hand)?;
+        if chart_action != ChartAction::NoAc {
+            return Ok((chart_action, table_index));
+        }
+
+        if player_hand.splittable() {
+            let (chart_action, table_index) =
+                SplitChart::lookup_action(self, player_hand, dealer_hand)?;
+            if chart_action != ChartAction::NoAc {
+                return Ok((chart_action, table_index));
+            }
+        }
+
+        if player_hand.is_soft() {
+            SoftChart::lookup_action(self, player_hand, dealer_hand)
+        } else {
+            HardChart::lookup_action(self, player_hand, dealer_hand)
+        }
+    }
+}
+
+impl Default for ChartSet {
+    fn default() -> Self {
+        ChartSet::bja()
+    }
+}
+
+// The hard chart's first row also covers the totals below it, and its last row the ones
+// above it.
+pub(crate) fn hard_chart_row(total: u8) -> usize {
+    let rows = TableType::Hard.rows();
+    (total.clamp(*rows.start(), *rows.end()) - rows.start()) as usize
+}
+
+pub(crate) fn soft_chart_row(total: u8) -> Option<usize> {
+    let rows = TableType::Soft.rows();
+    rows.contains(&total)
+        .then(|| (total - rows.start()) as usize)
+}
+
+pub(crate) fn split_chart_row(row: u8) -> Option<usize> {
+    (1..=10).contains(&row).then(|| (row - 1) as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strat::{RowIndex, new_table_index};
+
+    fn make_index(tt: TableType, row: u8, col: u8) -> TableIndex {
+        let ri = RowIndex::new(tt, row).unwrap();
+        let ci: ColIndex = col.to_string().parse().unwrap();
+        new_table_index(ri, ci)
+    }
+
+    fn enhc() -> Rules {
+        Rules {
+            hole_card: HoleCard::Enhc,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_rules_are_bja() {
+        assert_eq!(ChartSet::bja(), ChartSet::for_rules(&Rules::default()));
+    }
+
+    #[test]
+    fn enhc_hits_eleven_against_ten_and_ace() {
+        let charts = ChartSet::for_rules(&enhc());
+        let ti = make_index(TableType::Hard, 11, 10);
+        assert_eq!(ChartAction::Hit_, charts.get(&ti).unwrap());
+        let ti = make_index(TableType::Hard, 11, 1);
+        assert_eq!(ChartAction::Hit_, charts.get(&ti).unwrap());
+        // Other 11s still double.
+        let ti = make_index(TableType::Hard, 11, 9);
+        assert_eq!(ChartAction::DblH, charts.get(&ti).unwrap());
+    }
+
+    #[test]
+    fn enhc_does_not_split_eights_against_ace() {
+        let charts = ChartSet::for_rules(&enhc());
+        let ti = make_index(TableType::Split, 8, 1);
+        assert_eq!(ChartAction::NoAc, charts.get(&ti).unwrap());
+        let ti = make_index(TableType::Split, 8, 9);
+        assert_eq!(ChartAction::Splt, charts.get(&ti).unwrap());
+    }
+
+    #[test]
+    fn enhc_changes_only_override_cells() {
+        let bja = ChartSet::bja();
+        let charts = ChartSet::for_rules(&enhc());
+        let changed = |a: &[[ChartAction; 10]], b: &[[ChartAction; 10]]| {
+            a.iter()
+                .flatten()
+                .zip(b.iter().flatten())
+                .filter(|(a, b)| a != b)
+                .count()
+        };
+        let changed_hard = changed(&bja.hard, &charts.hard);
+        let changed_split = changed(&bja.split, &charts.split);
+        assert_eq!(bja.soft, charts.soft);
+        assert_eq!(ENHC_OVERRIDES.len(), changed_hard + changed_split);
+    }
+
+    #[test]
+    fn enhc_ignores_late_surrender() {
+        let rules = Rules {
+            surrender: true,
+            ..enhc()
+        };
+        assert_eq!(ChartSet::for_rules(&enhc()), ChartSet::for_rules(&rules));
+    }
+
+    fn spanish21() -> Rules {
+        Rules {
+            variant: GameVariant::Spanish21,
+            ..Default::default()
+        }
+    }
+
+    fn hand(s: &str) -> Hand {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn spanish21_uses_its_own_charts() {
+        let charts = ChartSet::for_rules(&spanish21());
+        assert_eq!(ChartSet::spanish21(), charts);
+        // BJA stands on 12 vs 4; Spanish 21 hits.
+        let ti = make_index(TableType::Hard, 12, 4);
+        assert_eq!(ChartAction::Stnd, ChartSet::bja().get(&ti).unwrap());
+        assert_eq!(ChartAction::Hit_, charts.get(&ti).unwrap());
+    }
+
+    #[test]
+    fn spanish21_ignores_enhc() {
+        let rules = Rules {
+            hole_card: HoleCard::Enhc,
+            ..spanish21()
+        };
+        assert_eq!(ChartSet::spanish21(), ChartSet::for_rules(&rules));
+    }
+
+    #[test]
+    fn spanish21_hits_sixteen_vs_two_with_four_cards() {
+        let charts = ChartSet::spanish21();
+        let dealer = hand("2S");
+        let (action, _) = charts.lookup_action(&hand("9H 7C"), &dealer).unwrap();
+        assert_eq!(ChartAction::Stnd, action);
+        let (action, _) = charts.lookup_action(&hand("4H 3C 9D"), &dealer).unwrap();
+        assert_eq!(ChartAction::Stnd, action);
+        let (action, ti) = charts.lookup_action(&hand("4H 3C 6D 3S"), &dealer).unwrap();
+        assert_eq!(ChartAction::Hit_, action);
+        assert_eq!("hard:16,2", ti.unwrap().to_string());
+    }
+
+    #[test]
+    fn spanish21_last_matching_card_count_wins() {
+        let charts = ChartSet::spanish21();
+        let dealer = hand("4S");
+        // Soft 18 vs 4: double on two cards, stand on three.
+        let (action, _) = charts.lookup_action(&hand("AH 7C"), &dealer).unwrap();
+        assert_eq!(ChartAction::DblS, action);
+        let (action, _) = charts.lookup_action(&hand("AH 3C 4D"), &dealer).unwrap();
+        assert_eq!(ChartAction::Stnd, action);
+    }
+
+    #[test]
+    fn spanish21_surrenders_only_two_cards() {
+        let charts = ChartSet::spanish21();
+        let dealer = hand("KS");
+        let (action, _) = charts.lookup_action(&hand("9H 7C"), &dealer).unwrap();
+        assert_eq!(ChartAction::SurH, action);
+        let (action, _) = charts.lookup_action(&hand("9H 4C 3D"), &dealer).unwrap();
+        assert_eq!(ChartAction::Hit_, action);
+    }
+
+    #[test]
+    fn free_bet_doubles_nine_to_eleven_and_splits_more() {
+        let rules = Rules {
+            variant: GameVariant::FreeBet,
+            ..Default::default()
+        };
+        let charts = ChartSet::for_rules(&rules);
+        assert_eq!(ChartSet::free_bet(), charts);
+        for total in 9..=11 {
+            for col in 1..=10 {
+                let ti = make_index(TableType::Hard, total, col);
+                assert_eq!(ChartAction::DblH, charts.get(&ti).unwrap(), "{}", ti);
+            }
+        }
+        // BJA never splits 4,4 vs 2; Free Bet does, since the split is free.
+        let ti = make_index(TableType::Split, 4, 2);
+        assert_eq!(ChartAction::NoAc, ChartSet::bja().get(&ti).unwrap());
+        assert_eq!(ChartAction::Splt, charts.get(&ti).unwrap());
+        // Tens are never split.
+        let ti = make_index(TableType::Split, 10, 6);
+        assert_eq!(ChartAction::NoAc, charts.get(&ti).unwrap());
+    }
+
+    #[test]
+    fn s17_stops_marginal_doubles() {
+        let rules = Rules {
+            soft_17: Soft17::Stands,
+            ..Default::default()
+        };
+        let charts = ChartSet::for_rules(&rules);
+        assert_eq!(
+            ChartAction::Hit_,
+            charts.get(&make_index(TableType::Hard, 11, 1)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::Stnd,
+            charts.get(&make_index(TableType::Soft, 18, 2)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::Stnd,
+            charts.get(&make_index(TableType::Soft, 19, 6)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::DblS,
+            charts.get(&make_index(TableType::Soft, 18, 3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn late_surrender_cells() {
+        let rules = Rules {
+            surrender: true,
+            ..Default::default()
+        };
+        let charts = ChartSet::for_rules(&rules);
+        assert_eq!(
+            ChartAction::SurH,
+            charts.get(&make_index(TableType::Hard, 16, 9)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::SurH,
+            charts.get(&make_index(TableType::Hard, 15, 1)).unwrap()
+        );
+        // 17 vs A: surrender, but stand on three cards or more
+        assert_eq!(
+            ChartAction::SurH,
+            charts.get(&make_index(TableType::Hard, 17, 1)).unwrap()
+        );
+        let (action, _) = charts.lookup_action(&hand("TH 7C"), &hand("AS")).unwrap();
+        assert_eq!(ChartAction::SurH, action);
+        let (action, _) = charts
+            .lookup_action(&hand("TH 4C 3D"), &hand("AS"))
+            .unwrap();
+        assert_eq!(ChartAction::Stnd, action);
+        let s17 = Rules {
+            soft_17: Soft17::Stands,
+            ..rules
+        };
+        let charts = ChartSet::for_rules(&s17);
+        assert_eq!(
+            ChartAction::Hit_,
+            charts.get(&make_index(TableType::Hard, 15, 1)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::SurH,
+            charts.get(&make_index(TableType::Hard, 15, 10)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::Stnd,
+            charts.get(&make_index(TableType::Hard, 17, 1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn no_das_drops_das_splits() {
+        let rules = Rules {
+            das: false,
+            ..Default::default()
+        };
+        let charts = ChartSet::for_rules(&rules);
+        assert!(
+            charts
+                .split
+                .iter()
+                .flatten()
+                .all(|&a| a != ChartAction::SDas)
+        );
+        assert_eq!(
+            ChartAction::NoAc,
+            charts.get(&make_index(TableType::Split, 2, 2)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::Splt,
+            charts.get(&make_index(TableType::Split, 2, 4)).unwrap()
+        );
+    }
+
+    #[test]
+    fn card_count_notes_describe_cells() {
+        let notes = ChartSet::spanish21().card_count_notes(TableType::Hard);
+        assert!(notes.contains(&"16 vs 2: Hit with 4+ cards".to_string()));
+        assert!(notes.contains(&"11 vs T: Hit with 4+ cards".to_string()));
+        let notes = ChartSet::spanish21().card_count_notes(TableType::Soft);
+        assert!(notes.contains(&"A,7 vs 4: Stand with 3+ cards".to_string()));
+        assert!(ChartSet::bja().card_count_notes(TableType::Hard).is_empty());
+    }
+
+    #[test]
+    fn composition_exceptions_depend_on_deck_count() {
+        let single = Rules {
+            decks: 1,
+            ..Default::default()
+        };
+        let player: Hand = "KH 2C".parse().unwrap();
+        let dealer: Hand = "4S".parse().unwrap();
+        let (action, _) = ChartSet::composition_dependent(&single)
+            .lookup_action(&player, &dealer)
+            .unwrap();
+        assert_eq!(ChartAction::Hit_, action);
+
+        let (action, _) = ChartSet::composition_dependent(&Rules::default())
+            .lookup_action(&player, &dealer)
+            .unwrap();
+        assert_eq!(ChartAction::Stnd, action);
+        // The standard charts have no exceptions.
+        assert!(ChartSet::for_rules(&single).exceptions().is_empty());
+        let notes = ChartSet::composition_dependent(&single).card_count_notes(TableType::Hard);
+        assert!(notes.contains(&"T,2 vs 4: Hit".to_string()));
+    }
+
+    #[test]
+    fn hard_row_groups_collapse_the_edges() {
+        let groups = ChartSet::bja().hard_row_groups();
+        assert_eq!(Some(&(5..=8)), groups.first());
+        assert_eq!(Some(&(17..=20)), groups.last());
+        assert_eq!(10, groups.len());
+        let mut charts = ChartSet::bja();
+        charts.hard[2][3] = ChartAction::DblH; // 7 vs 5
+        let groups = charts.hard_row_groups();
+        assert_eq!(vec![5..=6, 7..=7, 8..=8], groups[..3].to_vec());
+    }
+
+    #[test]
+    fn finer_rows_have_their_own_cells() {
+        let charts = ChartSet::bja();
+        assert_eq!(
+            ChartAction::Hit_,
+            charts.get(&make_index(TableType::Soft, 12, 5)).unwrap()
+        );
+        assert_eq!(
+            ChartAction::Stnd,
+            charts.get(&make_index(TableType::Hard, 20, 10)).unwrap()
+        );
+        // Unsplit aces play from the soft 12 row.
+        let mut no_aces = charts.clone();
+        no_aces.split[0] = [ChartAction::NoAc; 10];
+        no_aces.soft[0][4] = ChartAction::DblH;
+        let (action, ti) = no_aces.lookup_action(&hand("AH AC"), &hand("6S")).unwrap();
+        assert_eq!(ChartAction::DblH, action);
+        assert_eq!("soft:12,6", ti.unwrap().to_string());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::Hand;
+use crate::strat::charts::ChartAction;
+use crate::strat::charts::ChartAction::{Hit_, Stnd};
+use std::fmt::Display;
+
+// Composition-dependent exceptions.
+//
+// The charts play a hand by its total. A few hands with the same total play differently
+// depending on the cards that make them up, because those cards are no longer in the shoe.
+// The effect is biggest with few decks, so most exceptions only apply up to a deck count.
+//
+// Each exception is a drill item of its own (see `CompositionException::key`), since the
+// point is to learn it apart from the chart cell it overrides.
+
+/// How the player's hand must be made up for an exception to apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Makeup {
+    /// Exactly these two cards, by value (10 = any ten), in either order.
+    TwoCards(u8, u8),
+    /// Any hand of at least this many cards.
+    AtLeast(u8),
+}
+
+/// A hard hand that plays differently from its chart cell because of the cards in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompositionException {
+    /// Stable identifier, used in the spaced-rep key.
+    pub id: &'static str,
+    /// Hard total of the hand.
+    pub total: u8,
+    pub makeup: Makeup,
+    /// Dealer up card, as a chart column (1 = Ace).
+    pub dealer: u8,
+    /// The exception applies to games with at most this many decks.
+    pub max_decks: u8,
+    pub action: ChartAction,
+}
+
+pub(crate) const COMPOSITION_EXCEPTIONS: [CompositionException; 3] = [
+    // A 16 of three or more cards has used up the small cards that would help it, and
+    // left the tens in the shoe: stand rather than hit against a ten.
+    CompositionException {
+        id: "16-3cards-T",
+        total: 16,
+        makeup: Makeup::AtLeast(3),
+        dealer: 10,
+        max_decks: 8,
+        action: Stnd,
+    },
+    // T,2 vs 4: the ten in the hand is one fewer ten to bust on, but also one fewer for
+    // the dealer. With one or two decks, hitting comes out ahead.
+    CompositionException {
+        id: "12-T2-4",
+        total: 12,
+        makeup: Makeup::TwoCards(10, 2),
+        dealer: 4,
+        max_decks: 2,
+        action: Hit_,
+    },
+    // 7,7 vs T in single deck: two of the four remaining sevens are gone, so the hit that
+    // would make 21 is scarce. Stand.
+    CompositionException {
+        id: "14-77-T",
+        total: 14,
+        makeup: Makeup::TwoCards(7, 7),
+        dealer: 10,
+        max_decks: 1,
+        action: Stnd,
+    },
+];
+
+fn column_of(card_value: u8) -> u8 {
+    if card_value == 11 { 1 } else { card_value }
+}
+
+impl CompositionException {
+    /// Whether this exception decides the play for these hands.
+    pub fn matches(&self, player: &Hand, dealer: &Hand) -> bool {
+        let Some(up) = dealer.first_card() else {
+            return false;
+        };
+        if player.is_soft() || player.total() != self.total || column_of(up.value()) != self.dealer
+        {
+            return false;
+        }
+        match self.makeup {
+            Makeup::AtLeast(n) => player.num_cards() >= n as usize,
+            Makeup::TwoCards(a, b) => {
+                if player.num_cards() != 2 {
+                    return false;
+                }
+                let mut values: Vec<u8> = player
+                    .cards()
+                    .iter()
+                    .map(|c| column_of(c.value()))
+                    .collect();
+                values.sort_unstable();
+                values == [a.min(b), a.max(b)]
+            }
+        }
+    }
+
+    /// The spaced-rep key for this exception (before any variant prefix), e.g.
+    /// "comp:12-T2-4".
+    pub fn key(&self) -> String {
+        format!("comp:{}", self.id)
+    }
+}
+
+impl Display for CompositionException {
+    /// e.g. "T,2 vs 4: Hit" or "16 (3+ cards) vs T: Stand".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let card = |v: u8| match v {
+            1 => "A".to_string(),
+            10 => "T".to_string(),
+            v => v.to_string(),
+        };
+        let hand = match self.makeup {
+            Makeup::TwoCards(a, b) => format!("{},{}", card(a), card(b)),
+            Makeup::AtLeast(n) => format!("{} ({}+ cards)", self.total, n),
+        };
+        let play = self
+            .action
+            .apply_rules()
+            .map(|a| a.to_string())
+            .unwrap_or_default();
+        write!(f, "{} vs {}: {}", hand, card(self.dealer), play)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(s: &str) -> Hand {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn two_card_makeup_in_either_order() {
+        let ex = COMPOSITION_EXCEPTIONS[1];
+        assert!(ex.matches(&hand("KH 2C"), &hand("4S")));
+        assert!(ex.matches(&hand("2C TH"), &hand("4S")));
+        assert!(!ex.matches(&hand("9H 3C"), &hand("4S")));
+        assert!(!ex.matches(&hand("KH 2C"), &hand("5S")));
+    }
+
+    #[test]
+    fn at_least_makeup_counts_cards() {
+        let ex = COMPOSITION_EXCEPTIONS[0];
+        assert!(ex.matches(&hand("4H 5C 7D"), &hand("KS")));
+        assert!(!ex.matches(&hand("TH 6C"), &hand("KS")));
+        // Soft 16 isn't a hard 16.
+        assert!(!ex.matches(&hand("AH 2C 3D"), &hand("KS")));
+    }
+
+    #[test]
+    fn describe_and_key() {
+        assert_eq!("T,2 vs 4: Hit", COMPOSITION_EXCEPTIONS[1].to_string());
+        assert_eq!(
+            "16 (3+ cards) vs T: Stand",
+            COMPOSITION_EXCEPTIONS[0].to_string()
+        );
+        assert_eq!("comp:14-77-T", COMPOSITION_EXCEPTIONS[2].key());
+    }
+}
@@ -0,0 +1,460 @@
+use crate::strat::charts::{
+    COL_LABELS, ChartAction, ChartSet, HARD_LABELS, SOFT_LABELS, SPLIT_LABELS,
+};
+use crate::{BjError, BjResult};
+
+// Loading a chart set from a file.
+//
+// A custom chart uses the same row labels and cell codes that `all_charts` prints: rows
+// "5".."20" for hard totals, "A,A".."A,T" for soft totals and "A,A".."T,T" for pairs; cells
+// H, S, Dh, Ds, P, Pd, Rh and "-" (don't split), with the dealer columns 2-9, T, A in order.
+// Every row of every table must be given, so a chart can't be half-loaded. The exception is
+// the rows at the edges of the hard and soft tables (hard 5-7 and 18-20, soft A,A), which
+// charts written before they existed leave out. The hard rows copy the nearest row that is
+// given ("8" used to cover 8 and below, "17" 17 and up); soft A,A always hits.
+//
+// TOML: one table per chart, one key per row. A row is a string of codes or an array.
+//
+//     [hard]
+//     "16" = "S S S S S H H H H H"
+//     [soft]
+//     "A,7" = ["Ds", "Ds", "Ds", "Ds", "Ds", "S", "S", "H", "H", "H"]
+//     [split]
+//     "8,8" = "P P P P P P P P P P"
+//
+// CSV: one line per row: table, row label, then ten cells. A header line starting with
+// "table" and lines starting with '#' are skipped. Labels with a comma must be quoted
+// ("A,7"), or may be written without it (A7).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Table {
+    Hard,
+    Soft,
+    Split,
+}
+
+impl Table {
+    fn parse(s: &str) -> BjResult<Table> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "hard" => Ok(Table::Hard),
+            "soft" => Ok(Table::Soft),
+            "split" | "pairs" => Ok(Table::Split),
+            other => Err(BjError::UnknownTableType(other.to_string())),
+        }
+    }
+
+    fn labels(self) -> &'static [&'static str] {
+        match self {
+            Table::Hard => &HARD_LABELS,
+            Table::Soft => &SOFT_LABELS,
+            Table::Split => &SPLIT_LABELS,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Table::Hard => "hard",
+            Table::Soft => "soft",
+            Table::Split => "split",
+        }
+    }
+}
+
+// "A,7", "a7" and " A, 7 " all name the same row.
+fn normalize_label(label: &str) -> String {
+    label
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+fn invalid(msg: String) -> BjError {
+    BjError::InvalidChart(msg)
+}
+
+// Collects rows as they are parsed, then checks that nothing is missing.
+struct ChartBuilder {
+    charts: ChartSet,
+    hard: [bool; 16],
+    soft: [bool; 10],
+    split: [bool; 10],
+}
+
+// Rows that may be left out, and the row they copy: (table, row, copied from). Without a
+// row to copy they keep the standard play.
+const OPTIONAL_ROWS: [(Table, &str, Option<&str>); 7] = [
+    (Table::Hard, "7", Some("8")),
+    (Table::Hard, "6", Some("7")),
+    (Table::Hard, "5", Some("6")),
+    (Table::Hard, "18", Some("17")),
+    (Table::Hard, "19", Some("18")),
+    (Table::Hard, "20", Some("19")),
+    (Table::Soft, "A,A", None),
+];
+
+impl ChartBuilder {
+    fn new() -> Self {
+        ChartBuilder {
+            charts: ChartSet::bja(),
+            hard: [false; 16],
+            soft: [false; 10],
+            split: [false; 10],
+        }
+    }
+
+    fn row(table: Table, label: &str) -> BjResult<usize> {
+        let wanted = normalize_label(label);
+        table
+            .labels()
+            .iter()
+            .position(|l| normalize_label(l) == wanted)
+            .ok_or_else(|| invalid(format!("unknown {} row '{}'", table.name(), label)))
+    }
+
+    fn seen(&mut self, table: Table) -> &mut [bool] {
+        match table {
+            Table::Hard => &mut self.hard,
+            Table::Soft => &mut self.soft,
+            Table::Split => &mut self.split,
+        }
+    }
+
+    fn set_row(&mut self, table: Table, label: &str, cells: &[&str]) -> BjResult<()> {
+        let row = Self::row(table, label)?;
+        if cells.len() != COL_LABELS.len() {
+            return Err(invalid(format!(
+                "{} row '{}' has {} cells, expected {}",
+                table.name(),
+                label,
+                cells.len(),
+                COL_LABELS.len()
+            )));
+        }
+
+        let mut actions = [ChartAction::NoAc; 10];
+        for (i, code) in cells.iter().enumerate() {
+            let action = ChartAction::from_code(code.trim()).ok_or_else(|| {
+                invalid(format!(
+                    "{} row '{}' vs {}: unknown code '{}'",
+                    table.name(),
+                    label,
+                    COL_LABELS[i],
+                    code
+                ))
+            })?;
+            let allowed = match table {
+                Table::Hard | Table::Soft => !matches!(
+                    action,
+                    ChartAction::Splt | ChartAction::SDas | ChartAction::NoAc
+                ),
+                Table::Split => matches!(
+                    action,
+                    ChartAction::Splt | ChartAction::SDas | ChartAction::NoAc
+                ),
+            };
+            if !allowed {
+                return Err(invalid(format!(
+                    "{} row '{}' vs {}: '{}' is not allowed in this table",
+                    table.name(),
+                    label,
+                    COL_LABELS[i],
+                    code
+                )));
+            }
+            actions[i] = action;
+        }
+
+        let seen = match table {
+            Table::Hard => {
+                self.charts.hard[row] = actions;
+                &mut self.hard[row]
+            }
+            Table::Soft => {
+                self.charts.soft[row] = actions;
+                &mut self.soft[row]
+            }
+            Table::Split => {
+                self.charts.split[row] = actions;
+                &mut self.split[row]
+            }
+        };
+        if *seen {
+            return Err(invalid(format!(
+                "{} row '{}' is given twice",
+                table.name(),
+                label
+            )));
+        }
+        *seen = true;
+        Ok(())
+    }
+
+    fn finish(mut self) -> BjResult<ChartSet> {
+        for (table, label, from) in OPTIONAL_ROWS {
+            let row = Self::row(table, label)?;
+            if self.seen(table)[row] {
+                continue;
+            }
+            if let Some(from) = from {
+                let from = Self::row(table, from)?;
+                if !self.seen(table)[from] {
+                    continue;
+                }
+                match table {
+                    Table::Hard => self.charts.hard[row] = self.charts.hard[from],
+                    Table::Soft => self.charts.soft[row] = self.charts.soft[from],
+                    Table::Split => self.charts.split[row] = self.charts.split[from],
+                }
+            }
+            self.seen(table)[row] = true;
+        }
+
+        let missing: Vec<String> = [
+            (Table::Hard, &self.hard[..]),
+            (Table::Soft, &self.soft[..]),
+            (Table::Split, &self.split[..]),
+        ]
+        .into_iter()
+        .flat_map(|(table, seen)| {
+            seen.iter()
+                .zip(table.labels())
+                .filter(|(seen, _)| !**seen)
+                .map(move |(_, label)| format!("{} {}", table.name(), label))
+        })
+        .collect();
+        if !missing.is_empty() {
+            return Err(invalid(format!("missing rows: {}", missing.join(", "))));
+        }
+        Ok(self.charts)
+    }
+}
+
+// Split a CSV line on commas, honouring double quotes.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+impl ChartSet {
+    /// Load a chart set from either format: TOML if the first line that isn't blank or a
+    /// comment is a `[table]` header, CSV otherwise.
+    pub fn from_text(text: &str) -> BjResult<ChartSet> {
+        let first = text
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty() && !l.starts_with('#'));
+        match first {
+            Some(l) if l.starts_with('[') => ChartSet::from_toml(text),
+            _ => ChartSet::from_csv(text),
+        }
+    }
+
+    /// Load a complete chart set from TOML. See the module notes for the format.
+    pub fn from_toml(text: &str) -> BjResult<ChartSet> {
+        let doc: toml::Table = text
+            .parse()
+            .map_err(|e: toml::de::Error| invalid(e.to_string()))?;
+        let mut builder = ChartBuilder::new();
+        for (name, rows) in &doc {
+            let table = Table::parse(name)?;
+            let rows = rows
+                .as_table()
+                .ok_or_else(|| invalid(format!("'{}' should be a table of rows", name)))?;
+            for (label, value) in rows {
+                let cells: Vec<&str> = match value {
+                    toml::Value::String(s) => s
+                        .split(|c: char| c.is_whitespace() || c == ',')
+                        .filter(|c| !c.is_empty())
+                        .collect(),
+                    toml::Value::Array(a) => a.iter().map(|v| v.as_str().unwrap_or("?")).collect(),
+                    _ => {
+                        return Err(invalid(format!(
+                            "{} row '{}' should be a string or array",
+                            name, label
+                        )));
+                    }
+                };
+                builder.set_row(table, label, &cells)?;
+            }
+        }
+        builder.finish()
+    }
+
+    /// Load a complete chart set from CSV. See the module notes for the format.
+    pub fn from_csv(text: &str) -> BjResult<ChartSet> {
+        let mut builder = ChartBuilder::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = csv_fields(line);
+            if fields[0].trim().eq_ignore_ascii_case("table") {
+                continue;
+            }
+            if fields.len() < 2 {
+                return Err(invalid(format!("line '{}' has no row label", line)));
+            }
+            let table = Table::parse(&fields[0])?;
+            let cells: Vec<&str> = fields[2..].iter().map(|f| f.as_str()).collect();
+            builder.set_row(table, &fields[1], &cells)?;
+        }
+        builder.finish()
+    }
+
+    /// Write the two-card charts as CSV, in the format `from_csv` reads.
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("table,row,{}\n", COL_LABELS.join(","));
+        let mut push = |table: Table, label: &str, row: &[ChartAction; 10]| {
+            let cells: Vec<&str> = row.iter().map(|a| a.code()).collect();
+            out.push_str(&format!(
+                "{},\"{}\",{}\n",
+                table.name(),
+                label,
+                cells.join(",")
+            ));
+        };
+        for (label, row) in HARD_LABELS.iter().zip(&self.hard) {
+            push(Table::Hard, label, row);
+        }
+        for (label, row) in SOFT_LABELS.iter().zip(&self.soft) {
+            push(Table::Soft, label, row);
+        }
+        for (label, row) in SPLIT_LABELS.iter().zip(&self.split) {
+            push(Table::Split, label, row);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_roundtrip() {
+        let csv = ChartSet::bja().to_csv();
+        assert_eq!(ChartSet::bja(), ChartSet::from_csv(&csv).unwrap());
+        let csv = ChartSet::spanish21().to_csv();
+        let loaded = ChartSet::from_csv(&csv).unwrap();
+        assert_eq!(ChartSet::spanish21().hard, loaded.hard);
+        // Card-count plays aren't part of the file.
+        assert!(loaded.card_count.is_empty());
+    }
+
+    #[test]
+    fn csv_accepts_unquoted_labels() {
+        let csv = ChartSet::bja().to_csv().replace("\"A,7\"", "A7");
+        assert_eq!(ChartSet::bja(), ChartSet::from_csv(&csv).unwrap());
+    }
+
+    fn bja_toml() -> String {
+        let mut out = String::new();
+        for chart in crate::strat::all_charts(&Default::default()) {
+            let table = match chart.title {
+                "Hard Totals" => "hard",
+                "Soft Totals" => "soft",
+                _ => "split",
+            };
+            out.push_str(&format!("[{}]\n", table));
+            for (label, cells) in chart.rows {
+                out.push_str(&format!("\"{}\" = \"{}\"\n", label, cells.join(" ")));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn toml_matches_printed_charts() {
+        assert_eq!(ChartSet::bja(), ChartSet::from_toml(&bja_toml()).unwrap());
+    }
+
+    #[test]
+    fn toml_changes_a_cell() {
+        let text = bja_toml().replace(
+            "\"16\" = \"S S S S S H H H H H\"",
+            "\"16\" = [\"S\", \"S\", \"S\", \"S\", \"S\", \"H\", \"H\", \"H\", \"Rh\", \"Rh\"]",
+        );
+        let charts = ChartSet::from_toml(&text).unwrap();
+        assert_eq!(ChartAction::SurH, charts.hard[11][8]);
+        assert_ne!(ChartSet::bja(), charts);
+    }
+
+    #[test]
+    fn missing_rows_are_rejected() {
+        let text = bja_toml().replace("\"16\" = \"S S S S S H H H H H\"\n", "");
+        let err = ChartSet::from_toml(&text).unwrap_err();
+        assert_eq!(
+            BjError::InvalidChart("missing rows: hard 16".to_string()),
+            err
+        );
+    }
+
+    #[test]
+    fn edge_rows_may_be_left_out() {
+        // A chart written when the hard table ran 8-17 and the soft one A,2-A,T
+        let mut csv = ChartSet::bja().to_csv();
+        for label in ["5", "6", "7", "18", "19", "20", "A,A"] {
+            let start = csv.find(&format!(",\"{}\",", label)).unwrap();
+            let start = csv[..start].rfind('\n').unwrap() + 1;
+            let end = start + csv[start..].find('\n').unwrap() + 1;
+            csv.replace_range(start..end, "");
+        }
+        let csv = csv.replace(
+            "hard,\"8\",H,H,H,H,H,H,H,H,H,H",
+            "hard,\"8\",H,H,H,Dh,Dh,H,H,H,H,H",
+        );
+        let charts = ChartSet::from_csv(&csv).unwrap();
+        assert_eq!(charts.hard[3], charts.hard[0]);
+        assert_eq!(ChartAction::DblH, charts.hard[0][3]);
+        assert_eq!(ChartSet::bja().hard[15], charts.hard[15]);
+        assert_eq!(ChartSet::bja().soft, charts.soft);
+    }
+
+    #[test]
+    fn bad_cells_are_rejected() {
+        let text = bja_toml().replace(
+            "\"16\" = \"S S S S S H H H H H\"",
+            "\"16\" = \"S S S S S H H H H\"",
+        );
+        assert!(ChartSet::from_toml(&text).is_err());
+        let text = bja_toml().replace(
+            "\"16\" = \"S S S S S H H H H H\"",
+            "\"16\" = \"S S S S S H H H H P\"",
+        );
+        assert!(ChartSet::from_toml(&text).is_err());
+        let text = bja_toml().replace(
+            "\"16\" = \"S S S S S H H H H H\"",
+            "\"16\" = \"S S S S S H H H H X\"",
+        );
+        assert!(ChartSet::from_toml(&text).is_err());
+        let text = bja_toml().replace("\"16\"", "\"23\"");
+        assert!(ChartSet::from_toml(&text).is_err());
+    }
+
+    #[test]
+    fn from_text_detects_the_format() {
+        let toml = format!("# my chart\n{}", bja_toml());
+        assert_eq!(ChartSet::bja(), ChartSet::from_text(&toml).unwrap());
+        let csv = ChartSet::bja().to_csv();
+        assert_eq!(ChartSet::bja(), ChartSet::from_text(&csv).unwrap());
+    }
+
+    #[test]
+    fn duplicate_rows_are_rejected() {
+        let csv = ChartSet::bja().to_csv();
+        let csv = format!("{}hard,16,S,S,S,S,S,H,H,H,H,H\n", csv);
+        assert!(ChartSet::from_csv(&csv).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::strat::charts::ChartAction::{DblH, DblS, Hit_, NoAc, Splt, Stnd};
+use crate::strat::charts::{ChartAction, HardTable, SoftTable};
+
+// Free Bet Blackjack Basic Strategy: six decks, dealer hits soft 17 and peeks.
+//
+// The house pays for doubles on hard 9-11 and for splitting any pair except tens, so those
+// are taken far more often than in the BJA charts: the free part of the bet can only win.
+// In exchange a dealer 22 pushes every hand that is still live, which makes standing on
+// stiffs and paying for soft doubles worth less.
+//
+// Same layout as the BJA charts: rows as in HARD_CHART / SOFT_CHART / SPLIT_CHART,
+// columns are dealer 2-9, T, A.
+pub(crate) const FB_HARD_CHART: HardTable = [
+    /* 5 and lower */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 6 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 7 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 8 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 9 (free double) */
+    [DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH],
+    /* 10 (free double) */
+    [DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH],
+    /* 11 (free double) */
+    [DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH],
+    /* 12 */
+    [Hit_, Hit_, Hit_, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 13 */
+    [Hit_, Hit_, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 14 */
+    [Hit_, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 15 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 16 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 17 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 18 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 19 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 20 and up */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+];
+
+pub(crate) const FB_SOFT_CHART: SoftTable = [
+    /* 12 (A, A) */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 13 (A, 2) */
+    [Hit_, Hit_, Hit_, Hit_, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 14 (A, 3) */
+    [Hit_, Hit_, Hit_, Hit_, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 15 (A, 4) */
+    [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 16 (A, 5) */
+    [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 17 (A, 6) */
+    [Hit_, Hit_, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 18 (A, 7) */
+    [Stnd, DblS, DblS, DblS, DblS, Stnd, Stnd, Hit_, Hit_, Hit_],
+    /* 19 (A, 8) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 20 (A, 9) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 21 (A, 10) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+];
+
+// Every pair but tens is split for free. 5,5 is played as a free double on 10 instead.
+pub(crate) const FB_SPLIT_CHART: [[ChartAction; 10]; 10] = [
+    /*  2 (A, A) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt],
+    /*  4 (2, 2) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, NoAc],
+    /*  6 (3, 3) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, NoAc, NoAc],
+    /*  8 (4, 4) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc],
+    /* 10 (5, 5) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 12 (6, 6) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc],
+    /* 14 (7, 7) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc],
+    /* 16 (8, 8) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt],
+    /* 18 (9, 9) */
+    [Splt, Splt, Splt, Splt, Splt, NoAc, Splt, Splt, NoAc, NoAc],
+    /* 20 (T, T) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+];
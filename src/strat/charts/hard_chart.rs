@@ -1,11 +1,18 @@
 use crate::strat::charts::ChartAction::{DblH, Hit_, Stnd};
-use crate::strat::charts::{Chart, ChartAction, as_chart_column};
+use crate::strat::charts::chart_set::hard_chart_row;
+use crate::strat::charts::{Chart, ChartAction, ChartSet, HardTable, as_chart_column};
 use crate::strat::tableindex::{ColIndex, RowIndex, TableIndex, TableType, new_table_index};
 use crate::{BjError, BjResult, Hand};
 
 // Standard Basic Strategy Hard Totals from BJA
-pub(crate) const HARD_CHART: [[ChartAction; 10]; 10] = [
-    /* 8 and lower */
+pub(crate) const HARD_CHART: HardTable = [
+    /* 5 and lower */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 6 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 7 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 8 */
     [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
     /* 9 */
     [Hit_, DblH, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
@@ -23,7 +30,13 @@ pub(crate) const HARD_CHART: [[ChartAction; 10]; 10] = [
     [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
     /* 16 */
     [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
-    /* 17+ */
+    /* 17 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 18 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 19 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 20 and up */
     [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
 ];
 
@@ -31,6 +44,7 @@ pub struct HardChart;
 
 impl Chart for HardChart {
     fn lookup_action(
+        charts: &ChartSet,
         player_hand: &Hand,
         dealer_hand: &Hand,
     ) -> BjResult<(ChartAction, Option<TableIndex>)> {
@@ -39,25 +53,14 @@ impl Chart for HardChart {
         let col_index = ColIndex::new_with_card(dealer_card)?;
         let chart_index = as_chart_column(col_index);
 
-        if total <= 8 {
-            let row_index = RowIndex::new(TableType::Hard, 8)?;
-            Ok((
-                HARD_CHART[0][chart_index],
-                Some(new_table_index(row_index, col_index)),
-            ))
-        } else if total >= 17 {
-            let row_index = RowIndex::new(TableType::Hard, 17)?;
-            Ok((
-                HARD_CHART[9][chart_index],
-                Some(new_table_index(row_index, col_index)),
-            ))
-        } else {
-            let row_index = RowIndex::new(TableType::Hard, total)?;
-            Ok((
-                HARD_CHART[(total - 8) as usize][chart_index],
-                Some(new_table_index(row_index, col_index)),
-            ))
-        }
+        // Row 5 also covers a hard 4 (an unsplit 2,2), and row 20 a hard 21.
+        let rows = TableType::Hard.rows();
+        let row = total.clamp(*rows.start(), *rows.end());
+        let row_index = RowIndex::new(TableType::Hard, row)?;
+        Ok((
+            charts.hard[hard_chart_row(row)][chart_index],
+            Some(new_table_index(row_index, col_index)),
+        ))
     }
 }
 
@@ -72,7 +75,7 @@ mod test {
 
     #[test]
     fn test_lookup_low() {
-        // Hit at or below 8
+        // Hit at or below 8, including an unsplit 2,2
         assert_eq!(Hit_, LTH(&["2H", "2C"], &["AH"]));
         assert_eq!(Hit_, LTH(&["2H", "3C"], &["2H"]));
         assert_eq!(Hit_, LTH(&["4H", "2C"], &["3H"]));
@@ -80,6 +83,22 @@ mod test {
         assert_eq!(Hit_, LTH(&["3H", "5C"], &["TH"]));
     }
 
+    #[test]
+    fn test_lookup_rows() {
+        let (p, d) = crate::strat::charts::test::make_hands(&["2H", "2C"], &["5S"]);
+        let (_, ti) = HardChart::lookup_action(&ChartSet::bja(), &p, &d).unwrap();
+        assert_eq!("hard:5,5", ti.unwrap().to_string());
+        let (p, d) = crate::strat::charts::test::make_hands(&["3H", "4C"], &["5S"]);
+        let (_, ti) = HardChart::lookup_action(&ChartSet::bja(), &p, &d).unwrap();
+        assert_eq!("hard:7,5", ti.unwrap().to_string());
+        let (p, d) = crate::strat::charts::test::make_hands(&["9H", "TC"], &["5S"]);
+        let (_, ti) = HardChart::lookup_action(&ChartSet::bja(), &p, &d).unwrap();
+        assert_eq!("hard:19,5", ti.unwrap().to_string());
+        let (p, d) = crate::strat::charts::test::make_hands(&["9H", "5C", "7D"], &["5S"]);
+        let (_, ti) = HardChart::lookup_action(&ChartSet::bja(), &p, &d).unwrap();
+        assert_eq!("hard:20,5", ti.unwrap().to_string());
+    }
+
     #[test]
     fn test_lookup_mid() {
         // We are spot-checking some key values to try to check the chart lookup.
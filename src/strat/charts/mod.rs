@@ -1,16 +1,25 @@
 use crate::Action::Double;
-use crate::strat::charts::hard_chart::HardChart;
-use crate::strat::charts::soft_chart::SoftChart;
-use crate::strat::charts::split_chart::SplitChart;
-use crate::strat::charts::surrender_chart::SurrenderChart;
-use crate::strat::tableindex::{ColIndex, TableIndex};
+use crate::rules::Rules;
+use crate::strat::tableindex::{ColIndex, TableIndex, TableType};
 use crate::{Action, BjResult, Hand};
 
+mod chart_diff;
+mod chart_set;
+mod composition;
+mod custom_chart;
+mod free_bet_chart;
 mod hard_chart;
+mod overrides;
+mod simple_chart;
 mod soft_chart;
+mod spanish21_chart;
 mod split_chart;
 mod surrender_chart;
 
+pub use chart_diff::CellChange;
+pub use chart_set::ChartSet;
+pub use composition::{CompositionException, Makeup};
+
 // A list of possible values in the cells of the Basic Strategy charts.
 //
 // Every chart maps a players hand and the dealer's up card to an action.
@@ -30,10 +39,23 @@ pub enum ChartAction {
     Splt, // Split
     SDas, // Split if Double After Split allowed
 
+    SurH, // Surrender if allowed, otherwise Hit.
+
     NoAc, // No Action
 }
 
 impl ChartAction {
+    pub const ALL: [ChartAction; 8] = [
+        ChartAction::Hit_,
+        ChartAction::Stnd,
+        ChartAction::DblH,
+        ChartAction::DblS,
+        ChartAction::Splt,
+        ChartAction::SDas,
+        ChartAction::SurH,
+        ChartAction::NoAc,
+    ];
+
     // Apply the game Rules to a ChartAction to determine the Player Action.
     pub fn apply_rules(self) -> Option<Action> {
         // Currently, we have no rules.
@@ -43,9 +65,31 @@ impl ChartAction {
             ChartAction::Stnd => Some(Action::Stand),
             ChartAction::Splt => Some(Action::Split),
             ChartAction::SDas => Some(Action::Split),
+            ChartAction::SurH => Some(Action::Surrender),
             _ => None,
         }
     }
+
+    /// The short code used in the printed charts, e.g. "Dh" or "Pd".
+    pub fn code(self) -> &'static str {
+        match self {
+            ChartAction::Hit_ => "H",
+            ChartAction::Stnd => "S",
+            ChartAction::DblH => "Dh",
+            ChartAction::DblS => "Ds",
+            ChartAction::Splt => "P",
+            ChartAction::SDas => "Pd",
+            ChartAction::SurH => "Rh",
+            ChartAction::NoAc => "-",
+        }
+    }
+
+    /// Parse a chart code (case-insensitive), the inverse of `code`.
+    pub fn from_code(code: &str) -> Option<ChartAction> {
+        ChartAction::ALL
+            .into_iter()
+            .find(|a| a.code().eq_ignore_ascii_case(code))
+    }
 }
 
 // Returns a pair of the ChartAction and the index of the cell in the strategy table it came from.
@@ -57,67 +101,14 @@ impl ChartAction {
 pub fn lookup_action(
     player_hand: &Hand,
     dealer_hand: &Hand,
+    rules: &Rules,
 ) -> BjResult<(ChartAction, Option<TableIndex>)> {
-    // order of ops:
-    // 1. should I surrender
-    // 2. should I split
-    // 3. should I double
-    // 4. should I hit
-    // 5. stand
-
-    let (chart_action, table_index) = SurrenderChart::lookup_action(player_hand, dealer_hand)?;
-    if chart_action != ChartAction::NoAc {
-        return Ok((chart_action, table_index));
-    }
-
-    if player_hand.splittable() {
-        let (chart_action, table_index) = SplitChart::lookup_action(player_hand, dealer_hand)?;
-        if chart_action != ChartAction::NoAc {
-            return Ok((chart_action, table_index));
-        }
-    }
-
-    if player_hand.is_soft() {
-        SoftChart::lookup_action(player_hand, dealer_hand)
-    } else {
-        HardChart::lookup_action(player_hand, dealer_hand)
-    }
+    ChartSet::for_rules(rules).lookup_action(player_hand, dealer_hand)
 }
 
 /// Look up the ChartAction for a given TableIndex directly (without needing actual hands).
-pub fn lookup_by_index(index: &TableIndex) -> BjResult<ChartAction> {
-    let col = index.col_index();
-    let chart_col = as_chart_column(col);
-    let row = index.row_index();
-
-    match index.table_type() {
-        crate::strat::TableType::Hard => {
-            let chart_row = if row <= 8 {
-                0
-            } else if row >= 17 {
-                9
-            } else {
-                (row - 8) as usize
-            };
-            Ok(hard_chart::HARD_CHART[chart_row][chart_col])
-        }
-        crate::strat::TableType::Soft => {
-            if !(13..=21).contains(&row) {
-                return Err(crate::BjError::ValueOutOfRange(row, 13, 21));
-            }
-            Ok(soft_chart::SOFT_CHART[(row - 13) as usize][chart_col])
-        }
-        crate::strat::TableType::Split => {
-            if !(1..=10).contains(&row) {
-                return Err(crate::BjError::ValueOutOfRange(row, 1, 10));
-            }
-            Ok(split_chart::SPLIT_CHART[(row - 1) as usize][chart_col])
-        }
-        crate::strat::TableType::Surrender => {
-            // Surrender chart is stubbed — always NoAc
-            Ok(ChartAction::NoAc)
-        }
-    }
+pub fn lookup_by_index(index: &TableIndex, rules: &Rules) -> BjResult<ChartAction> {
+    ChartSet::for_rules(rules).get(index)
 }
 
 /// A displayable strategy chart: row labels, column headers, and cell values.
@@ -125,110 +116,82 @@ pub struct StrategyChart {
     pub title: &'static str,
     pub col_headers: Vec<&'static str>,
     pub rows: Vec<(&'static str, Vec<&'static str>)>,
+    /// Plays that change with the number of cards in the hand (e.g. "16 vs 2: Hit with 4+
+    /// cards"). Empty for charts without such entries.
+    pub notes: Vec<String>,
 }
 
-/// Get all strategy charts for display.
-pub fn all_charts() -> Vec<StrategyChart> {
-    let cols = vec!["2", "3", "4", "5", "6", "7", "8", "9", "T", "A"];
+// The tables, one row per `TableType::rows()` entry: hard 5-20, soft 12-21 and pairs A-T.
+// Columns are dealer 2-9, T, A.
+pub(crate) type HardTable = [[ChartAction; 10]; 16];
+pub(crate) type SoftTable = [[ChartAction; 10]; 10];
+pub(crate) type SplitTable = [[ChartAction; 10]; 10];
+
+// Row labels as displayed, indexed by chart row.
+pub(crate) const HARD_LABELS: [&str; 16] = [
+    "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20",
+];
+pub(crate) const SOFT_LABELS: [&str; 10] = [
+    "A,A", "A,2", "A,3", "A,4", "A,5", "A,6", "A,7", "A,8", "A,9", "A,T",
+];
+pub(crate) const SPLIT_LABELS: [&str; 10] = [
+    "A,A", "2,2", "3,3", "4,4", "5,5", "6,6", "7,7", "8,8", "9,9", "T,T",
+];
+pub(crate) const COL_LABELS: [&str; 10] = ["2", "3", "4", "5", "6", "7", "8", "9", "T", "A"];
+
+/// Row labels of a table, indexed by chart row. Empty for the surrender table, which has no
+/// chart of its own.
+pub(crate) fn row_labels(table: TableType) -> &'static [&'static str] {
+    match table {
+        TableType::Hard => &HARD_LABELS,
+        TableType::Soft => &SOFT_LABELS,
+        TableType::Split => &SPLIT_LABELS,
+        TableType::Surrender => &[],
+    }
+}
 
-    let action_str = |a: ChartAction| -> &'static str {
-        match a {
-            ChartAction::Hit_ => "H",
-            ChartAction::Stnd => "S",
-            ChartAction::DblH => "Dh",
-            ChartAction::DblS => "Ds",
-            ChartAction::Splt => "P",
-            ChartAction::SDas => "Pd",
-            ChartAction::NoAc => "-",
-        }
-    };
-
-    let hard_rows: Vec<(&str, Vec<&str>)> = (0..10)
-        .rev()
-        .map(|r| {
-            let label: &'static str = match r {
-                0 => "8",
-                1 => "9",
-                2 => "10",
-                3 => "11",
-                4 => "12",
-                5 => "13",
-                6 => "14",
-                7 => "15",
-                8 => "16",
-                _ => "17",
-            };
-            let cells: Vec<&str> = (0..10)
-                .map(|c| action_str(hard_chart::HARD_CHART[r][c]))
-                .collect();
-            (label, cells)
-        })
-        .collect();
-
-    let soft_rows: Vec<(&str, Vec<&str>)> = (0..9)
-        .rev()
-        .map(|r| {
-            let label: &'static str = match r {
-                0 => "A,2",
-                1 => "A,3",
-                2 => "A,4",
-                3 => "A,5",
-                4 => "A,6",
-                5 => "A,7",
-                6 => "A,8",
-                7 => "A,9",
-                _ => "A,T",
-            };
-            let cells: Vec<&str> = (0..10)
-                .map(|c| action_str(soft_chart::SOFT_CHART[r][c]))
-                .collect();
-            (label, cells)
-        })
-        .collect();
-
-    let split_rows: Vec<(&str, Vec<&str>)> = (0..10)
-        .rev()
-        .map(|r| {
-            let label: &'static str = match r {
-                0 => "A,A",
-                1 => "2,2",
-                2 => "3,3",
-                3 => "4,4",
-                4 => "5,5",
-                5 => "6,6",
-                6 => "7,7",
-                7 => "8,8",
-                8 => "9,9",
-                _ => "T,T",
-            };
-            let cells: Vec<&str> = (0..10)
-                .map(|c| action_str(split_chart::SPLIT_CHART[r][c]))
-                .collect();
-            (label, cells)
-        })
-        .collect();
+/// Get all strategy charts for display, adjusted for the given rules.
+pub fn all_charts(rules: &Rules) -> Vec<StrategyChart> {
+    strategy_charts(&ChartSet::for_rules(rules))
+}
+
+/// Get the charts of a chart set for display.
+pub fn strategy_charts(charts: &ChartSet) -> Vec<StrategyChart> {
+    fn rows<const N: usize>(
+        labels: &[&'static str; N],
+        table: &[[ChartAction; 10]; N],
+    ) -> Vec<(&'static str, Vec<&'static str>)> {
+        (0..N)
+            .rev()
+            .map(|r| (labels[r], table[r].iter().map(|a| a.code()).collect()))
+            .collect()
+    }
 
     vec![
         StrategyChart {
             title: "Hard Totals",
-            col_headers: cols.clone(),
-            rows: hard_rows,
+            col_headers: COL_LABELS.to_vec(),
+            rows: rows(&HARD_LABELS, &charts.hard),
+            notes: charts.card_count_notes(TableType::Hard),
         },
         StrategyChart {
             title: "Soft Totals",
-            col_headers: cols.clone(),
-            rows: soft_rows,
+            col_headers: COL_LABELS.to_vec(),
+            rows: rows(&SOFT_LABELS, &charts.soft),
+            notes: charts.card_count_notes(TableType::Soft),
         },
         StrategyChart {
             title: "Pairs (Split)",
-            col_headers: cols,
-            rows: split_rows,
+            col_headers: COL_LABELS.to_vec(),
+            rows: rows(&SPLIT_LABELS, &charts.split),
+            notes: charts.card_count_notes(TableType::Split),
         },
     ]
 }
 
 trait Chart {
     fn lookup_action(
+        charts: &ChartSet,
         player_hand: &Hand,
         dealer_hand: &Hand,
     ) -> BjResult<(ChartAction, Option<TableIndex>)>;
@@ -251,7 +214,7 @@ mod test {
 
     pub fn lookup_test_hands<C: Chart>(player: &[&str], dealer: &[&str]) -> BjResult<ChartAction> {
         let (player_hand, dealer_hand) = make_hands(player, dealer);
-        let (action, _) = C::lookup_action(&player_hand, &dealer_hand)?;
+        let (action, _) = C::lookup_action(&ChartSet::bja(), &player_hand, &dealer_hand)?;
         Ok(action)
     }
 
@@ -308,6 +271,11 @@ mod test {
         assert_eq!(Some(Action::Split), ChartAction::SDas.apply_rules());
     }
 
+    #[test]
+    fn apply_rules_surh_returns_surrender() {
+        assert_eq!(Some(Action::Surrender), ChartAction::SurH.apply_rules());
+    }
+
     #[test]
     fn apply_rules_noac_returns_none() {
         assert_eq!(None, ChartAction::NoAc.apply_rules());
@@ -318,28 +286,28 @@ mod test {
     #[test]
     fn lookup_action_hard_16_vs_7_is_hit() {
         let (p, d) = make_hands(&["9H", "7C"], &["7S"]);
-        let (action, _) = lookup_action(&p, &d).unwrap();
+        let (action, _) = lookup_action(&p, &d, &Rules::default()).unwrap();
         assert_eq!(ChartAction::Hit_, action);
     }
 
     #[test]
     fn lookup_action_hard_17_vs_6_is_stand() {
         let (p, d) = make_hands(&["9H", "8C"], &["6S"]);
-        let (action, _) = lookup_action(&p, &d).unwrap();
+        let (action, _) = lookup_action(&p, &d, &Rules::default()).unwrap();
         assert_eq!(ChartAction::Stnd, action);
     }
 
     #[test]
     fn lookup_action_hard_11_vs_5_is_double() {
         let (p, d) = make_hands(&["5H", "6C"], &["5S"]);
-        let (action, _) = lookup_action(&p, &d).unwrap();
+        let (action, _) = lookup_action(&p, &d, &Rules::default()).unwrap();
         assert_eq!(ChartAction::DblH, action);
     }
 
     #[test]
     fn lookup_action_aces_pair_vs_6_is_split() {
         let (p, d) = make_hands(&["AH", "AC"], &["6S"]);
-        let (action, _) = lookup_action(&p, &d).unwrap();
+        let (action, _) = lookup_action(&p, &d, &Rules::default()).unwrap();
         // Aces always split
         assert_eq!(ChartAction::Splt, action);
     }
@@ -347,28 +315,28 @@ mod test {
     #[test]
     fn lookup_action_eights_pair_vs_9_is_split() {
         let (p, d) = make_hands(&["8H", "8C"], &["9S"]);
-        let (action, _) = lookup_action(&p, &d).unwrap();
+        let (action, _) = lookup_action(&p, &d, &Rules::default()).unwrap();
         assert_eq!(ChartAction::Splt, action);
     }
 
     #[test]
     fn lookup_action_soft_18_vs_2_is_double() {
         let (p, d) = make_hands(&["AH", "7C"], &["2S"]);
-        let (action, _) = lookup_action(&p, &d).unwrap();
+        let (action, _) = lookup_action(&p, &d, &Rules::default()).unwrap();
         assert_eq!(ChartAction::DblS, action);
     }
 
     #[test]
     fn lookup_action_soft_18_vs_7_is_stand() {
         let (p, d) = make_hands(&["AH", "7C"], &["7S"]);
-        let (action, _) = lookup_action(&p, &d).unwrap();
+        let (action, _) = lookup_action(&p, &d, &Rules::default()).unwrap();
         assert_eq!(ChartAction::Stnd, action);
     }
 
     #[test]
     fn lookup_action_returns_table_index() {
         let (p, d) = make_hands(&["9H", "8C"], &["6S"]);
-        let (_, idx) = lookup_action(&p, &d).unwrap();
+        let (_, idx) = lookup_action(&p, &d, &Rules::default()).unwrap();
         assert!(idx.is_some());
     }
 
@@ -376,7 +344,7 @@ mod test {
     fn lookup_action_error_on_missing_dealer_card() {
         let player: Hand = "9H 8C".parse().unwrap();
         let dealer = Hand::default(); // empty
-        assert!(lookup_action(&player, &dealer).is_err());
+        assert!(lookup_action(&player, &dealer, &Rules::default()).is_err());
     }
 
     // --- lookup_by_index ---
@@ -384,34 +352,49 @@ mod test {
     #[test]
     fn lookup_by_index_hard_8_is_hit() {
         let idx = make_index(TableType::Hard, 8, 5);
-        assert_eq!(ChartAction::Hit_, lookup_by_index(&idx).unwrap());
+        assert_eq!(
+            ChartAction::Hit_,
+            lookup_by_index(&idx, &Rules::default()).unwrap()
+        );
     }
 
     #[test]
     fn lookup_by_index_hard_17_is_stand() {
         let idx = make_index(TableType::Hard, 17, 7);
-        assert_eq!(ChartAction::Stnd, lookup_by_index(&idx).unwrap());
+        assert_eq!(
+            ChartAction::Stnd,
+            lookup_by_index(&idx, &Rules::default()).unwrap()
+        );
     }
 
     #[test]
     fn lookup_by_index_hard_11_vs_ace_is_double() {
         // Dealer Ace = col 1
         let idx = make_index(TableType::Hard, 11, 1);
-        assert_eq!(ChartAction::DblH, lookup_by_index(&idx).unwrap());
+        assert_eq!(
+            ChartAction::DblH,
+            lookup_by_index(&idx, &Rules::default()).unwrap()
+        );
     }
 
     #[test]
     fn lookup_by_index_soft_18_vs_2_is_double() {
         // Soft 18 (A,7) vs dealer 2
         let idx = make_index(TableType::Soft, 18, 2);
-        assert_eq!(ChartAction::DblS, lookup_by_index(&idx).unwrap());
+        assert_eq!(
+            ChartAction::DblS,
+            lookup_by_index(&idx, &Rules::default()).unwrap()
+        );
     }
 
     #[test]
     fn lookup_by_index_soft_20_vs_any_is_stand() {
         for col in 1u8..=10 {
             let idx = make_index(TableType::Soft, 20, col);
-            assert_eq!(ChartAction::Stnd, lookup_by_index(&idx).unwrap());
+            assert_eq!(
+                ChartAction::Stnd,
+                lookup_by_index(&idx, &Rules::default()).unwrap()
+            );
         }
     }
 
@@ -420,7 +403,10 @@ mod test {
         // Row 1 = Aces
         for col in 1u8..=10 {
             let idx = make_index(TableType::Split, 1, col);
-            assert_eq!(ChartAction::Splt, lookup_by_index(&idx).unwrap());
+            assert_eq!(
+                ChartAction::Splt,
+                lookup_by_index(&idx, &Rules::default()).unwrap()
+            );
         }
     }
 
@@ -429,23 +415,29 @@ mod test {
         // Row 10 = tens
         for col in 1u8..=10 {
             let idx = make_index(TableType::Split, 10, col);
-            assert_eq!(ChartAction::NoAc, lookup_by_index(&idx).unwrap());
+            assert_eq!(
+                ChartAction::NoAc,
+                lookup_by_index(&idx, &Rules::default()).unwrap()
+            );
         }
     }
 
     #[test]
     fn lookup_by_index_surrender_always_no_action() {
         let idx = make_index(TableType::Surrender, 16, 9);
-        assert_eq!(ChartAction::NoAc, lookup_by_index(&idx).unwrap());
+        assert_eq!(
+            ChartAction::NoAc,
+            lookup_by_index(&idx, &Rules::default()).unwrap()
+        );
     }
 
     #[test]
-    fn lookup_by_index_soft_valid_range_13_to_21() {
-        // Confirm all valid soft rows (13-21) look up without error
-        for row in 13u8..=21 {
+    fn lookup_by_index_soft_valid_range_12_to_21() {
+        // Confirm all valid soft rows (12-21) look up without error
+        for row in 12u8..=21 {
             let idx = make_index(TableType::Soft, row, 5);
             assert!(
-                lookup_by_index(&idx).is_ok(),
+                lookup_by_index(&idx, &Rules::default()).is_ok(),
                 "lookup_by_index failed for soft:{}",
                 row
             );
@@ -0,0 +1,56 @@
+use crate::strat::charts::ChartAction;
+use crate::strat::charts::ChartAction::{Hit_, NoAc, Stnd, SurH};
+use crate::strat::tableindex::TableType;
+use crate::strat::tableindex::TableType::{Hard, Soft, Split};
+
+// A single cell that a rule changes: (table, row, dealer column (1 = Ace), new action).
+// Rows and columns use the same numbering as TableIndex.
+pub(crate) type Override = (TableType, u8, u8, ChartAction);
+
+// A cell whose play changes once the player's hand has at least this many cards:
+// (table, row, dealer column (1 = Ace), minimum number of cards, new action).
+pub(crate) type CardCountOverride = (TableType, u8, u8, u8, ChartAction);
+
+// European No Hole Card.
+//
+// The dealer's second card is drawn after the player acts, and a dealer blackjack takes the
+// doubled or split bet as well as the original one. Against a ten or an Ace that makes
+// the extra bet too expensive in these cells, so the player just plays the hand.
+pub(crate) const ENHC_OVERRIDES: [Override; 3] = [
+    // 11 vs T and A: hit instead of doubling.
+    (Hard, 11, 10, Hit_),
+    (Hard, 11, 1, Hit_),
+    // 8,8 vs A: don't split; play it as a hard 16.
+    (Split, 8, 1, NoAc),
+];
+
+// Dealer stands on soft 17.
+//
+// The BJA charts assume the dealer hits soft 17, which makes the dealer bust a little more
+// and beat a soft 18 or 19 a little more often. When the dealer stands, these doubles are no
+// longer worth it.
+pub(crate) const S17_OVERRIDES: [Override; 3] = [
+    // 11 vs A: hit instead of doubling.
+    (Hard, 11, 1, Hit_),
+    // A,7 vs 2 and A,8 vs 6: stand instead of doubling.
+    (Soft, 18, 2, Stnd),
+    (Soft, 19, 6, Stnd),
+];
+
+// Late surrender, when the table offers it.
+pub(crate) const LATE_SURRENDER_OVERRIDES: [Override; 5] = [
+    (Hard, 15, 10, SurH),
+    (Hard, 15, 1, SurH),
+    (Hard, 16, 9, SurH),
+    (Hard, 16, 10, SurH),
+    (Hard, 16, 1, SurH),
+];
+
+// Late surrender when the dealer stands on soft 17: 15 vs A is no longer bad enough to give up.
+pub(crate) const S17_LATE_SURRENDER_OVERRIDES: [Override; 1] = [(Hard, 15, 1, Hit_)];
+
+// Late surrender when the dealer hits soft 17: 17 vs A is bad enough to give up.
+pub(crate) const H17_LATE_SURRENDER_OVERRIDES: [Override; 1] = [(Hard, 17, 1, SurH)];
+
+// Past two cards 17 vs A can't be surrendered, and stands rather than hits.
+pub(crate) const H17_LATE_SURRENDER_CARD_COUNT: [CardCountOverride; 1] = [(Hard, 17, 1, 3, Stnd)];
@@ -0,0 +1,94 @@
+use crate::strat::charts::ChartAction::{DblH, DblS, Hit_, NoAc, Splt, Stnd};
+use crate::strat::charts::{ChartAction, HardTable, SoftTable};
+
+// A simplified basic strategy for beginners, in the spirit of the Wizard of Odds' "simple
+// strategy". Each decision follows one short rule, at a cost of a few hundredths of a percent
+// against the full charts:
+//
+// - Stand on hard 12-16 against 2-6, hit them against 7-A. Always stand on 17 or more.
+// - Double 10 and 11 against 2-9.
+// - Double soft 13-18 against 5 and 6. Stand on soft 18 against 2-8, otherwise hit it.
+// - Always split Aces and 8s. Split 2s, 3s, 6s, 7s and 9s against 2-6.
+//
+// Same layout as the BJA charts: rows as in HARD_CHART / SOFT_CHART / SPLIT_CHART,
+// columns are dealer 2-9, T, A.
+pub(crate) const SIMPLE_HARD_CHART: HardTable = [
+    /* 5 and lower */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 6 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 7 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 8 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 9 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 10 */
+    [DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, Hit_, Hit_],
+    /* 11 */
+    [DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, Hit_, Hit_],
+    /* 12 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 13 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 14 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 15 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 16 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 17 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 18 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 19 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 20 and up */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+];
+
+pub(crate) const SIMPLE_SOFT_CHART: SoftTable = [
+    /* 12 (A, A) */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 13 (A, 2) */
+    [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 14 (A, 3) */
+    [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 15 (A, 4) */
+    [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 16 (A, 5) */
+    [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 17 (A, 6) */
+    [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 18 (A, 7) */
+    [Stnd, Stnd, Stnd, DblS, DblS, Stnd, Stnd, Hit_, Hit_, Hit_],
+    /* 19 (A, 8) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 20 (A, 9) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 21 (A, 10) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+];
+
+pub(crate) const SIMPLE_SPLIT_CHART: [[ChartAction; 10]; 10] = [
+    /*  2 (A, A) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt],
+    /*  4 (2, 2) */
+    [Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /*  6 (3, 3) */
+    [Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /*  8 (4, 4) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 10 (5, 5) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 12 (6, 6) */
+    [Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 14 (7, 7) */
+    [Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 16 (8, 8) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt],
+    /* 18 (9, 9) */
+    [Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 20 (T, T) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+];
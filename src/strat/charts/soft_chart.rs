@@ -1,12 +1,14 @@
 use crate::strat::ChartAction::NoAc;
 use crate::strat::charts::ChartAction::{DblH, DblS, Hit_, Stnd};
-use crate::strat::charts::{Chart, ChartAction, as_chart_column};
+use crate::strat::charts::{Chart, ChartAction, ChartSet, SoftTable, as_chart_column};
 use crate::strat::tableindex::TableType::Soft;
 use crate::strat::tableindex::{ColIndex, RowIndex, TableIndex, new_table_index};
 use crate::{BjError, BjResult, Hand};
 
 // Standard Basic Strategy Soft Totals from BJA
-pub(crate) const SOFT_CHART: [[ChartAction; 10]; 9] = [
+pub(crate) const SOFT_CHART: SoftTable = [
+    /* 12 (A, A) */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
     /* 13 (A, 2) */
     [Hit_, Hit_, Hit_, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
     /* 14 (A, 3) */
@@ -31,6 +33,7 @@ pub struct SoftChart;
 
 impl Chart for SoftChart {
     fn lookup_action(
+        charts: &ChartSet,
         player_hand: &Hand,
         dealer_hand: &Hand,
     ) -> BjResult<(ChartAction, Option<TableIndex>)> {
@@ -40,17 +43,14 @@ impl Chart for SoftChart {
 
         let dealer_card = dealer_hand.first_card().ok_or(BjError::MissingDealerCard)?;
         let total = player_hand.total();
-        if !(13..=21).contains(&total) {
-            // All soft-totals *must* be in the range of 13--21.
-            // (12 is only possible with A,A which is handled by the SplitChart.)
-            return Err(BjError::ValueOutOfRange(total, 13, 21));
-        }
+        // Soft 12 is an A,A that isn't split; soft 21 with two cards is a blackjack, which
+        // is never asked about.
+        let row_index = RowIndex::new(Soft, total)?;
 
         let col_index = ColIndex::new_with_card(dealer_card)?;
         let chart_index = as_chart_column(col_index);
-        let row_index = RowIndex::new(Soft, total)?;
         Ok((
-            SOFT_CHART[(total - 13) as usize][chart_index],
+            charts.soft[(total - 12) as usize][chart_index],
             Some(new_table_index(row_index, col_index)),
         ))
     }
@@ -96,5 +96,9 @@ mod test {
         assert_eq!(DblS, LTH(&["AH", "8C"], &["6S"]));
         assert_eq!(Stnd, LTH(&["AH", "8C"], &["7S"]));
         assert_eq!(Stnd, LTH(&["AH", "8C"], &["TS"]));
+
+        // Unsplit aces and a multi-card 21
+        assert_eq!(Hit_, LTH(&["AH", "AC"], &["6S"]));
+        assert_eq!(Stnd, LTH(&["AH", "4C", "6D"], &["TS"]));
     }
 }
@@ -0,0 +1,143 @@
+use crate::strat::charts::ChartAction::{DblH, DblS, Hit_, NoAc, Splt, Stnd, SurH};
+use crate::strat::charts::overrides::CardCountOverride;
+use crate::strat::charts::{ChartAction, HardTable, SoftTable};
+use crate::strat::tableindex::TableType::{Hard, Soft};
+
+// Spanish 21 Basic Strategy: six or eight 48-card decks, dealer stands on soft 17, double on
+// any number of cards, double after split, late surrender and double-down rescue.
+//
+// The tables below are the play for a two-card hand. Removing the pip tens makes the dealer
+// bust less often and the player's draws safer, so the charts stand and double less than the
+// BJA charts. Larger hands are covered by S21_CARD_COUNT below.
+//
+// Same layout as the BJA charts: rows as in HARD_CHART / SOFT_CHART / SPLIT_CHART,
+// columns are dealer 2-9, T, A.
+pub(crate) const S21_HARD_CHART: HardTable = [
+    /* 5 and lower */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 6 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 7 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 8 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 9 */
+    [Hit_, Hit_, Hit_, Hit_, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 10 */
+    [DblH, DblH, DblH, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_],
+    /* 11 */
+    [DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH, DblH],
+    /* 12 */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 13 */
+    [Hit_, Hit_, Hit_, Hit_, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 14 */
+    [Hit_, Hit_, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 15 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, Hit_, SurH],
+    /* 16 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Hit_, Hit_, Hit_, SurH, SurH],
+    /* 17 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 18 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 19 */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 20 and up */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+];
+
+pub(crate) const S21_SOFT_CHART: SoftTable = [
+    /* 12 (A, A) */
+    [Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 13 (A, 2) */
+    [Hit_, Hit_, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 14 (A, 3) */
+    [Hit_, Hit_, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 15 (A, 4) */
+    [Hit_, Hit_, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 16 (A, 5) */
+    [Hit_, Hit_, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 17 (A, 6) */
+    [Hit_, Hit_, DblH, DblH, DblH, Hit_, Hit_, Hit_, Hit_, Hit_],
+    /* 18 (A, 7) */
+    [Stnd, Stnd, DblS, DblS, DblS, Stnd, Stnd, Hit_, Hit_, Hit_],
+    /* 19 (A, 8) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 20 (A, 9) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+    /* 21 (A, 10) */
+    [Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd, Stnd],
+];
+
+pub(crate) const S21_SPLIT_CHART: [[ChartAction; 10]; 10] = [
+    /*  2 (A, A) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt],
+    /*  4 (2, 2) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc],
+    /*  6 (3, 3) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc],
+    /*  8 (4, 4) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 10 (5, 5) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 12 (6, 6) */
+    [NoAc, NoAc, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc, NoAc],
+    /* 14 (7, 7) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, NoAc, NoAc, NoAc, NoAc],
+    /* 16 (8, 8) */
+    [Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt, Splt],
+    /* 18 (9, 9) */
+    [NoAc, Splt, Splt, Splt, Splt, NoAc, Splt, Splt, NoAc, NoAc],
+    /* 20 (T, T) */
+    [NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc, NoAc],
+];
+
+// Plays that change as the hand grows. A stiff hand made of several small cards has used up
+// the cards that would help it, so it hits (or stops doubling) sooner.
+//
+// Entries for the same cell are listed from fewest to most cards.
+pub(crate) const S21_CARD_COUNT: [CardCountOverride; 34] = [
+    // Hard 11: stop doubling against a ten or an Ace once the hand has four cards.
+    (Hard, 11, 10, 4, Hit_),
+    (Hard, 11, 1, 4, Hit_),
+    // Hard 13 vs 6: stand on two or three cards, hit four or more.
+    (Hard, 13, 6, 4, Hit_),
+    // Hard 14 vs 4-6.
+    (Hard, 14, 4, 4, Hit_),
+    (Hard, 14, 5, 5, Hit_),
+    (Hard, 14, 6, 6, Hit_),
+    // Hard 15 vs 2-6.
+    (Hard, 15, 2, 4, Hit_),
+    (Hard, 15, 3, 5, Hit_),
+    (Hard, 15, 4, 5, Hit_),
+    (Hard, 15, 5, 6, Hit_),
+    (Hard, 15, 6, 6, Hit_),
+    // Hard 16 vs 2-6.
+    (Hard, 16, 2, 4, Hit_),
+    (Hard, 16, 3, 5, Hit_),
+    (Hard, 16, 4, 5, Hit_),
+    (Hard, 16, 5, 6, Hit_),
+    (Hard, 16, 6, 6, Hit_),
+    // Soft doubles: only double small soft hands with few cards.
+    (Soft, 13, 4, 3, Hit_),
+    (Soft, 13, 5, 4, Hit_),
+    (Soft, 13, 6, 5, Hit_),
+    (Soft, 14, 4, 3, Hit_),
+    (Soft, 14, 5, 4, Hit_),
+    (Soft, 14, 6, 5, Hit_),
+    (Soft, 15, 4, 4, Hit_),
+    (Soft, 15, 5, 5, Hit_),
+    (Soft, 16, 4, 4, Hit_),
+    (Soft, 16, 5, 5, Hit_),
+    (Soft, 17, 4, 3, Hit_),
+    (Soft, 17, 5, 4, Hit_),
+    (Soft, 17, 6, 5, Hit_),
+    // Soft 18: stand rather than double with more cards; hit against 2 and 3 with four
+    // or more.
+    (Soft, 18, 4, 3, Stnd),
+    (Soft, 18, 5, 4, Stnd),
+    (Soft, 18, 6, 5, Stnd),
+    (Soft, 18, 2, 4, Hit_),
+    (Soft, 18, 3, 4, Hit_),
+];
@@ -1,5 +1,5 @@
 use crate::strat::charts::ChartAction::{NoAc, SDas, Splt};
-use crate::strat::charts::{Chart, ChartAction, as_chart_column};
+use crate::strat::charts::{Chart, ChartAction, ChartSet, as_chart_column};
 use crate::strat::tableindex::TableType::Split;
 use crate::strat::tableindex::{ColIndex, RowIndex, TableIndex, new_table_index};
 use crate::{BjError, BjResult, Hand};
@@ -38,6 +38,7 @@ pub struct SplitChart;
 
 impl Chart for SplitChart {
     fn lookup_action(
+        charts: &ChartSet,
         player_hand: &Hand,
         dealer_hand: &Hand,
     ) -> BjResult<(ChartAction, Option<TableIndex>)> {
@@ -59,7 +60,7 @@ impl Chart for SplitChart {
 
         let row_index = RowIndex::new(Split, row)?;
         let table_index = new_table_index(row_index, col_index);
-        let chart_action = SPLIT_CHART[(row - 1) as usize][chart_index];
+        let chart_action = charts.split[(row - 1) as usize][chart_index];
         Ok((chart_action, Some(table_index)))
     }
 }
@@ -1,5 +1,5 @@
 use crate::strat::charts::ChartAction::NoAc;
-use crate::strat::charts::{Chart, ChartAction};
+use crate::strat::charts::{Chart, ChartAction, ChartSet};
 use crate::strat::tableindex::TableIndex;
 use crate::{BjResult, Hand};
 
@@ -7,6 +7,7 @@ pub struct SurrenderChart;
 
 impl Chart for SurrenderChart {
     fn lookup_action(
+        _charts: &ChartSet,
         _player_hand: &Hand,
         _dealer_hand: &Hand,
     ) -> BjResult<(ChartAction, Option<TableIndex>)> {
@@ -1,9 +1,17 @@
 mod actions;
 mod charts;
 mod phrases;
+mod rescue;
+mod strategy;
 mod tableindex;
 
 pub use actions::Action;
-pub use charts::{ChartAction, StrategyChart, all_charts, lookup_action, lookup_by_index};
+pub(crate) use charts::{COL_LABELS, row_labels};
+pub use charts::{
+    CellChange, ChartAction, ChartSet, CompositionException, Makeup, StrategyChart, all_charts,
+    lookup_action, lookup_by_index, strategy_charts,
+};
 pub use phrases::{all_phrases, phrase_for_row};
+pub use rescue::rescue_action;
+pub use strategy::{BasicStrategy, CompositionStrategy, SimpleStrategy, Strategy, StrategyKind};
 pub use tableindex::{ColIndex, RowIndex, TableIndex, TableType, new_table_index};
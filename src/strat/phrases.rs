@@ -1,221 +1,303 @@
-use crate::card::Pip::{Ace, Eight, Five, Four, Nine, Seven, Six, Ten, Three, Two};
-use crate::rowidx;
-use crate::strat::tableindex::RowIndex;
+use crate::strat::charts::{ChartAction, ChartSet};
 use crate::strat::tableindex::TableType::{Hard, Soft, Split, Surrender};
-use std::collections::HashMap;
-use std::sync::LazyLock;
+use crate::strat::tableindex::{ColIndex, RowIndex, TableIndex, new_table_index};
+
+// Mnemonic sentences for the rows of a chart set, in the style of the Blackjack Apprentice
+// site (https://www.blackjackapprenticeship.com/blackjack-strategy-charts/), e.g. "12 stands
+// against dealer 4 through 6, otherwise hit."
+//
+// They are generated from the charts, so custom charts, rule variants and other games get
+// text that matches what they grade against.
+
+// Dealer up cards in chart order, as table column values (1 = Ace).
+const DEALER_COLUMNS: [u8; 10] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 1];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Play {
+    Hit,
+    Stand,
+    Double,
+    Split,
+}
+
+impl Play {
+    // "12 stands against..."
+    fn third_person(self) -> &'static str {
+        match self {
+            Play::Hit => "hits",
+            Play::Stand => "stands",
+            Play::Double => "doubles",
+            Play::Split => "splits",
+        }
+    }
+
+    // "...otherwise hit."
+    fn base_form(self) -> &'static str {
+        match self {
+            Play::Hit => "hit",
+            Play::Stand => "stand",
+            Play::Double => "double",
+            Play::Split => "split",
+        }
+    }
+
+    // How a hard or soft cell reads. Surrender has its own phrases; in the totals it reverts
+    // to the play it falls back to (see `total_cell`).
+    fn for_total(action: ChartAction) -> Option<Play> {
+        match action {
+            ChartAction::Hit_ | ChartAction::SurH => Some(Play::Hit),
+            ChartAction::Stnd => Some(Play::Stand),
+            ChartAction::DblH | ChartAction::DblS => Some(Play::Double),
+            ChartAction::Splt | ChartAction::SDas => Some(Play::Split),
+            ChartAction::NoAc => None,
+        }
+    }
+}
+
+fn dealer_label(col: u8) -> &'static str {
+    const LABELS: [&str; 10] = ["Ace", "2", "3", "4", "5", "6", "7", "8", "9", "10"];
+    LABELS[(col - 1) as usize]
+}
+
+fn cell(charts: &ChartSet, row: RowIndex, col: u8) -> Option<ChartAction> {
+    let col: ColIndex = col.to_string().parse().ok()?;
+    let index: TableIndex = new_table_index(row, col);
+    charts.get(&index).ok()
+}
+
+// A cell of the totals, with surrender taken away: 17 vs Ace stands, not hits, when it
+// can't surrender.
+fn total_cell(charts: &ChartSet, row: RowIndex, col: u8) -> Option<ChartAction> {
+    let col: ColIndex = col.to_string().parse().ok()?;
+    charts.without_surrender(&new_table_index(row, col)).ok()
+}
+
+// The dealer cards in `positions` (indices into DEALER_COLUMNS, ascending), e.g.
+// "2 through 6", "5 and 6", "2 through 9, except for 7".
+fn describe_dealer(positions: &[usize]) -> String {
+    let (first, last) = (positions[0], positions[positions.len() - 1]);
+    let label = |p: usize| dealer_label(DEALER_COLUMNS[p]);
+    if positions.len() >= 3 && last - first == positions.len() {
+        // One card missing from the span
+        let gap = (first..=last)
+            .find(|p| !positions.contains(p))
+            .unwrap_or(first);
+        return format!(
+            "{} through {}, except for {}",
+            label(first),
+            label(last),
+            label(gap)
+        );
+    }
+    let mut runs: Vec<String> = Vec::new();
+    let mut start = first;
+    for (i, &p) in positions.iter().enumerate() {
+        let run_ends = positions.get(i + 1).is_none_or(|&next| next != p + 1);
+        if run_ends {
+            runs.push(match p - start {
+                0 => label(p).to_string(),
+                1 => format!("{} and {}", label(start), label(p)),
+                _ => format!("{} through {}", label(start), label(p)),
+            });
+            if let Some(&next) = positions.get(i + 1) {
+                start = next;
+            }
+        }
+    }
+    match runs.len() {
+        1 => runs.remove(0),
+        _ => {
+            let last_run = runs.pop().unwrap_or_default();
+            format!("{} and {}", runs.join(", "), last_run)
+        }
+    }
+}
 
-macro_rules! phrase_row {
-    ($m:expr_2021, $t:expr_2021, $c:expr_2021, $p:expr_2021) => {
-        $m.insert(rowidx!($t, $c), $p);
+// "{subject} stands against dealer 4 through 6, otherwise hit." The fallback is the hit or
+// stand that covers the most dealer cards, hit on a tie.
+fn describe_row(subject: &str, plays: &[Option<Play>; 10]) -> String {
+    let known: Vec<Play> = plays.iter().flatten().copied().collect();
+    let Some(&first) = known.first() else {
+        return format!("{} has no play in this chart.", subject);
     };
+    if known.iter().all(|&p| p == first) {
+        return format!("{} always {}.", subject, first.third_person());
+    }
+    let count = |play: Play| known.iter().filter(|&&p| p == play).count();
+    // max_by_key keeps the last of equal elements
+    let fallback = [Play::Stand, Play::Hit]
+        .into_iter()
+        .filter(|&p| count(p) > 0)
+        .max_by_key(|&p| count(p))
+        .unwrap_or(first);
+
+    let mut clauses = Vec::new();
+    let mut seen = Vec::new();
+    for play in known {
+        if play == fallback || seen.contains(&play) {
+            continue;
+        }
+        seen.push(play);
+        let positions: Vec<usize> = (0..10).filter(|&i| plays[i] == Some(play)).collect();
+        let against = if clauses.is_empty() {
+            "against dealer"
+        } else {
+            "against"
+        };
+        clauses.push(format!(
+            "{} {} {}",
+            play.third_person(),
+            against,
+            describe_dealer(&positions)
+        ));
+    }
+    format!(
+        "{} {}, otherwise {}.",
+        subject,
+        clauses.join(", and "),
+        fallback.base_form()
+    )
 }
 
-// These phrases are from the Blackjack Apprentice site.
-// They are available at this publicly readable link: https://www.blackjackapprenticeship.com/blackjack-strategy-charts/
-static PHRASES: LazyLock<HashMap<RowIndex, &'static str>> = LazyLock::new(|| {
-    let mut m = HashMap::new();
-
-    phrase_row!(
-        m,
-        Surrender,
-        15,
-        "15 surrenders against dealer 10, otherwise don’t surrender (revert to hard totals)."
-    );
-    phrase_row!(
-        m,
-        Surrender,
-        16,
-        "16 surrenders against dealer 9 through Ace, otherwise don’t surrender (revert to hard totals)."
-    );
-
-    phrase_row!(m, Split, Ace.value() - 10, "Always split Aces.");
-    phrase_row!(
-        m,
-        Split,
-        Two.value(),
-        "A pair of 2’s splits against dealer 2 through 7, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Split,
-        Three.value(),
-        "A pair of 3’s splits against dealer 2 through 7, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Split,
-        Four.value(),
-        "A pair of 4’s splits against dealer 5 and 6 , otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Split,
-        Five.value(),
-        "A pair of 5’s doubles against dealer 2 through 9 otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Split,
-        Six.value(),
-        "A pair of 6’s splits against dealer 2 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Split,
-        Seven.value(),
-        "A pair of 7’s splits against dealer 2 through 7, otherwise hit."
-    );
-    phrase_row!(m, Split, Eight.value(), "Always split 8’s.");
-    phrase_row!(
-        m,
-        Split,
-        Nine.value(),
-        "A pair of 9’s splits against dealer 2 through 9, except for 7, otherwise stand."
-    );
-    phrase_row!(m, Split, Ten.value(), "Never split tens.");
-
-    phrase_row!(
-        m,
-        Soft,
-        13,
-        "Soft 13 (A,2) doubles against dealer 5 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Soft,
-        14,
-        "Soft 14 (A,3) doubles against dealer 5 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Soft,
-        15,
-        "Soft 15 (A,4) doubles against dealer 4 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Soft,
-        16,
-        "Soft 16 (A,5) doubles against dealer 4 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Soft,
-        17,
-        "Soft 17 (A,6) doubles against dealer 3 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Soft,
-        18,
-        "Soft 18 (A,7) doubles against dealer 2 through 6, and hits against 9 through Ace, otherwise stand."
-    );
-    phrase_row!(
-        m,
-        Soft,
-        19,
-        "Soft 19 (A,8) doubles against dealer 6, otherwise stand."
-    );
-    phrase_row!(m, Soft, 20, "Soft 20 (A,9) always stands");
-    phrase_row!(m, Soft, 21, "Is a Blackjack. Stand, idiot!");
-
-    phrase_row!(m, Hard, 8, "8 always hits.");
-    phrase_row!(
-        m,
-        Hard,
-        9,
-        "9 doubles against dealer 3 through 6 otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Hard,
-        10,
-        "10 doubles against dealer 2 through 9 otherwise hit."
-    );
-    phrase_row!(m, Hard, 11, "11 always doubles.");
-    phrase_row!(
-        m,
-        Hard,
-        12,
-        "12 stands against dealer 4 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Hard,
-        13,
-        "13 stands against dealer 2 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Hard,
-        14,
-        "14 stands against dealer 2 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Hard,
-        15,
-        "15 stands against dealer 2 through 6, otherwise hit."
-    );
-    phrase_row!(
-        m,
-        Hard,
-        16,
-        "16 stands against dealer 2 through 6, otherwise hit."
-    );
-    phrase_row!(m, Hard, 17, "17 and up always stands.");
-
-    m
-});
-
-pub fn phrase_for_row(ri: RowIndex) -> &'static str {
-    PHRASES
-        .get(&ri)
-        .unwrap_or(&"Internal Error: RowIndex unrecognized.")
+// "8 and below", "13", "17 and up", for the group of hard rows that `total` is in.
+fn hard_subject(charts: &ChartSet, total: u8) -> String {
+    let rows = Hard.rows();
+    let group = charts
+        .hard_row_groups()
+        .into_iter()
+        .find(|g| g.contains(&total))
+        .unwrap_or(total..=total);
+    if group.start() == rows.start() {
+        format!("{} and below", group.end())
+    } else if group.end() == rows.end() {
+        format!("{} and up", group.start())
+    } else {
+        total.to_string()
+    }
+}
+
+fn pair_name(row: u8) -> &'static str {
+    const NAMES: [&str; 10] = [
+        "Aces", "2’s", "3’s", "4’s", "5’s", "6’s", "7’s", "8’s", "9’s", "tens",
+    ];
+    NAMES[(row - 1) as usize]
 }
 
-/// All strategy phrases grouped by category, in display order.
+fn totals_phrase(charts: &ChartSet, ri: RowIndex, subject: &str) -> String {
+    let mut plays = [None; 10];
+    for (i, &col) in DEALER_COLUMNS.iter().enumerate() {
+        plays[i] = total_cell(charts, ri, col).and_then(Play::for_total);
+    }
+    describe_row(subject, &plays)
+}
+
+fn split_phrase(charts: &ChartSet, ri: RowIndex) -> String {
+    let row = ri.index;
+    // Unsplit, a pair plays as its total; a pair of Aces as a soft 12, which always hits.
+    let unsplit = match row {
+        1 => None,
+        v => RowIndex::new(Hard, v * 2).ok(),
+    };
+    let mut plays = [None; 10];
+    let mut splits = 0;
+    for (i, &col) in DEALER_COLUMNS.iter().enumerate() {
+        plays[i] = match cell(charts, ri, col) {
+            Some(ChartAction::Splt | ChartAction::SDas) => {
+                splits += 1;
+                Some(Play::Split)
+            }
+            _ => match unsplit {
+                Some(hard) => total_cell(charts, hard, col).and_then(Play::for_total),
+                None => Some(Play::Hit),
+            },
+        };
+    }
+    match splits {
+        10 => format!("Always split {}.", pair_name(row)),
+        0 => format!("Never split {}.", pair_name(row)),
+        _ => describe_row(&format!("A pair of {}", pair_name(row)), &plays),
+    }
+}
+
+fn surrender_phrase(charts: &ChartSet, total: u8) -> String {
+    let Ok(hard) = RowIndex::new(Hard, total) else {
+        return String::new();
+    };
+    let positions: Vec<usize> = (0..10)
+        .filter(|&i| cell(charts, hard, DEALER_COLUMNS[i]) == Some(ChartAction::SurH))
+        .collect();
+    if positions.is_empty() {
+        format!("{} never surrenders.", total)
+    } else {
+        format!(
+            "{} surrenders against dealer {}, otherwise don’t surrender (revert to hard totals).",
+            total,
+            describe_dealer(&positions)
+        )
+    }
+}
+
+/// The phrase for a chart row, e.g. "A pair of 2’s splits against dealer 2 through 7,
+/// otherwise hit."
+pub fn phrase_for_row(charts: &ChartSet, ri: RowIndex) -> String {
+    match (ri.table_type, ri.index) {
+        (Hard, total @ 5..=20) => totals_phrase(charts, ri, &hard_subject(charts, total)),
+        (Soft, total @ 12..=21) => {
+            let other = match total - 11 {
+                1 => "A".to_string(),
+                10 => "T".to_string(),
+                v => v.to_string(),
+            };
+            totals_phrase(charts, ri, &format!("Soft {} (A,{})", total, other))
+        }
+        (Split, 1..=10) => split_phrase(charts, ri),
+        (Surrender, total @ 15..=17) => surrender_phrase(charts, total),
+        _ => "Internal Error: RowIndex unrecognized.".to_string(),
+    }
+}
+
+// The hard totals the charts surrender with.
+fn surrender_rows(charts: &ChartSet) -> Vec<u8> {
+    Hard.rows()
+        .filter(|&total| {
+            RowIndex::new(Hard, total).is_ok_and(|hard| {
+                DEALER_COLUMNS
+                    .iter()
+                    .any(|&col| cell(charts, hard, col) == Some(ChartAction::SurH))
+            })
+        })
+        .collect()
+}
+
+/// All strategy phrases for a chart set grouped by category, in display order. The
+/// Surrender category only appears for charts that surrender.
 /// Returns Vec of (category_name, Vec<phrase>).
-pub fn all_phrases() -> Vec<(&'static str, Vec<&'static str>)> {
+pub fn all_phrases(charts: &ChartSet) -> Vec<(&'static str, Vec<String>)> {
     let mut result = Vec::new();
 
-    let surrender: Vec<&str> = [15u8, 16]
-        .iter()
-        .filter_map(|&r| RowIndex::new(Surrender, r).ok())
-        .map(phrase_for_row)
-        .collect();
-    result.push(("Surrender", surrender));
-
-    let split: Vec<&str> = [
-        Ace.value() - 10,
-        Two.value(),
-        Three.value(),
-        Four.value(),
-        Five.value(),
-        Six.value(),
-        Seven.value(),
-        Eight.value(),
-        Nine.value(),
-        Ten.value(),
-    ]
-    .iter()
-    .filter_map(|&r| RowIndex::new(Split, r).ok())
-    .map(phrase_for_row)
-    .collect();
-    result.push(("Splits", split));
-
-    let soft: Vec<&str> = (13..=21)
-        .filter_map(|r| RowIndex::new(Soft, r).ok())
-        .map(phrase_for_row)
+    let surrender: Vec<String> = surrender_rows(charts)
+        .into_iter()
+        .map(|total| surrender_phrase(charts, total))
         .collect();
-    result.push(("Soft Totals", soft));
+    if !surrender.is_empty() {
+        result.push(("Surrender", surrender));
+    }
 
-    let hard: Vec<&str> = (8..=17)
-        .filter_map(|r| RowIndex::new(Hard, r).ok())
-        .map(phrase_for_row)
-        .collect();
-    result.push(("Hard Totals", hard));
+    let rows = |table_type, range: std::ops::RangeInclusive<u8>| -> Vec<String> {
+        range
+            .filter_map(|r| RowIndex::new(table_type, r).ok())
+            .map(|ri| phrase_for_row(charts, ri))
+            .collect()
+    };
+    result.push(("Splits", rows(Split, Split.rows())));
+    result.push(("Soft Totals", rows(Soft, Soft.rows())));
+    // One phrase per group of hard rows that play the same ("17 and up")
+    let hard_groups = charts.hard_row_groups();
+    let hard_firsts = hard_groups.iter().map(|g| *g.start());
+    result.push((
+        "Hard Totals",
+        hard_firsts.flat_map(|r| rows(Hard, r..=r)).collect(),
+    ));
 
     result
 }
@@ -223,14 +305,27 @@ pub fn all_phrases() -> Vec<(&'static str, Vec<&'static str>)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rules::{Rules, Soft17};
+    use crate::strat::tableindex::TableType;
+
+    fn surrender_charts() -> ChartSet {
+        ChartSet::for_rules(&Rules {
+            surrender: true,
+            ..Default::default()
+        })
+    }
+
+    fn phrase(charts: &ChartSet, table_type: TableType, row: u8) -> String {
+        phrase_for_row(charts, RowIndex::new(table_type, row).unwrap())
+    }
 
     // --- phrase_for_row(): returns non-error strings for all known rows ---
 
     #[test]
-    fn phrase_for_row_hard_8_through_17_all_return_real_phrases() {
-        for row in 8u8..=17 {
+    fn phrase_for_row_hard_5_through_20_all_return_real_phrases() {
+        for row in 5u8..=20 {
             let ri = RowIndex::new(Hard, row).unwrap();
-            let phrase = phrase_for_row(ri);
+            let phrase = phrase_for_row(&ChartSet::bja(), ri);
             assert!(
                 !phrase.starts_with("Internal Error"),
                 "unexpected error phrase for hard:{}: {}",
@@ -242,10 +337,10 @@ mod tests {
     }
 
     #[test]
-    fn phrase_for_row_soft_13_through_21_all_return_real_phrases() {
-        for row in 13u8..=21 {
+    fn phrase_for_row_soft_12_through_21_all_return_real_phrases() {
+        for row in 12u8..=21 {
             let ri = RowIndex::new(Soft, row).unwrap();
-            let phrase = phrase_for_row(ri);
+            let phrase = phrase_for_row(&ChartSet::bja(), ri);
             assert!(
                 !phrase.starts_with("Internal Error"),
                 "unexpected error phrase for soft:{}: {}",
@@ -260,7 +355,7 @@ mod tests {
     fn phrase_for_row_split_1_through_10_all_return_real_phrases() {
         for row in 1u8..=10 {
             let ri = RowIndex::new(Split, row).unwrap();
-            let phrase = phrase_for_row(ri);
+            let phrase = phrase_for_row(&ChartSet::bja(), ri);
             assert!(
                 !phrase.starts_with("Internal Error"),
                 "unexpected error phrase for split:{}: {}",
@@ -275,7 +370,7 @@ mod tests {
     fn phrase_for_row_surrender_15_and_16_return_real_phrases() {
         for row in [15u8, 16] {
             let ri = RowIndex::new(Surrender, row).unwrap();
-            let phrase = phrase_for_row(ri);
+            let phrase = phrase_for_row(&ChartSet::bja(), ri);
             assert!(
                 !phrase.starts_with("Internal Error"),
                 "unexpected error phrase for surrender:{}: {}",
@@ -289,10 +384,10 @@ mod tests {
     #[test]
     fn phrase_for_row_unknown_row_index_returns_internal_error() {
         // RowIndex::new validates the range, so build one for a valid row but
-        // a type that has no phrase entry. Use Hard:2 which is valid for the table
-        // type range check but absent from PHRASES.
-        let ri = RowIndex::new(Hard, 2).unwrap();
-        let phrase = phrase_for_row(ri);
+        // a type that has no phrase entry. Use Surrender:14 which is valid for the table
+        // type range check but has no surrender phrase.
+        let ri = RowIndex::new(Surrender, 14).unwrap();
+        let phrase = phrase_for_row(&ChartSet::bja(), ri);
         assert!(
             phrase.starts_with("Internal Error"),
             "expected internal error phrase, got: {}",
@@ -305,7 +400,7 @@ mod tests {
     #[test]
     fn phrase_for_row_hard_11_mentions_always_doubles() {
         let ri = RowIndex::new(Hard, 11).unwrap();
-        let phrase = phrase_for_row(ri);
+        let phrase = phrase_for_row(&ChartSet::bja(), ri);
         assert!(
             phrase.contains("always") || phrase.contains("double") || phrase.contains("Double"),
             "unexpected phrase for hard:11: {}",
@@ -316,7 +411,7 @@ mod tests {
     #[test]
     fn phrase_for_row_split_aces_mentions_split() {
         let ri = RowIndex::new(Split, 1).unwrap();
-        let phrase = phrase_for_row(ri);
+        let phrase = phrase_for_row(&ChartSet::bja(), ri);
         assert!(
             phrase.contains("split") || phrase.contains("Split"),
             "unexpected phrase for split:1: {}",
@@ -324,17 +419,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn phrases_follow_the_chart_rows() {
+        let charts = ChartSet::bja();
+        assert_eq!(
+            "12 stands against dealer 4 through 6, otherwise hit.",
+            phrase(&charts, Hard, 12)
+        );
+        assert_eq!("11 always doubles.", phrase(&charts, Hard, 11));
+        assert_eq!("8 and below always hits.", phrase(&charts, Hard, 6));
+        assert_eq!("17 and up always stands.", phrase(&charts, Hard, 19));
+        assert_eq!("Soft 12 (A,A) always hits.", phrase(&charts, Soft, 12));
+        assert_eq!(
+            "13 stands against dealer 2 through 6, otherwise hit.",
+            phrase(&charts, Hard, 13)
+        );
+        assert_eq!(
+            "9 doubles against dealer 3 through 6, otherwise hit.",
+            phrase(&charts, Hard, 9)
+        );
+        assert_eq!(
+            "Soft 13 (A,2) doubles against dealer 5 and 6, otherwise hit.",
+            phrase(&charts, Soft, 13)
+        );
+        assert_eq!(
+            "A pair of 2’s splits against dealer 2 through 7, otherwise hit.",
+            phrase(&charts, Split, 2)
+        );
+        assert_eq!(
+            "A pair of 9’s splits against dealer 2 through 9, except for 7, otherwise stand.",
+            phrase(&charts, Split, 9)
+        );
+        assert_eq!("Always split Aces.", phrase(&charts, Split, 1));
+        // The 5,5 row never splits; it plays as a hard 10.
+        assert_eq!("Never split 5’s.", phrase(&charts, Split, 5));
+    }
+
+    #[test]
+    fn phrases_follow_the_rules() {
+        let s17 = ChartSet::for_rules(&Rules {
+            soft_17: Soft17::Stands,
+            ..Default::default()
+        });
+        assert_eq!(
+            "11 doubles against dealer 2 through 10, otherwise hit.",
+            phrase(&s17, Hard, 11)
+        );
+        assert_eq!(
+            "16 surrenders against dealer 9 through Ace, otherwise don’t surrender (revert to hard totals).",
+            phrase(&surrender_charts(), Surrender, 16)
+        );
+        assert_eq!(
+            "16 never surrenders.",
+            phrase(&ChartSet::bja(), Surrender, 16)
+        );
+        // No surrender, no Surrender category.
+        assert_eq!(3, all_phrases(&ChartSet::bja()).len());
+    }
+
     // --- all_phrases(): structure ---
 
     #[test]
-    fn all_phrases_returns_four_categories() {
-        let phrases = all_phrases();
+    fn all_phrases_returns_four_categories_with_surrender() {
+        let phrases = all_phrases(&surrender_charts());
         assert_eq!(4, phrases.len());
     }
 
     #[test]
     fn all_phrases_category_names_are_correct() {
-        let phrases = all_phrases();
+        let phrases = all_phrases(&surrender_charts());
         let names: Vec<&str> = phrases.iter().map(|(name, _)| *name).collect();
         assert!(names.contains(&"Surrender"));
         assert!(names.contains(&"Splits"));
@@ -343,47 +496,53 @@ mod tests {
     }
 
     #[test]
-    fn all_phrases_surrender_has_two_entries() {
-        let phrases = all_phrases();
+    fn all_phrases_surrender_has_three_entries() {
+        let phrases = all_phrases(&surrender_charts());
         let surrender = phrases
             .iter()
             .find(|(name, _)| *name == "Surrender")
             .unwrap();
-        assert_eq!(2, surrender.1.len());
+        // 15, 16, and 17 vs Ace with H17
+        assert_eq!(3, surrender.1.len());
+        assert_eq!(
+            "17 surrenders against dealer Ace, otherwise don’t surrender (revert to hard totals).",
+            surrender.1[2]
+        );
     }
 
     #[test]
     fn all_phrases_splits_has_ten_entries() {
-        let phrases = all_phrases();
+        let phrases = all_phrases(&surrender_charts());
         let splits = phrases.iter().find(|(name, _)| *name == "Splits").unwrap();
         assert_eq!(10, splits.1.len());
     }
 
     #[test]
-    fn all_phrases_soft_totals_has_nine_entries() {
-        let phrases = all_phrases();
+    fn all_phrases_soft_totals_has_ten_entries() {
+        let phrases = all_phrases(&surrender_charts());
         let soft = phrases
             .iter()
             .find(|(name, _)| *name == "Soft Totals")
             .unwrap();
-        // 13..=21 = 9 rows
-        assert_eq!(9, soft.1.len());
+        // 12..=21 = 10 rows
+        assert_eq!(10, soft.1.len());
     }
 
     #[test]
     fn all_phrases_hard_totals_has_ten_entries() {
-        let phrases = all_phrases();
+        let phrases = all_phrases(&surrender_charts());
         let hard = phrases
             .iter()
             .find(|(name, _)| *name == "Hard Totals")
             .unwrap();
-        // 8..=17 = 10 rows
-        assert_eq!(10, hard.1.len());
+        // 8 and below, 9..=17, 18 and up: surrendering 17 vs Ace sets 17 apart
+        assert_eq!(11, hard.1.len());
+        assert_eq!("17 always stands.", hard.1[9]);
     }
 
     #[test]
     fn all_phrases_no_entry_starts_with_internal_error() {
-        let phrases = all_phrases();
+        let phrases = all_phrases(&surrender_charts());
         for (cat, entries) in &phrases {
             for phrase in entries {
                 assert!(
@@ -398,7 +557,7 @@ mod tests {
 
     #[test]
     fn all_phrases_no_entry_is_empty() {
-        let phrases = all_phrases();
+        let phrases = all_phrases(&surrender_charts());
         for (cat, entries) in &phrases {
             for phrase in entries {
                 assert!(
@@ -0,0 +1,67 @@
+use crate::rules::Rules;
+use crate::{Action, Hand};
+
+// Double-down rescue (Spanish 21).
+//
+// After doubling, the player may take the hand back: surrender it and lose only the original
+// bet, keeping the doubled part. A doubled hand of 16 or less only wins if the dealer busts,
+// so standing on it is worth 2 * (2 * bust - 1) original bets, against -1 for a rescue.
+// That makes rescue right whenever the dealer busts less than a quarter of the time, which is
+// against an 8, 9, ten or Ace.
+const RESCUE_MAX_TOTAL: u8 = 16;
+
+/// The play for a hand that has been doubled and dealt its card: Surrender to rescue it, or
+/// Stand. Returns None when the rules don't offer rescue or the dealer has no upcard.
+pub fn rescue_action(player_hand: &Hand, dealer_hand: &Hand, rules: &Rules) -> Option<Action> {
+    if !rules.double_down_rescue() {
+        return None;
+    }
+    let upcard = dealer_hand.first_card()?;
+    let strong_upcard = matches!(upcard.value(), 8..=11);
+    if player_hand.total() <= RESCUE_MAX_TOTAL && strong_upcard {
+        Some(Action::Surrender)
+    } else {
+        Some(Action::Stand)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::GameVariant;
+
+    fn spanish21() -> Rules {
+        Rules {
+            variant: GameVariant::Spanish21,
+            ..Default::default()
+        }
+    }
+
+    fn hand(s: &str) -> Hand {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn no_rescue_in_standard_blackjack() {
+        let action = rescue_action(&hand("6H 5C 4D"), &hand("TS"), &Rules::default());
+        assert_eq!(None, action);
+    }
+
+    #[test]
+    fn rescue_stiff_hand_against_strong_upcard() {
+        let rules = spanish21();
+        for up in ["8S", "9S", "KS", "AS"] {
+            let action = rescue_action(&hand("6H 5C 4D"), &hand(up), &rules);
+            assert_eq!(Some(Action::Surrender), action, "vs {}", up);
+        }
+    }
+
+    #[test]
+    fn stand_against_weak_upcard_or_made_hand() {
+        let rules = spanish21();
+        let action = rescue_action(&hand("6H 5C 4D"), &hand("7S"), &rules);
+        assert_eq!(Some(Action::Stand), action);
+        let action = rescue_action(&hand("6H 5C 7D"), &hand("TS"), &rules);
+        assert_eq!(Some(Action::Stand), action);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::rules::Rules;
+use crate::strat::charts::{ChartAction, ChartSet};
+use crate::strat::tableindex::TableIndex;
+use crate::{BjResult, Hand};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// A way of playing the hands: what the player is learning.
+///
+/// Every strategy is built on a set of two-card charts, which is what the strategy screens
+/// show and what per-cell study modes (e.g. Doubles) are drawn from. `lookup` decides the play
+/// for an actual hand, and may look past the charts at the cards in the hand or the count.
+pub trait Strategy {
+    /// The charts for these rules.
+    fn charts(&self, rules: &Rules) -> ChartSet;
+
+    /// The strategy's part of the spaced-rep keys learned under it, e.g. "basic".
+    fn key(&self) -> String;
+
+    /// The play for this hand, and the chart cell it is graded under. `count` is the true
+    /// count; strategies without count-based plays ignore it.
+    fn lookup(
+        &self,
+        player: &Hand,
+        dealer: &Hand,
+        rules: &Rules,
+        count: i32,
+    ) -> BjResult<(ChartAction, Option<TableIndex>)> {
+        let _ = count;
+        self.charts(rules).lookup_action(player, dealer)
+    }
+}
+
+/// Total-dependent basic strategy: the BJA charts, adjusted for the rules.
+pub struct BasicStrategy;
+
+impl Strategy for BasicStrategy {
+    fn charts(&self, rules: &Rules) -> ChartSet {
+        ChartSet::for_rules(rules)
+    }
+
+    fn key(&self) -> String {
+        "basic".to_string()
+    }
+}
+
+/// A beginner strategy that is easier to remember than the full charts. It is the same for
+/// every set of rules.
+pub struct SimpleStrategy;
+
+impl Strategy for SimpleStrategy {
+    fn charts(&self, _rules: &Rules) -> ChartSet {
+        ChartSet::simple()
+    }
+
+    fn key(&self) -> String {
+        "simple".to_string()
+    }
+}
+
+/// Basic strategy plus the plays that depend on which cards make up the hand.
+pub struct CompositionStrategy;
+
+impl Strategy for CompositionStrategy {
+    fn charts(&self, rules: &Rules) -> ChartSet {
+        ChartSet::composition_dependent(rules)
+    }
+
+    fn key(&self) -> String {
+        "comp".to_string()
+    }
+}
+
+/// A fixed chart set (e.g. loaded from a file) is a strategy that ignores the rules.
+impl Strategy for ChartSet {
+    fn charts(&self, _rules: &Rules) -> ChartSet {
+        self.clone()
+    }
+
+    /// "chart" and the fingerprint, so each chart keeps its own progress.
+    fn key(&self) -> String {
+        format!("chart{}", self.fingerprint())
+    }
+
+    fn lookup(
+        &self,
+        player: &Hand,
+        dealer: &Hand,
+        _rules: &Rules,
+        _count: i32,
+    ) -> BjResult<(ChartAction, Option<TableIndex>)> {
+        self.lookup_action(player, dealer)
+    }
+}
+
+/// The built-in strategies a user can choose to learn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StrategyKind {
+    #[default]
+    Basic,
+    Simple,
+    Composition,
+}
+
+impl StrategyKind {
+    pub fn next(self) -> Self {
+        match self {
+            StrategyKind::Basic => StrategyKind::Simple,
+            StrategyKind::Simple => StrategyKind::Composition,
+            StrategyKind::Composition => StrategyKind::Basic,
+        }
+    }
+
+    pub fn strategy(self) -> &'static dyn Strategy {
+        match self {
+            StrategyKind::Basic => &BasicStrategy,
+            StrategyKind::Simple => &SimpleStrategy,
+            StrategyKind::Composition => &CompositionStrategy,
+        }
+    }
+}
+
+impl Display for StrategyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            StrategyKind::Basic => "Basic strategy",
+            StrategyKind::Simple => "Simple strategy",
+            StrategyKind::Composition => "Composition-dependent",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(s: &str) -> Hand {
+        s.parse().unwrap()
+    }
+
+    fn play(kind: StrategyKind, player: &str, dealer: &str) -> ChartAction {
+        let rules = Rules::default();
+        let (action, _) = kind
+            .strategy()
+            .lookup(&hand(player), &hand(dealer), &rules, 0)
+            .unwrap();
+        action
+    }
+
+    #[test]
+    fn basic_is_the_rules_charts() {
+        let rules = Rules::default();
+        assert_eq!(ChartSet::for_rules(&rules), BasicStrategy.charts(&rules));
+        assert_eq!(ChartAction::DblH, play(StrategyKind::Basic, "5H 4C", "3S"));
+    }
+
+    #[test]
+    fn simple_strategy_keeps_it_simple() {
+        // Basic doubles 9 vs 3 and 11 vs A; simple just hits.
+        assert_eq!(ChartAction::Hit_, play(StrategyKind::Simple, "5H 4C", "3S"));
+        assert_eq!(ChartAction::Hit_, play(StrategyKind::Simple, "5H 6C", "AS"));
+        // Basic hits 12 vs 2; simple stands on every stiff against 2-6.
+        assert_eq!(ChartAction::Stnd, play(StrategyKind::Simple, "TH 2C", "2S"));
+        assert_eq!(ChartAction::Splt, play(StrategyKind::Simple, "8H 8C", "AS"));
+    }
+
+    #[test]
+    fn composition_stands_on_multi_card_sixteen_vs_ten() {
+        assert_eq!(
+            ChartAction::Hit_,
+            play(StrategyKind::Basic, "4H 5C 7D", "TS")
+        );
+        assert_eq!(
+            ChartAction::Stnd,
+            play(StrategyKind::Composition, "4H 5C 7D", "TS")
+        );
+        assert_eq!(
+            ChartAction::Hit_,
+            play(StrategyKind::Composition, "TH 6C", "TS")
+        );
+    }
+
+    #[test]
+    fn kinds_cycle() {
+        let mut kind = StrategyKind::default();
+        for _ in 0..3 {
+            kind = kind.next();
+        }
+        assert_eq!(StrategyKind::Basic, kind);
+    }
+}
@@ -13,7 +13,7 @@ impl ColIndex {
         ColIndex::new(card.value())
     }
 
-    fn new(val: u8) -> BjResult<ColIndex> {
+    pub(crate) fn new(val: u8) -> BjResult<ColIndex> {
         if val == 11 {
             return Ok(ColIndex(1));
         }
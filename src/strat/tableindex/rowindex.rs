@@ -45,24 +45,24 @@ mod test {
         assert_eq!(
             RowIndex {
                 table_type: "hard".parse().unwrap(),
-                index: 3
+                index: 13
             },
-            RowIndex::new(TableType::Hard, 3).unwrap()
+            RowIndex::new(TableType::Hard, 13).unwrap()
         );
     }
 
     #[test]
     fn test_parse() {
         assert_eq!(
-            RowIndex::new(TableType::Hard, 3).unwrap(),
-            "hard:3".parse().unwrap()
+            RowIndex::new(TableType::Hard, 13).unwrap(),
+            "hard:13".parse().unwrap()
         );
     }
 
     #[test]
     fn test_range_check_works() {
         assert_eq!(
-            Err(BjError::ValueOutOfRange(0, 2, 21)),
+            Err(BjError::ValueOutOfRange(0, 5, 20)),
             "hard:0".parse::<RowIndex>()
         );
     }
@@ -1,6 +1,7 @@
 use crate::BjError::{UnknownTableType, ValueOutOfRange};
 use crate::{BjError, BjResult};
 use std::fmt::Display;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -12,30 +13,38 @@ pub enum TableType {
 }
 
 impl TableType {
-    // TODO: verify these ranges against the strategy tables.
-    pub fn range_check(self, row: u8) -> BjResult<()> {
+    /// The rows of this table in the strategy charts. Hard row 5 also covers a hard 4 (an
+    /// unsplit 2,2) and row 20 a multi-card hard 21. Soft 12 is an unsplit A,A and soft 21
+    /// a multi-card 21 (two cards make a blackjack).
+    pub fn rows(self) -> RangeInclusive<u8> {
         match self {
-            TableType::Hard | TableType::Soft => {
-                if (2u8..=21).contains(&row) {
-                    Ok(())
-                } else {
-                    Err(BjError::ValueOutOfRange(row, 2, 21))
-                }
-            }
-            TableType::Split => {
-                if (1u8..=10).contains(&row) {
-                    Ok(())
-                } else {
-                    Err(ValueOutOfRange(row, 1, 10))
-                }
-            }
-            TableType::Surrender => {
-                if (2u8..=20).contains(&row) {
-                    Ok(())
-                } else {
-                    Err(ValueOutOfRange(row, 2, 20))
-                }
-            }
+            TableType::Hard => 5..=20,
+            TableType::Soft => 12..=21,
+            TableType::Split => 1..=10,
+            TableType::Surrender => 2..=20,
+        }
+    }
+
+    pub fn range_check(self, row: u8) -> BjResult<()> {
+        let rows = self.rows();
+        if rows.contains(&row) {
+            Ok(())
+        } else {
+            Err(ValueOutOfRange(row, *rows.start(), *rows.end()))
+        }
+    }
+}
+
+impl TryFrom<u8> for TableType {
+    type Error = BjError;
+
+    fn try_from(value: u8) -> BjResult<Self> {
+        match value {
+            0 => Ok(TableType::Hard),
+            1 => Ok(TableType::Soft),
+            2 => Ok(TableType::Split),
+            3 => Ok(TableType::Surrender),
+            _ => Err(ValueOutOfRange(value, 0, 3)),
         }
     }
 }
@@ -84,17 +93,17 @@ mod test {
     #[test]
     fn test_hard_range_check() {
         assert!(TableType::Hard.range_check(0).is_err());
-        assert!(TableType::Hard.range_check(1).is_err());
-        assert!(TableType::Hard.range_check(2).is_ok());
-        assert!(TableType::Hard.range_check(21).is_ok());
-        assert!(TableType::Hard.range_check(22).is_err());
+        assert!(TableType::Hard.range_check(4).is_err());
+        assert!(TableType::Hard.range_check(5).is_ok());
+        assert!(TableType::Hard.range_check(20).is_ok());
+        assert!(TableType::Hard.range_check(21).is_err());
     }
 
     #[test]
     fn test_soft_range_check() {
         assert!(TableType::Soft.range_check(0).is_err());
-        assert!(TableType::Soft.range_check(1).is_err());
-        assert!(TableType::Soft.range_check(2).is_ok());
+        assert!(TableType::Soft.range_check(11).is_err());
+        assert!(TableType::Soft.range_check(12).is_ok());
         assert!(TableType::Soft.range_check(21).is_ok());
         assert!(TableType::Soft.range_check(22).is_err());
     }
@@ -0,0 +1,274 @@
+use crate::strat::{Action, ChartSet, TableIndex, TableType};
+use crate::table_index_keys::indices_for_type;
+use crate::{BjError, BjResult};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A named group of chart cells the player picked to practise, e.g. "Soft doubles: soft vs
+/// 3-6 double". Every filter that is left empty matches everything.
+///
+/// As text a set is its name, a colon and the filter. The filter is any of:
+/// - tables: `hard`, `soft`, `pairs`
+/// - a row or range of rows, as the table numbers them: `16`, `13-18` (pairs by card, `A`-`T`)
+/// - `vs` and dealer upcards: `vs 3-6`, `vs 9 T A`
+/// - plays: `hit`, `stand`, `double`, `split`, `surrender`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StudySet {
+    pub name: String,
+    #[serde(default)]
+    pub tables: Vec<TableType>,
+    /// First and last row.
+    #[serde(default)]
+    pub rows: Option<(u8, u8)>,
+    /// Dealer upcards, 1 for an ace.
+    #[serde(default)]
+    pub upcards: Vec<u8>,
+    /// The plays under the charts being graded.
+    #[serde(default)]
+    pub actions: Vec<Action>,
+}
+
+impl StudySet {
+    /// The cells of the set in the given charts, hard then soft then pairs.
+    pub fn indices(&self, charts: &ChartSet) -> Vec<TableIndex> {
+        [TableType::Hard, TableType::Soft, TableType::Split]
+            .into_iter()
+            .filter(|tt| self.tables.is_empty() || self.tables.contains(tt))
+            .flat_map(indices_for_type)
+            .filter(|ti| self.matches(ti, charts))
+            .collect()
+    }
+
+    fn matches(&self, index: &TableIndex, charts: &ChartSet) -> bool {
+        let row = index.row_index();
+        self.rows
+            .is_none_or(|(first, last)| (first..=last).contains(&row))
+            && (self.upcards.is_empty() || self.upcards.contains(&index.col_index().value()))
+            && (self.actions.is_empty()
+                || charts
+                    .get(index)
+                    .ok()
+                    .and_then(|a| a.apply_rules())
+                    .is_some_and(|a| self.actions.contains(&a)))
+    }
+
+    /// The filter as text, e.g. "soft vs 3-6 double"; "everything" when nothing is filtered.
+    pub fn filter(&self) -> String {
+        let mut parts: Vec<String> = self
+            .tables
+            .iter()
+            .map(|tt| match tt {
+                TableType::Split => "pairs".to_string(),
+                tt => tt.to_string(),
+            })
+            .collect();
+        if let Some((first, last)) = self.rows {
+            let pairs = self.tables == [TableType::Split];
+            let label = |row| {
+                if pairs {
+                    card_label(row)
+                } else {
+                    row.to_string()
+                }
+            };
+            parts.push(if first == last {
+                label(first)
+            } else {
+                format!("{}-{}", label(first), label(last))
+            });
+        }
+        if !self.upcards.is_empty() {
+            parts.push(format!("vs {}", upcards_label(&self.upcards)));
+        }
+        parts.extend(self.actions.iter().map(|a| a.to_string().to_lowercase()));
+        if parts.is_empty() {
+            "everything".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+
+    /// Parse a filter, as `filter` writes it, into a set with the given name.
+    pub fn parse(name: &str, filter: &str) -> BjResult<Self> {
+        let bad = |why: &str| BjError::InvalidStudySet(format!("{}: {}", why, filter.trim()));
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(bad("the set needs a name"));
+        }
+        let mut set = StudySet {
+            name: name.to_string(),
+            tables: Vec::new(),
+            rows: None,
+            upcards: Vec::new(),
+            actions: Vec::new(),
+        };
+        let mut after_vs = false;
+        for token in filter
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+        {
+            let token = token.to_lowercase();
+            match token.as_str() {
+                "everything" => {}
+                "hard" => set.tables.push(TableType::Hard),
+                "soft" => set.tables.push(TableType::Soft),
+                "pairs" => set.tables.push(TableType::Split),
+                "hit" => set.actions.push(Action::Hit),
+                "stand" => set.actions.push(Action::Stand),
+                "double" => set.actions.push(Action::Double),
+                "split" => set.actions.push(Action::Split),
+                "surrender" => set.actions.push(Action::Surrender),
+                "vs" => after_vs = true,
+                range => {
+                    let (first, last) = parse_range(range)
+                        .ok_or_else(|| bad("not a table, play, card or range"))?;
+                    if after_vs {
+                        if last > 10 {
+                            return Err(bad("dealer cards run from 2 to A"));
+                        }
+                        set.upcards.extend(first..=last);
+                    } else if set.rows.is_some() {
+                        return Err(bad("give one row or one range of rows"));
+                    } else {
+                        set.rows = Some((first, last));
+                    }
+                }
+            }
+        }
+        set.tables.dedup();
+        set.upcards.sort_unstable();
+        set.upcards.dedup();
+        set.actions.dedup();
+        Ok(set)
+    }
+}
+
+// "13", "3-6", "A", "9-T"
+fn parse_range(s: &str) -> Option<(u8, u8)> {
+    let value = |v: &str| match v {
+        "a" => Some(1),
+        "t" => Some(10),
+        n => n.parse::<u8>().ok().filter(|n| (1..=21).contains(n)),
+    };
+    let (first, last) = match s.split_once('-') {
+        Some((first, last)) => (value(first)?, value(last)?),
+        None => (value(s)?, value(s)?),
+    };
+    (first <= last).then_some((first, last))
+}
+
+fn card_label(value: u8) -> String {
+    match value {
+        1 => "A".to_string(),
+        10 => "T".to_string(),
+        n => n.to_string(),
+    }
+}
+
+// Runs of upcards as ranges, the ace last as the charts have it: "3-6", "9 T A".
+fn upcards_label(upcards: &[u8]) -> String {
+    let mut parts = Vec::new();
+    let mut cards = upcards.iter().copied().filter(|&c| c != 1).peekable();
+    while let Some(first) = cards.next() {
+        let mut last = first;
+        while cards.peek() == Some(&(last + 1)) {
+            last = cards.next().unwrap_or(last);
+        }
+        match last - first {
+            0 => parts.push(card_label(first)),
+            1 => parts.extend([card_label(first), card_label(last)]),
+            _ => parts.push(format!("{}-{}", card_label(first), card_label(last))),
+        }
+    }
+    if upcards.contains(&1) {
+        parts.push("A".to_string());
+    }
+    parts.join(" ")
+}
+
+impl Display for StudySet {
+    /// e.g. "Soft doubles: soft vs 3-6 double".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.filter())
+    }
+}
+
+impl FromStr for StudySet {
+    type Err = BjError;
+
+    /// A set as `Display` writes it: the name, a colon and the filter.
+    fn from_str(s: &str) -> BjResult<Self> {
+        let (name, filter) = s
+            .split_once(':')
+            .ok_or_else(|| BjError::InvalidStudySet(format!("expected \"name: filter\": {}", s)))?;
+        StudySet::parse(name, filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rules;
+    use crate::strat::ChartAction;
+
+    #[test]
+    fn soft_doubles_vs_three_to_six() {
+        let set: StudySet = "Soft doubles: soft vs 3-6 double".parse().unwrap();
+        assert_eq!("Soft doubles: soft vs 3-6 double", set.to_string());
+        let charts = ChartSet::for_rules(&Rules::default());
+        let cells = set.indices(&charts);
+        assert!(!cells.is_empty());
+        for ti in &cells {
+            assert_eq!(TableType::Soft, ti.table_type());
+            assert!((3..=6).contains(&ti.col_index().value()));
+            let play = charts.get(ti).unwrap();
+            assert!(matches!(play, ChartAction::DblH | ChartAction::DblS));
+        }
+        assert!(cells.contains(&"soft:18,6".parse().unwrap()));
+    }
+
+    #[test]
+    fn rows_and_upcards() {
+        let set = StudySet::parse("Stiffs", "hard 12-16 vs 7 8 9 T A").unwrap();
+        assert_eq!("hard 12-16 vs 7-T A", set.filter());
+        assert_eq!(25, set.indices(&ChartSet::bja()).len());
+        let pairs = StudySet::parse("Big pairs", "pairs 8-a").unwrap_err();
+        assert!(matches!(pairs, BjError::InvalidStudySet(_)));
+        let pairs = StudySet::parse("Aces and eights", "pairs a-8 vs 9,t").unwrap();
+        assert_eq!("pairs A-8 vs 9 T", pairs.filter());
+    }
+
+    #[test]
+    fn an_empty_filter_is_every_cell() {
+        let set = StudySet::parse("All", "").unwrap();
+        assert_eq!("everything", set.filter());
+        assert_eq!(set, StudySet::parse("All", "everything").unwrap());
+        assert_eq!(160 + 90 + 100, set.indices(&ChartSet::bja()).len());
+    }
+
+    #[test]
+    fn bad_filters_are_errors() {
+        assert!(StudySet::parse("", "hard").is_err());
+        assert!(StudySet::parse("x", "hard vs 11").is_err());
+        assert!(StudySet::parse("x", "hard 12 13").is_err());
+        assert!(StudySet::parse("x", "soft doubles").is_err());
+        assert!("no colon".parse::<StudySet>().is_err());
+    }
+
+    #[test]
+    fn sets_save_as_toml() {
+        let set: StudySet = "Soft doubles: soft vs 3-6 double".parse().unwrap();
+        #[derive(Serialize, Deserialize)]
+        struct Saved {
+            sets: Vec<StudySet>,
+        }
+        let text = toml::to_string(&Saved {
+            sets: vec![set.clone()],
+        })
+        .unwrap();
+        assert!(text.contains("tables = [\"soft\"]"));
+        let back: Saved = toml::from_str(&text).unwrap();
+        assert_eq!(vec![set], back.sets);
+    }
+}
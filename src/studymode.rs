@@ -10,6 +10,16 @@ pub enum StudyMode {
     Splits,
     Doubles,
     Drill,
+    /// Only the cells that differ from the rules the player already knows.
+    Changes,
+    /// The cells the player keeps getting wrong, from the answer history and the deck.
+    Weak,
+    /// Spaced repetition over the chart a region at a time, each unlocking when the one
+    /// before is mastered.
+    Curriculum,
+    /// The cells of a study set the player defined. Not in `ALL` or the `next` cycle: it is
+    /// picked through a set.
+    Custom,
 }
 
 impl StudyMode {
@@ -20,7 +30,10 @@ impl StudyMode {
             StudyMode::Hard => StudyMode::Soft,
             StudyMode::Soft => StudyMode::Splits,
             StudyMode::Splits => StudyMode::Doubles,
-            StudyMode::Doubles => StudyMode::All,
+            StudyMode::Doubles => StudyMode::Changes,
+            StudyMode::Changes => StudyMode::Weak,
+            StudyMode::Weak => StudyMode::Curriculum,
+            StudyMode::Curriculum | StudyMode::Custom => StudyMode::All,
         }
     }
 
@@ -38,6 +51,10 @@ impl StudyMode {
             StudyMode::Splits => "splits",
             StudyMode::Doubles => "doubles",
             StudyMode::Drill => "drill",
+            StudyMode::Changes => "changes",
+            StudyMode::Weak => "weak",
+            StudyMode::Curriculum => "curriculum",
+            StudyMode::Custom => "custom",
         }
     }
 
@@ -50,6 +67,10 @@ impl StudyMode {
             "splits" => Some(StudyMode::Splits),
             "doubles" => Some(StudyMode::Doubles),
             "drill" => Some(StudyMode::Drill),
+            "changes" => Some(StudyMode::Changes),
+            "weak" => Some(StudyMode::Weak),
+            "curriculum" => Some(StudyMode::Curriculum),
+            "custom" => Some(StudyMode::Custom),
             _ => None,
         }
     }
@@ -62,18 +83,25 @@ impl StudyMode {
             StudyMode::Hard => "\u{1F4AA}", // 💪 flexed biceps
             StudyMode::Soft => "A2",
             StudyMode::Splits => "AA",
-            StudyMode::Doubles => "\u{23EC}", // ⏬ double down
+            StudyMode::Doubles => "\u{23EC}",     // ⏬ double down
+            StudyMode::Changes => "\u{0394}",     // Δ delta
+            StudyMode::Weak => "\u{1FA79}",       // 🩹 adhesive bandage
+            StudyMode::Curriculum => "\u{1F393}", // 🎓 graduation cap
+            StudyMode::Custom => "\u{1F3AF}",     // 🎯 direct hit
         }
     }
 
     /// All variants in display order.
-    pub const ALL: [StudyMode; 6] = [
+    pub const ALL: [StudyMode; 9] = [
         StudyMode::All,
         StudyMode::Drill,
         StudyMode::Hard,
         StudyMode::Soft,
         StudyMode::Splits,
         StudyMode::Doubles,
+        StudyMode::Changes,
+        StudyMode::Weak,
+        StudyMode::Curriculum,
     ];
 }
 
@@ -86,6 +114,10 @@ impl Display for StudyMode {
             StudyMode::Splits => "Splits",
             StudyMode::Doubles => "Doubles",
             StudyMode::Drill => "Drill (spaced rep)",
+            StudyMode::Changes => "What changes",
+            StudyMode::Weak => "Weak spots",
+            StudyMode::Curriculum => "Curriculum",
+            StudyMode::Custom => "Study set",
         };
         write!(f, "{}", s)
     }
@@ -98,8 +130,8 @@ mod tests {
     // --- ALL constant ordering ---
 
     #[test]
-    fn all_contains_all_six_variants() {
-        assert_eq!(6, StudyMode::ALL.len());
+    fn all_contains_all_nine_variants() {
+        assert_eq!(9, StudyMode::ALL.len());
     }
 
     #[test]
@@ -116,6 +148,9 @@ mod tests {
             StudyMode::Soft,
             StudyMode::Splits,
             StudyMode::Doubles,
+            StudyMode::Changes,
+            StudyMode::Weak,
+            StudyMode::Curriculum,
         ];
         assert_eq!(expected, StudyMode::ALL);
     }
@@ -129,6 +164,9 @@ mod tests {
         assert!(all.contains(&StudyMode::Soft));
         assert!(all.contains(&StudyMode::Splits));
         assert!(all.contains(&StudyMode::Doubles));
+        assert!(all.contains(&StudyMode::Changes));
+        assert!(all.contains(&StudyMode::Weak));
+        assert!(all.contains(&StudyMode::Curriculum));
     }
 
     // --- next() cycling ---
@@ -145,8 +183,8 @@ mod tests {
             }
             visited.push(mode);
         }
-        // Should have visited all 6 variants exactly once before wrapping
-        assert_eq!(6, visited.len());
+        // Should have visited all 9 variants exactly once before wrapping
+        assert_eq!(9, visited.len());
     }
 
     #[test]
@@ -175,8 +213,23 @@ mod tests {
     }
 
     #[test]
-    fn next_doubles_wraps_back_to_all() {
-        assert_eq!(StudyMode::All, StudyMode::Doubles.next());
+    fn next_doubles_returns_changes() {
+        assert_eq!(StudyMode::Changes, StudyMode::Doubles.next());
+    }
+
+    #[test]
+    fn next_changes_returns_weak() {
+        assert_eq!(StudyMode::Weak, StudyMode::Changes.next());
+    }
+
+    #[test]
+    fn next_weak_returns_curriculum() {
+        assert_eq!(StudyMode::Curriculum, StudyMode::Weak.next());
+    }
+
+    #[test]
+    fn next_curriculum_wraps_back_to_all() {
+        assert_eq!(StudyMode::All, StudyMode::Curriculum.next());
     }
 
     // --- key() / from_key() round-trip ---
@@ -198,6 +251,17 @@ mod tests {
         assert_eq!("splits", StudyMode::Splits.key());
         assert_eq!("doubles", StudyMode::Doubles.key());
         assert_eq!("drill", StudyMode::Drill.key());
+        assert_eq!("changes", StudyMode::Changes.key());
+        assert_eq!("weak", StudyMode::Weak.key());
+        assert_eq!("curriculum", StudyMode::Curriculum.key());
+    }
+
+    #[test]
+    fn custom_round_trips_but_is_not_cycled() {
+        assert_eq!(Some(StudyMode::Custom), StudyMode::from_key("custom"));
+        assert!(!StudyMode::ALL.contains(&StudyMode::Custom));
+        assert_eq!(StudyMode::All, StudyMode::Custom.next());
+        assert!(StudyMode::Custom.is_constructed());
     }
 
     #[test]
@@ -4,6 +4,13 @@
 //! helpers, refresh) live in the `leit-auth` crate; this module re-exports
 //! them so callers can keep their existing `bjsc::supabase::*` imports.
 
+use crate::blitz::BlitzScore;
+use crate::class::Assignment;
+use crate::exam::ExamReport;
+use crate::presets::preset_for_rules;
+use crate::rules::Rules;
+use crate::strat::{StrategyKind, TableIndex};
+use crate::study_set::StudySet;
 use crate::studymode::StudyMode;
 use serde::{Deserialize, Serialize};
 use spaced_rep::Deck;
@@ -33,6 +40,23 @@ pub struct UserDeckRow {
     pub user_id: String,
     pub study_mode: StudyMode,
     pub deck: Deck,
+    #[serde(default)]
+    pub rules: Rules,
+    #[serde(default)]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub strategy: StrategyKind,
+    #[serde(default)]
+    pub study_sets: Vec<StudySet>,
+    /// Name of the study set in use.
+    #[serde(default)]
+    pub study_set: Option<String>,
+    /// The latest curriculum lesson unlocked.
+    #[serde(default)]
+    pub lesson: usize,
+    /// Keys answered right but slowly once.
+    #[serde(default)]
+    pub half_credit: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<String>,
 }
@@ -54,13 +78,56 @@ pub fn fetch_deck_request(config: &SupabaseConfig, access_token: &str) -> Reques
     }
 }
 
+impl UserDeckRow {
+    /// A row to upsert. The preset is derived from the rules.
+    pub fn new(
+        user_id: &str,
+        mode: StudyMode,
+        deck: Deck,
+        rules: Rules,
+        strategy: StrategyKind,
+    ) -> Self {
+        UserDeckRow {
+            id: None,
+            user_id: user_id.to_string(),
+            study_mode: mode,
+            deck,
+            rules,
+            preset: preset_for_rules(&rules).map(|p| p.key.to_string()),
+            strategy,
+            study_sets: Vec::new(),
+            study_set: None,
+            lesson: 0,
+            half_credit: Vec::new(),
+            updated_at: None,
+        }
+    }
+
+    /// The row with the player's study sets and the name of the one in use.
+    pub fn with_study_sets(mut self, sets: &[StudySet], active: Option<&StudySet>) -> Self {
+        self.study_sets = sets.to_vec();
+        self.study_set = active.map(|s| s.name.clone());
+        self
+    }
+
+    /// The row with the player's place in the curriculum.
+    pub fn with_lesson(mut self, lesson: usize) -> Self {
+        self.lesson = lesson;
+        self
+    }
+
+    /// The row with the keys answered right but slowly once.
+    pub fn with_half_credit(mut self, keys: Vec<String>) -> Self {
+        self.half_credit = keys;
+        self
+    }
+}
+
 /// Build a request to upsert the user's deck.
 pub fn upsert_deck_request(
     config: &SupabaseConfig,
     access_token: &str,
-    user_id: &str,
-    mode: StudyMode,
-    deck: &Deck,
+    row: &UserDeckRow,
 ) -> Result<RequestDetails, String> {
     let mut headers = common_headers(config, access_token);
     headers.push((
@@ -72,19 +139,11 @@ pub fn upsert_deck_request(
         Cow::Borrowed("resolution=merge-duplicates"),
     ));
 
-    let row = UserDeckRow {
-        id: None,
-        user_id: user_id.to_string(),
-        study_mode: mode,
-        deck: deck.clone(),
-        updated_at: None,
-    };
-
     Ok(RequestDetails {
         url: format!("{}/rest/v1/user_deck?on_conflict=user_id", config.base_url),
         method: "POST".to_string(),
         headers,
-        body: Some(serde_json::to_string(&row).map_err(|e| e.to_string())?),
+        body: Some(serde_json::to_string(row).map_err(|e| e.to_string())?),
     })
 }
 
@@ -96,6 +155,8 @@ pub struct AnswerLogRow {
     pub correct: bool,
     pub player_action: String,
     pub correct_action: String,
+    /// Milliseconds from the deal to the answer; None when it wasn't timed.
+    pub latency_ms: Option<u32>,
 }
 
 /// Build a request to insert an answer log entry.
@@ -122,15 +183,17 @@ pub fn insert_answer_log_request(
 pub fn fetch_answer_logs_request(
     config: &SupabaseConfig,
     access_token: &str,
+    user_id: &str,
     limit: u32,
 ) -> RequestDetails {
     let mut headers = common_headers(config, access_token);
     headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
 
+    // Filtered by user: instructors can see their class members' logs too
     RequestDetails {
         url: format!(
-            "{}/rest/v1/answer_log?select=*&order=created_at.desc&limit={}",
-            config.base_url, limit
+            "{}/rest/v1/answer_log?select=*&user_id=eq.{}&order=created_at.desc&limit={}",
+            config.base_url, user_id, limit
         ),
         method: "GET".to_string(),
         headers,
@@ -146,10 +209,551 @@ pub struct AnswerLogEntry {
     pub player_action: String,
     pub correct_action: String,
     pub created_at: String,
+    /// Missing from rows logged before answers were timed.
+    #[serde(default)]
+    pub latency_ms: Option<u32>,
+}
+
+impl AnswerLogEntry {
+    /// The chart cell the answer was for; None for composition exceptions and bad keys.
+    pub fn cell(&self) -> Option<TableIndex> {
+        self.table_index.rsplit('/').next()?.parse().ok()
+    }
+}
+
+/// Row for the exam_results table: a graded exam and who took it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExamResultRow {
+    pub user_id: String,
+    #[serde(flatten)]
+    pub report: ExamReport,
+}
+
+/// Build a request to save an exam result.
+pub fn insert_exam_result_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    row: &ExamResultRow,
+) -> Result<RequestDetails, String> {
+    let mut headers = common_headers(config, access_token);
+    headers.push((
+        Cow::Borrowed("Content-Type"),
+        Cow::Borrowed("application/json"),
+    ));
+
+    Ok(RequestDetails {
+        url: format!("{}/rest/v1/exam_results", config.base_url),
+        method: "POST".to_string(),
+        headers,
+        body: Some(serde_json::to_string(row).map_err(|e| e.to_string())?),
+    })
+}
+
+/// Build a request to fetch the user's exam results, newest first.
+pub fn fetch_exam_results_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    user_id: &str,
+    limit: u32,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    // Filtered by user: the user's groups and instructors can see their results too
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/exam_results?select=*&user_id=eq.{}&order=taken_at.desc&limit={}",
+            config.base_url, user_id, limit
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Row for the blitz_scores table: a finished Blitz and who played it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlitzScoreRow {
+    pub user_id: String,
+    #[serde(flatten)]
+    pub score: BlitzScore,
+}
+
+/// Build a request to save a Blitz score.
+pub fn insert_blitz_score_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    row: &BlitzScoreRow,
+) -> Result<RequestDetails, String> {
+    let mut headers = common_headers(config, access_token);
+    headers.push((
+        Cow::Borrowed("Content-Type"),
+        Cow::Borrowed("application/json"),
+    ));
+
+    Ok(RequestDetails {
+        url: format!("{}/rest/v1/blitz_scores", config.base_url),
+        method: "POST".to_string(),
+        headers,
+        body: Some(serde_json::to_string(row).map_err(|e| e.to_string())?),
+    })
+}
+
+/// Build a request to fetch the user's best Blitz score for the same rule set, strategy
+/// and length as `challenge`: most right, then fewest wrong.
+pub fn fetch_blitz_best_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    user_id: &str,
+    challenge: &BlitzScore,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+    let strategy = serde_json::to_value(challenge.strategy).unwrap_or_default();
+
+    // Filtered by user: the user's groups can see their scores too
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/blitz_scores?select=*&user_id=eq.{}&rule_set=eq.{}&strategy=eq.{}&duration_secs=eq.{}&order=correct.desc,wrong.asc&limit=1",
+            config.base_url,
+            user_id,
+            challenge.rule_set,
+            strategy.as_str().unwrap_or_default(),
+            challenge.duration_secs
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// A row of the groups table. Members share the join code to let others in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupRow {
+    pub id: String,
+    pub name: String,
+    pub join_code: String,
+}
+
+/// A row of the group_members table. Joining a group is what opts a player in to its
+/// leaderboards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupMemberRow {
+    pub group_id: String,
+    pub user_id: String,
+    /// The name the group sees on its leaderboards.
+    pub display_name: String,
+}
+
+/// Row for the practice_streaks view: the player's latest run of days with answers, as
+/// shown on group leaderboards. The database works it out from the answer log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreakRow {
+    pub user_id: String,
+    /// The last day of the run, as "YYYY-MM-DD".
+    pub last_day: String,
+    pub days: u32,
+}
+
+/// PostgREST's filter for a column matching any of `values`, e.g. "in.(a,b)".
+fn in_filter(values: &[String]) -> String {
+    format!("in.({})", values.join(","))
+}
+
+/// Build a request to create a group, joining it as `display_name`. Returns the new group.
+pub fn create_group_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    name: &str,
+    display_name: &str,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((
+        Cow::Borrowed("Content-Type"),
+        Cow::Borrowed("application/json"),
+    ));
+
+    RequestDetails {
+        url: format!("{}/rest/v1/rpc/create_group", config.base_url),
+        method: "POST".to_string(),
+        headers,
+        body: Some(
+            serde_json::json!({ "group_name": name, "display_name": display_name }).to_string(),
+        ),
+    }
+}
+
+/// Build a request to join the group with a join code as `display_name`, or change the
+/// name shown in a group already joined. Returns the group.
+pub fn join_group_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    join_code: &str,
+    display_name: &str,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((
+        Cow::Borrowed("Content-Type"),
+        Cow::Borrowed("application/json"),
+    ));
+
+    RequestDetails {
+        url: format!("{}/rest/v1/rpc/join_group", config.base_url),
+        method: "POST".to_string(),
+        headers,
+        body: Some(
+            serde_json::json!({ "code": join_code, "display_name": display_name }).to_string(),
+        ),
+    }
+}
+
+/// Build a request to leave a group.
+pub fn leave_group_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    group_id: &str,
+    user_id: &str,
+) -> RequestDetails {
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/group_members?group_id=eq.{}&user_id=eq.{}",
+            config.base_url, group_id, user_id
+        ),
+        method: "DELETE".to_string(),
+        headers: common_headers(config, access_token),
+        body: None,
+    }
+}
+
+/// Build a request to fetch the groups the user belongs to, by name.
+pub fn fetch_groups_request(config: &SupabaseConfig, access_token: &str) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/groups?select=id,name,join_code&order=name.asc",
+            config.base_url
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
 }
 
-/// Build a request to call the coaching edge function.
-pub fn coaching_request(config: &SupabaseConfig, access_token: &str) -> RequestDetails {
+/// Build a request to fetch a group's members.
+pub fn fetch_group_members_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    group_id: &str,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/group_members?select=group_id,user_id,display_name&group_id=eq.{}",
+            config.base_url, group_id
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Build a request to fetch the Blitz scores of `user_ids` for the same rule set, strategy
+/// and length as `challenge`, best first.
+pub fn fetch_group_blitz_scores_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    user_ids: &[String],
+    challenge: &BlitzScore,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+    let strategy = serde_json::to_value(challenge.strategy).unwrap_or_default();
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/blitz_scores?select=*&user_id={}&rule_set=eq.{}&strategy=eq.{}&duration_secs=eq.{}&order=correct.desc,wrong.asc",
+            config.base_url,
+            in_filter(user_ids),
+            challenge.rule_set,
+            strategy.as_str().unwrap_or_default(),
+            challenge.duration_secs
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Build a request to fetch the exam results of `user_ids`.
+pub fn fetch_group_exam_results_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    user_ids: &[String],
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/exam_results?select=*&user_id={}",
+            config.base_url,
+            in_filter(user_ids)
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Build a request to fetch the practice streaks of `user_ids`.
+pub fn fetch_group_streaks_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    user_ids: &[String],
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/practice_streaks?select=*&user_id={}",
+            config.base_url,
+            in_filter(user_ids)
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// A row of the classes table. The instructor who made the class shares its join code with
+/// the players they're onboarding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassRow {
+    pub id: String,
+    pub name: String,
+    pub join_code: String,
+    pub instructor_id: String,
+}
+
+/// A row of the class_members table. Joining a class lets its instructor see the member's
+/// answer history and exam results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassMemberRow {
+    pub class_id: String,
+    pub user_id: String,
+    /// The name the instructor sees.
+    pub display_name: String,
+}
+
+/// A row of the class_assignments table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignmentRow {
+    /// Set by the database; None for a new assignment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub class_id: String,
+    pub assignment: Assignment,
+}
+
+/// Build a request to create a class, with the user as its instructor. Returns the new
+/// class.
+pub fn create_class_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    name: &str,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((
+        Cow::Borrowed("Content-Type"),
+        Cow::Borrowed("application/json"),
+    ));
+
+    RequestDetails {
+        url: format!("{}/rest/v1/rpc/create_class", config.base_url),
+        method: "POST".to_string(),
+        headers,
+        body: Some(serde_json::json!({ "class_name": name }).to_string()),
+    }
+}
+
+/// Build a request to join the class with a join code as `display_name`, or change the
+/// name shown in a class already joined. Returns the class.
+pub fn join_class_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    join_code: &str,
+    display_name: &str,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((
+        Cow::Borrowed("Content-Type"),
+        Cow::Borrowed("application/json"),
+    ));
+
+    RequestDetails {
+        url: format!("{}/rest/v1/rpc/join_class", config.base_url),
+        method: "POST".to_string(),
+        headers,
+        body: Some(
+            serde_json::json!({ "code": join_code, "display_name": display_name }).to_string(),
+        ),
+    }
+}
+
+/// Build a request to take `user_id` out of a class: a member leaving, or the instructor
+/// removing them.
+pub fn leave_class_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    class_id: &str,
+    user_id: &str,
+) -> RequestDetails {
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/class_members?class_id=eq.{}&user_id=eq.{}",
+            config.base_url, class_id, user_id
+        ),
+        method: "DELETE".to_string(),
+        headers: common_headers(config, access_token),
+        body: None,
+    }
+}
+
+/// Build a request to fetch the classes the user teaches or belongs to, by name.
+pub fn fetch_classes_request(config: &SupabaseConfig, access_token: &str) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/classes?select=id,name,join_code,instructor_id&order=name.asc",
+            config.base_url
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Build a request to fetch a class's members, by name. Only the instructor sees them all.
+pub fn fetch_class_members_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    class_id: &str,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/class_members?select=class_id,user_id,display_name&class_id=eq.{}&order=display_name.asc",
+            config.base_url, class_id
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Row for the recent_answers view: an answer and whose it is.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemberAnswerRow {
+    pub user_id: String,
+    #[serde(flatten)]
+    pub entry: AnswerLogEntry,
+}
+
+/// Build a request to fetch the last `per_member` answers of each of `user_ids`, newest
+/// first, in one go.
+pub fn fetch_class_answers_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    user_ids: &[String],
+    per_member: u32,
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/recent_answers?select=*&user_id={}&recent=lte.{}&order=created_at.desc",
+            config.base_url,
+            in_filter(user_ids),
+            per_member
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Build a request to set a class an assignment.
+pub fn insert_assignment_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    row: &AssignmentRow,
+) -> Result<RequestDetails, String> {
+    let mut headers = common_headers(config, access_token);
+    headers.push((
+        Cow::Borrowed("Content-Type"),
+        Cow::Borrowed("application/json"),
+    ));
+
+    Ok(RequestDetails {
+        url: format!("{}/rest/v1/class_assignments", config.base_url),
+        method: "POST".to_string(),
+        headers,
+        body: Some(serde_json::to_string(row).map_err(|e| e.to_string())?),
+    })
+}
+
+/// Build a request to withdraw an assignment.
+pub fn delete_assignment_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    id: i64,
+) -> RequestDetails {
+    RequestDetails {
+        url: format!("{}/rest/v1/class_assignments?id=eq.{}", config.base_url, id),
+        method: "DELETE".to_string(),
+        headers: common_headers(config, access_token),
+        body: None,
+    }
+}
+
+/// Build a request to fetch the assignments of `class_ids`, newest first.
+pub fn fetch_assignments_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    class_ids: &[String],
+) -> RequestDetails {
+    let mut headers = common_headers(config, access_token);
+    headers.push((Cow::Borrowed("Accept"), Cow::Borrowed("application/json")));
+
+    RequestDetails {
+        url: format!(
+            "{}/rest/v1/class_assignments?select=id,class_id,assignment&class_id={}&order=id.desc",
+            config.base_url,
+            in_filter(class_ids)
+        ),
+        method: "GET".to_string(),
+        headers,
+        body: None,
+    }
+}
+
+/// Build a request to call the coaching edge function. `strategy` is the player's strategy
+/// as prompt text (see `strategy_prompt`); the coach teaches from it.
+pub fn coaching_request(
+    config: &SupabaseConfig,
+    access_token: &str,
+    strategy: &str,
+) -> RequestDetails {
     let mut headers = common_headers(config, access_token);
     headers.push((
         Cow::Borrowed("Content-Type"),
@@ -160,6 +764,6 @@ pub fn coaching_request(config: &SupabaseConfig, access_token: &str) -> RequestD
         url: format!("{}/functions/v1/coaching", config.base_url),
         method: "POST".to_string(),
         headers,
-        body: Some("{}".to_string()),
+        body: Some(serde_json::json!({ "strategy": strategy }).to_string()),
     }
 }
@@ -1,14 +1,20 @@
+use crate::rules::{GameVariant, HoleCard, Rules};
 use crate::strat::{
-    ChartAction, ColIndex, RowIndex, TableIndex, TableType, lookup_by_index, new_table_index,
+    ChartAction, ColIndex, CompositionException, RowIndex, Strategy, TableIndex, TableType,
+    new_table_index,
 };
 use crate::studymode::StudyMode;
+use serde_json::Value;
+use spaced_rep::Deck;
 
 /// Enumerate all valid TableIndex cells for a given TableType.
-fn indices_for_type(tt: TableType) -> Vec<TableIndex> {
+pub(crate) fn indices_for_type(tt: TableType) -> Vec<TableIndex> {
     let row_range: Box<dyn Iterator<Item = u8>> = match tt {
-        TableType::Hard => Box::new(8..=17),
-        TableType::Soft => Box::new(13..=20), // exclude 21 (natural blackjack)
-        TableType::Split => Box::new(1..=10),
+        TableType::Hard => Box::new(tt.rows()),
+        // Soft 12 only comes up when A,A can't be split; dealt, it's a pair question.
+        // Soft 21 is dealt as a multi-card hand.
+        TableType::Soft => Box::new(13..=21),
+        TableType::Split => Box::new(tt.rows()),
         TableType::Surrender => Box::new(15..=16), // only rows with actual decisions
     };
 
@@ -33,8 +39,13 @@ fn all_indices() -> Vec<TableIndex> {
     all
 }
 
-/// Get all valid TableIndex cells for a study mode.
-pub fn indices_for_mode(mode: StudyMode) -> Vec<TableIndex> {
+/// Get all valid TableIndex cells for a study mode under the given rules, for a player
+/// learning the given strategy.
+pub fn indices_for_mode(
+    mode: StudyMode,
+    rules: &Rules,
+    strategy: &dyn Strategy,
+) -> Vec<TableIndex> {
     match mode {
         StudyMode::All | StudyMode::Drill => all_indices(),
         StudyMode::Hard => indices_for_type(TableType::Hard),
@@ -42,10 +53,11 @@ pub fn indices_for_mode(mode: StudyMode) -> Vec<TableIndex> {
         StudyMode::Splits => indices_for_type(TableType::Split),
         StudyMode::Doubles => {
             // All cells where the correct action is Double (DblH or DblS)
+            let charts = strategy.charts(rules);
             let mut result = Vec::new();
             for tt in [TableType::Hard, TableType::Soft] {
                 for idx in indices_for_type(tt) {
-                    if let Ok(action) = lookup_by_index(&idx)
+                    if let Ok(action) = charts.get(&idx)
                         && matches!(action, ChartAction::DblH | ChartAction::DblS)
                     {
                         result.push(idx);
@@ -54,36 +66,238 @@ pub fn indices_for_mode(mode: StudyMode) -> Vec<TableIndex> {
             }
             result
         }
+        StudyMode::Changes => changed_indices(&Rules::standard(), rules, strategy),
+        // The cells come from the player's study set, answer history or curriculum lesson,
+        // which the game state holds.
+        StudyMode::Custom | StudyMode::Weak | StudyMode::Curriculum => Vec::new(),
     }
 }
 
-/// Convert a TableIndex to a spaced-rep key string.
-pub fn table_index_to_key(ti: &TableIndex) -> String {
-    ti.to_string()
+/// The cells that play differently under `rules` than under `baseline`, for a player
+/// learning the given strategy: what's different at this table from the game the player
+/// already knows.
+pub fn changed_indices(
+    baseline: &Rules,
+    rules: &Rules,
+    strategy: &dyn Strategy,
+) -> Vec<TableIndex> {
+    strategy
+        .charts(baseline)
+        .diff(&strategy.charts(rules))
+        .iter()
+        .map(|change| change.index)
+        .collect()
+}
+
+/// Version of the spaced-rep key scheme. Keys without a version are version 1.
+pub const KEY_VERSION: u8 = 3;
+
+/// The rules part of the spaced-rep key scope: the scheme version, the game, and for
+/// blackjack the rules its charts depend on, e.g. "v3/bj-h17-das-nols-peek".
+pub fn rules_scope(rules: &Rules) -> String {
+    let game = rules.variant.key_namespace();
+    match rules.variant {
+        GameVariant::Standard => format!(
+            "v{}/{}-{}-{}-{}-{}",
+            KEY_VERSION,
+            game,
+            rules.soft_17.to_string().to_lowercase(),
+            if rules.das { "das" } else { "nodas" },
+            if rules.surrender { "ls" } else { "nols" },
+            match rules.hole_card {
+                HoleCard::Peek => "peek",
+                HoleCard::Enhc => "enhc",
+            }
+        ),
+        // The other games have one chart each, whatever the rules.
+        _ => format!("v{}/{}", KEY_VERSION, game),
+    }
+}
+
+/// The start of every spaced-rep key under the given rules and strategy: the rules scope and
+/// the strategy's key, e.g. "v3/bj-h17-das-nols-peek-basic". Progress under one rule set or
+/// strategy doesn't count for another, and every key says which charts it was learned from.
+pub fn key_scope(rules: &Rules, strategy: &dyn Strategy) -> String {
+    format!("{}-{}", rules_scope(rules), strategy.key())
+}
+
+/// The spaced-rep key of a composition-dependent exception, scoped like the table keys.
+pub fn exception_key(
+    exception: &CompositionException,
+    rules: &Rules,
+    strategy: &dyn Strategy,
+) -> String {
+    format!("{}/{}", key_scope(rules, strategy), exception.key())
+}
+
+/// Convert a TableIndex to a spaced-rep key string, e.g.
+/// "v3/bj-h17-das-nols-peek-basic/hard:16,9".
+pub fn table_index_to_key(ti: &TableIndex, rules: &Rules, strategy: &dyn Strategy) -> String {
+    format!("{}/{}", key_scope(rules, strategy), ti)
+}
+
+/// Parse a spaced-rep key back into a TableIndex. Returns None if the key is malformed or
+/// belongs to a different game, rule set or strategy.
+pub fn key_to_table_index(key: &str, rules: &Rules, strategy: &dyn Strategy) -> Option<TableIndex> {
+    key.strip_prefix(&key_scope(rules, strategy))?
+        .strip_prefix('/')?
+        .parse()
+        .ok()
+}
+
+/// The key without its version, rules and strategy, as the answer log displays it: the cell, prefixed
+/// with the game for games other than blackjack (e.g. "s21/hard:16,9"). Version 1 keys are
+/// already in this form.
+pub fn short_key(key: &str) -> String {
+    let Some(rest) = key.strip_prefix('v') else {
+        return key.to_string();
+    };
+    let mut parts = rest.splitn(3, '/');
+    let (Some(_version), Some(scope), Some(cell)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return key.to_string();
+    };
+    match scope.split('-').next() {
+        Some("bj") => cell.to_string(),
+        Some(game) => format!("{}/{}", game, cell),
+        None => cell.to_string(),
+    }
+}
+
+/// Parse a key as `short_key` writes it back into a TableIndex. Returns None for exceptions
+/// and for cells of another game.
+pub fn short_key_to_table_index(short: &str, rules: &Rules) -> Option<TableIndex> {
+    let cell = match rules.variant {
+        GameVariant::Standard => short,
+        variant => short
+            .strip_prefix(variant.key_namespace())?
+            .strip_prefix('/')?,
+    };
+    cell.parse().ok()
+}
+
+/// The current key for an older key, for a deck saved under `rules` and `strategy`. Returns
+/// None if the key is already current or isn't a spaced-rep key.
+///
+/// Version 1 keys were the bare cell ("hard:16,9", "comp:12-T2-4"), prefixed for games other
+/// than blackjack ("s21/hard:16,9"). They didn't record the rules, so they are taken to be
+/// the rules the deck was saved with. Their hard 8 and 17 rows, which stood for "8 and lower"
+/// and "17 and up", stay rows 8 and 17; the finer rows start unseen. Version 2 keys recorded
+/// the rules ("v2/bj-h17-das-nols-peek/hard:16,9") but not the strategy, which is taken to be
+/// the one the deck was saved with.
+pub fn migrate_key(key: &str, rules: &Rules, strategy: &dyn Strategy) -> Option<String> {
+    if let Some(rest) = key.strip_prefix("v2/") {
+        let (scope, cell) = rest.split_once('/')?;
+        return Some(format!(
+            "v{}/{}-{}/{}",
+            KEY_VERSION,
+            scope,
+            strategy.key(),
+            cell
+        ));
+    }
+    let (variant, cell) = match key.split_once('/') {
+        Some((ns, cell)) => (
+            [GameVariant::Spanish21, GameVariant::FreeBet]
+                .into_iter()
+                .find(|v| v.key_namespace() == ns)?,
+            cell,
+        ),
+        None => (GameVariant::Standard, key),
+    };
+    if !cell.starts_with("comp:") && cell.parse::<TableIndex>().is_err() {
+        return None;
+    }
+    let rules = Rules { variant, ..*rules };
+    Some(format!("{}/{}", key_scope(&rules, strategy), cell))
+}
+
+/// The deck with its older keys moved to the current scheme, for a deck saved under `rules`
+/// and `strategy`. History already under a current key wins over an old copy of the same
+/// cell.
+pub fn migrate_deck(deck: Deck, rules: &Rules, strategy: &dyn Strategy) -> Deck {
+    let Ok(mut value) = serde_json::to_value(&deck) else {
+        return deck;
+    };
+    if !migrate_value(&mut value, &|key| migrate_key(key, rules, strategy)) {
+        return deck;
+    }
+    serde_json::from_value(value).unwrap_or(deck)
+}
+
+// How the deck lays out its cards is the spaced-rep crate's business, so keys are renamed
+// wherever they appear, as map keys or as values. Returns whether anything changed.
+fn migrate_value(value: &mut Value, migrate: &dyn Fn(&str) -> Option<String>) -> bool {
+    match value {
+        Value::Object(map) => {
+            let mut changed = false;
+            let mut moved = Vec::new();
+            for (key, mut item) in std::mem::take(map) {
+                changed |= migrate_value(&mut item, migrate);
+                match migrate(&key) {
+                    Some(new_key) => moved.push((new_key, item)),
+                    None => {
+                        map.insert(key, item);
+                    }
+                }
+            }
+            changed |= !moved.is_empty();
+            for (key, item) in moved {
+                map.entry(key).or_insert(item);
+            }
+            changed
+        }
+        Value::Array(items) => items.iter_mut().fold(false, |changed, item| {
+            migrate_value(item, migrate) | changed
+        }),
+        Value::String(s) => match migrate(s) {
+            Some(new_key) => {
+                *s = new_key;
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    }
 }
 
-/// Get all spaced-rep keys for a study mode.
-pub fn keys_for_mode(mode: StudyMode) -> Vec<String> {
-    indices_for_mode(mode)
+/// Get all spaced-rep keys for a study mode under the given rules and strategy. Drill mode
+/// also drills the strategy's composition-dependent exceptions.
+pub fn keys_for_mode(mode: StudyMode, rules: &Rules, strategy: &dyn Strategy) -> Vec<String> {
+    let scope = key_scope(rules, strategy);
+    let mut keys: Vec<String> = indices_for_mode(mode, rules, strategy)
         .iter()
-        .map(table_index_to_key)
-        .collect()
+        .map(|ti| format!("{}/{}", scope, ti))
+        .collect();
+    if mode == StudyMode::Drill {
+        let charts = strategy.charts(rules);
+        keys.extend(
+            charts
+                .exceptions()
+                .iter()
+                .map(|e| format!("{}/{}", scope, e.key())),
+        );
+    }
+    keys
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::strat::{
+        BasicStrategy, ChartSet, CompositionStrategy, SimpleStrategy, lookup_by_index,
+    };
 
     #[test]
     fn test_hard_indices_count() {
         let indices = indices_for_type(TableType::Hard);
-        assert_eq!(indices.len(), 100); // 10 rows * 10 cols
+        assert_eq!(indices.len(), 160); // 16 rows (5-20) * 10 cols
     }
 
     #[test]
     fn test_soft_indices_count() {
         let indices = indices_for_type(TableType::Soft);
-        assert_eq!(indices.len(), 80); // 8 rows (13-20) * 10 cols, excludes natural 21
+        assert_eq!(indices.len(), 90); // 9 rows (13-21) * 10 cols, excludes unsplit aces
     }
 
     #[test]
@@ -94,10 +308,10 @@ mod tests {
 
     #[test]
     fn test_doubles_are_subset() {
-        let doubles = indices_for_mode(StudyMode::Doubles);
+        let doubles = indices_for_mode(StudyMode::Doubles, &Rules::default(), &BasicStrategy);
         assert!(!doubles.is_empty());
         for idx in &doubles {
-            let action = lookup_by_index(idx).unwrap();
+            let action = lookup_by_index(idx, &Rules::default()).unwrap();
             assert!(
                 matches!(action, ChartAction::DblH | ChartAction::DblS),
                 "non-double action found: {:?}",
@@ -106,13 +320,281 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_enhc_drops_eleven_vs_ten_from_doubles() {
+        let rules = Rules {
+            hole_card: crate::rules::HoleCard::Enhc,
+            ..Default::default()
+        };
+        let standard = indices_for_mode(StudyMode::Doubles, &Rules::default(), &BasicStrategy);
+        let enhc = indices_for_mode(StudyMode::Doubles, &rules, &BasicStrategy);
+        let eleven_vs_ten: TableIndex = "hard:11,10".parse().unwrap();
+        assert!(standard.contains(&eleven_vs_ten));
+        assert!(!enhc.contains(&eleven_vs_ten));
+    }
+
+    #[test]
+    fn test_doubles_follow_the_strategy() {
+        let rules = Rules::default();
+        let basic = indices_for_mode(StudyMode::Doubles, &rules, &BasicStrategy);
+        let simple = indices_for_mode(StudyMode::Doubles, &rules, &SimpleStrategy);
+        let nine_vs_three: TableIndex = "hard:9,3".parse().unwrap();
+        assert!(basic.contains(&nine_vs_three));
+        assert!(!simple.contains(&nine_vs_three));
+    }
+
+    #[test]
+    fn test_drill_includes_exceptions_for_composition_strategy() {
+        let rules = Rules {
+            decks: 1,
+            ..Default::default()
+        };
+        let basic = keys_for_mode(StudyMode::Drill, &rules, &BasicStrategy);
+        let cd = keys_for_mode(StudyMode::Drill, &rules, &CompositionStrategy);
+        assert_eq!(basic.len() + 3, cd.len());
+        assert!(cd.contains(&"v3/bj-h17-das-nols-peek-comp/comp:12-T2-4".to_string()));
+        // Six decks: only the 16 vs T exception applies.
+        let six = keys_for_mode(StudyMode::Drill, &Rules::default(), &CompositionStrategy);
+        assert_eq!(basic.len() + 1, six.len());
+        // Category modes don't.
+        let hard = keys_for_mode(StudyMode::Hard, &rules, &CompositionStrategy);
+        assert!(hard.iter().all(|k| !k.contains("/comp:")));
+    }
+
+    #[test]
+    fn test_changes_mode_drills_only_changed_cells() {
+        let rules = Rules {
+            decks: 2,
+            das: false,
+            ..Default::default()
+        };
+        let changed = indices_for_mode(StudyMode::Changes, &rules, &BasicStrategy);
+        assert!(!changed.is_empty());
+        assert!(changed.iter().all(|ti| ti.table_type() == TableType::Split));
+        assert!(changed_indices(&rules, &rules, &BasicStrategy).is_empty());
+        assert!(indices_for_mode(StudyMode::Changes, &Rules::default(), &BasicStrategy).is_empty());
+    }
+
     #[test]
     fn test_key_roundtrip() {
-        let indices = indices_for_mode(StudyMode::Hard);
+        let indices = indices_for_mode(StudyMode::Hard, &Rules::default(), &BasicStrategy);
         for idx in &indices {
-            let key = table_index_to_key(idx);
-            let restored: TableIndex = key.parse().unwrap();
-            assert_eq!(*idx, restored);
+            let key = table_index_to_key(idx, &Rules::default(), &BasicStrategy);
+            assert_eq!(
+                Some(*idx),
+                key_to_table_index(&key, &Rules::default(), &BasicStrategy)
+            );
         }
     }
+
+    #[test]
+    fn test_spanish21_keys_are_namespaced() {
+        let rules = Rules {
+            variant: crate::rules::GameVariant::Spanish21,
+            ..Default::default()
+        };
+        let keys = keys_for_mode(StudyMode::Hard, &rules, &BasicStrategy);
+        assert!(keys.iter().all(|k| k.starts_with("v3/s21-basic/hard:")));
+        let standard = keys_for_mode(StudyMode::Hard, &Rules::default(), &BasicStrategy);
+        assert!(keys.iter().all(|k| !standard.contains(k)));
+        for key in &keys {
+            let idx = key_to_table_index(key, &rules, &BasicStrategy).unwrap();
+            assert_eq!(*key, table_index_to_key(&idx, &rules, &BasicStrategy));
+        }
+    }
+
+    #[test]
+    fn test_key_from_other_variant_is_rejected() {
+        let rules = Rules {
+            variant: crate::rules::GameVariant::Spanish21,
+            ..Default::default()
+        };
+        assert_eq!(
+            None,
+            key_to_table_index(
+                "v3/bj-h17-das-nols-peek-basic/hard:16,9",
+                &rules,
+                &BasicStrategy
+            )
+        );
+        assert_eq!(
+            None,
+            key_to_table_index("v3/s21-basic/hard:16,9", &Rules::default(), &BasicStrategy)
+        );
+        // Older keys have to be migrated first.
+        assert_eq!(
+            None,
+            key_to_table_index("hard:16,9", &Rules::default(), &BasicStrategy)
+        );
+        assert_eq!(
+            None,
+            key_to_table_index(
+                "v2/bj-h17-das-nols-peek/hard:16,9",
+                &Rules::default(),
+                &BasicStrategy
+            )
+        );
+    }
+
+    #[test]
+    fn test_keys_are_scoped_to_the_chart_rules() {
+        let ti: TableIndex = "hard:16,9".parse().unwrap();
+        let s17 = Rules {
+            soft_17: crate::rules::Soft17::Stands,
+            surrender: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            "v3/bj-s17-das-ls-peek-basic/hard:16,9",
+            table_index_to_key(&ti, &s17, &BasicStrategy)
+        );
+        assert_eq!(
+            None,
+            key_to_table_index(
+                &table_index_to_key(&ti, &s17, &BasicStrategy),
+                &Rules::default(),
+                &BasicStrategy
+            )
+        );
+        // Rules the charts don't depend on share progress.
+        let eight_decks = Rules {
+            decks: 8,
+            penetration: 60,
+            ..Default::default()
+        };
+        assert_eq!(
+            table_index_to_key(&ti, &Rules::default(), &BasicStrategy),
+            table_index_to_key(&ti, &eight_decks, &BasicStrategy)
+        );
+    }
+
+    #[test]
+    fn test_migrate_key_from_version_1() {
+        let enhc = Rules {
+            hole_card: crate::rules::HoleCard::Enhc,
+            ..Default::default()
+        };
+        assert_eq!(
+            Some("v3/bj-h17-das-nols-enhc-basic/hard:17,10".to_string()),
+            migrate_key("hard:17,10", &enhc, &BasicStrategy)
+        );
+        assert_eq!(
+            Some("v3/bj-h17-das-nols-peek-basic/comp:12-T2-4".to_string()),
+            migrate_key("comp:12-T2-4", &Rules::default(), &BasicStrategy)
+        );
+        // The game in the key wins over the rules it was saved with.
+        assert_eq!(
+            Some("v3/fb-basic/split:8,6".to_string()),
+            migrate_key("fb/split:8,6", &Rules::default(), &BasicStrategy)
+        );
+        assert_eq!(
+            None,
+            migrate_key("v3/fb-basic/split:8,6", &Rules::default(), &BasicStrategy)
+        );
+        assert_eq!(
+            None,
+            migrate_key("items", &Rules::default(), &BasicStrategy)
+        );
+        assert_eq!(
+            None,
+            migrate_key("xx/hard:16,9", &Rules::default(), &BasicStrategy)
+        );
+    }
+
+    #[test]
+    fn test_keys_are_scoped_to_the_strategy() {
+        let ti: TableIndex = "hard:9,3".parse().unwrap();
+        let rules = Rules::default();
+        let basic = table_index_to_key(&ti, &rules, &BasicStrategy);
+        let simple = table_index_to_key(&ti, &rules, &SimpleStrategy);
+        assert_eq!("v3/bj-h17-das-nols-peek-simple/hard:9,3", simple);
+        assert_ne!(basic, simple);
+        assert_eq!(None, key_to_table_index(&simple, &rules, &BasicStrategy));
+        // Each custom chart is a strategy of its own
+        let chart = ChartSet::for_rules(&rules);
+        let custom = table_index_to_key(&ti, &rules, &chart);
+        assert!(
+            custom.starts_with("v3/bj-h17-das-nols-peek-chart"),
+            "{}",
+            custom
+        );
+        assert_ne!(custom, table_index_to_key(&ti, &rules, &ChartSet::simple()));
+        assert_eq!(custom, table_index_to_key(&ti, &rules, &chart.clone()));
+    }
+
+    #[test]
+    fn test_migrate_key_from_version_2() {
+        let key = "v2/bj-s17-das-ls-peek/hard:9,3";
+        let rules = Rules::default();
+        // The rules come from the key, the strategy from the deck
+        assert_eq!(
+            Some("v3/bj-s17-das-ls-peek-simple/hard:9,3".to_string()),
+            migrate_key(key, &rules, &SimpleStrategy)
+        );
+        assert_eq!(
+            Some("v3/s21-comp/comp:16-T-3".to_string()),
+            migrate_key("v2/s21/comp:16-T-3", &rules, &CompositionStrategy)
+        );
+
+        // Mastered under the simple strategy, nothing counts for basic strategy
+        let mut deck = Deck::new();
+        deck.record("v2/bj-h17-das-nols-peek/hard:9,3", true);
+        let deck = migrate_deck(deck, &rules, &SimpleStrategy);
+        let ti: TableIndex = "hard:9,3".parse().unwrap();
+        let seen = |strategy: &dyn Strategy| {
+            deck.unseen_count(&[table_index_to_key(&ti, &rules, strategy)]) == 0
+        };
+        assert!(seen(&SimpleStrategy));
+        assert!(!seen(&BasicStrategy));
+    }
+
+    #[test]
+    fn test_migrate_deck_keeps_history() {
+        let rules = Rules::default();
+        let mut deck = Deck::new();
+        deck.record("hard:16,10", false);
+        deck.record("s21/soft:18,2", true);
+        let deck = migrate_deck(deck, &rules, &BasicStrategy);
+        let hard = table_index_to_key(&"hard:16,10".parse().unwrap(), &rules, &BasicStrategy);
+        assert_eq!(
+            0,
+            deck.unseen_count(&[hard, "v3/s21-basic/soft:18,2".to_string()])
+        );
+        assert_eq!(1, deck.unseen_count(&["hard:16,10".to_string()]));
+        // A second migration has nothing to do.
+        let json = serde_json::to_string(&deck).unwrap();
+        assert_eq!(
+            json,
+            serde_json::to_string(&migrate_deck(deck, &rules, &BasicStrategy)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_short_key() {
+        assert_eq!(
+            "hard:16,9",
+            short_key("v3/bj-h17-das-nols-peek-basic/hard:16,9")
+        );
+        assert_eq!("s21/hard:16,9", short_key("v3/s21-simple/hard:16,9"));
+        assert_eq!("s21/hard:16,9", short_key("s21/hard:16,9"));
+        assert_eq!("comp:12-T2-4", short_key("comp:12-T2-4"));
+    }
+
+    #[test]
+    fn short_keys_parse_for_their_own_game() {
+        let ti: TableIndex = "hard:16,9".parse().unwrap();
+        let standard = Rules::default();
+        let spanish = Rules {
+            variant: GameVariant::Spanish21,
+            ..Rules::default()
+        };
+        assert_eq!(Some(ti), short_key_to_table_index("hard:16,9", &standard));
+        assert_eq!(None, short_key_to_table_index("s21/hard:16,9", &standard));
+        assert_eq!(
+            Some(ti),
+            short_key_to_table_index("s21/hard:16,9", &spanish)
+        );
+        assert_eq!(None, short_key_to_table_index("hard:16,9", &spanish));
+        assert_eq!(None, short_key_to_table_index("comp:12-T2-4", &standard));
+    }
 }
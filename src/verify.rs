@@ -0,0 +1,540 @@
+use crate::rules::{Rules, Soft17};
+use crate::strat::{
+    ChartAction, ChartSet, ColIndex, RowIndex, TableIndex, TableType, all_phrases, new_table_index,
+    strategy_charts,
+};
+use std::fmt::Display;
+
+// Cross-checks of the places the strategy is written down against the Rust charts: the
+// rendered tables, the generated phrases and the table in the coaching prompt. Clients send
+// the coach their strategy (see `strategy_prompt`), but the function keeps hand-maintained
+// standard tables for clients that don't, and a chart edit that isn't copied there would
+// leave the coach teaching a different strategy than the trainer grades.
+
+/// The coaching function, whose fallback prompt carries a copy of the standard charts.
+pub const COACHING_SOURCE: &str = include_str!("../supabase/functions/coaching/index.ts");
+
+/// One disagreement between a copy of the strategy and the charts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Where the copy lives, e.g. "coaching prompt".
+    pub source: &'static str,
+    /// The row or cell, e.g. "hard:12,4".
+    pub location: String,
+    pub detail: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}: {}", self.source, self.location, self.detail)
+    }
+}
+
+// Dealer up cards in chart order, as table column values (1 = Ace).
+const DEALER_COLUMNS: [u8; 10] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 1];
+
+fn index(row: RowIndex, col: u8) -> Option<TableIndex> {
+    let col: ColIndex = col.to_string().parse().ok()?;
+    Some(new_table_index(row, col))
+}
+
+fn chart_row(charts: &ChartSet, row: RowIndex) -> Vec<ChartAction> {
+    DEALER_COLUMNS
+        .iter()
+        .filter_map(|&col| index(row, col))
+        .map(|ti| charts.get(&ti).unwrap_or(ChartAction::NoAc))
+        .collect()
+}
+
+// A row label as the tables write it ("8-", "17+", "A,7 (18)", "A,7", "8,8", "T,T") to
+// the row it names.
+fn parse_row_label(table_type: TableType, label: &str) -> Option<RowIndex> {
+    let label = label.trim();
+    let card = |s: &str| match s.trim() {
+        "A" => Some(1),
+        "T" => Some(10),
+        s => s.parse::<u8>().ok(),
+    };
+    let row = match table_type {
+        TableType::Soft => match label.split_once('(') {
+            Some((_, total)) => total.trim_end_matches(')').trim().parse().ok()?,
+            None => 11 + card(label.strip_prefix("A,")?)?,
+        },
+        TableType::Split => card(label.split(',').next()?)?,
+        _ => label.trim_end_matches(['-', '+']).parse().ok()?,
+    };
+    RowIndex::new(table_type, row).ok()
+}
+
+// The rows a label covers: "8-" is every hard row up to 8, "17+" every one from 17.
+fn covered_rows(table_type: TableType, label: &str) -> Option<Vec<RowIndex>> {
+    let row = parse_row_label(table_type, label)?;
+    let rows = table_type.rows();
+    let covered = match label.trim().chars().last() {
+        Some('-') => *rows.start()..=row.index,
+        Some('+') => row.index..=*rows.end(),
+        _ => row.index..=row.index,
+    };
+    Some(
+        covered
+            .filter_map(|r| RowIndex::new(table_type, r).ok())
+            .collect(),
+    )
+}
+
+/// Check that the displayed tables show the charts' cells.
+pub fn check_rendering(charts: &ChartSet) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for (chart, table_type) in
+        strategy_charts(charts)
+            .iter()
+            .zip([TableType::Hard, TableType::Soft, TableType::Split])
+    {
+        for (label, cells) in &chart.rows {
+            let Some(row) = parse_row_label(table_type, label) else {
+                mismatches.push(Mismatch {
+                    source: "chart rendering",
+                    location: format!("{} row {}", chart.title, label),
+                    detail: "unknown row label".to_string(),
+                });
+                continue;
+            };
+            let expected: Vec<&str> = chart_row(charts, row).iter().map(|a| a.code()).collect();
+            if *cells != expected {
+                mismatches.push(Mismatch {
+                    source: "chart rendering",
+                    location: row.to_string(),
+                    detail: format!(
+                        "shows {} but the chart has {}",
+                        cells.join(" "),
+                        expected.join(" ")
+                    ),
+                });
+            }
+        }
+    }
+    mismatches
+}
+
+/// Check the tables in a coaching prompt against the charts for `rules`, and the rules the
+/// prompt states.
+pub fn check_coaching_prompt(prompt: &str, rules: &Rules) -> Vec<Mismatch> {
+    let charts = ChartSet::for_rules(rules);
+    let mismatch = |location: String, detail: String| Mismatch {
+        source: "coaching prompt",
+        location,
+        detail,
+    };
+    let mut mismatches = Vec::new();
+
+    let sections = [
+        ("## Hard Totals", TableType::Hard),
+        ("## Soft Totals", TableType::Soft),
+        ("## Pair Splitting", TableType::Split),
+    ];
+    for (heading, table_type) in sections {
+        let Some(start) = prompt.find(heading) else {
+            mismatches.push(mismatch(heading.to_string(), "section missing".to_string()));
+            continue;
+        };
+        let mut seen = Vec::new();
+        let lines = prompt[start + heading.len()..]
+            .lines()
+            .skip(1)
+            .take_while(|line| !line.trim().is_empty());
+        for line in lines {
+            let Some((label, codes)) = line.split_once(':') else {
+                continue;
+            };
+            if label.trim() == "Columns" {
+                continue;
+            }
+            let Some(rows) = covered_rows(table_type, label) else {
+                mismatches.push(mismatch(
+                    format!("{} row {}", heading, label.trim()),
+                    "unknown row label".to_string(),
+                ));
+                continue;
+            };
+            let found: Vec<&str> = codes.split_whitespace().collect();
+            for row in rows {
+                seen.push(row.index);
+                let expected: Vec<&str> =
+                    chart_row(&charts, row).iter().map(|a| a.code()).collect();
+                if found.len() != expected.len() {
+                    mismatches.push(mismatch(
+                        row.to_string(),
+                        format!("{} cells, expected {}", found.len(), expected.len()),
+                    ));
+                    continue;
+                }
+                for ((&col, found), expected) in DEALER_COLUMNS.iter().zip(&found).zip(expected) {
+                    if ChartAction::from_code(found).map(|a| a.code()) != Some(expected) {
+                        let location = index(row, col).map_or(row.to_string(), |ti| ti.to_string());
+                        mismatches.push(mismatch(
+                            location,
+                            format!("says {} but the chart has {}", found, expected),
+                        ));
+                    }
+                }
+            }
+        }
+        for row in table_type.rows().filter(|r| !seen.contains(r)) {
+            if let Ok(row) = RowIndex::new(table_type, row) {
+                mismatches.push(mismatch(row.to_string(), "row missing".to_string()));
+            }
+        }
+    }
+
+    match prompt.lines().find(|line| line.starts_with("Rules:")) {
+        None => mismatches.push(mismatch("Rules".to_string(), "line missing".to_string())),
+        Some(line) => {
+            let line = line.to_lowercase();
+            let mut expect = |ok: bool, what: String| {
+                if !ok {
+                    mismatches.push(mismatch(
+                        "Rules".to_string(),
+                        format!("should say {}", what),
+                    ));
+                }
+            };
+            expect(
+                line.contains(&format!("{}-deck", rules.decks)),
+                format!("{}-deck", rules.decks),
+            );
+            let soft_17 = match rules.soft_17 {
+                Soft17::Hits => "hits soft 17",
+                Soft17::Stands => "stands on soft 17",
+            };
+            expect(line.contains(soft_17), format!("dealer {}", soft_17));
+            let surrender = if rules.late_surrender() {
+                "late surrender"
+            } else {
+                "no surrender"
+            };
+            expect(line.contains(surrender), surrender.to_string());
+            let das = if rules.das {
+                "(das)"
+            } else {
+                "no double after split"
+            };
+            expect(line.contains(das), das.to_string());
+        }
+    }
+    mismatches
+}
+
+// The play a phrase gives for each dealer card, parsed back from its wording. The phrase
+// grammar is the one `phrase_for_row` writes:
+//   "{hand} always {plays}."
+//   "{hand} {plays} against dealer {cards}[, and {plays} against {cards}]*, otherwise {play}."
+//   "Always split {pairs}." / "Never split {pairs}."
+//   "{total} surrenders against dealer {cards}, otherwise don’t surrender ..."
+//   "{total} never surrenders."
+fn parse_phrase(phrase: &str) -> Option<(String, [Option<String>; 10])> {
+    let phrase = phrase.trim_end_matches('.');
+    let mut plays: [Option<String>; 10] = Default::default();
+    for verb in ["Always split ", "Never split "] {
+        if let Some(pairs) = phrase.strip_prefix(verb) {
+            let play = if verb.starts_with("Always") {
+                "splits"
+            } else {
+                "-"
+            };
+            plays = std::array::from_fn(|_| Some(play.to_string()));
+            return Some((format!("pair of {}", pairs), plays));
+        }
+    }
+    if let Some((hand, verb)) = phrase.split_once(" always ") {
+        plays = std::array::from_fn(|_| Some(verb.to_string()));
+        return Some((hand.to_string(), plays));
+    }
+    if let Some(total) = phrase.strip_suffix(" never surrenders") {
+        plays = std::array::from_fn(|_| Some("-".to_string()));
+        return Some((total.to_string(), plays));
+    }
+
+    let (body, otherwise) = phrase.rsplit_once(", otherwise ")?;
+    let (hand, clauses) = body.split_once(" against dealer ")?;
+    // The hand ends with the first clause's verb
+    let (hand, first_verb) = hand.rsplit_once(' ')?;
+    let mut verb = first_verb.to_string();
+    for (i, clause) in clauses.split(", and ").enumerate() {
+        let cards = if i == 0 {
+            clause
+        } else {
+            let (v, cards) = clause.split_once(" against ")?;
+            verb = v.to_string();
+            cards
+        };
+        for position in parse_dealer_cards(cards)? {
+            plays[position] = Some(verb.clone());
+        }
+    }
+    let otherwise = if otherwise.starts_with("don’t") {
+        "-".to_string()
+    } else {
+        // "otherwise hit" reads as the third person "hits"
+        format!("{}s", otherwise)
+    };
+    for play in plays.iter_mut().filter(|p| p.is_none()) {
+        *play = Some(otherwise.clone());
+    }
+    Some((hand.to_string(), plays))
+}
+
+// "2 through 9, except for 7", "5 and 6", "2, 4 and 6" to positions in DEALER_COLUMNS.
+fn parse_dealer_cards(cards: &str) -> Option<Vec<usize>> {
+    let position = |label: &str| {
+        let col = match label.trim() {
+            "Ace" => 1,
+            l => l.parse::<u8>().ok()?,
+        };
+        DEALER_COLUMNS.iter().position(|&c| c == col)
+    };
+    let (cards, except) = match cards.split_once(", except for ") {
+        Some((cards, except)) => (cards, Some(position(except)?)),
+        None => (cards, None),
+    };
+    let mut positions = Vec::new();
+    for item in cards.split(", ").flat_map(|s| s.split(" and ")) {
+        match item.split_once(" through ") {
+            Some((from, to)) => positions.extend(position(from)?..=position(to)?),
+            None => positions.push(position(item)?),
+        }
+    }
+    positions.retain(|&p| Some(p) != except);
+    Some(positions)
+}
+
+// The phrase wording for a chart cell. `unsplit` is the play of a pair that doesn't split.
+fn expected_wording(action: ChartAction, unsplit: Option<ChartAction>) -> &'static str {
+    match action {
+        ChartAction::Hit_ => "hits",
+        ChartAction::Stnd => "stands",
+        ChartAction::DblH | ChartAction::DblS => "doubles",
+        ChartAction::Splt | ChartAction::SDas => "splits",
+        ChartAction::SurH => "hits",
+        ChartAction::NoAc => unsplit.map_or("hits", |a| expected_wording(a, None)),
+    }
+}
+
+/// Parse the generated phrases back and check them against the charts.
+pub fn check_phrases(charts: &ChartSet) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for (category, phrases) in all_phrases(charts) {
+        let table_type = match category {
+            "Surrender" => TableType::Surrender,
+            "Splits" => TableType::Split,
+            "Soft Totals" => TableType::Soft,
+            _ => TableType::Hard,
+        };
+        for phrase in phrases {
+            let Some((hand, found)) = parse_phrase(&phrase) else {
+                mismatches.push(Mismatch {
+                    source: "phrases",
+                    location: category.to_string(),
+                    detail: format!("can't read \"{}\"", phrase),
+                });
+                continue;
+            };
+            let Some((row, expected)) = expected_phrase_row(charts, table_type, &hand, &found)
+            else {
+                mismatches.push(Mismatch {
+                    source: "phrases",
+                    location: category.to_string(),
+                    detail: format!("unknown hand in \"{}\"", phrase),
+                });
+                continue;
+            };
+            for (i, (found, expected)) in found.iter().zip(expected).enumerate() {
+                if found.as_deref() != Some(expected) {
+                    let location =
+                        index(row, DEALER_COLUMNS[i]).map_or(row.to_string(), |ti| ti.to_string());
+                    mismatches.push(Mismatch {
+                        source: "phrases",
+                        location,
+                        detail: format!(
+                            "\"{}\" says {} but the chart {}",
+                            phrase,
+                            found.as_deref().unwrap_or("nothing"),
+                            expected
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    mismatches
+}
+
+// The row a phrase describes and the wording the charts call for in each column.
+fn expected_phrase_row(
+    charts: &ChartSet,
+    table_type: TableType,
+    hand: &str,
+    found: &[Option<String>; 10],
+) -> Option<(RowIndex, Vec<&'static str>)> {
+    match table_type {
+        TableType::Surrender => {
+            let row = RowIndex::new(TableType::Hard, hand.parse().ok()?).ok()?;
+            let expected = chart_row(charts, row)
+                .iter()
+                .map(|&a| {
+                    if a == ChartAction::SurH {
+                        "surrenders"
+                    } else {
+                        "-"
+                    }
+                })
+                .collect();
+            Some((row, expected))
+        }
+        TableType::Split => {
+            let pairs = hand.rsplit(' ').next()?;
+            let value = match pairs {
+                "Aces" => 1,
+                "tens" => 10,
+                p => p.chars().next()?.to_digit(10)? as u8,
+            };
+            let row = RowIndex::new(TableType::Split, value).ok()?;
+            let unsplit = RowIndex::new(TableType::Hard, value * 2)
+                .ok()
+                .filter(|_| value != 1)
+                .map(|hard| chart_row(charts, hard));
+            let never = found.iter().all(|p| p.as_deref() == Some("-"));
+            let expected = chart_row(charts, row)
+                .iter()
+                .enumerate()
+                .map(|(i, &a)| match a {
+                    ChartAction::Splt | ChartAction::SDas => "splits",
+                    // "Never split" doesn't say how the pair plays instead
+                    _ if never => "-",
+                    _ => expected_wording(a, unsplit.as_ref().map(|u| u[i])),
+                })
+                .collect();
+            Some((row, expected))
+        }
+        _ => {
+            let label = match table_type {
+                TableType::Soft => hand.split_once('(')?.1.trim_end_matches(')'),
+                _ => hand.split(' ').next()?,
+            };
+            let row = parse_row_label(table_type, label)?;
+            // The totals give the play for when surrender isn't on offer
+            let expected = DEALER_COLUMNS
+                .iter()
+                .filter_map(|&col| index(row, col))
+                .map(|ti| charts.without_surrender(&ti).unwrap_or(ChartAction::NoAc))
+                .map(|a| expected_wording(a, None))
+                .collect();
+            Some((row, expected))
+        }
+    }
+}
+
+/// Every cross-check for `rules`: rendering and phrases for its charts, and the coaching
+/// prompt against the standard game it describes.
+pub fn verify(rules: &Rules) -> Vec<Mismatch> {
+    let charts = ChartSet::for_rules(rules);
+    let mut mismatches = check_rendering(&charts);
+    mismatches.extend(check_phrases(&charts));
+    mismatches.extend(check_coaching_prompt(COACHING_SOURCE, &Rules::standard()));
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::PRESETS;
+
+    #[test]
+    fn everything_agrees_with_the_charts() {
+        for preset in PRESETS.iter() {
+            let mismatches = verify(&preset.rules);
+            assert!(
+                mismatches.is_empty(),
+                "{}:\n{}",
+                preset.key,
+                mismatches
+                    .iter()
+                    .map(|m| m.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+    }
+
+    #[test]
+    fn late_surrender_with_h17_agrees_with_the_charts() {
+        let rules = Rules {
+            surrender: true,
+            ..Rules::standard()
+        };
+        assert!(verify(&rules).is_empty());
+    }
+
+    #[test]
+    fn a_changed_prompt_cell_is_caught() {
+        let prompt =
+            COACHING_SOURCE.replace("12:       H  H  S  S  S  H", "12:       H  S  S  S  S  H");
+        let mismatches = check_coaching_prompt(&prompt, &Rules::standard());
+        assert_eq!(1, mismatches.len());
+        assert_eq!("hard:12,3", mismatches[0].location);
+    }
+
+    #[test]
+    fn a_missing_prompt_row_is_caught() {
+        let prompt = COACHING_SOURCE.replace("5,5:      -  -  -  -  -  -  -  -  -  -\n", "");
+        let mismatches = check_coaching_prompt(&prompt, &Rules::standard());
+        assert_eq!(
+            vec!["split:5".to_string()],
+            mismatches
+                .iter()
+                .map(|m| m.location.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn the_prompt_is_checked_against_other_rules() {
+        let s17 = Rules {
+            soft_17: Soft17::Stands,
+            ..Default::default()
+        };
+        let locations: Vec<String> = check_coaching_prompt(COACHING_SOURCE, &s17)
+            .into_iter()
+            .map(|m| m.location)
+            .collect();
+        assert!(locations.contains(&"hard:11,1".to_string()));
+        assert!(locations.contains(&"Rules".to_string()));
+    }
+
+    #[test]
+    fn collapsed_prompt_rows_cover_the_rows_they_stand_for() {
+        let mut charts = ChartSet::bja();
+        charts.hard[1][4] = ChartAction::DblH; // 6 vs 6
+        let prompt = crate::coaching::strategy_prompt(&charts, &Rules::standard());
+        assert!(prompt.contains("\n5-:"));
+        assert!(prompt.contains("\n6:        H  H  H  H  Dh"));
+        assert!(prompt.contains("\n17+:"));
+        // The standard charts don't double 6 vs 6
+        let locations: Vec<String> = check_coaching_prompt(&prompt, &Rules::standard())
+            .into_iter()
+            .map(|m| m.location)
+            .collect();
+        assert_eq!(vec!["hard:6,6".to_string()], locations);
+    }
+
+    #[test]
+    fn phrases_parse_back() {
+        let (hand, plays) = parse_phrase(
+            "A pair of 9’s splits against dealer 2 through 9, except for 7, otherwise stand.",
+        )
+        .unwrap();
+        assert_eq!("A pair of 9’s", hand);
+        assert_eq!(Some("stands"), plays[5].as_deref());
+        assert_eq!(Some("splits"), plays[6].as_deref());
+        assert_eq!(Some("stands"), plays[9].as_deref());
+    }
+}
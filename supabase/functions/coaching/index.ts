@@ -10,6 +10,107 @@ const ALLOWED_ORIGINS = [
   "http://localhost:3141",
 ];
 
+// Longest strategy text accepted from the client.
+const MAX_STRATEGY_LENGTH = 8000;
+
+// The standard 6-deck H17 DAS tables, for clients that don't send their own. `cargo test`
+// checks these against the Rust charts (see src/verify.rs).
+const DEFAULT_STRATEGY = `Table index format: "type:row,col" where col is dealer's up card (1=Ace, 2-10).
+Actions: H=Hit, S=Stand, Dh=Double (Hit if can't), Ds=Double (Stand if can't), P=Split, Pd=Split (DAS only, otherwise don't split).
+
+## Hard Totals
+Columns: 2  3  4  5  6  7  8  9  T  A
+8-:       H  H  H  H  H  H  H  H  H  H
+9:        H  Dh Dh Dh Dh H  H  H  H  H
+10:       Dh Dh Dh Dh Dh Dh Dh Dh H  H
+11:       Dh Dh Dh Dh Dh Dh Dh Dh Dh Dh
+12:       H  H  S  S  S  H  H  H  H  H
+13:       S  S  S  S  S  H  H  H  H  H
+14:       S  S  S  S  S  H  H  H  H  H
+15:       S  S  S  S  S  H  H  H  H  H
+16:       S  S  S  S  S  H  H  H  H  H
+17+:      S  S  S  S  S  S  S  S  S  S
+
+## Soft Totals
+Columns: 2  3  4  5  6  7  8  9  T  A
+A,A (12): H  H  H  H  H  H  H  H  H  H
+A,2 (13): H  H  H  Dh Dh H  H  H  H  H
+A,3 (14): H  H  H  Dh Dh H  H  H  H  H
+A,4 (15): H  H  Dh Dh Dh H  H  H  H  H
+A,5 (16): H  H  Dh Dh Dh H  H  H  H  H
+A,6 (17): H  Dh Dh Dh Dh H  H  H  H  H
+A,7 (18): Ds Ds Ds Ds Ds S  S  H  H  H
+A,8 (19): S  S  S  S  Ds S  S  S  S  S
+A,9 (20): S  S  S  S  S  S  S  S  S  S
+A,T (21): S  S  S  S  S  S  S  S  S  S
+
+## Pair Splitting
+Columns: 2  3  4  5  6  7  8  9  T  A
+A,A:      P  P  P  P  P  P  P  P  P  P
+2,2:      Pd Pd P  P  P  P  -  -  -  -
+3,3:      Pd Pd P  P  P  P  -  -  -  -
+4,4:      -  -  -  Pd Pd -  -  -  -  -
+5,5:      -  -  -  -  -  -  -  -  -  -
+6,6:      Pd P  P  P  P  -  -  -  -  -
+7,7:      P  P  P  P  P  P  -  -  -  -
+8,8:      P  P  P  P  P  P  P  P  P  P
+9,9:      P  P  P  P  P  -  P  P  -  -
+T,T:      -  -  -  -  -  -  -  -  -  -
+
+("-" means do NOT split; use the hard total instead. "Pd" means split only with DAS.)
+Rules: 6-deck shoe, dealer hits soft 17, double after split allowed (DAS), no surrender.`;
+
+// The lines `bjsc::strategy_prompt` writes. Client strategy text goes into the system
+// prompt, so every line must be one of these: nothing else from the client reaches it.
+const LEGEND_LINES = [
+  `Table index format: "type:row,col" where col is dealer's up card (1=Ace, 2-10).`,
+  `Actions: H=Hit, S=Stand, Dh=Double (Hit if can't), Ds=Double (Stand if can't), P=Split, Pd=Split (DAS only, otherwise don't split), Rh=Surrender (Hit if can't).`,
+  `## Hard Totals`,
+  `## Soft Totals`,
+  `## Pair Splitting`,
+  `Columns: 2  3  4  5  6  7  8  9  T  A`,
+  `("-" means do NOT split; use the hard total instead. "Pd" means split only with DAS.)`,
+];
+
+const CODE = String.raw`(?:H|S|Dh|Ds|P|Pd|Rh|-)`;
+const CARD = "[2-9TA]";
+// e.g. "17+:      S  S ...", "A,7 (18): Ds Ds ..." or "8,8:      P  P ..."
+const ROW_LINE = new RegExp(
+  String.raw`^(?:\d{1,2}[-+]?|${CARD},${CARD}(?: \(\d{1,2}\))?): +${CODE}(?: +${CODE}){9}$`,
+);
+// e.g. "Also: 16 vs 2: Hit with 4+ cards", "Also: 16 (3+ cards) vs T: Stand" or
+// "Also: T,2 vs 4: Hit"
+const NOTE_LINE = new RegExp(
+  String.raw`^Also: (?:\d{1,2}(?: \(\d\+ cards\))?|${CARD},${CARD}) vs ${CARD}: (?:Hit|Stand|Double|Split|Surrender)(?: with \d{1,2}\+ cards)?$`,
+);
+const RULE_PARTS = [
+  /^Spanish 21$/,
+  /^Free Bet$/,
+  /^\d{1,2}-deck shoe$/,
+  /^dealer (?:hits|stands on) soft 17$/,
+  /^double after split allowed \(DAS\)$/,
+  /^no double after split$/,
+  /^(?:late|no) surrender$/,
+  /^resplit aces$/,
+  /^no dealer hole card \(ENHC\)$/,
+  /^blackjack pays \d{1,2}:\d{1,2}$/,
+];
+
+function isStrategyLine(line: string): boolean {
+  if (line === "" || LEGEND_LINES.includes(line)) return true;
+  if (ROW_LINE.test(line) || NOTE_LINE.test(line)) return true;
+  const rules = line.match(/^Rules: (.*)\.$/);
+  return rules !== null &&
+    rules[1].split(", ").every((part) => RULE_PARTS.some((re) => re.test(part)));
+}
+
+// The client's strategy text if every line is in the chart format, otherwise null.
+function checkStrategy(text: string): string | null {
+  if (text.length === 0 || text.length > MAX_STRATEGY_LENGTH) return null;
+  const lines = text.split("\n");
+  return lines.every(isStrategyLine) ? lines.join("\n") : null;
+}
+
 function corsHeaders(req: Request) {
   const origin = req.headers.get("Origin") ?? "";
   return {
@@ -132,6 +233,27 @@ Deno.serve(async (req) => {
 
     const studyMode = deckData?.study_mode ?? "unknown";
 
+    // The app sends the strategy the user is training (rules, variant or custom chart),
+    // rendered by `bjsc::strategy_prompt`. Older clients send nothing and get the standard
+    // tables; anything that isn't in the chart format is refused.
+    const body = await req.json().catch(() => ({}));
+    let strategy = DEFAULT_STRATEGY;
+    if (body?.strategy !== undefined) {
+      const checked = typeof body.strategy === "string"
+        ? checkStrategy(body.strategy)
+        : null;
+      if (checked === null) {
+        return new Response(
+          JSON.stringify({ error: "Strategy isn't in the chart format" }),
+          {
+            status: 400,
+            headers: { ...corsHeaders(req), "Content-Type": "application/json" },
+          },
+        );
+      }
+      strategy = checked;
+    }
+
     const systemPrompt = `You are a blackjack basic strategy coach. The user is practicing memorizing the basic strategy chart using a spaced repetition trainer. Your role is to:
 
 1. Analyze their performance data and identify patterns in their mistakes
@@ -143,49 +265,7 @@ Keep your response concise (under 300 words). Use short paragraphs. Reference sp
 
 IMPORTANT: Use ONLY the strategy tables below when coaching. Do NOT use any other basic strategy — the user is training with these exact tables.
 
-Table index format: "type:row,col" where col is dealer's up card (1=Ace, 2-10).
-Actions: H=Hit, S=Stand, Dh=Double (Hit if can't), Ds=Double (Stand if can't), P=Split, Pd=Split (DAS only, otherwise don't split).
-
-## Hard Totals
-Columns: 2  3  4  5  6  7  8  9  T  A
-8-:       H  H  H  H  H  H  H  H  H  H
-9:        H  Dh Dh Dh Dh H  H  H  H  H
-10:       Dh Dh Dh Dh Dh Dh Dh Dh H  H
-11:       Dh Dh Dh Dh Dh Dh Dh Dh Dh Dh
-12:       H  H  S  S  S  H  H  H  H  H
-13:       S  S  S  S  S  H  H  H  H  H
-14:       S  S  S  S  S  H  H  H  H  H
-15:       S  S  S  S  S  H  H  H  H  H
-16:       S  S  S  S  S  H  H  H  H  H
-17+:      S  S  S  S  S  S  S  S  S  S
-
-## Soft Totals
-Columns: 2  3  4  5  6  7  8  9  T  A
-A,2 (13): H  H  H  Dh Dh H  H  H  H  H
-A,3 (14): H  H  H  Dh Dh H  H  H  H  H
-A,4 (15): H  H  Dh Dh Dh H  H  H  H  H
-A,5 (16): H  H  Dh Dh Dh H  H  H  H  H
-A,6 (17): H  Dh Dh Dh Dh H  H  H  H  H
-A,7 (18): Ds Ds Ds Ds Ds S  S  H  H  H
-A,8 (19): S  S  S  S  Ds S  S  S  S  S
-A,9 (20): S  S  S  S  S  S  S  S  S  S
-A,T (21): S  S  S  S  S  S  S  S  S  S
-
-## Pair Splitting
-Columns: 2  3  4  5  6  7  8  9  T  A
-A,A:      P  P  P  P  P  P  P  P  P  P
-2,2:      Pd Pd P  P  P  P  -  -  -  -
-3,3:      Pd Pd P  P  P  P  -  -  -  -
-4,4:      -  -  -  Pd Pd -  -  -  -  -
-5,5:      -  -  -  -  -  -  -  -  -  -
-6,6:      Pd P  P  P  P  -  -  -  -  -
-7,7:      P  P  P  P  P  P  -  -  -  -
-8,8:      P  P  P  P  P  P  P  P  P  P
-9,9:      P  P  P  P  P  -  P  P  -  -
-T,T:      -  -  -  -  -  -  -  -  -  -
-
-("-" means do NOT split; use the hard total instead. "Pd" means split only with DAS.)
-Rules: 6-deck shoe, dealer stands on soft 17, double after split allowed (DAS), no surrender.`;
+${strategy}`;
 
     const userMessage = `Here's my practice data:
 
@@ -0,0 +1,4 @@
+-- user_deck.rules: the table rules the user studies under (e.g. hole card / ENHC).
+-- Stored as the serde JSON of bjsc::Rules; missing fields fall back to defaults.
+alter table user_deck
+  add column if not exists rules jsonb not null default '{}'::jsonb;
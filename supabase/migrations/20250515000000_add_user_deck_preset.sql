@@ -0,0 +1,4 @@
+-- user_deck.preset: key of the rules preset the user picked (e.g. 'vegas-strip'), or null
+-- for custom rules. When set, it takes precedence over user_deck.rules on load.
+alter table user_deck
+  add column if not exists preset text;
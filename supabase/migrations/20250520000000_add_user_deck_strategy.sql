@@ -0,0 +1,3 @@
+-- user_deck.strategy: which strategy the user is learning ("Basic", "Simple", "Composition").
+alter table user_deck
+  add column if not exists strategy text not null default 'Basic';
@@ -0,0 +1,6 @@
+-- user_deck.study_sets: the user's own study sets, each a name and a filter over tables,
+-- rows, dealer upcards and plays (see bjsc::StudySet).
+-- user_deck.study_set: name of the set the Custom mode deals from, or null.
+alter table user_deck
+  add column if not exists study_sets jsonb not null default '[]'::jsonb,
+  add column if not exists study_set text;
@@ -0,0 +1,4 @@
+-- user_deck.lesson: the latest lesson the Curriculum mode has unlocked, counting from 0
+-- (see bjsc::curriculum::LESSONS).
+alter table user_deck
+  add column if not exists lesson smallint not null default 0;
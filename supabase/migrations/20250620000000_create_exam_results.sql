@@ -0,0 +1,32 @@
+-- exam_results: one row per exam taken, graded against the rules and strategy it was
+-- taken under, or the player's own charts (see bjsc::exam::ExamReport). Whether it passed
+-- follows from the score, so it isn't stored.
+create table if not exists exam_results (
+  id             bigint generated always as identity primary key,
+  user_id        uuid not null references auth.users(id) on delete cascade,
+  taken_at       timestamptz not null,
+  duration_secs  integer not null,
+  rules          jsonb not null,
+  strategy       text not null,
+  custom_charts  boolean not null default false,
+  correct        integer not null,
+  total          integer not null,
+  by_table       jsonb not null default '[]'::jsonb,
+  missed         jsonb not null default '[]'::jsonb,
+  created_at     timestamptz not null default now()
+);
+
+create index if not exists idx_exam_results_user_taken
+  on exam_results (user_id, taken_at desc);
+
+alter table exam_results enable row level security;
+
+-- Users can read and insert their own results. Results are never updated, so a
+-- certification can't be edited after the fact.
+create policy "Users can select their own exam results"
+  on exam_results for select
+  using (auth.uid() = user_id);
+
+create policy "Users can insert their own exam results"
+  on exam_results for insert
+  with check (auth.uid() = user_id);
@@ -0,0 +1,4 @@
+-- answer_log.latency_ms: milliseconds from the deal to the answer. Null for answers
+-- logged before they were timed.
+alter table answer_log
+  add column if not exists latency_ms integer;
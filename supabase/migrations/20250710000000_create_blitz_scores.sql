@@ -0,0 +1,28 @@
+-- blitz_scores: one row per Blitz played (see bjsc::blitz::BlitzScore). A personal best is
+-- the row with the most right answers, then the fewest wrong, for a rule set, strategy and
+-- length.
+create table if not exists blitz_scores (
+  id             bigint generated always as identity primary key,
+  user_id        uuid not null references auth.users(id) on delete cascade,
+  taken_at       timestamptz not null,
+  rule_set       text not null,
+  strategy       text not null,
+  duration_secs  integer not null,
+  correct        integer not null,
+  wrong          integer not null,
+  created_at     timestamptz not null default now()
+);
+
+create index if not exists idx_blitz_scores_challenge
+  on blitz_scores (user_id, rule_set, strategy, duration_secs, correct desc, wrong);
+
+alter table blitz_scores enable row level security;
+
+-- Users can read and insert their own scores. Scores are never updated.
+create policy "Users can select their own blitz scores"
+  on blitz_scores for select
+  using (auth.uid() = user_id);
+
+create policy "Users can insert their own blitz scores"
+  on blitz_scores for insert
+  with check (auth.uid() = user_id);
@@ -0,0 +1,195 @@
+-- Groups and their leaderboards. Joining a group is the opt-in: a player's Blitz scores,
+-- exam results and practice streak are visible to the members of groups they've joined,
+-- and to no one else. Leaving a group takes them off its boards.
+
+create table if not exists groups (
+  id          uuid primary key default gen_random_uuid(),
+  name        text not null,
+  -- Shared by members to let others in (see join_group)
+  join_code   text not null unique
+              default substr(md5(random()::text || clock_timestamp()::text), 1, 8),
+  created_by  uuid not null references auth.users(id) on delete cascade,
+  created_at  timestamptz not null default now()
+);
+
+create table if not exists group_members (
+  group_id      uuid not null references groups(id) on delete cascade,
+  user_id       uuid not null references auth.users(id) on delete cascade,
+  -- The name the group sees on its leaderboards
+  display_name  text not null,
+  joined_at     timestamptz not null default now(),
+  primary key (group_id, user_id)
+);
+
+create index if not exists idx_group_members_user on group_members (user_id);
+
+-- Membership checks for the policies below. They're security definer so the policies on
+-- group_members can look at group_members without recursing.
+create or replace function is_group_member(gid uuid)
+returns boolean
+language sql stable security definer set search_path = public
+as $$
+  select exists (
+    select 1 from group_members where group_id = gid and user_id = auth.uid()
+  );
+$$;
+
+create or replace function shares_group_with(other uuid)
+returns boolean
+language sql stable security definer set search_path = public
+as $$
+  select exists (
+    select 1
+    from group_members mine
+    join group_members theirs on theirs.group_id = mine.group_id
+    where mine.user_id = auth.uid() and theirs.user_id = other
+  );
+$$;
+
+-- practice_streaks: each player's latest run of UTC days with answers (see
+-- bjsc::supabase::StreakRow), worked out from answer_log so it can't be claimed. The view
+-- reads answer_log as its owner, so it shows only the caller's streak and those of the
+-- players they share a group with.
+create or replace view practice_streaks as
+with days as (
+  select distinct user_id, (created_at at time zone 'utc')::date as day
+  from answer_log
+  where user_id = auth.uid() or shares_group_with(user_id)
+),
+runs as (
+  -- Days in a row share a run: the day less its place in the player's days
+  select user_id, day,
+         day - (row_number() over (partition by user_id order by day))::integer as run
+  from days
+)
+select user_id, max(day) as last_day, count(*)::integer as days
+from runs
+group by user_id, run
+having max(day) = (select max(d.day) from days d where d.user_id = runs.user_id);
+
+-- The streaks count answer_log's days, so the database stamps when an answer was logged.
+create or replace function stamp_created_at()
+returns trigger
+language plpgsql
+as $$
+begin
+  new.created_at = now();
+  return new;
+end;
+$$;
+
+create trigger stamp_answer_log_created_at
+  before insert on answer_log
+  for each row
+  execute function stamp_created_at();
+
+-- Scores on the boards must be ones a game could produce: a Blitz length on offer, no
+-- more than three right answers a second, and no more wrong answers than the clock
+-- allows (each takes five seconds off it). An exam's misses are the questions it lost.
+alter table blitz_scores
+  add constraint blitz_scores_length check (duration_secs in (30, 60, 120)),
+  add constraint blitz_scores_counts check (
+    correct >= 0 and wrong >= 0
+    and correct <= duration_secs * 3
+    and wrong * 5 <= duration_secs
+  );
+
+alter table exam_results
+  add constraint exam_results_counts check (
+    total > 0 and correct between 0 and total
+    and jsonb_array_length(missed) = total - correct
+    and duration_secs >= 0
+  );
+
+-- One Blitz score per game: a player can't post another until a game of that length
+-- could have been played since their last one.
+create or replace function check_blitz_score()
+returns trigger
+language plpgsql security definer set search_path = public
+as $$
+begin
+  if exists (
+    select 1 from blitz_scores
+    where user_id = new.user_id
+      and created_at > now() - make_interval(secs => new.duration_secs)
+  ) then
+    raise exception 'Blitz score posted too soon after the last one';
+  end if;
+  new.created_at = now();
+  return new;
+end;
+$$;
+
+create trigger check_blitz_score
+  before insert on blitz_scores
+  for each row
+  execute function check_blitz_score();
+
+-- Create a group and join it. Groups are only made this way, so every group starts with
+-- its creator as a member.
+create or replace function create_group(group_name text, display_name text)
+returns groups
+language plpgsql security definer set search_path = public
+as $$
+declare
+  g groups;
+begin
+  if auth.uid() is null then
+    raise exception 'Not signed in';
+  end if;
+  insert into groups (name, created_by) values (group_name, auth.uid()) returning * into g;
+  insert into group_members (group_id, user_id, display_name)
+    values (g.id, auth.uid(), create_group.display_name);
+  return g;
+end;
+$$;
+
+-- Join the group with a join code, or change the name shown in a group already joined.
+create or replace function join_group(code text, display_name text)
+returns groups
+language plpgsql security definer set search_path = public
+as $$
+declare
+  g groups;
+begin
+  if auth.uid() is null then
+    raise exception 'Not signed in';
+  end if;
+  select * into g from groups where join_code = code;
+  if not found then
+    raise exception 'No group with that code';
+  end if;
+  insert into group_members (group_id, user_id, display_name)
+    values (g.id, auth.uid(), join_group.display_name)
+    on conflict (group_id, user_id) do update set display_name = excluded.display_name;
+  return g;
+end;
+$$;
+
+alter table groups enable row level security;
+alter table group_members enable row level security;
+
+create policy "Members can select their groups"
+  on groups for select
+  using (is_group_member(id));
+
+create policy "Creators can delete their groups"
+  on groups for delete
+  using (auth.uid() = created_by);
+
+create policy "Members can select their groups' members"
+  on group_members for select
+  using (is_group_member(group_id));
+
+create policy "Users can leave groups"
+  on group_members for delete
+  using (auth.uid() = user_id);
+
+-- The leaderboards read other members' scores; writes stay with their owners
+create policy "Group members can select each other's blitz scores"
+  on blitz_scores for select
+  using (shares_group_with(user_id));
+
+create policy "Group members can select each other's exam results"
+  on exam_results for select
+  using (shares_group_with(user_id));
@@ -0,0 +1,150 @@
+-- Classes: an instructor onboarding a batch of players. Any player can start a class and
+-- be its instructor. Joining a class with its join code lets the instructor read the
+-- member's answer log and exam results, and shows the member the class's assignments.
+-- Leaving the class (or being removed by the instructor) ends that access.
+
+create table if not exists classes (
+  id             uuid primary key default gen_random_uuid(),
+  name           text not null,
+  -- Shared by the instructor to let members in (see join_class)
+  join_code      text not null unique
+                 default substr(md5(random()::text || clock_timestamp()::text), 1, 8),
+  instructor_id  uuid not null references auth.users(id) on delete cascade,
+  created_at     timestamptz not null default now()
+);
+
+create index if not exists idx_classes_instructor on classes (instructor_id);
+
+create table if not exists class_members (
+  class_id      uuid not null references classes(id) on delete cascade,
+  user_id       uuid not null references auth.users(id) on delete cascade,
+  -- The name the instructor sees
+  display_name  text not null,
+  joined_at     timestamptz not null default now(),
+  primary key (class_id, user_id)
+);
+
+create index if not exists idx_class_members_user on class_members (user_id);
+
+-- class_assignments: work set by the instructor, a bjsc::class::Assignment as JSON, e.g.
+-- {"kind": "exam", "per_table": 10} or {"kind": "study_set", "name": "Pairs", ...}
+create table if not exists class_assignments (
+  id          bigserial primary key,
+  class_id    uuid not null references classes(id) on delete cascade,
+  assignment  jsonb not null,
+  created_at  timestamptz not null default now()
+);
+
+create index if not exists idx_class_assignments_class on class_assignments (class_id);
+
+-- Checks for the policies below. They're security definer so the policies on classes and
+-- class_members can look at each other without recursing.
+create or replace function is_class_member(cid uuid)
+returns boolean
+language sql stable security definer set search_path = public
+as $$
+  select exists (
+    select 1 from class_members where class_id = cid and user_id = auth.uid()
+  );
+$$;
+
+create or replace function is_class_instructor(cid uuid)
+returns boolean
+language sql stable security definer set search_path = public
+as $$
+  select exists (
+    select 1 from classes where id = cid and instructor_id = auth.uid()
+  );
+$$;
+
+create or replace function instructs(member uuid)
+returns boolean
+language sql stable security definer set search_path = public
+as $$
+  select exists (
+    select 1
+    from class_members m
+    join classes c on c.id = m.class_id
+    where m.user_id = member and c.instructor_id = auth.uid()
+  );
+$$;
+
+-- Create a class with the caller as its instructor.
+create or replace function create_class(class_name text)
+returns classes
+language plpgsql security definer set search_path = public
+as $$
+declare
+  c classes;
+begin
+  if auth.uid() is null then
+    raise exception 'Not signed in';
+  end if;
+  insert into classes (name, instructor_id) values (class_name, auth.uid()) returning * into c;
+  return c;
+end;
+$$;
+
+-- Join the class with a join code, or change the name shown in a class already joined.
+create or replace function join_class(code text, display_name text)
+returns classes
+language plpgsql security definer set search_path = public
+as $$
+declare
+  c classes;
+begin
+  if auth.uid() is null then
+    raise exception 'Not signed in';
+  end if;
+  select * into c from classes where join_code = code;
+  if not found then
+    raise exception 'No class with that code';
+  end if;
+  insert into class_members (class_id, user_id, display_name)
+    values (c.id, auth.uid(), join_class.display_name)
+    on conflict (class_id, user_id) do update set display_name = excluded.display_name;
+  return c;
+end;
+$$;
+
+alter table classes enable row level security;
+alter table class_members enable row level security;
+alter table class_assignments enable row level security;
+
+create policy "Instructors and members can select their classes"
+  on classes for select
+  using (auth.uid() = instructor_id or is_class_member(id));
+
+create policy "Instructors can delete their classes"
+  on classes for delete
+  using (auth.uid() = instructor_id);
+
+-- Members see only their own membership; the instructor sees everyone's
+create policy "Members and instructors can select class members"
+  on class_members for select
+  using (auth.uid() = user_id or is_class_instructor(class_id));
+
+create policy "Members can leave and instructors can remove them"
+  on class_members for delete
+  using (auth.uid() = user_id or is_class_instructor(class_id));
+
+create policy "Instructors and members can select assignments"
+  on class_assignments for select
+  using (is_class_instructor(class_id) or is_class_member(class_id));
+
+create policy "Instructors can insert assignments"
+  on class_assignments for insert
+  with check (is_class_instructor(class_id));
+
+create policy "Instructors can delete assignments"
+  on class_assignments for delete
+  using (is_class_instructor(class_id));
+
+-- Instructors read their members' progress; writes stay with the members
+create policy "Instructors can select their members' logs"
+  on answer_log for select
+  using (instructs(user_id));
+
+create policy "Instructors can select their members' exam results"
+  on exam_results for select
+  using (instructs(user_id));
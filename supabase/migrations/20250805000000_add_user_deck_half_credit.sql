@@ -0,0 +1,4 @@
+-- user_deck.half_credit: spaced-rep keys answered right but slowly once, which a second
+-- slow answer moves up.
+alter table user_deck
+  add column if not exists half_credit jsonb not null default '[]'::jsonb;
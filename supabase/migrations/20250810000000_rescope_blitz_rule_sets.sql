@@ -0,0 +1,6 @@
+-- blitz_scores.rule_set: the spaced-rep key scheme went from version 2 to 3 when keys
+-- started recording the strategy. A challenge's rule set is the rules part of the scope,
+-- which is otherwise unchanged ("v2/bj-h17-das-nols-peek" becomes "v3/bj-h17-das-nols-peek").
+update blitz_scores
+  set rule_set = 'v3/' || substr(rule_set, 4)
+  where rule_set like 'v2/%';
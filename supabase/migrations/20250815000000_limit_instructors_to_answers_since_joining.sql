@@ -0,0 +1,26 @@
+-- Instructors read a member's answers and exam results only from when the member joined
+-- their class, not the history from before. A row counts by when the database stored it
+-- (created_at), which the member can't backdate.
+create or replace function instructs_since(member uuid, at timestamptz)
+returns boolean
+language sql stable security definer set search_path = public
+as $$
+  select exists (
+    select 1
+    from class_members m
+    join classes c on c.id = m.class_id
+    where m.user_id = member and c.instructor_id = auth.uid() and m.joined_at <= at
+  );
+$$;
+
+drop policy if exists "Instructors can select their members' logs" on answer_log;
+create policy "Instructors can select their members' logs"
+  on answer_log for select
+  using (instructs_since(user_id, created_at));
+
+drop policy if exists "Instructors can select their members' exam results" on exam_results;
+create policy "Instructors can select their members' exam results"
+  on exam_results for select
+  using (instructs_since(user_id, created_at));
+
+drop function if exists instructs(uuid);
@@ -0,0 +1,11 @@
+-- recent_answers: answer_log numbered newest first within each player (recent = 1 for the
+-- latest), so an instructor can fetch the last answers of a whole class in one request
+-- (see bjsc::supabase::MemberAnswerRow). Like practice_streaks, the view reads answer_log
+-- as its owner, so it repeats the policies: the caller's own answers, and their members'
+-- answers from after they joined.
+create or replace view recent_answers as
+select a.*,
+       (row_number() over (partition by a.user_id order by a.created_at desc))::integer
+         as recent
+from answer_log a
+where a.user_id = auth.uid() or instructs_since(a.user_id, a.created_at);